@@ -0,0 +1,1494 @@
+//! Comparing the exposed API of two versions of a package, for `roc api-diff`.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use roc_docs::type_annotation_to_string;
+use roc_load::docs::{
+    DocEntry, ImplementsClause, ModuleDocumentation, RecordField, Tag, TypeAnnotation,
+};
+use roc_load::LoadedModule;
+use roc_module::symbol::{Interns, ModuleId, Symbol};
+use roc_parse::ast::FunctionArrow;
+use roc_types::subs::{Content, FlatType, GetSubsSlice, Subs, Variable};
+use roc_types::types::AliasKind;
+
+/// The exposed part of a module, with everything that isn't part of its API left out.
+#[derive(Debug, Clone)]
+pub struct ApiModule {
+    pub name: String,
+    pub defs: Vec<ApiDef>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiDef {
+    pub name: String,
+    /// The type variables of a type alias, opaque type or ability.
+    pub type_vars: Vec<String>,
+    pub type_annotation: TypeAnnotation,
+}
+
+impl ApiModule {
+    /// Values are described by their solved type when `solved_type` knows it, so that values
+    /// without an annotation are compared too, and aliases don't hide that two types are the
+    /// same. Types, aliases and abilities are described by their definitions.
+    pub fn from_docs(
+        docs: &ModuleDocumentation,
+        mut solved_type: impl FnMut(Symbol) -> Option<TypeAnnotation>,
+    ) -> Self {
+        let defs = docs
+            .entries
+            .iter()
+            .filter_map(|entry| match entry {
+                DocEntry::DocDef(doc_def) if docs.exposed_symbols.contains(&doc_def.symbol) => {
+                    let type_annotation = if is_value_name(&doc_def.name) {
+                        solved_type(doc_def.symbol)
+                    } else {
+                        None
+                    };
+
+                    Some(ApiDef {
+                        name: doc_def.name.clone(),
+                        type_vars: doc_def.type_vars.clone(),
+                        type_annotation: type_annotation
+                            .unwrap_or_else(|| doc_def.type_annotation.clone()),
+                    })
+                }
+                _ => None,
+            })
+            .collect();
+
+        Self {
+            name: docs.name.clone(),
+            defs,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum VersionBump {
+    /// Nothing in the API changed.
+    Patch,
+    /// The API only grew.
+    Minor,
+    /// Code that uses the old API might not compile against the new one.
+    Major,
+}
+
+impl VersionBump {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VersionBump::Patch => "patch",
+            VersionBump::Minor => "minor",
+            VersionBump::Major => "major",
+        }
+    }
+}
+
+/// Ordered by severity, so the more severe of two changes is the greater one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangeKind {
+    Additive,
+    Breaking,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiChange {
+    pub kind: ChangeKind,
+    pub description: String,
+}
+
+#[derive(Debug, Default)]
+pub struct ApiDiff {
+    pub changes: Vec<ApiChange>,
+    /// Exposed values whose types weren't compared, because their solved types weren't available
+    /// and one of the versions doesn't annotate them.
+    pub unannotated: Vec<String>,
+}
+
+impl ApiDiff {
+    pub fn required_bump(&self) -> VersionBump {
+        self.changes
+            .iter()
+            .map(|change| match change.kind {
+                ChangeKind::Breaking => VersionBump::Major,
+                ChangeKind::Additive => VersionBump::Minor,
+            })
+            .max()
+            .unwrap_or(VersionBump::Patch)
+    }
+
+    pub fn has_breaking_changes(&self) -> bool {
+        self.required_bump() == VersionBump::Major
+    }
+
+    fn push(&mut self, kind: ChangeKind, description: String) {
+        self.changes.push(ApiChange { kind, description });
+    }
+}
+
+/// Compares the exposed modules, values and types of two versions of a package.
+pub fn diff_apis(old: &[ApiModule], new: &[ApiModule]) -> ApiDiff {
+    let mut diff = ApiDiff::default();
+
+    for old_module in old {
+        match new.iter().find(|module| module.name == old_module.name) {
+            Some(new_module) => diff_modules(&mut diff, old_module, new_module),
+            None => diff.push(
+                ChangeKind::Breaking,
+                format!("The module {} is no longer exposed.", old_module.name),
+            ),
+        }
+    }
+
+    for new_module in new {
+        if !old.iter().any(|module| module.name == new_module.name) {
+            diff.push(
+                ChangeKind::Additive,
+                format!("The module {} is newly exposed.", new_module.name),
+            );
+        }
+    }
+
+    diff
+}
+
+fn diff_modules(diff: &mut ApiDiff, old: &ApiModule, new: &ApiModule) {
+    let module_name = old.name.as_str();
+
+    for old_def in old.defs.iter() {
+        let qualified_name = format!("{module_name}.{}", old_def.name);
+
+        let new_def = match new.defs.iter().find(|def| def.name == old_def.name) {
+            Some(new_def) => new_def,
+            None => {
+                diff.push(
+                    ChangeKind::Breaking,
+                    format!("{qualified_name} is no longer exposed."),
+                );
+                continue;
+            }
+        };
+
+        if old_def.type_vars.len() != new_def.type_vars.len() {
+            diff.push(
+                ChangeKind::Breaking,
+                format!(
+                    "{qualified_name} now has {} type variables instead of {}.",
+                    new_def.type_vars.len(),
+                    old_def.type_vars.len()
+                ),
+            );
+            continue;
+        }
+
+        let mut renaming = Renaming::default();
+
+        for (old_var, new_var) in old_def.type_vars.iter().zip(new_def.type_vars.iter()) {
+            renaming.bind(old_var, new_var);
+        }
+
+        match (&old_def.type_annotation, &new_def.type_annotation) {
+            (TypeAnnotation::NoTypeAnn, TypeAnnotation::NoTypeAnn) => {
+                // Opaque types don't expose their structure, so only values are worth a note.
+                if is_value_name(&old_def.name) {
+                    diff.unannotated.push(qualified_name);
+                }
+            }
+            // Adding or removing an annotation doesn't change a value's type. Without the solved
+            // type, there's nothing to compare the annotated version to.
+            (TypeAnnotation::NoTypeAnn, _) | (_, TypeAnnotation::NoTypeAnn)
+                if is_value_name(&old_def.name) =>
+            {
+                diff.unannotated.push(qualified_name);
+            }
+            (old_ann, new_ann) => {
+                // Types exposed by name can be used anywhere, so only a value's own type is
+                // known to be in output position.
+                let position = if is_value_name(&old_def.name) {
+                    Position::Output
+                } else {
+                    Position::Input
+                };
+
+                if let Some(change) = type_change(old_ann, new_ann, &mut renaming, position) {
+                    diff.push(
+                        change.kind,
+                        format!("{qualified_name} {}", change.description),
+                    );
+                }
+            }
+        }
+    }
+
+    for new_def in new.defs.iter() {
+        if !old.defs.iter().any(|def| def.name == new_def.name) {
+            diff.push(
+                ChangeKind::Additive,
+                format!("{module_name}.{} is newly exposed.", new_def.name),
+            );
+        }
+    }
+}
+
+fn is_value_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_lowercase())
+}
+
+/// Type variables of the old version and the names they have in the new one. Renaming a type
+/// variable doesn't change a type.
+#[derive(Debug, Default)]
+struct Renaming {
+    old_to_new: HashMap<String, String>,
+    new_to_old: HashMap<String, String>,
+}
+
+impl Renaming {
+    /// Returns whether the two variables are the same under this renaming, binding them if
+    /// neither is bound yet.
+    fn bind(&mut self, old: &str, new: &str) -> bool {
+        match (self.old_to_new.get(old), self.new_to_old.get(new)) {
+            (None, None) => {
+                self.old_to_new.insert(old.to_string(), new.to_string());
+                self.new_to_old.insert(new.to_string(), old.to_string());
+
+                true
+            }
+            (Some(bound_new), Some(bound_old)) => bound_new == new && bound_old == old,
+            _ => false,
+        }
+    }
+}
+
+/// How a type changed between two versions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TypeChange {
+    kind: ChangeKind,
+    description: String,
+}
+
+impl TypeChange {
+    fn breaking(description: String) -> Self {
+        Self {
+            kind: ChangeKind::Breaking,
+            description,
+        }
+    }
+
+    fn additive(description: String) -> Self {
+        Self {
+            kind: ChangeKind::Additive,
+            description,
+        }
+    }
+}
+
+/// Where a type appears, which decides whether some changes to it are additive. A value's type,
+/// and the output of a function, are given to the code that uses them. A function's arguments
+/// come from that code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+    Output,
+    Input,
+}
+
+impl Position {
+    fn flip(self) -> Self {
+        match self {
+            Position::Output => Position::Input,
+            Position::Input => Position::Output,
+        }
+    }
+}
+
+/// Describes how the type changed, or returns `None` if it's the same up to renaming type
+/// variables and reordering tags and fields.
+///
+/// Only two kinds of change are additive: a new tag in an open tag union in output position,
+/// which code that uses it must already handle with a `_` branch, and a new optional record
+/// field, which code that uses it doesn't have to give. Any other change counts as breaking,
+/// even one that only accepts more inputs: type inference can make code that uses a package
+/// depend on its exact types.
+fn type_change(
+    old: &TypeAnnotation,
+    new: &TypeAnnotation,
+    renaming: &mut Renaming,
+    position: Position,
+) -> Option<TypeChange> {
+    let changed_as = |kind| {
+        Some(TypeChange {
+            kind,
+            description: format!(
+                "changed type from {} to {}.",
+                type_annotation_to_string(old),
+                type_annotation_to_string(new)
+            ),
+        })
+    };
+    let changed = || changed_as(ChangeKind::Breaking);
+
+    match (old, new) {
+        (TypeAnnotation::BoundVariable(old_var), TypeAnnotation::BoundVariable(new_var)) => {
+            if renaming.bind(old_var, new_var) {
+                None
+            } else {
+                changed()
+            }
+        }
+        (
+            TypeAnnotation::Apply {
+                name: old_name,
+                parts: old_parts,
+            },
+            TypeAnnotation::Apply {
+                name: new_name,
+                parts: new_parts,
+            },
+        ) => {
+            // `Num.U64` and `U64` are the same type
+            if unqualified(old_name) != unqualified(new_name) || old_parts.len() != new_parts.len()
+            {
+                return changed();
+            }
+
+            types_change(old_parts, new_parts, renaming, position)
+                .and_then(|change| changed_as(change.kind))
+        }
+        (
+            TypeAnnotation::Function {
+                args: old_args,
+                arrow: old_arrow,
+                output: old_output,
+            },
+            TypeAnnotation::Function {
+                args: new_args,
+                arrow: new_arrow,
+                output: new_output,
+            },
+        ) => {
+            if old_args.len() != new_args.len() || old_arrow != new_arrow {
+                return changed();
+            }
+
+            most_severe([
+                types_change(old_args, new_args, renaming, position.flip()),
+                type_change(old_output, new_output, renaming, position),
+            ])
+            .and_then(|change| changed_as(change.kind))
+        }
+        (
+            TypeAnnotation::TagUnion {
+                tags: old_tags,
+                extension: old_ext,
+            },
+            TypeAnnotation::TagUnion {
+                tags: new_tags,
+                extension: new_ext,
+            },
+        ) => {
+            let is_exhaustive = matches!(**old_ext, TypeAnnotation::NoTypeAnn)
+                && matches!(**new_ext, TypeAnnotation::NoTypeAnn);
+            let mut payload_change = None;
+
+            for old_tag in old_tags {
+                match new_tags.iter().find(|tag| tag.name == old_tag.name) {
+                    Some(new_tag) => {
+                        if old_tag.values.len() != new_tag.values.len() {
+                            payload_change = Some(ChangeKind::Breaking);
+                        } else if let Some(change) =
+                            types_change(&old_tag.values, &new_tag.values, renaming, position)
+                        {
+                            payload_change = payload_change.max(Some(change.kind));
+                        }
+
+                        if payload_change == Some(ChangeKind::Breaking) {
+                            return Some(TypeChange::breaking(format!(
+                                "changed the payload of the tag {} in {}.",
+                                old_tag.name,
+                                type_annotation_to_string(new)
+                            )));
+                        }
+                    }
+                    None => {
+                        return Some(TypeChange::breaking(format!(
+                            "no longer has the tag {} in {}.",
+                            old_tag.name,
+                            type_annotation_to_string(new)
+                        )))
+                    }
+                }
+            }
+
+            let ext_change = type_change(old_ext, new_ext, renaming, position)
+                .and_then(|change| changed_as(change.kind));
+
+            let added_change = new_tags
+                .iter()
+                .find(|new_tag| !old_tags.iter().any(|tag| tag.name == new_tag.name))
+                .map(|added| {
+                    if is_exhaustive {
+                        TypeChange::breaking(format!(
+                            "has a new tag {} in the exhaustive tag union {}, which `when` expressions on it don't handle.",
+                            added.name,
+                            type_annotation_to_string(new)
+                        ))
+                    } else {
+                        let description = format!(
+                            "has a new tag {} in {}.",
+                            added.name,
+                            type_annotation_to_string(new)
+                        );
+
+                        match position {
+                            Position::Output => TypeChange::additive(description),
+                            Position::Input => TypeChange::breaking(description),
+                        }
+                    }
+                });
+
+            let payload_change = payload_change.and_then(changed_as);
+
+            most_severe([added_change, ext_change, payload_change])
+        }
+        (
+            TypeAnnotation::Record {
+                fields: old_fields,
+                extension: old_ext,
+            },
+            TypeAnnotation::Record {
+                fields: new_fields,
+                extension: new_ext,
+            },
+        ) => {
+            let mut field_change = None;
+
+            for old_field in old_fields {
+                let field_name = record_field_name(old_field);
+
+                match new_fields
+                    .iter()
+                    .find(|field| record_field_name(field) == field_name)
+                {
+                    Some(new_field) => {
+                        if let Some(kind) =
+                            record_field_change(old_field, new_field, renaming, position)
+                        {
+                            field_change = field_change.max(Some(kind));
+                        }
+                    }
+                    None => {
+                        return Some(TypeChange::breaking(format!(
+                            "no longer has the field {field_name} in {}.",
+                            type_annotation_to_string(new)
+                        )))
+                    }
+                }
+            }
+
+            let added_changes = new_fields
+                .iter()
+                .filter(|new_field| {
+                    !old_fields
+                        .iter()
+                        .any(|field| record_field_name(field) == record_field_name(new_field))
+                })
+                .map(|added| {
+                    let description = format!(
+                        "has a new field {} in {}.",
+                        record_field_name(added),
+                        type_annotation_to_string(new)
+                    );
+
+                    match added {
+                        RecordField::OptionalField { .. } => TypeChange::additive(description),
+                        _ => TypeChange::breaking(description),
+                    }
+                });
+            let ext_change = type_change(old_ext, new_ext, renaming, position)
+                .and_then(|change| changed_as(change.kind));
+            let field_change = field_change.and_then(changed_as);
+
+            most_severe(added_changes.map(Some).chain([field_change, ext_change]))
+        }
+        (
+            TypeAnnotation::Tuple {
+                elems: old_elems,
+                extension: old_ext,
+            },
+            TypeAnnotation::Tuple {
+                elems: new_elems,
+                extension: new_ext,
+            },
+        ) => {
+            if old_elems.len() != new_elems.len() {
+                return changed();
+            }
+
+            most_severe([
+                types_change(old_elems, new_elems, renaming, position),
+                type_change(old_ext, new_ext, renaming, position),
+            ])
+            .and_then(|change| changed_as(change.kind))
+        }
+        (
+            TypeAnnotation::Ability {
+                members: old_members,
+            },
+            TypeAnnotation::Ability {
+                members: new_members,
+            },
+        ) => {
+            for old_member in old_members {
+                match new_members
+                    .iter()
+                    .find(|member| member.name == old_member.name)
+                {
+                    Some(new_member) => {
+                        // Code that uses an ability both calls its members and implements them,
+                        // so any change to a member's type is breaking.
+                        if type_change(
+                            &old_member.type_annotation,
+                            &new_member.type_annotation,
+                            renaming,
+                            position,
+                        )
+                        .is_some()
+                        {
+                            return Some(TypeChange::breaking(format!(
+                                "changed the type of its member {}.",
+                                old_member.name
+                            )));
+                        }
+                    }
+                    None => {
+                        return Some(TypeChange::breaking(format!(
+                            "no longer has the member {}.",
+                            old_member.name
+                        )))
+                    }
+                }
+            }
+
+            new_members
+                .iter()
+                .find(|new_member| !old_members.iter().any(|m| m.name == new_member.name))
+                .map(|added| {
+                    TypeChange::breaking(format!(
+                        "has a new member {}, which every implementation must define.",
+                        added.name
+                    ))
+                })
+        }
+        (
+            TypeAnnotation::Where {
+                ann: old_ann,
+                implements: old_implements,
+            },
+            TypeAnnotation::Where {
+                ann: new_ann,
+                implements: new_implements,
+            },
+        ) => {
+            if old_implements.len() != new_implements.len() {
+                return changed();
+            }
+
+            let ann_change = type_change(old_ann, new_ann, renaming, position);
+
+            for (old_clause, new_clause) in old_implements.iter().zip(new_implements.iter()) {
+                if !renaming.bind(&old_clause.name, &new_clause.name)
+                    || old_clause.abilities.len() != new_clause.abilities.len()
+                    || types_change(
+                        &old_clause.abilities,
+                        &new_clause.abilities,
+                        renaming,
+                        position,
+                    )
+                    .is_some()
+                {
+                    return changed();
+                }
+            }
+
+            ann_change.and_then(|change| changed_as(change.kind))
+        }
+        (
+            TypeAnnotation::As {
+                ann: old_ann,
+                name: old_name,
+                vars: old_vars,
+            },
+            TypeAnnotation::As {
+                ann: new_ann,
+                name: new_name,
+                vars: new_vars,
+            },
+        ) => {
+            if old_name != new_name || old_vars.len() != new_vars.len() {
+                return changed();
+            }
+
+            type_change(old_ann, new_ann, renaming, position)
+                .and_then(|change| changed_as(change.kind))
+        }
+        (TypeAnnotation::ObscuredTagUnion, TypeAnnotation::ObscuredTagUnion)
+        | (TypeAnnotation::ObscuredRecord, TypeAnnotation::ObscuredRecord)
+        | (TypeAnnotation::Wildcard, TypeAnnotation::Wildcard)
+        | (TypeAnnotation::NoTypeAnn, TypeAnnotation::NoTypeAnn) => None,
+        _ => changed(),
+    }
+}
+
+/// The most severe of several changes, preferring the first of equally severe ones.
+fn most_severe(changes: impl IntoIterator<Item = Option<TypeChange>>) -> Option<TypeChange> {
+    changes.into_iter().flatten().reduce(|most, change| {
+        if change.kind > most.kind {
+            change
+        } else {
+            most
+        }
+    })
+}
+
+fn types_change(
+    old: &[TypeAnnotation],
+    new: &[TypeAnnotation],
+    renaming: &mut Renaming,
+    position: Position,
+) -> Option<TypeChange> {
+    let changes: Vec<_> = old
+        .iter()
+        .zip(new.iter())
+        .map(|(old, new)| type_change(old, new, renaming, position))
+        .collect();
+
+    most_severe(changes)
+}
+
+fn unqualified(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+fn record_field_name(field: &RecordField) -> &str {
+    match field {
+        RecordField::RecordField { name, .. }
+        | RecordField::OptionalField { name, .. }
+        | RecordField::LabelOnly { name } => name,
+    }
+}
+
+fn record_field_change(
+    old: &RecordField,
+    new: &RecordField,
+    renaming: &mut Renaming,
+    position: Position,
+) -> Option<ChangeKind> {
+    match (old, new) {
+        (
+            RecordField::RecordField {
+                type_annotation: old_ann,
+                ..
+            },
+            RecordField::RecordField {
+                type_annotation: new_ann,
+                ..
+            },
+        )
+        | (
+            RecordField::OptionalField {
+                type_annotation: old_ann,
+                ..
+            },
+            RecordField::OptionalField {
+                type_annotation: new_ann,
+                ..
+            },
+        ) => type_change(old_ann, new_ann, renaming, position).map(|change| change.kind),
+        (RecordField::LabelOnly { .. }, RecordField::LabelOnly { .. }) => None,
+        _ => Some(ChangeKind::Breaking),
+    }
+}
+
+/// The solved type of an exposed value of one of the loaded modules.
+pub fn solved_type(
+    loaded: &LoadedModule,
+    module_id: ModuleId,
+    symbol: Symbol,
+) -> Option<TypeAnnotation> {
+    let (subs, var) = if module_id == loaded.module_id {
+        (loaded.solved.inner(), *loaded.exposed_to_host.get(&symbol)?)
+    } else {
+        let (_, var) = loaded
+            .exposes
+            .get(&module_id)?
+            .iter()
+            .find(|(exposed, _)| *exposed == symbol)?;
+
+        (
+            loaded.typechecked.get(&module_id)?.solved_subs.inner(),
+            *var,
+        )
+    };
+
+    Some(SolvedTypeConverter::new(subs, &loaded.interns).convert_toplevel(var))
+}
+
+/// Turns a solved type into the [TypeAnnotation] that docs use, so it can be compared like one.
+///
+/// Structural aliases defined outside the builtins are unfolded, so that introducing, renaming
+/// or removing an alias is not reported as a change. Builtin aliases like `U64` are kept, as
+/// unfolding them only makes reports harder to read. Lambda sets are not part of the API, and
+/// are left out.
+struct SolvedTypeConverter<'a> {
+    subs: &'a Subs,
+    interns: &'a Interns,
+    names: HashMap<Variable, String>,
+    taken_names: HashSet<String>,
+    /// The structures being converted, to stop at recursion
+    in_progress: Vec<Variable>,
+    implements: Vec<ImplementsClause>,
+}
+
+impl<'a> SolvedTypeConverter<'a> {
+    fn new(subs: &'a Subs, interns: &'a Interns) -> Self {
+        Self {
+            subs,
+            interns,
+            names: HashMap::new(),
+            taken_names: HashSet::new(),
+            in_progress: Vec::new(),
+            implements: Vec::new(),
+        }
+    }
+
+    fn convert_toplevel(mut self, var: Variable) -> TypeAnnotation {
+        let ann = self.convert(var);
+
+        if self.implements.is_empty() {
+            ann
+        } else {
+            TypeAnnotation::Where {
+                ann: Box::new(ann),
+                implements: self.implements,
+            }
+        }
+    }
+
+    fn convert(&mut self, var: Variable) -> TypeAnnotation {
+        let subs = self.subs;
+        let var = subs.get_root_key_without_compacting(var);
+
+        if self.in_progress.contains(&var) {
+            return TypeAnnotation::BoundVariable(self.var_name(var));
+        }
+
+        match *subs.get_content_without_compacting(var) {
+            Content::FlexVar(_) | Content::RigidVar(_) | Content::RecursionVar { .. } => {
+                TypeAnnotation::BoundVariable(self.var_name(var))
+            }
+            Content::FlexAbleVar(_, abilities) | Content::RigidAbleVar(_, abilities) => {
+                let name = self.var_name(var);
+
+                if !self.implements.iter().any(|clause| clause.name == name) {
+                    let abilities = subs
+                        .get_subs_slice(abilities)
+                        .iter()
+                        .map(|ability| self.apply(*ability, Vec::new()))
+                        .collect();
+
+                    self.implements.push(ImplementsClause {
+                        name: name.clone(),
+                        abilities,
+                    });
+                }
+
+                TypeAnnotation::BoundVariable(name)
+            }
+            Content::Structure(flat_type) => {
+                self.in_progress.push(var);
+                let ann = self.convert_flat_type(flat_type);
+                self.in_progress.pop();
+
+                ann
+            }
+            Content::Alias(symbol, _, real_var, AliasKind::Structural)
+                if !symbol.module_id().is_builtin() =>
+            {
+                self.convert(real_var)
+            }
+            Content::Alias(symbol, alias_vars, _, _) => {
+                let parts = alias_vars
+                    .named_type_arguments()
+                    .map(|index| self.convert(subs[index]))
+                    .collect();
+
+                self.apply(symbol, parts)
+            }
+            Content::RangedNumber(_) => TypeAnnotation::Apply {
+                name: "Num".to_string(),
+                parts: vec![TypeAnnotation::BoundVariable(self.var_name(var))],
+            },
+            Content::LambdaSet(_)
+            | Content::ErasedLambda
+            | Content::Pure
+            | Content::Effectful
+            | Content::Error => TypeAnnotation::Wildcard,
+        }
+    }
+
+    fn convert_flat_type(&mut self, flat_type: FlatType) -> TypeAnnotation {
+        let subs = self.subs;
+
+        match flat_type {
+            FlatType::Apply(symbol, args) => {
+                let parts = subs
+                    .get_subs_slice(args)
+                    .iter()
+                    .map(|arg| self.convert(*arg))
+                    .collect();
+
+                self.apply(symbol, parts)
+            }
+            FlatType::Func(args, _closure_var, ret_var, fx_var) => TypeAnnotation::Function {
+                args: subs
+                    .get_subs_slice(args)
+                    .iter()
+                    .map(|arg| self.convert(*arg))
+                    .collect(),
+                arrow: match subs.get_content_without_compacting(fx_var) {
+                    Content::Effectful => FunctionArrow::Effectful,
+                    _ => FunctionArrow::Pure,
+                },
+                output: Box::new(self.convert(ret_var)),
+            },
+            FlatType::EffectfulFunc => TypeAnnotation::Wildcard,
+            FlatType::Record(fields, ext) => {
+                let (fields, ext) = fields.sorted_iterator_and_ext(subs, ext);
+
+                TypeAnnotation::Record {
+                    fields: fields
+                        .map(|(label, field)| {
+                            let name = label.as_str().to_string();
+                            let type_annotation = self.convert(*field.as_inner());
+
+                            if field.is_optional() {
+                                RecordField::OptionalField {
+                                    name,
+                                    type_annotation,
+                                }
+                            } else {
+                                RecordField::RecordField {
+                                    name,
+                                    type_annotation,
+                                }
+                            }
+                        })
+                        .collect(),
+                    extension: Box::new(self.extension(ext)),
+                }
+            }
+            FlatType::Tuple(elems, ext) => {
+                let (elems, ext) = elems.sorted_iterator_and_ext(subs, ext);
+
+                TypeAnnotation::Tuple {
+                    elems: elems.map(|(_, elem)| self.convert(elem)).collect(),
+                    extension: Box::new(self.extension(ext)),
+                }
+            }
+            FlatType::TagUnion(tags, ext) | FlatType::RecursiveTagUnion(_, tags, ext) => {
+                let (tags, ext) = tags.sorted_iterator_and_ext(subs, ext);
+
+                TypeAnnotation::TagUnion {
+                    tags: tags
+                        .map(|(tag_name, payload)| Tag {
+                            name: tag_name.0.as_str().to_string(),
+                            values: payload.iter().map(|var| self.convert(*var)).collect(),
+                        })
+                        .collect(),
+                    extension: Box::new(self.extension(ext.var())),
+                }
+            }
+            FlatType::FunctionOrTagUnion(tag_names, _, ext) => TypeAnnotation::TagUnion {
+                tags: subs
+                    .get_subs_slice(tag_names)
+                    .iter()
+                    .map(|tag_name| Tag {
+                        name: tag_name.0.as_str().to_string(),
+                        values: Vec::new(),
+                    })
+                    .collect(),
+                extension: Box::new(self.extension(ext.var())),
+            },
+            FlatType::EmptyRecord => TypeAnnotation::Record {
+                fields: Vec::new(),
+                extension: Box::new(TypeAnnotation::NoTypeAnn),
+            },
+            FlatType::EmptyTagUnion => TypeAnnotation::TagUnion {
+                tags: Vec::new(),
+                extension: Box::new(TypeAnnotation::NoTypeAnn),
+            },
+        }
+    }
+
+    /// Like docs, a closed record or tag union has no extension.
+    fn extension(&mut self, ext: Variable) -> TypeAnnotation {
+        match self.subs.get_content_without_compacting(ext) {
+            Content::Structure(FlatType::EmptyRecord | FlatType::EmptyTagUnion) => {
+                TypeAnnotation::NoTypeAnn
+            }
+            _ => self.convert(ext),
+        }
+    }
+
+    fn apply(&self, symbol: Symbol, parts: Vec<TypeAnnotation>) -> TypeAnnotation {
+        TypeAnnotation::Apply {
+            name: symbol.as_str(self.interns).to_string(),
+            parts,
+        }
+    }
+
+    /// Names a type variable after the name the solved type gives it, if that name is free.
+    fn var_name(&mut self, var: Variable) -> String {
+        if let Some(name) = self.names.get(&var) {
+            return name.clone();
+        }
+
+        let given_name = match *self.subs.get_content_without_compacting(var) {
+            Content::FlexVar(Some(name))
+            | Content::FlexAbleVar(Some(name), _)
+            | Content::RigidVar(name)
+            | Content::RigidAbleVar(name, _)
+            | Content::RecursionVar {
+                opt_name: Some(name),
+                ..
+            } => Some(self.subs[name].as_str().to_string()),
+            _ => None,
+        };
+
+        let name = given_name
+            .filter(|name| !self.taken_names.contains(name))
+            .unwrap_or_else(|| {
+                (0..)
+                    .map(|index: usize| {
+                        let letter = (b'a' + (index % 26) as u8) as char;
+
+                        match index / 26 {
+                            0 => letter.to_string(),
+                            n => format!("{letter}{n}"),
+                        }
+                    })
+                    .find(|name| !self.taken_names.contains(name))
+                    .unwrap()
+            });
+
+        self.taken_names.insert(name.clone());
+        self.names.insert(var, name.clone());
+
+        name
+    }
+}
+
+pub fn render_report(diff: &ApiDiff) -> String {
+    let mut buf = String::new();
+
+    for (kind, heading) in [
+        (ChangeKind::Breaking, "Breaking changes:"),
+        (ChangeKind::Additive, "Additions:"),
+    ] {
+        let mut changes = diff.changes.iter().filter(|change| change.kind == kind);
+
+        if let Some(first) = changes.next() {
+            writeln!(buf, "{heading}\n").unwrap();
+
+            for change in std::iter::once(first).chain(changes) {
+                writeln!(buf, "    - {}", change.description).unwrap();
+            }
+
+            buf.push('\n');
+        }
+    }
+
+    if !diff.unannotated.is_empty() {
+        writeln!(
+            buf,
+            "These exposed values have no type annotation in one of the versions, so changes to their types weren't checked:\n"
+        )
+        .unwrap();
+
+        for name in diff.unannotated.iter() {
+            writeln!(buf, "    - {name}").unwrap();
+        }
+
+        buf.push('\n');
+    }
+
+    let bump = diff.required_bump();
+
+    match bump {
+        VersionBump::Patch => writeln!(
+            buf,
+            "The exposed API didn't change. Suggested version bump: {}",
+            bump.as_str()
+        ),
+        VersionBump::Minor | VersionBump::Major => {
+            writeln!(buf, "Suggested version bump: {}", bump.as_str())
+        }
+    }
+    .unwrap();
+
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bumpalo::Bump;
+    use indoc::indoc;
+    use roc_load::FunctionKind;
+    use roc_packaging::cache::RocCacheDir;
+    use roc_reporting::report::{RenderTarget, DEFAULT_PALETTE};
+    use roc_target::Target;
+    use std::path::PathBuf;
+
+    fn apply(name: &str, parts: Vec<TypeAnnotation>) -> TypeAnnotation {
+        TypeAnnotation::Apply {
+            name: name.to_string(),
+            parts,
+        }
+    }
+
+    fn var(name: &str) -> TypeAnnotation {
+        TypeAnnotation::BoundVariable(name.to_string())
+    }
+
+    fn function(args: Vec<TypeAnnotation>, output: TypeAnnotation) -> TypeAnnotation {
+        TypeAnnotation::Function {
+            args,
+            arrow: FunctionArrow::Pure,
+            output: Box::new(output),
+        }
+    }
+
+    fn closed_tag_union(tags: &[&str]) -> TypeAnnotation {
+        TypeAnnotation::TagUnion {
+            tags: tags
+                .iter()
+                .map(|name| Tag {
+                    name: name.to_string(),
+                    values: Vec::new(),
+                })
+                .collect(),
+            extension: Box::new(TypeAnnotation::NoTypeAnn),
+        }
+    }
+
+    fn open_tag_union(tags: &[&str]) -> TypeAnnotation {
+        match closed_tag_union(tags) {
+            TypeAnnotation::TagUnion { tags, .. } => TypeAnnotation::TagUnion {
+                tags,
+                extension: Box::new(TypeAnnotation::Wildcard),
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    fn closed_record(fields: Vec<RecordField>) -> TypeAnnotation {
+        TypeAnnotation::Record {
+            fields,
+            extension: Box::new(TypeAnnotation::NoTypeAnn),
+        }
+    }
+
+    fn def(name: &str, type_vars: &[&str], type_annotation: TypeAnnotation) -> ApiDef {
+        ApiDef {
+            name: name.to_string(),
+            type_vars: type_vars.iter().map(|var| var.to_string()).collect(),
+            type_annotation,
+        }
+    }
+
+    fn module(name: &str, defs: Vec<ApiDef>) -> ApiModule {
+        ApiModule {
+            name: name.to_string(),
+            defs,
+        }
+    }
+
+    /// The exposed values of a module, described by their solved types.
+    fn solved_module(src: &'static str) -> ApiModule {
+        let arena = Bump::new();
+        let loaded = roc_load::load_and_typecheck_str(
+            &arena,
+            PathBuf::from("Util.roc"),
+            src,
+            PathBuf::from("."),
+            None,
+            Target::LinuxX64,
+            FunctionKind::LambdaSet,
+            RenderTarget::Generic,
+            RocCacheDir::Disallowed,
+            DEFAULT_PALETTE,
+        )
+        .unwrap();
+
+        let mut defs: Vec<_> = loaded
+            .exposed_to_host
+            .keys()
+            .map(|symbol| ApiDef {
+                name: symbol.as_str(&loaded.interns).to_string(),
+                type_vars: Vec::new(),
+                type_annotation: solved_type(&loaded, loaded.module_id, *symbol).unwrap(),
+            })
+            .collect();
+        defs.sort_by(|a, b| a.name.cmp(&b.name));
+
+        module("Util", defs)
+    }
+
+    fn list_map(a: &str, b: &str) -> ApiDef {
+        def(
+            "map",
+            &[],
+            function(
+                vec![apply("List", vec![var(a)]), function(vec![var(a)], var(b))],
+                apply("List", vec![var(b)]),
+            ),
+        )
+    }
+
+    #[test]
+    fn renaming_type_variables_is_not_a_change() {
+        let old = [module("Util", vec![list_map("a", "b")])];
+        let new = [module("Util", vec![list_map("x", "y")])];
+
+        let diff = diff_apis(&old, &new);
+
+        assert_eq!(diff.changes, []);
+        assert_eq!(diff.required_bump(), VersionBump::Patch);
+    }
+
+    #[test]
+    fn unifying_type_variables_is_breaking() {
+        let old = [module("Util", vec![list_map("a", "b")])];
+        let new = [module("Util", vec![list_map("a", "a")])];
+
+        let diff = diff_apis(&old, &new);
+
+        assert_eq!(
+            diff.changes,
+            [ApiChange {
+                kind: ChangeKind::Breaking,
+                description: "Util.map changed type from List a, (a -> b) -> List b to List a, (a -> a) -> List a.".to_string(),
+            }]
+        );
+        assert_eq!(diff.required_bump(), VersionBump::Major);
+    }
+
+    #[test]
+    fn additions_are_minor() {
+        let old = [module("Util", vec![list_map("a", "b")])];
+        let new = [
+            module(
+                "Util",
+                vec![
+                    list_map("a", "b"),
+                    def("empty", &[], apply("Str", Vec::new())),
+                ],
+            ),
+            module("Extra", Vec::new()),
+        ];
+
+        let diff = diff_apis(&old, &new);
+
+        assert_eq!(
+            diff.changes
+                .iter()
+                .map(|change| (change.kind, change.description.as_str()))
+                .collect::<Vec<_>>(),
+            [
+                (ChangeKind::Additive, "Util.empty is newly exposed."),
+                (ChangeKind::Additive, "The module Extra is newly exposed."),
+            ]
+        );
+        assert_eq!(diff.required_bump(), VersionBump::Minor);
+    }
+
+    #[test]
+    fn removals_are_breaking() {
+        let old = [
+            module("Util", vec![list_map("a", "b")]),
+            module("Extra", Vec::new()),
+        ];
+        let new = [module("Util", Vec::new())];
+
+        let diff = diff_apis(&old, &new);
+
+        assert_eq!(
+            diff.changes
+                .iter()
+                .map(|change| (change.kind, change.description.as_str()))
+                .collect::<Vec<_>>(),
+            [
+                (ChangeKind::Breaking, "Util.map is no longer exposed."),
+                (
+                    ChangeKind::Breaking,
+                    "The module Extra is no longer exposed."
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn new_tag_in_exhaustive_union_is_breaking() {
+        let old = [module(
+            "Color",
+            vec![def("Color", &[], closed_tag_union(&["Red", "Green"]))],
+        )];
+        let new = [module(
+            "Color",
+            vec![def(
+                "Color",
+                &[],
+                closed_tag_union(&["Green", "Red", "Blue"]),
+            )],
+        )];
+
+        let diff = diff_apis(&old, &new);
+
+        assert_eq!(
+            diff.changes,
+            [ApiChange {
+                kind: ChangeKind::Breaking,
+                description: "Color.Color has a new tag Blue in the exhaustive tag union [ Green, Red, Blue ], which `when` expressions on it don't handle.".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn new_tag_in_open_union_in_output_position_is_additive() {
+        let parse = |tags: &[&str]| {
+            def(
+                "parse",
+                &[],
+                function(vec![apply("Str", Vec::new())], open_tag_union(tags)),
+            )
+        };
+        let old = [module("Util", vec![parse(&["Ok", "Err"])])];
+        let new = [module("Util", vec![parse(&["Ok", "Err", "Empty"])])];
+
+        let diff = diff_apis(&old, &new);
+
+        assert_eq!(
+            diff.changes
+                .iter()
+                .map(|change| change.kind)
+                .collect::<Vec<_>>(),
+            [ChangeKind::Additive]
+        );
+        assert_eq!(diff.required_bump(), VersionBump::Minor);
+    }
+
+    #[test]
+    fn new_tag_in_open_union_in_input_position_is_breaking() {
+        let describe = |tags: &[&str]| {
+            def(
+                "describe",
+                &[],
+                function(vec![open_tag_union(tags)], apply("Str", Vec::new())),
+            )
+        };
+        let old = [module("Util", vec![describe(&["Red", "Green"])])];
+        let new = [module("Util", vec![describe(&["Red", "Green", "Blue"])])];
+
+        assert_eq!(diff_apis(&old, &new).required_bump(), VersionBump::Major);
+    }
+
+    #[test]
+    fn new_optional_field_is_additive() {
+        let str_field = |name: &str| RecordField::RecordField {
+            name: name.to_string(),
+            type_annotation: apply("Str", Vec::new()),
+        };
+        let run = |fields| {
+            def(
+                "run",
+                &[],
+                function(vec![closed_record(fields)], apply("Str", Vec::new())),
+            )
+        };
+        let old = [module("Util", vec![run(vec![str_field("name")])])];
+        let new = [module(
+            "Util",
+            vec![run(vec![
+                str_field("name"),
+                RecordField::OptionalField {
+                    name: "greeting".to_string(),
+                    type_annotation: apply("Str", Vec::new()),
+                },
+            ])],
+        )];
+
+        let diff = diff_apis(&old, &new);
+
+        assert_eq!(
+            diff.changes
+                .iter()
+                .map(|change| change.kind)
+                .collect::<Vec<_>>(),
+            [ChangeKind::Additive]
+        );
+        assert_eq!(diff.required_bump(), VersionBump::Minor);
+    }
+
+    #[test]
+    fn new_required_field_is_breaking() {
+        let str_field = |name: &str| RecordField::RecordField {
+            name: name.to_string(),
+            type_annotation: apply("Str", Vec::new()),
+        };
+        let run = |fields| {
+            def(
+                "run",
+                &[],
+                function(vec![closed_record(fields)], apply("Str", Vec::new())),
+            )
+        };
+        let old = [module("Util", vec![run(vec![str_field("name")])])];
+        let new = [module(
+            "Util",
+            vec![run(vec![str_field("name"), str_field("greeting")])],
+        )];
+
+        assert_eq!(diff_apis(&old, &new).required_bump(), VersionBump::Major);
+    }
+
+    #[test]
+    fn reordering_tags_is_not_a_change() {
+        let old = [module(
+            "Color",
+            vec![def("Color", &[], closed_tag_union(&["Red", "Green"]))],
+        )];
+        let new = [module(
+            "Color",
+            vec![def("Color", &[], closed_tag_union(&["Green", "Red"]))],
+        )];
+
+        assert_eq!(diff_apis(&old, &new).changes, []);
+    }
+
+    #[test]
+    fn annotating_a_value_is_not_breaking() {
+        let old = [module(
+            "Util",
+            vec![def("helper", &[], TypeAnnotation::NoTypeAnn)],
+        )];
+        let new = [module(
+            "Util",
+            vec![def("helper", &[], apply("Str", Vec::new()))],
+        )];
+
+        let diff = diff_apis(&old, &new);
+
+        assert_eq!(diff.changes, []);
+        assert_eq!(diff.unannotated, ["Util.helper"]);
+    }
+
+    #[test]
+    fn solved_types_ignore_annotations_and_aliases() {
+        let old = [solved_module(indoc!(
+            r#"
+            module [greet, count]
+
+            Count : U64
+
+            greet = |name| Str.concat("Hello, ", name)
+
+            count : Str -> Count
+            count = |str| Str.count_utf8_bytes(str)
+            "#
+        ))];
+        let new = [solved_module(indoc!(
+            r#"
+            module [greet, count]
+
+            greet : Str -> Str
+            greet = |name| Str.concat("Hello, ", name)
+
+            count : Str -> U64
+            count = |str| Str.count_utf8_bytes(str)
+            "#
+        ))];
+
+        let diff = diff_apis(&old, &new);
+
+        assert_eq!(diff.changes, []);
+        assert!(diff.unannotated.is_empty());
+    }
+
+    #[test]
+    fn solved_types_of_unannotated_values_are_compared() {
+        let old = [solved_module(indoc!(
+            r#"
+            module [greet]
+
+            greet = |name| Str.concat("Hello, ", name)
+            "#
+        ))];
+        let new = [solved_module(indoc!(
+            r#"
+            module [greet]
+
+            greet = |name| Str.concat("Hello, ", Num.to_str(name))
+            "#
+        ))];
+
+        let diff = diff_apis(&old, &new);
+
+        assert_eq!(
+            diff.changes
+                .iter()
+                .map(|change| change.kind)
+                .collect::<Vec<_>>(),
+            [ChangeKind::Breaking]
+        );
+    }
+
+    #[test]
+    fn report() {
+        let old = [module(
+            "Util",
+            vec![
+                list_map("a", "b"),
+                def("helper", &[], TypeAnnotation::NoTypeAnn),
+            ],
+        )];
+        let new = [module(
+            "Util",
+            vec![
+                def("helper", &[], TypeAnnotation::NoTypeAnn),
+                def("empty", &[], apply("Str", Vec::new())),
+            ],
+        )];
+
+        assert_eq!(
+            render_report(&diff_apis(&old, &new)),
+            indoc::indoc!(
+                "
+                Breaking changes:
+
+                    - Util.map is no longer exposed.
+
+                Additions:
+
+                    - Util.empty is newly exposed.
+
+                These exposed values have no type annotation in one of the versions, so changes to their types weren't checked:
+
+                    - Util.helper
+
+                Suggested version bump: major
+                "
+            )
+        );
+    }
+}
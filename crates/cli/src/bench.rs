@@ -0,0 +1,199 @@
+//! Timing and reporting for `roc bench`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// How long to run a benchmark before measuring it, so caches and branch predictors are warm.
+const WARMUP_TIME: Duration = Duration::from_millis(500);
+
+/// Each sample runs the benchmark for about this long.
+const SAMPLE_TIME: Duration = Duration::from_millis(20);
+
+const SAMPLE_COUNT: usize = 50;
+
+/// Summary statistics of one benchmark, in nanoseconds per call.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BenchStats {
+    pub mean: f64,
+    pub median: f64,
+    pub stddev: f64,
+}
+
+impl BenchStats {
+    pub fn from_samples(samples: &[f64]) -> Self {
+        debug_assert!(!samples.is_empty());
+
+        let count = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / count;
+
+        let variance = if samples.len() > 1 {
+            samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (count - 1.0)
+        } else {
+            0.0
+        };
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+
+        let middle = sorted.len() / 2;
+        let median = if sorted.len() % 2 == 0 {
+            (sorted[middle - 1] + sorted[middle]) / 2.0
+        } else {
+            sorted[middle]
+        };
+
+        Self {
+            mean,
+            median,
+            stddev: variance.sqrt(),
+        }
+    }
+}
+
+/// Run `call` repeatedly: first to warm up and to pick how many calls make up one sample, then
+/// to take the samples. Stops at the first crash and returns its message.
+pub fn measure(mut call: impl FnMut() -> Result<(), String>) -> Result<BenchStats, String> {
+    let warmup_start = Instant::now();
+    let mut warmup_calls: u32 = 0;
+
+    while warmup_start.elapsed() < WARMUP_TIME {
+        call()?;
+        warmup_calls += 1;
+    }
+
+    let time_per_call = warmup_start.elapsed() / warmup_calls;
+    let calls_per_sample = (SAMPLE_TIME.as_nanos() / time_per_call.as_nanos().max(1)).max(1);
+
+    let mut samples = Vec::with_capacity(SAMPLE_COUNT);
+
+    for _ in 0..SAMPLE_COUNT {
+        let sample_start = Instant::now();
+
+        for _ in 0..calls_per_sample {
+            call()?;
+        }
+
+        samples.push(sample_start.elapsed().as_nanos() as f64 / calls_per_sample as f64);
+    }
+
+    Ok(BenchStats::from_samples(&samples))
+}
+
+/// The results of a `roc bench` run, by benchmark name, as saved with `--save-baseline`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    pub benches: BTreeMap<String, BenchStats>,
+}
+
+impl Baseline {
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| format!("Could not read {}: {err}", path.display()))?;
+
+        serde_json::from_str(&contents)
+            .map_err(|err| format!("{} is not a valid baseline: {err}", path.display()))
+    }
+
+    pub fn write(&self, path: &Path) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+
+        std::fs::write(path, json)
+    }
+}
+
+/// One line describing the benchmark's timings, followed by how they compare to the baseline.
+pub fn report(name: &str, stats: &BenchStats, baseline: Option<&BenchStats>) -> String {
+    let mut buf = format!(
+        "{name}: mean {} ± {}, median {}",
+        format_nanos(stats.mean),
+        format_nanos(stats.stddev),
+        format_nanos(stats.median)
+    );
+
+    if let Some(baseline) = baseline {
+        let change = (stats.mean - baseline.mean) / baseline.mean * 100.0;
+
+        // Differences within the noise of either run are not worth reporting as a change.
+        let verdict = if (stats.mean - baseline.mean).abs() <= stats.stddev + baseline.stddev {
+            "no significant change"
+        } else if change < 0.0 {
+            "faster"
+        } else {
+            "slower"
+        };
+
+        write!(buf, " ({change:+.1}% vs baseline, {verdict})").unwrap();
+    }
+
+    buf
+}
+
+fn format_nanos(nanos: f64) -> String {
+    if nanos < 1_000.0 {
+        format!("{nanos:.1} ns")
+    } else if nanos < 1_000_000.0 {
+        format!("{:.2} µs", nanos / 1_000.0)
+    } else if nanos < 1_000_000_000.0 {
+        format!("{:.2} ms", nanos / 1_000_000.0)
+    } else {
+        format!("{:.2} s", nanos / 1_000_000_000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let stats = BenchStats::from_samples(&[4.0, 1.0, 3.0, 2.0]);
+
+        assert_eq!(stats.mean, 2.5);
+        assert_eq!(stats.median, 2.5);
+        assert!((stats.stddev - 1.2909944).abs() < 1e-6);
+
+        let stats = BenchStats::from_samples(&[5.0, 1.0, 3.0]);
+
+        assert_eq!(stats.median, 3.0);
+    }
+
+    #[test]
+    fn report_against_baseline() {
+        let stats = BenchStats {
+            mean: 1_500.0,
+            median: 1_450.0,
+            stddev: 20.0,
+        };
+
+        assert_eq!(
+            report("bench_sort", &stats, None),
+            "bench_sort: mean 1.50 µs ± 20.0 ns, median 1.45 µs"
+        );
+
+        let slower_baseline = BenchStats {
+            mean: 2_000.0,
+            median: 2_000.0,
+            stddev: 30.0,
+        };
+
+        assert_eq!(
+            report("bench_sort", &stats, Some(&slower_baseline)),
+            "bench_sort: mean 1.50 µs ± 20.0 ns, median 1.45 µs (-25.0% vs baseline, faster)"
+        );
+
+        let noisy_baseline = BenchStats {
+            mean: 1_510.0,
+            median: 1_500.0,
+            stddev: 30.0,
+        };
+
+        assert_eq!(
+            report("bench_sort", &stats, Some(&noisy_baseline)),
+            "bench_sort: mean 1.50 µs ± 20.0 ns, median 1.45 µs (-0.7% vs baseline, no significant change)"
+        );
+    }
+}
@@ -0,0 +1,271 @@
+//! Turns the hit counts recorded by `roc test --coverage` into an LCOV file and a per-module
+//! summary.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use roc_module::symbol::Interns;
+use roc_mono::coverage::{CoverageKind, CoveragePoint, CoverageSite};
+use roc_region::all::{LineInfo, Region};
+
+/// Where `roc test --coverage` writes its LCOV report, relative to the current directory.
+pub const DEFAULT_LCOV_FILENAME: &str = "lcov.info";
+
+#[derive(Debug, Default)]
+pub struct CoverageReport {
+    files: BTreeMap<PathBuf, FileCoverage>,
+}
+
+#[derive(Debug, Default)]
+struct FileCoverage {
+    /// A module imported by several of the tested files is loaded once per file, so points are
+    /// keyed by their location and the hits of each load are summed.
+    points: BTreeMap<(Region, CoverageKind), PointCoverage>,
+}
+
+#[derive(Debug)]
+struct PointCoverage {
+    /// 1-based, like LCOV expects
+    line: u32,
+    /// The name of the def, for [CoverageKind::Def]
+    name: Option<String>,
+    hits: u64,
+}
+
+impl CoverageReport {
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    pub fn add_module(
+        &mut self,
+        path: &Path,
+        source: &str,
+        interns: &Interns,
+        sites: &[CoverageSite],
+        mut get_hits: impl FnMut(&CoveragePoint) -> u64,
+    ) {
+        let line_info = LineInfo::new(source);
+        let file = self.files.entry(path.to_path_buf()).or_default();
+
+        for site in sites {
+            let hits = get_hits(&site.point);
+
+            file.points
+                .entry((site.region, site.kind))
+                .and_modify(|point| point.hits += hits)
+                .or_insert_with(|| PointCoverage {
+                    line: line_info.convert_pos(site.region.start()).line + 1,
+                    name: match site.point {
+                        CoveragePoint::Def(symbol) => Some(symbol.as_str(interns).to_string()),
+                        CoveragePoint::Branch(..) => None,
+                    },
+                    hits,
+                });
+        }
+    }
+
+    /// Render the report in the LCOV tracefile format, see `man geninfo`.
+    pub fn to_lcov(&self) -> String {
+        let mut buf = String::new();
+
+        for (path, file) in self.files.iter() {
+            writeln!(buf, "TN:").unwrap();
+            writeln!(buf, "SF:{}", path.display()).unwrap();
+
+            let defs = || file.points_of_kind(|kind| kind == CoverageKind::Def);
+
+            for def in defs() {
+                writeln!(buf, "FN:{},{}", def.line, def.name.as_deref().unwrap_or("")).unwrap();
+            }
+            for def in defs() {
+                writeln!(
+                    buf,
+                    "FNDA:{},{}",
+                    def.hits,
+                    def.name.as_deref().unwrap_or("")
+                )
+                .unwrap();
+            }
+            writeln!(buf, "FNF:{}", defs().count()).unwrap();
+            writeln!(buf, "FNH:{}", defs().filter(|def| def.hits > 0).count()).unwrap();
+
+            let branches = || file.points_of_kind(|kind| kind != CoverageKind::Def);
+
+            for (index, branch) in branches().enumerate() {
+                writeln!(buf, "BRDA:{},0,{},{}", branch.line, index, branch.hits).unwrap();
+            }
+            writeln!(buf, "BRF:{}", branches().count()).unwrap();
+            writeln!(buf, "BRH:{}", branches().filter(|b| b.hits > 0).count()).unwrap();
+
+            // A line counts as executed if any point starting on it was.
+            let mut lines: BTreeMap<u32, u64> = BTreeMap::new();
+            for point in file.points.values() {
+                let hits = lines.entry(point.line).or_default();
+                *hits = (*hits).max(point.hits);
+            }
+
+            for (line, hits) in lines.iter() {
+                writeln!(buf, "DA:{line},{hits}").unwrap();
+            }
+            writeln!(buf, "LF:{}", lines.len()).unwrap();
+            writeln!(
+                buf,
+                "LH:{}",
+                lines.values().filter(|hits| **hits > 0).count()
+            )
+            .unwrap();
+
+            writeln!(buf, "end_of_record").unwrap();
+        }
+
+        buf
+    }
+
+    /// One line per module, with how many of its defs and branches the tests reached.
+    pub fn summary(&self) -> String {
+        let mut buf = String::from("Coverage:\n");
+
+        for (path, file) in self.files.iter() {
+            let module_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy())
+                .unwrap_or_else(|| path.to_string_lossy());
+
+            let count = |is_kind: fn(CoverageKind) -> bool| {
+                let total = file.points_of_kind(is_kind).count();
+                let hit = file
+                    .points_of_kind(is_kind)
+                    .filter(|point| point.hits > 0)
+                    .count();
+
+                (hit, total)
+            };
+
+            let (defs_hit, defs_total) = count(|kind| kind == CoverageKind::Def);
+            let (branches_hit, branches_total) = count(|kind| kind != CoverageKind::Def);
+
+            writeln!(
+                buf,
+                "    {module_name}: {defs_hit}/{defs_total} defs and {branches_hit}/{branches_total} branches covered",
+            )
+            .unwrap();
+        }
+
+        buf
+    }
+}
+
+impl FileCoverage {
+    fn points_of_kind(
+        &self,
+        is_kind: impl Fn(CoverageKind) -> bool,
+    ) -> impl Iterator<Item = &PointCoverage> {
+        self.points
+            .iter()
+            .filter(move |((_, kind), _)| is_kind(*kind))
+            .map(|(_, point)| point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use roc_module::symbol::Symbol;
+    use roc_region::all::Position;
+
+    fn region(start: u32, end: u32) -> Region {
+        Region::new(Position::new(start), Position::new(end))
+    }
+
+    #[test]
+    fn lcov_and_summary() {
+        let source = indoc!(
+            r#"
+            module [is_small]
+
+            is_small = \n ->
+                if n < 10 then
+                    Bool.true
+                else
+                    Bool.false
+            "#
+        );
+
+        let mut interns = Interns::default();
+        let home = interns.module_ids.get_or_insert(&"Small".into());
+        let is_small = Symbol::new(
+            home,
+            interns
+                .all_ident_ids
+                .get_or_insert(home)
+                .add_str("is_small"),
+        );
+
+        let def_region = region(19, 100);
+        let then_region = region(63, 72);
+        let else_region = region(90, 100);
+
+        let sites = [
+            CoverageSite {
+                point: CoveragePoint::Def(is_small),
+                kind: CoverageKind::Def,
+                region: def_region,
+            },
+            CoverageSite {
+                point: CoveragePoint::Branch(home, then_region),
+                kind: CoverageKind::IfArm,
+                region: then_region,
+            },
+            CoverageSite {
+                point: CoveragePoint::Branch(home, else_region),
+                kind: CoverageKind::IfArm,
+                region: else_region,
+            },
+        ];
+
+        let mut report = CoverageReport::default();
+        report.add_module(
+            Path::new("Small.roc"),
+            source,
+            &interns,
+            &sites,
+            |point| match point {
+                CoveragePoint::Def(_) => 2,
+                CoveragePoint::Branch(_, region) if *region == then_region => 2,
+                CoveragePoint::Branch(..) => 0,
+            },
+        );
+
+        assert_eq!(
+            report.to_lcov(),
+            indoc!(
+                r#"
+                TN:
+                SF:Small.roc
+                FN:3,is_small
+                FNDA:2,is_small
+                FNF:1
+                FNH:1
+                BRDA:5,0,0,2
+                BRDA:7,0,1,0
+                BRF:2
+                BRH:1
+                DA:3,2
+                DA:5,2
+                DA:7,0
+                LF:3
+                LH:2
+                end_of_record
+                "#
+            )
+        );
+
+        assert_eq!(
+            report.summary(),
+            "Coverage:\n    Small.roc: 1/1 defs and 1/2 branches covered\n"
+        );
+    }
+}
@@ -2,16 +2,17 @@ use std::ffi::OsStr;
 use std::io::Write;
 use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::process::Command;
 
 use bumpalo::{collections::String as BumpString, Bump};
 use roc_can::abilities::{IAbilitiesStore, Resolved};
 use roc_can::expr::{DeclarationTag, Declarations, Expr};
 use roc_error_macros::{internal_error, user_error};
-use roc_fmt::def::fmt_defs;
+use roc_fmt::def::{fmt_defs, fmt_defs_in_ranges};
 use roc_fmt::header::fmt_header;
 use roc_fmt::migrate::MigrateError;
 use roc_fmt::Buf;
-use roc_fmt::MigrationFlags;
+use roc_fmt::{FormatConfig, MigrationFlags};
 use roc_load::{ExecutionMode, FunctionKind, LoadConfig, LoadedModule, LoadingProblem, Threading};
 use roc_module::symbol::{Interns, ModuleId};
 use roc_packaging::cache::{self, RocCacheDir};
@@ -24,6 +25,7 @@ use roc_region::all::{LineColumn, LineInfo};
 use roc_reporting::report::{RenderTarget, DEFAULT_PALETTE};
 use roc_target::Target;
 use roc_types::subs::{Subs, Variable};
+use serde::Deserialize;
 
 #[derive(Copy, Clone, Debug)]
 pub enum FormatMode {
@@ -32,6 +34,70 @@ pub enum FormatMode {
     CheckOnly,
 }
 
+/// The file that configures `roc format` for the package whose root directory it is in.
+pub const FORMAT_CONFIG_FILENAME: &str = "roc-format.json";
+
+/// Formatting style options, as given in a [FORMAT_CONFIG_FILENAME] file or on the command line.
+/// Anything left unset falls back to the standard Roc style.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct FormatOptions {
+    pub max_line_width: Option<u16>,
+    pub trailing_commas: Option<bool>,
+}
+
+impl FormatOptions {
+    /// Fill in the options that were not set from `fallback`.
+    pub fn or(self, fallback: FormatOptions) -> FormatOptions {
+        FormatOptions {
+            max_line_width: self.max_line_width.or(fallback.max_line_width),
+            trailing_commas: self.trailing_commas.or(fallback.trailing_commas),
+        }
+    }
+
+    pub fn to_config(self) -> FormatConfig {
+        let default = FormatConfig::default();
+
+        FormatConfig {
+            max_line_width: self.max_line_width.or(default.max_line_width),
+            trailing_commas: self.trailing_commas.unwrap_or(default.trailing_commas),
+        }
+    }
+
+    /// Read the [FORMAT_CONFIG_FILENAME] that applies to `path`: the one in the closest
+    /// directory containing it. Returns the default options if there is none.
+    pub fn find_for(path: &Path) -> Result<FormatOptions, String> {
+        let absolute = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let start = if absolute.is_dir() {
+            Some(absolute.as_path())
+        } else {
+            absolute.parent()
+        };
+
+        for dir in start.into_iter().flat_map(Path::ancestors) {
+            let config_path = dir.join(FORMAT_CONFIG_FILENAME);
+
+            if config_path.is_file() {
+                return Self::read(&config_path);
+            }
+        }
+
+        Ok(FormatOptions::default())
+    }
+
+    fn read(path: &Path) -> Result<FormatOptions, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| format!("Could not read {}: {err}", path.display()))?;
+
+        serde_json::from_str(&contents).map_err(|err| {
+            format!(
+                "{} is not a valid `roc format` config: {err}",
+                path.display()
+            )
+        })
+    }
+}
+
 fn flatten_directories(files: std::vec::Vec<PathBuf>) -> std::vec::Vec<PathBuf> {
     let mut to_flatten = files;
     let mut files = vec![];
@@ -76,18 +142,38 @@ fn is_roc_file(path: &Path) -> bool {
     matches!(path.extension().and_then(OsStr::to_str), Some("roc"))
 }
 
+/// Format the given files and directories. The `options` from the command line take precedence
+/// over the [FORMAT_CONFIG_FILENAME] that applies to each file.
+///
+/// With `since`, only the top-level defs that overlap lines changed since that git revision are
+/// formatted, and the rest of each file is left as it is.
 pub fn format_files(
     files: std::vec::Vec<PathBuf>,
     mode: FormatMode,
     migrate: bool,
+    options: FormatOptions,
+    since: Option<&str>,
 ) -> Result<(), String> {
     let arena = Bump::new();
     let mut files_to_reformat = Vec::new(); // to track which files failed `roc format --check`
 
     for file in flatten_directories(files) {
         let src = std::fs::read_to_string(&file).unwrap();
+        let config = options.or(FormatOptions::find_for(&file)?).to_config();
+
+        let result = match since {
+            None => format_src(&arena, &src, migrate, config),
+            Some(rev) => match changed_lines(&file, rev)? {
+                // git doesn't know this file, so all of it is new
+                None => format_src(&arena, &src, false, config),
+                Some(lines) if lines.is_empty() => continue,
+                Some(lines) => {
+                    format_changed_src(&arena, &src, &line_byte_ranges(&src, &lines), config)
+                }
+            },
+        };
 
-        match format_src(&arena, &src, migrate) {
+        match result {
             Ok(buf) => {
                 match mode {
                     FormatMode::CheckOnly => {
@@ -206,7 +292,12 @@ pub enum FormatProblem {
     UnsupportedMigration(MigrateError),
 }
 
-pub fn format_src(arena: &Bump, src: &str, migrate: bool) -> Result<String, FormatProblem> {
+pub fn format_src(
+    arena: &Bump,
+    src: &str,
+    migrate: bool,
+    config: FormatConfig,
+) -> Result<String, FormatProblem> {
     let ast = arena.alloc(parse_all(arena, src).unwrap_or_else(|e| {
         user_error!("Unexpected parse failure when parsing this formatting:\n\n{src}\n\nParse error was:\n\n{:#?}\n\n", e)
     }));
@@ -214,7 +305,7 @@ pub fn format_src(arena: &Bump, src: &str, migrate: bool) -> Result<String, Form
         snakify: migrate,
         parens_and_commas: migrate,
     };
-    let mut buf = Buf::new_in(arena, flags);
+    let mut buf = Buf::new_with_config_in(arena, flags, config);
 
     if migrate {
         roc_fmt::migrate::fmt_header(&mut buf, &ast.header)
@@ -227,11 +318,64 @@ pub fn format_src(arena: &Bump, src: &str, migrate: bool) -> Result<String, Form
 
     fmt_all(&mut buf, ast);
 
-    let reparsed_ast = match arena.alloc(parse_all(arena, buf.as_str())) {
+    let formatted = buf.into_bump_str();
+
+    verify_formatting(arena, ast, formatted, |reparsed_ast| {
+        let mut reformatted_buf = Buf::new_with_config_in(arena, MigrationFlags::default(), config);
+
+        fmt_all(&mut reformatted_buf, reparsed_ast);
+
+        reformatted_buf.into_bump_str()
+    })?;
+
+    Ok(formatted.to_string())
+}
+
+/// Format only the top-level defs of `src` that overlap one of the `changed` byte ranges.
+pub fn format_changed_src(
+    arena: &Bump,
+    src: &str,
+    changed: &[Range<usize>],
+    config: FormatConfig,
+) -> Result<String, FormatProblem> {
+    let ast = arena.alloc(parse_all(arena, src).unwrap_or_else(|e| {
+        user_error!("Unexpected parse failure when parsing this formatting:\n\n{src}\n\nParse error was:\n\n{:#?}\n\n", e)
+    }));
+    let flags = MigrationFlags::default();
+
+    let mut buf = Buf::new_with_config_in(arena, flags, config);
+    let formatted_ranges = fmt_defs_in_ranges(&mut buf, src, &ast.defs, changed);
+    let formatted = buf.into_bump_str();
+
+    verify_formatting(arena, ast, formatted, |reparsed_ast| {
+        let mut reformatted_buf = Buf::new_with_config_in(arena, flags, config);
+
+        fmt_defs_in_ranges(
+            &mut reformatted_buf,
+            formatted,
+            &reparsed_ast.defs,
+            &formatted_ranges,
+        );
+
+        reformatted_buf.into_bump_str()
+    })?;
+
+    Ok(formatted.to_string())
+}
+
+/// Check that `formatted` parses to the same tree as `ast`, and that formatting it again with
+/// `reformat` does not change it.
+fn verify_formatting<'a>(
+    arena: &'a Bump,
+    ast: &FullAst<'a>,
+    formatted: &'a str,
+    reformat: impl FnOnce(&'a FullAst<'a>) -> &'a str,
+) -> Result<(), FormatProblem> {
+    let reparsed_ast = match arena.alloc(parse_all(arena, formatted)) {
         Ok(ast) => ast,
         Err(e) => {
             return Err(FormatProblem::ParsingFailed {
-                formatted_src: buf.as_str().to_string(),
+                formatted_src: formatted.to_string(),
                 parse_err: format!("{:?}", e),
             });
         }
@@ -247,25 +391,114 @@ pub fn format_src(arena: &Bump, src: &str, migrate: bool) -> Result<String, Form
     // TODO: fix PartialEq impl on ast types
     if format!("{ast_normalized:?}") != format!("{reparsed_ast_normalized:?}") {
         return Err(FormatProblem::ReformattingChangedAst {
-            formatted_src: buf.as_str().to_string(),
+            formatted_src: formatted.to_string(),
             ast_before: format!("{ast_normalized:#?}\n"),
             ast_after: format!("{reparsed_ast_normalized:#?}\n"),
         });
     }
 
     // Now verify that the resultant formatting is _stable_ - i.e. that it doesn't change again if re-formatted
-    let mut reformatted_buf = Buf::new_in(arena, MigrationFlags::default());
+    let reformatted = reformat(reparsed_ast);
 
-    fmt_all(&mut reformatted_buf, reparsed_ast);
-
-    if buf.as_str() != reformatted_buf.as_str() {
+    if formatted != reformatted {
         return Err(FormatProblem::ReformattingUnstable {
-            formatted_src: buf.as_str().to_string(),
-            reformatted_src: reformatted_buf.as_str().to_string(),
+            formatted_src: formatted.to_string(),
+            reformatted_src: reformatted.to_string(),
         });
     }
 
-    Ok(buf.as_str().to_string())
+    Ok(())
+}
+
+/// The lines of `file` that changed since the git revision `rev`, as ranges of 1-based line
+/// numbers. Returns `None` if git does not track the file, in which case all of it is new.
+fn changed_lines(file: &Path, rev: &str) -> Result<Option<Vec<Range<usize>>>, String> {
+    let dir = match file.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let file_name = file.file_name().unwrap_or(file.as_os_str());
+
+    let run_git = |args: &[&str]| {
+        Command::new("git")
+            .current_dir(dir)
+            .args(args)
+            .arg("--")
+            .arg(file_name)
+            .output()
+            .map_err(|err| {
+                format!(
+                    "Could not run git to find the changes in {}: {err}",
+                    file.display()
+                )
+            })
+    };
+
+    let diff = run_git(&["diff", "--unified=0", "--no-color", "--no-ext-diff", rev])?;
+
+    if !diff.status.success() {
+        return Err(format!(
+            "`git diff {rev}` failed for {}:\n{}",
+            file.display(),
+            String::from_utf8_lossy(&diff.stderr).trim()
+        ));
+    }
+
+    let diff = String::from_utf8_lossy(&diff.stdout);
+
+    if diff.is_empty() {
+        let tracked = run_git(&["ls-files", "--error-unmatch"])?;
+
+        if !tracked.status.success() {
+            return Ok(None);
+        }
+    }
+
+    Ok(Some(parse_changed_lines(&diff)))
+}
+
+/// Read the changed lines of the new version of a file from the hunk headers of a
+/// `git diff --unified=0`, which look like `@@ -12,3 +12,4 @@`.
+fn parse_changed_lines(diff: &str) -> Vec<Range<usize>> {
+    let mut lines = Vec::new();
+
+    for hunk_header in diff.lines().filter(|line| line.starts_with("@@ ")) {
+        let Some(added) = hunk_header
+            .split_whitespace()
+            .find_map(|part| part.strip_prefix('+'))
+        else {
+            continue;
+        };
+
+        let (start, count) = match added.split_once(',') {
+            Some((start, count)) => (start.parse::<usize>(), count.parse()),
+            None => (added.parse::<usize>(), Ok(1)),
+        };
+
+        match (start, count) {
+            // Lines were only removed, after line `start`. The defs on either side may have
+            // changed, e.g. by losing their last line.
+            (Ok(start), Ok(0)) => lines.push(start.max(1)..start + 2),
+            (Ok(start), Ok(count)) => lines.push(start..start + count),
+            _ => {}
+        }
+    }
+
+    lines
+}
+
+/// Convert ranges of 1-based line numbers to the ranges of bytes those lines take up in `src`.
+fn line_byte_ranges(src: &str, lines: &[Range<usize>]) -> Vec<Range<usize>> {
+    let mut line_starts = vec![0];
+    line_starts.extend(src.match_indices('\n').map(|(index, _)| index + 1));
+    line_starts.push(src.len());
+
+    let line_start = |line: usize| line_starts[(line - 1).min(line_starts.len() - 1)];
+
+    lines
+        .iter()
+        .map(|range| line_start(range.start)..line_start(range.end))
+        .collect()
 }
 
 fn parse_all<'a>(arena: &'a Bump, src: &'a str) -> Result<FullAst<'a>, SyntaxError<'a>> {
@@ -502,7 +735,13 @@ main =
     fn test_single_file_needs_reformatting() {
         let dir = tempdir().unwrap();
         let file_path = setup_test_file(dir.path(), "test1.roc", UNFORMATTED_ROC);
-        let result = format_files(vec![file_path.clone()], FormatMode::CheckOnly, false);
+        let result = format_files(
+            vec![file_path.clone()],
+            FormatMode::CheckOnly,
+            false,
+            FormatOptions::default(),
+            None,
+        );
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err(),
@@ -520,7 +759,13 @@ main =
         let dir = tempdir().unwrap();
         let file1 = setup_test_file(dir.path(), "test1.roc", UNFORMATTED_ROC);
         let file2 = setup_test_file(dir.path(), "test2.roc", UNFORMATTED_ROC);
-        let result = format_files(vec![file1, file2], FormatMode::CheckOnly, false);
+        let result = format_files(
+            vec![file1, file2],
+            FormatMode::CheckOnly,
+            false,
+            FormatOptions::default(),
+            None,
+        );
         assert!(result.is_err());
         let error_message = result.unwrap_err();
         assert!(error_message.contains("test1.roc") && error_message.contains("test2.roc"));
@@ -532,7 +777,13 @@ main =
     fn test_no_files_need_reformatting() {
         let dir = tempdir().unwrap();
         let file_path = setup_test_file(dir.path(), "formatted.roc", FORMATTED_ROC);
-        let result = format_files(vec![file_path], FormatMode::CheckOnly, false);
+        let result = format_files(
+            vec![file_path],
+            FormatMode::CheckOnly,
+            false,
+            FormatOptions::default(),
+            None,
+        );
         assert!(result.is_ok());
 
         cleanup_temp_dir(dir);
@@ -548,6 +799,8 @@ main =
             vec![file_formatted, file1_unformated, file2_unformated],
             FormatMode::CheckOnly,
             false,
+            FormatOptions::default(),
+            None,
         );
         assert!(result.is_err());
         let error_message = result.unwrap_err();
@@ -557,6 +810,178 @@ main =
         cleanup_temp_dir(dir);
     }
 
+    #[test]
+    fn test_config_file_and_overrides() {
+        let dir = tempdir().unwrap();
+        let package_dir = dir.path().join("package");
+        let nested_dir = package_dir.join("nested");
+        std::fs::create_dir_all(&nested_dir).unwrap();
+
+        let outside = setup_test_file(dir.path(), "outside.roc", FORMATTED_ROC);
+        let nested = setup_test_file(&nested_dir, "nested.roc", FORMATTED_ROC);
+
+        std::fs::write(
+            package_dir.join(FORMAT_CONFIG_FILENAME),
+            r#"{ "max-line-width": 80, "trailing-commas": false }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            FormatOptions::find_for(&outside).unwrap(),
+            FormatOptions::default()
+        );
+
+        let from_file = FormatOptions::find_for(&nested).unwrap();
+        assert_eq!(
+            from_file,
+            FormatOptions {
+                max_line_width: Some(80),
+                trailing_commas: Some(false),
+            }
+        );
+
+        let from_flags = FormatOptions {
+            max_line_width: Some(120),
+            trailing_commas: None,
+        };
+        assert_eq!(
+            from_flags.or(from_file).to_config(),
+            FormatConfig {
+                max_line_width: Some(120),
+                trailing_commas: false,
+            }
+        );
+
+        std::fs::write(
+            package_dir.join(FORMAT_CONFIG_FILENAME),
+            r#"{ "line-width": 80 }"#,
+        )
+        .unwrap();
+        assert!(FormatOptions::find_for(&nested).is_err());
+
+        cleanup_temp_dir(dir);
+    }
+
+    #[test]
+    fn test_default_config_leaves_long_lines() {
+        let src = indoc! {r#"
+            module [numbers]
+
+            numbers = [one_hundred_and_one, one_hundred_and_two, one_hundred_and_three, one_hundred_and_four]
+        "#};
+
+        let arena = Bump::new();
+        let formatted = format_src(&arena, src, false, FormatConfig::default()).unwrap();
+
+        assert_eq!(formatted, src);
+    }
+
+    #[test]
+    fn test_wrap_to_max_line_width() {
+        let src = indoc! {r#"
+            module [numbers]
+
+            numbers = List.concat [one_hundred_and_one, one_hundred_and_two] [one_hundred_and_three, one_hundred_and_four]
+        "#};
+
+        let config = FormatConfig {
+            max_line_width: Some(40),
+            trailing_commas: true,
+        };
+
+        let arena = Bump::new();
+        let formatted = format_src(&arena, src, false, config).unwrap();
+
+        assert_eq!(
+            formatted,
+            indoc! {r#"
+                module [numbers]
+
+                numbers = List.concat
+                    [
+                        one_hundred_and_one,
+                        one_hundred_and_two,
+                    ]
+                    [
+                        one_hundred_and_three,
+                        one_hundred_and_four,
+                    ]
+            "#}
+        );
+    }
+
+    #[test]
+    fn test_parse_changed_lines() {
+        let diff = indoc! {r#"
+            diff --git a/main.roc b/main.roc
+            index 1b2c3d4..5e6f7a8 100644
+            --- a/main.roc
+            +++ b/main.roc
+            @@ -3 +3 @@ main =
+            -    1
+            +    2
+            @@ -10,2 +10,3 @@ other =
+            @@ -20,4 +21,0 @@ removed =
+        "#};
+
+        assert_eq!(parse_changed_lines(diff), vec![3..4, 10..13, 21..23]);
+
+        let src = "a\nbb\nccc\n";
+        assert_eq!(line_byte_ranges(src, &[2..3, 3..5]), vec![2..5, 5..9]);
+    }
+
+    #[test]
+    fn test_format_only_changed_defs() {
+        let dir = tempdir().unwrap();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .current_dir(dir.path())
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {args:?} failed");
+        };
+
+        let committed = indoc! {r#"
+            module [a, b]
+
+            a =   1
+
+            b =   2
+        "#};
+        let file = setup_test_file(dir.path(), "Main.roc", committed);
+
+        git(&["init", "--quiet"]);
+        git(&["add", "Main.roc"]);
+        git(&["commit", "--quiet", "-m", "initial"]);
+
+        std::fs::write(&file, committed.replace("b =   2", "b =   3")).unwrap();
+
+        format_files(
+            vec![file.clone()],
+            FormatMode::WriteToFile,
+            false,
+            FormatOptions::default(),
+            Some("HEAD"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            read_to_string(&file).unwrap(),
+            indoc! {r#"
+                module [a, b]
+
+                a =   1
+
+                b = 3
+            "#}
+        );
+
+        cleanup_temp_dir(dir);
+    }
+
     const HEADER: &str = indoc! {r#"
         interface Test
             exposes []
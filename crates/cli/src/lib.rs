@@ -41,12 +41,19 @@ use strum::IntoEnumIterator;
 #[cfg(not(target_os = "linux"))]
 use tempfile::TempDir;
 
+mod api_diff;
+#[cfg(not(windows))]
+mod bench;
+#[cfg(not(windows))]
+mod coverage;
 mod format;
 pub use format::{
     annotate_file, annotation_edit, annotation_edits, format_files, format_src, AnnotationProblem,
-    FormatMode,
+    FormatMode, FormatOptions,
 };
 
+pub mod watch;
+
 pub const CMD_BUILD: &str = "build";
 pub const CMD_RUN: &str = "run";
 pub const CMD_DEV: &str = "dev";
@@ -57,9 +64,11 @@ pub const CMD_VERSION: &str = "version";
 pub const CMD_FORMAT: &str = "format";
 pub const CMD_FORMAT_ANNOTATE: &str = "annotate";
 pub const CMD_TEST: &str = "test";
+pub const CMD_BENCH: &str = "bench";
 pub const CMD_GLUE: &str = "glue";
 pub const CMD_PREPROCESS_HOST: &str = "preprocess-host";
 pub const CMD_LICENSES: &str = "licenses";
+pub const CMD_API_DIFF: &str = "api-diff";
 
 pub const FLAG_EMIT_LLVM_IR: &str = "emit-llvm-ir";
 pub const FLAG_PROFILING: &str = "profiling";
@@ -84,17 +93,32 @@ pub const FLAG_STDOUT: &str = "stdout";
 pub const FLAG_WASM_STACK_SIZE_KB: &str = "wasm-stack-size-kb";
 pub const FLAG_OUTPUT: &str = "output";
 pub const FLAG_FUZZ: &str = "fuzz";
+pub const FLAG_EXPORT_DICT_SEED: &str = "export-dict-seed";
 pub const FLAG_MAIN: &str = "main";
+pub const FLAG_COVERAGE: &str = "coverage";
+pub const FLAG_DOC: &str = "doc";
+pub const FLAG_WATCH: &str = "watch";
+pub const FLAG_BASELINE: &str = "baseline";
+pub const FLAG_SAVE_BASELINE: &str = "save-baseline";
 pub const ROC_FILE: &str = "ROC_FILE";
 pub const GLUE_DIR: &str = "GLUE_DIR";
 pub const GLUE_SPEC: &str = "GLUE_SPEC";
 pub const DIRECTORY_OR_FILES: &str = "DIRECTORY_OR_FILES";
+pub const OLD_ROC_FILE: &str = "OLD_ROC_FILE";
+pub const NEW_ROC_FILE: &str = "NEW_ROC_FILE";
 pub const ARGS_FOR_APP: &str = "ARGS_FOR_APP";
 pub const FLAG_PP_HOST: &str = "host";
 pub const FLAG_PP_PLATFORM: &str = "platform";
 pub const FLAG_PP_DYLIB: &str = "lib";
 pub const FLAG_MIGRATE: &str = "migrate";
+pub const FLAG_MAX_LINE_WIDTH: &str = "max-line-width";
+pub const FLAG_TRAILING_COMMAS: &str = "trailing-commas";
+pub const FLAG_CHANGED: &str = "changed";
+pub const FLAG_SINCE: &str = "since";
 pub const FLAG_DOCS_ROOT: &str = "root-dir";
+pub const FLAG_DOCS_FORMAT: &str = "format";
+pub const FLAG_DOCS_SOURCE_URL: &str = "source-url";
+pub const FLAG_VERIFY: &str = "verify";
 
 pub const VERSION: &str = env!("ROC_VERSION");
 const DEFAULT_GENERATED_DOCS_DIR: &str = "generated-docs";
@@ -172,12 +196,24 @@ pub fn build_app() -> Command {
         .action(ArgAction::SetTrue)
         .required(false);
 
+    let flag_export_dict_seed = Arg::new(FLAG_EXPORT_DICT_SEED)
+        .long(FLAG_EXPORT_DICT_SEED)
+        .help("Export the seed the app's Dict hashes keys with as roc_dict_pseudo_seed,\nfor hosts that build or look up Dicts themselves (like roc_std's RocDict)")
+        .action(ArgAction::SetTrue)
+        .required(false);
+
     let flag_main = Arg::new(FLAG_MAIN)
         .long(FLAG_MAIN)
         .help("The .roc file of the main app/package module to resolve dependencies from")
         .value_parser(value_parser!(PathBuf))
         .required(false);
 
+    let flag_watch = Arg::new(FLAG_WATCH)
+        .long(FLAG_WATCH)
+        .help("Keep running, and run again whenever one of the .roc files changes")
+        .action(ArgAction::SetTrue)
+        .required(false);
+
     let roc_file_to_run = Arg::new(ROC_FILE)
         .help("The .roc file of an app to run")
         .value_parser(value_parser!(PathBuf))
@@ -222,6 +258,7 @@ pub fn build_app() -> Command {
             .arg(flag_build_host.clone())
             .arg(flag_suppress_build_host_warning.clone())
             .arg(flag_fuzz.clone())
+            .arg(flag_export_dict_seed.clone())
             .arg(flag_wasm_stack_size_kb)
             .arg(
                 Arg::new(FLAG_TARGET)
@@ -282,6 +319,7 @@ pub fn build_app() -> Command {
             .arg(flag_build_host.clone())
             .arg(flag_suppress_build_host_warning.clone())
             .arg(flag_fuzz.clone())
+            .arg(flag_watch.clone())
             .arg(
                 Arg::new(FLAG_VERBOSE)
                     .long(FLAG_VERBOSE)
@@ -289,6 +327,20 @@ pub fn build_app() -> Command {
                     .action(ArgAction::SetTrue)
                     .required(false)
             )
+            .arg(
+                Arg::new(FLAG_COVERAGE)
+                    .long(FLAG_COVERAGE)
+                    .help("Record which defs and branches the tests run, print a summary per module, and write an LCOV report to lcov.info")
+                    .action(ArgAction::SetTrue)
+                    .required(false)
+            )
+            .arg(
+                Arg::new(FLAG_DOC)
+                    .long(FLAG_DOC)
+                    .help("Also run the ```roc code blocks in the doc comments of the tested modules, as expects")
+                    .action(ArgAction::SetTrue)
+                    .required(false)
+            )
             .arg(
                 Arg::new(ROC_FILE)
                     .help("The .roc file to test")
@@ -299,6 +351,32 @@ pub fn build_app() -> Command {
             )
             .arg(args_for_app.clone().last(true))
         )
+        .subcommand(Command::new(CMD_BENCH)
+            .about("Time all top-level functions named `bench_…` that take `{}` or a `bench_…_input`, in an optimized build of a module")
+            .arg(flag_main.clone())
+            .arg(flag_max_threads.clone())
+            .arg(
+                Arg::new(FLAG_BASELINE)
+                    .long(FLAG_BASELINE)
+                    .help("Compare the timings with a baseline saved by --save-baseline")
+                    .value_parser(value_parser!(PathBuf))
+                    .required(false)
+            )
+            .arg(
+                Arg::new(FLAG_SAVE_BASELINE)
+                    .long(FLAG_SAVE_BASELINE)
+                    .help("Save the timings as a JSON baseline to compare later runs with")
+                    .value_parser(value_parser!(PathBuf))
+                    .required(false)
+            )
+            .arg(
+                Arg::new(ROC_FILE)
+                    .help("The .roc file with the benchmarks")
+                    .value_parser(value_parser!(PathBuf))
+                    .required(false)
+                    .default_value(DEFAULT_ROC_FILENAME)
+            )
+        )
         .subcommand(Command::new(CMD_REPL)
             .about("Launch the interactive Read Eval Print Loop (REPL)")
             .arg(
@@ -329,6 +407,7 @@ pub fn build_app() -> Command {
             .arg(flag_build_host.clone())
             .arg(flag_suppress_build_host_warning.clone())
             .arg(flag_fuzz.clone())
+            .arg(flag_export_dict_seed.clone())
             .arg(roc_file_to_run.clone())
             .arg(args_for_app.clone().last(true))
         )
@@ -345,6 +424,7 @@ pub fn build_app() -> Command {
             .arg(flag_build_host.clone())
             .arg(flag_suppress_build_host_warning.clone())
             .arg(flag_fuzz.clone())
+            .arg(flag_export_dict_seed.clone())
             .arg(roc_file_to_run.clone())
             .arg(args_for_app.clone().last(true))
         )
@@ -370,6 +450,37 @@ pub fn build_app() -> Command {
                     .action(ArgAction::SetTrue)
                     .required(false),
             )
+            .arg(
+                Arg::new(FLAG_CHANGED)
+                    .long(FLAG_CHANGED)
+                    .help("Only format the top-level definitions that overlap lines changed since the last git commit")
+                    .action(ArgAction::SetTrue)
+                    .conflicts_with(FLAG_MIGRATE)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_SINCE)
+                    .long(FLAG_SINCE)
+                    .help("Like --changed, but for the lines changed since the given git revision")
+                    .value_name("REV")
+                    .value_parser(value_parser!(String))
+                    .conflicts_with(FLAG_MIGRATE)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_MAX_LINE_WIDTH)
+                    .long(FLAG_MAX_LINE_WIDTH)
+                    .help("Wrap applications, records, lists and tuples that would run past this column\n(Overrides the max-line-width setting in roc-format.json.)")
+                    .value_parser(value_parser!(u16))
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_TRAILING_COMMAS)
+                    .long(FLAG_TRAILING_COMMAS)
+                    .help("Whether to put a comma after the last item of a multiline collection\n(Overrides the trailing-commas setting in roc-format.json.)")
+                    .value_parser(value_parser!(bool))
+                    .required(false),
+            )
             .arg(
                 Arg::new(FLAG_STDIN)
                     .long(FLAG_STDIN)
@@ -384,7 +495,7 @@ pub fn build_app() -> Command {
                     .action(ArgAction::SetTrue)
                     .required(false),
             )
-            .after_help("If DIRECTORY_OR_FILES is omitted, the .roc files in the current working\ndirectory are formatted.")
+            .after_help("If DIRECTORY_OR_FILES is omitted, the .roc files in the current working\ndirectory are formatted.\n\nStyle options are read from the roc-format.json in the closest directory containing\neach file, e.g. {\"max-line-width\": 100, \"trailing-commas\": true}")
             .subcommand(Command::new(CMD_FORMAT_ANNOTATE)
                 .about("Annotate all top level definitions from a .roc file")
                 .arg(
@@ -405,6 +516,7 @@ pub fn build_app() -> Command {
             .arg(flag_main.clone())
             .arg(flag_time.clone())
             .arg(flag_max_threads.clone())
+            .arg(flag_watch.clone())
             .arg(
                 Arg::new(ROC_FILE)
                     .help("The .roc file to check")
@@ -430,6 +542,34 @@ pub fn build_app() -> Command {
                     .default_value(DEFAULT_ROC_FILENAME),
                 )
                 .arg(flag_docs_root_dir)
+                .arg(Arg::new(FLAG_DOCS_FORMAT)
+                    .long(FLAG_DOCS_FORMAT)
+                    .help("Generate an HTML site, a docs.json file following the schema in crates/docs/docs.schema.json, a Markdown file per module, or a man page per module (in a man3 directory, for `man -M`)")
+                    .value_parser(["html", "json", "markdown", "man"])
+                    .required(false)
+                    .default_value("html"),
+                )
+                .arg(Arg::new(FLAG_DOCS_SOURCE_URL)
+                    .long(FLAG_DOCS_SOURCE_URL)
+                    .help("Link each entry to its source in a repository, like https://github.com/org/repo/blob/main/{path}#L{line}\n({path} is the module's path relative to the package's main .roc file. By default, the links point to highlighted copies of the sources generated alongside the docs.)")
+                    .value_name("URL_TEMPLATE")
+                    .value_parser(value_parser!(String))
+                    .required(false),
+                )
+        )
+        .subcommand(
+            Command::new(CMD_API_DIFF)
+                .about("Compare the exposed API of two versions of a package, and suggest a version bump\n(Exits with code 1 if there are breaking changes.)")
+                .arg(Arg::new(OLD_ROC_FILE)
+                    .help("The main .roc file of the old version of the package")
+                    .value_parser(value_parser!(PathBuf))
+                    .required(true),
+                )
+                .arg(Arg::new(NEW_ROC_FILE)
+                    .help("The main .roc file of the new version of the package")
+                    .value_parser(value_parser!(PathBuf))
+                    .required(true),
+                )
         )
         .subcommand(Command::new(CMD_GLUE)
             .about("Generate glue code between a platform's Roc API and its host language")
@@ -454,6 +594,13 @@ pub fn build_app() -> Command {
                     .default_value(DEFAULT_ROC_FILENAME)
             )
             .arg(flag_linker.clone())
+            .arg(
+                Arg::new(FLAG_VERIFY)
+                    .long(FLAG_VERIFY)
+                    .help("Compile the generated glue with the host's compiler, to check that it lays out each type the way Roc does")
+                    .action(ArgAction::SetTrue)
+                    .required(false)
+            )
         )
         .subcommand(Command::new(CMD_PREPROCESS_HOST)
             .about("Runs the surgical linker preprocessor to generate `.rh` and `.rm` files.")
@@ -502,6 +649,7 @@ pub fn build_app() -> Command {
         .arg(flag_build_host)
         .arg(flag_suppress_build_host_warning)
         .arg(flag_fuzz)
+        .arg(flag_export_dict_seed)
         .arg(roc_file_to_run)
         .arg(args_for_app.trailing_var_arg(true))
 }
@@ -527,6 +675,36 @@ fn opt_level_from_flags(matches: &ArgMatches) -> OptLevel {
     }
 }
 
+/// Prints the differences between the exposed APIs of two versions of a package. Returns 1 if
+/// there are breaking changes, so CI can catch them.
+pub fn api_diff(matches: &ArgMatches) -> i32 {
+    let load_api = |arg: &str| {
+        let path = matches.get_one::<PathBuf>(arg).unwrap();
+        let mut loaded_module = roc_docs::load_module_for_docs(path.to_owned());
+
+        roc_docs::get_exposed_module_docs(&mut loaded_module)
+            .iter()
+            .map(|(module_id, docs)| {
+                api_diff::ApiModule::from_docs(docs, |symbol| {
+                    api_diff::solved_type(&loaded_module, *module_id, symbol)
+                })
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let old = load_api(OLD_ROC_FILE);
+    let new = load_api(NEW_ROC_FILE);
+    let diff = api_diff::diff_apis(&old, &new);
+
+    print!("{}", api_diff::render_report(&diff));
+
+    if diff.has_breaking_changes() {
+        1
+    } else {
+        0
+    }
+}
+
 #[cfg(windows)]
 pub fn test(_matches: &ArgMatches, _target: Target) -> io::Result<i32> {
     todo!("running tests does not work on windows right now")
@@ -542,21 +720,6 @@ struct ModuleTestResults {
 
 #[cfg(not(windows))]
 pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
-    use roc_build::program::report_problems_monomorphized;
-    use roc_load::{ExecutionMode, FunctionKind, LoadConfig, LoadMonomorphizedError};
-    use roc_packaging::cache;
-
-    let start_time = Instant::now();
-    let arena = Bump::new();
-    let opt_level = opt_level_from_flags(matches);
-
-    let threading = match matches.get_one::<usize>(FLAG_MAX_THREADS) {
-        None => Threading::AllAvailable,
-        Some(0) => user_error!("cannot build with at most 0 threads"),
-        Some(1) => Threading::Single,
-        Some(n) => Threading::AtMost(*n),
-    };
-
     let paths: Vec<_> = matches.get_many::<PathBuf>(ROC_FILE).unwrap().collect();
 
     let paths: Vec<_> = {
@@ -589,10 +752,92 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
         flatten_paths
     };
 
+    let mut session = watch::TestSession::default();
+
+    if !matches.get_flag(FLAG_WATCH) {
+        return test_paths(matches, target, &paths, None, &mut session);
+    }
+
+    for path in paths.iter() {
+        session.watcher.watch_root(path);
+    }
+
+    let mut changed_files = None;
+
+    loop {
+        test_paths(
+            matches,
+            target,
+            &paths,
+            changed_files.as_ref(),
+            &mut session,
+        )?;
+
+        watch::print_watching();
+
+        changed_files = Some(session.watcher.wait_for_changes());
+    }
+}
+
+/// Run the expects in the given files and the modules they import.
+///
+/// In watch mode, `only_changed` holds the files that changed since the previous run. Only the
+/// expects in those files and in the modules that import them are run again; the others keep
+/// their results from the previous run. Likewise, only those modules are type checked again. A
+/// file whose module graph contains none of the changes is not loaded again at all.
+#[cfg(not(windows))]
+fn test_paths(
+    matches: &ArgMatches,
+    target: Target,
+    paths: &[PathBuf],
+    only_changed: Option<&roc_collections::MutSet<PathBuf>>,
+    session: &mut watch::TestSession,
+) -> io::Result<i32> {
+    use roc_build::program::report_problems_monomorphized;
+    use roc_load::{ExecutionMode, FunctionKind, LoadConfig, LoadMonomorphizedError, TestOptions};
+    use roc_packaging::cache;
+
+    let start_time = Instant::now();
+    let arena = Bump::new();
+    let opt_level = opt_level_from_flags(matches);
+
+    let threading = match matches.get_one::<usize>(FLAG_MAX_THREADS) {
+        None => Threading::AllAvailable,
+        Some(0) => user_error!("cannot build with at most 0 threads"),
+        Some(1) => Threading::Single,
+        Some(n) => Threading::AtMost(*n),
+    };
+
+    let collect_coverage = matches.get_flag(FLAG_COVERAGE);
+    let exec_mode = ExecutionMode::Test(TestOptions {
+        coverage: collect_coverage,
+        doc_tests: matches.get_flag(FLAG_DOC),
+    });
+    let mut coverage_report = coverage::CoverageReport::default();
+
     let mut all_files_total_failed_count = 0;
     let mut all_files_total_passed_count = 0;
 
     for path in paths.iter() {
+        let previous_graph = session.graphs.remove(path);
+
+        let affected = only_changed
+            .zip(previous_graph.as_ref())
+            .map(|(changed, graph)| graph.affected_by(changed));
+
+        if affected
+            .as_ref()
+            .is_some_and(|affected| affected.is_empty())
+        {
+            let graph = previous_graph.unwrap();
+            let (failed_count, passed_count) = graph.total_results();
+            all_files_total_failed_count += failed_count;
+            all_files_total_passed_count += passed_count;
+
+            session.graphs.insert(path.clone(), graph);
+            continue;
+        }
+
         let arena = &arena;
         let function_kind = FunctionKind::from_env();
 
@@ -606,14 +851,15 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
             render: roc_reporting::report::RenderTarget::ColorTerminal,
             palette: roc_reporting::report::DEFAULT_PALETTE,
             threading,
-            exec_mode: ExecutionMode::Test,
+            exec_mode,
         };
-        let load_result = roc_load::load_and_monomorphize(
+        let load_result = roc_load::load_and_monomorphize_with_cache(
             arena,
             path.to_path_buf(),
             opt_main_path.cloned(),
             RocCacheDir::Persistent(cache::roc_cache_packages_dir().as_path()),
             load_config,
+            session.load_caches.entry(path.clone()).or_default(),
         );
 
         let mut loaded = match load_result {
@@ -621,17 +867,33 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
             Err(LoadMonomorphizedError::LoadingProblem(problem)) => {
                 return handle_loading_problem(problem);
             }
-            Err(LoadMonomorphizedError::ErrorModule(module)) => {
+            Err(LoadMonomorphizedError::ErrorModule(mut module)) => {
+                session
+                    .load_caches
+                    .insert(path.clone(), std::mem::take(&mut module.cache));
+
                 return handle_error_module(module, start_time.elapsed());
             }
         };
         let problems = report_problems_monomorphized(&mut loaded);
 
+        session
+            .load_caches
+            .insert(path.clone(), std::mem::take(&mut loaded.cache));
+
         let mut expectations = std::mem::take(&mut loaded.expectations);
 
         let interns = loaded.interns.clone();
         let sources = loaded.sources.clone();
 
+        session.watcher.record(
+            sources
+                .values()
+                .map(|(module_path, source)| (module_path.as_path(), &**source)),
+        );
+
+        let mut graph = watch::ModuleGraph::new(&sources, &loaded.imports);
+
         let (dyn_lib, expects_by_module, layout_interner) =
             roc_repl_expect::run::expect_mono_module_to_dylib(
                 arena,
@@ -669,6 +931,21 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
         let compilation_duration = start_time.elapsed();
 
         for (module_id, expects) in expects_by_module.into_iter() {
+            let (module_path, _) = sources.get(&module_id).unwrap();
+
+            if let (Some(affected), Some(previous_graph)) = (&affected, &previous_graph) {
+                if !affected.contains(module_path) {
+                    if let Some((failed_count, passed_count)) =
+                        previous_graph.previous_result(module_path)
+                    {
+                        graph.record_result(module_path, failed_count, passed_count);
+                        all_files_total_failed_count += failed_count;
+                        all_files_total_passed_count += passed_count;
+                        continue;
+                    }
+                }
+            }
+
             let test_start_time = Instant::now();
 
             let (failed_count, passed_count) = roc_repl_expect::run::run_toplevel_expects(
@@ -685,6 +962,8 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
 
             let tests_duration = test_start_time.elapsed();
 
+            graph.record_result(module_path, failed_count, passed_count);
+
             results_by_module.push(ModuleTestResults {
                 module_id,
                 failed_count,
@@ -696,6 +975,21 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
             total_passed_count += passed_count;
         }
 
+        session.graphs.insert(path.clone(), graph);
+
+        if collect_coverage {
+            for (module_id, data) in expectations.iter() {
+                let Some(sites) = &data.coverage else {
+                    continue;
+                };
+                let (module_path, source) = sources.get(module_id).unwrap();
+
+                coverage_report.add_module(module_path, source, interns, sites, |point| {
+                    roc_repl_expect::run::coverage_hits(&dyn_lib, point)
+                });
+            }
+        }
+
         let total_duration = start_time.elapsed();
         all_files_total_failed_count += total_failed_count;
         all_files_total_passed_count += total_passed_count;
@@ -713,6 +1007,15 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
             println!("{test_summary_str}");
         }
     }
+
+    if !coverage_report.is_empty() {
+        println!("\n{}", coverage_report.summary());
+
+        let lcov_path = Path::new(coverage::DEFAULT_LCOV_FILENAME);
+        std::fs::write(lcov_path, coverage_report.to_lcov())?;
+        println!("Wrote coverage report to {}", lcov_path.display());
+    }
+
     if all_files_total_failed_count == 0 && all_files_total_passed_count == 0 {
         // TODO print this in a more nicely formatted way!
         println!("No expectations were found.");
@@ -728,6 +1031,133 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
     }
 }
 
+#[cfg(windows)]
+pub fn bench(_matches: &ArgMatches, _target: Target) -> io::Result<i32> {
+    todo!("running benchmarks does not work on windows right now")
+}
+
+#[cfg(not(windows))]
+pub fn bench(matches: &ArgMatches, target: Target) -> io::Result<i32> {
+    use roc_build::program::report_problems_monomorphized;
+    use roc_load::{ExecutionMode, FunctionKind, LoadConfig, LoadMonomorphizedError};
+    use roc_packaging::cache;
+    use roc_repl_expect::run::BenchFunction;
+
+    let start_time = Instant::now();
+    let arena = Bump::new();
+
+    let threading = match matches.get_one::<usize>(FLAG_MAX_THREADS) {
+        None => Threading::AllAvailable,
+        Some(0) => user_error!("cannot build with at most 0 threads"),
+        Some(1) => Threading::Single,
+        Some(n) => Threading::AtMost(*n),
+    };
+
+    let path = matches.get_one::<PathBuf>(ROC_FILE).unwrap();
+    let opt_main_path = matches.get_one::<PathBuf>(FLAG_MAIN);
+
+    let baseline = match matches.get_one::<PathBuf>(FLAG_BASELINE) {
+        Some(baseline_path) => match bench::Baseline::read(baseline_path) {
+            Ok(baseline) => Some(baseline),
+            Err(message) => user_error!("{message}"),
+        },
+        None => None,
+    };
+
+    let load_config = LoadConfig {
+        target,
+        function_kind: FunctionKind::from_env(),
+        render: roc_reporting::report::RenderTarget::ColorTerminal,
+        palette: roc_reporting::report::DEFAULT_PALETTE,
+        threading,
+        exec_mode: ExecutionMode::Bench,
+    };
+    let load_result = roc_load::load_and_monomorphize(
+        &arena,
+        path.to_path_buf(),
+        opt_main_path.cloned(),
+        RocCacheDir::Persistent(cache::roc_cache_packages_dir().as_path()),
+        load_config,
+    );
+
+    let mut loaded = match load_result {
+        Ok(loaded) => loaded,
+        Err(LoadMonomorphizedError::LoadingProblem(problem)) => {
+            return handle_loading_problem(problem);
+        }
+        Err(LoadMonomorphizedError::ErrorModule(module)) => {
+            return handle_error_module(module, start_time.elapsed());
+        }
+    };
+    let problems = report_problems_monomorphized(&mut loaded);
+
+    debug_assert_eq!(
+        problems.errors, 0,
+        "if there were errors, we would have already exited."
+    );
+    if problems.warnings > 0 {
+        problems.print_error_warning_count(start_time.elapsed());
+        println!(".\n");
+    }
+
+    let interns = loaded.interns.clone();
+
+    // Benchmarks are always timed in an optimized build; anything else would be misleading.
+    let (dyn_lib, expects_by_module, _layout_interner) =
+        roc_repl_expect::run::expect_mono_module_to_dylib(
+            &arena,
+            target,
+            loaded,
+            OptLevel::Optimize,
+            LlvmBackendMode::CliTest,
+        )
+        .unwrap();
+
+    let _memory = roc_repl_expect::run::bench_memory(&dyn_lib);
+
+    let mut results = bench::Baseline::default();
+    let mut crashed = 0;
+
+    for toplevel_bench in expects_by_module
+        .values()
+        .flat_map(|expects| expects.benches.iter())
+    {
+        let name = toplevel_bench.symbol.as_str(&interns);
+        let function = BenchFunction::new(&dyn_lib, toplevel_bench);
+
+        match bench::measure(|| function.call()) {
+            Ok(stats) => {
+                let baseline_stats = baseline
+                    .as_ref()
+                    .and_then(|baseline| baseline.benches.get(name));
+
+                println!("{}", bench::report(name, &stats, baseline_stats));
+
+                results.benches.insert(name.to_string(), stats);
+            }
+            Err(message) => {
+                println!("{name}: crashed with \"{message}\"");
+
+                crashed += 1;
+            }
+        }
+    }
+
+    if results.benches.is_empty() && crashed == 0 {
+        println!("No benchmarks were found. Benchmarks are top-level functions named `bench_…` that take `{{}}` or the value of a top-level `bench_…_input` constant.");
+
+        return Ok(2);
+    }
+
+    if let Some(baseline_path) = matches.get_one::<PathBuf>(FLAG_SAVE_BASELINE) {
+        results.write(baseline_path)?;
+
+        println!("\nSaved the timings to {}", baseline_path.display());
+    }
+
+    Ok((crashed > 0) as i32)
+}
+
 fn find_all_roc_files(path: &PathBuf, flatten_paths: &mut Vec<PathBuf>) {
     if path.is_dir() {
         if let Ok(entries) = std::fs::read_dir(path) {
@@ -988,6 +1418,11 @@ pub fn build(
         user_error!("Cannot instrument binary for fuzzing while using a dev backend.");
     }
 
+    let export_dict_seed = matches.get_flag(FLAG_EXPORT_DICT_SEED);
+    if export_dict_seed && !matches!(code_gen_backend, CodeGenBackend::Llvm(_)) {
+        user_error!("Cannot export the Dict seed while using a dev backend.");
+    }
+
     let wasm_dev_stack_bytes: Option<u32> = matches
         .try_get_one::<u32>(FLAG_WASM_STACK_SIZE_KB)
         .ok()
@@ -1005,6 +1440,7 @@ pub fn build(
         emit_debug_info,
         emit_llvm_ir,
         fuzz,
+        export_dict_seed,
     };
 
     let load_config = standard_load_config(target, build_ordering, threading);
@@ -2,20 +2,23 @@
 use bumpalo::Bump;
 use roc_build::link::LinkType;
 use roc_build::program::{check_file, CodeGenBackend};
+use roc_cli::watch::{print_watching, Watcher};
 use roc_cli::{
-    annotate_file, build_app, default_linking_strategy, format_files, format_src, test,
-    AnnotationProblem, BuildConfig, FormatMode, CMD_BUILD, CMD_CHECK, CMD_DEV, CMD_DOCS,
-    CMD_FORMAT, CMD_FORMAT_ANNOTATE, CMD_GLUE, CMD_LICENSES, CMD_PREPROCESS_HOST, CMD_REPL,
-    CMD_RUN, CMD_TEST, CMD_VERSION, DIRECTORY_OR_FILES, FLAG_CHECK, FLAG_DEV, FLAG_DOCS_ROOT,
-    FLAG_LIB, FLAG_MAIN, FLAG_MIGRATE, FLAG_NO_COLOR, FLAG_NO_HEADER, FLAG_NO_LINK, FLAG_OUTPUT,
-    FLAG_PP_DYLIB, FLAG_PP_HOST, FLAG_PP_PLATFORM, FLAG_STDIN, FLAG_STDOUT, FLAG_TARGET, FLAG_TIME,
-    FLAG_VERBOSE, GLUE_DIR, GLUE_SPEC, ROC_FILE, VERSION,
+    annotate_file, api_diff, bench, build_app, default_linking_strategy, format_files, format_src,
+    test, AnnotationProblem, BuildConfig, FormatMode, FormatOptions, CMD_API_DIFF, CMD_BENCH,
+    CMD_BUILD, CMD_CHECK, CMD_DEV, CMD_DOCS, CMD_FORMAT, CMD_FORMAT_ANNOTATE, CMD_GLUE,
+    CMD_LICENSES, CMD_PREPROCESS_HOST, CMD_REPL, CMD_RUN, CMD_TEST, CMD_VERSION,
+    DIRECTORY_OR_FILES, FLAG_CHANGED, FLAG_CHECK, FLAG_DEV, FLAG_DOCS_FORMAT, FLAG_DOCS_ROOT,
+    FLAG_DOCS_SOURCE_URL, FLAG_LIB, FLAG_MAIN, FLAG_MAX_LINE_WIDTH, FLAG_MIGRATE, FLAG_NO_COLOR,
+    FLAG_NO_HEADER, FLAG_NO_LINK, FLAG_OUTPUT, FLAG_PP_DYLIB, FLAG_PP_HOST, FLAG_PP_PLATFORM,
+    FLAG_SINCE, FLAG_STDIN, FLAG_STDOUT, FLAG_TARGET, FLAG_TIME, FLAG_TRAILING_COMMAS,
+    FLAG_VERBOSE, FLAG_VERIFY, FLAG_WATCH, GLUE_DIR, GLUE_SPEC, ROC_FILE, VERSION,
 };
-use roc_docs::generate_docs_html;
+use roc_docs::{generate_docs_html, generate_docs_json, generate_docs_man, generate_docs_markdown};
 use roc_error_macros::{internal_error, user_error};
 use roc_gen_dev::AssemblyBackendMode;
 use roc_gen_llvm::llvm::build::LlvmBackendMode;
-use roc_load::{LoadingProblem, Threading};
+use roc_load::{LoadCache, LoadingProblem, Threading};
 use roc_packaging::cache::{self, RocCacheDir};
 use roc_target::Target;
 use std::fs::{self, FileType};
@@ -80,6 +83,7 @@ fn main() -> io::Result<()> {
                 Ok(1)
             }
         }
+        Some((CMD_BENCH, matches)) => bench(matches, Triple::host().into()),
         Some((CMD_TEST, matches)) => {
             if matches.contains_id(ROC_FILE) {
                 test(matches, Triple::host().into())
@@ -130,6 +134,7 @@ fn main() -> io::Result<()> {
                     backend,
                     link_type,
                     linking_strategy,
+                    matches.get_flag(FLAG_VERIFY),
                 )
             } else {
                 eprintln!("`roc glue` must be given a directory to output into, because the glue might generate multiple files.");
@@ -229,6 +234,10 @@ fn main() -> io::Result<()> {
 
             match roc_file_path.extension().and_then(OsStr::to_str) {
                 Some("md") => {
+                    if matches.get_flag(FLAG_WATCH) {
+                        user_error!("`roc check --watch` does not support Markdown files yet");
+                    }
+
                     // Extract the blocks of roc code
                     let file = fs::File::open(roc_file_path.as_path())?;
                     let markdown_file_reader = io::BufReader::new(file);
@@ -264,8 +273,9 @@ fn main() -> io::Result<()> {
                             emit_timings,
                             RocCacheDir::Persistent(cache::roc_cache_packages_dir().as_path()),
                             threading,
+                            &LoadCache::default(),
                         ) {
-                            Ok((problems, total_time)) => {
+                            Ok((problems, total_time, _, _)) => {
                                 problems.print_error_warning_count(total_time);
                                 println!(".\n");
 
@@ -290,28 +300,57 @@ fn main() -> io::Result<()> {
                     Ok(exit_code)
                 }
                 _ => {
-                    match check_file(
-                        &arena,
-                        roc_file_path.to_owned(),
-                        opt_main_path.cloned(),
-                        emit_timings,
-                        RocCacheDir::Persistent(cache::roc_cache_packages_dir().as_path()),
-                        threading,
-                    ) {
-                        Ok((problems, total_time)) => {
-                            problems.print_error_warning_count(total_time);
-                            println!(".\n");
-                            Ok(problems.exit_code())
-                        }
+                    let watch = matches.get_flag(FLAG_WATCH);
+                    let mut watcher = Watcher::default();
+                    let mut load_cache = LoadCache::default();
 
-                        Err(LoadingProblem::FormattedReport(report, _)) => {
-                            print!("{report}");
+                    if watch {
+                        watcher.watch_root(roc_file_path);
+                    }
 
-                            Ok(1)
-                        }
-                        Err(other) => {
-                            panic!("build_file failed with error:\n{other:?}");
+                    loop {
+                        let arena = Bump::new();
+
+                        let exit_code = match check_file(
+                            &arena,
+                            roc_file_path.to_owned(),
+                            opt_main_path.cloned(),
+                            emit_timings,
+                            RocCacheDir::Persistent(cache::roc_cache_packages_dir().as_path()),
+                            threading,
+                            &load_cache,
+                        ) {
+                            Ok((problems, total_time, sources, new_load_cache)) => {
+                                problems.print_error_warning_count(total_time);
+                                println!(".\n");
+
+                                load_cache = new_load_cache;
+
+                                watcher.record(
+                                    sources
+                                        .values()
+                                        .map(|(path, source)| (path.as_path(), &**source)),
+                                );
+
+                                problems.exit_code()
+                            }
+
+                            Err(LoadingProblem::FormattedReport(report, _)) => {
+                                print!("{report}");
+
+                                1
+                            }
+                            Err(other) => {
+                                panic!("build_file failed with error:\n{other:?}");
+                            }
+                        };
+
+                        if !watch {
+                            break Ok(exit_code);
                         }
+
+                        print_watching();
+                        watcher.wait_for_changes();
                     }
                 }
             }
@@ -322,6 +361,7 @@ fn main() -> io::Result<()> {
 
             Ok(roc_repl_cli::main(has_color, has_header))
         }
+        Some((CMD_API_DIFF, matches)) => Ok(api_diff(matches)),
         Some((CMD_DOCS, matches)) => {
             let root_path = matches.get_one::<PathBuf>(ROC_FILE).unwrap();
             let out_dir = matches.get_one::<OsString>(FLAG_OUTPUT).unwrap();
@@ -340,11 +380,20 @@ fn main() -> io::Result<()> {
                 }
             };
 
-            generate_docs_html(
-                root_path.to_owned(),
-                out_dir.as_ref(),
-                maybe_root_dir.clone(),
-            );
+            match matches
+                .get_one::<String>(FLAG_DOCS_FORMAT)
+                .map(AsRef::as_ref)
+            {
+                Some("json") => generate_docs_json(root_path.to_owned(), out_dir.as_ref()),
+                Some("markdown") => generate_docs_markdown(root_path.to_owned(), out_dir.as_ref()),
+                Some("man") => generate_docs_man(root_path.to_owned(), out_dir.as_ref()),
+                _ => generate_docs_html(
+                    root_path.to_owned(),
+                    out_dir.as_ref(),
+                    maybe_root_dir.clone(),
+                    matches.get_one::<String>(FLAG_DOCS_SOURCE_URL).cloned(),
+                ),
+            }
 
             Ok(0)
         }
@@ -388,6 +437,15 @@ fn main() -> io::Result<()> {
             let from_stdin = matches.get_flag(FLAG_STDIN);
             let to_stdout = matches.get_flag(FLAG_STDOUT);
             let migrate = matches.get_flag(FLAG_MIGRATE);
+            let options = FormatOptions {
+                max_line_width: matches.get_one::<u16>(FLAG_MAX_LINE_WIDTH).copied(),
+                trailing_commas: matches.get_one::<bool>(FLAG_TRAILING_COMMAS).copied(),
+            };
+            let since = match matches.get_one::<String>(FLAG_SINCE) {
+                Some(rev) => Some(rev.as_str()),
+                None if matches.get_flag(FLAG_CHANGED) => Some("HEAD"),
+                None => None,
+            };
             let format_mode = if to_stdout {
                 FormatMode::WriteToStdout
             } else {
@@ -397,6 +455,11 @@ fn main() -> io::Result<()> {
                 }
             };
 
+            if from_stdin && since.is_some() {
+                eprintln!("The --changed and --since flags can't be used with --stdin, because there's no file to compare with git.");
+                std::process::exit(1);
+            }
+
             if from_stdin && matches!(format_mode, FormatMode::WriteToFile) {
                 eprintln!("When using the --stdin flag, either the --check or the --stdout flag must also be specified. (Otherwise, it's unclear what filename to write to!)");
                 std::process::exit(1);
@@ -448,7 +511,16 @@ fn main() -> io::Result<()> {
                     std::process::exit(1);
                 });
 
-                match format_src(&arena, src, migrate) {
+                // There's no file to find the config for, so use the one for the current directory.
+                let config = match FormatOptions::find_for(&std::env::current_dir()?) {
+                    Ok(file_options) => options.or(file_options).to_config(),
+                    Err(message) => {
+                        eprintln!("{message}");
+                        std::process::exit(1);
+                    }
+                };
+
+                match format_src(&arena, src, migrate, config) {
                     Ok(formatted_src) => {
                         match format_mode {
                             FormatMode::CheckOnly => {
@@ -480,7 +552,7 @@ fn main() -> io::Result<()> {
                     }
                 }
             } else {
-                match format_files(roc_files, format_mode, migrate) {
+                match format_files(roc_files, format_mode, migrate, options, since) {
                     Ok(()) => 0,
                     Err(message) => {
                         eprintln!("{message}");
@@ -0,0 +1,256 @@
+//! Support for `roc check --watch` and `roc test --watch`.
+//!
+//! Files are polled rather than subscribed to, and compared by content hash, so that saving a
+//! file without changing it (or touching it) does not trigger a new run.
+
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use roc_collections::{MutMap, MutSet};
+use roc_load::{hash_contents, LoadCache};
+use roc_module::symbol::ModuleId;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// The content hashes of every `.roc` file that the last run depended on.
+#[derive(Debug, Default)]
+pub struct Watcher {
+    hashes: MutMap<PathBuf, Option<String>>,
+}
+
+impl Watcher {
+    /// Remember the sources of the modules that were just loaded.
+    ///
+    /// The hash is taken from the source the compiler actually used, so that an edit made
+    /// while it was loading still counts as a change.
+    pub fn record<'s>(&mut self, sources: impl IntoIterator<Item = (&'s Path, &'s str)>) {
+        for (path, source) in sources {
+            // builtins are embedded in the compiler, and have no file to watch
+            if path.is_file() {
+                self.hashes
+                    .insert(path.to_path_buf(), Some(hash_contents(source)));
+            }
+        }
+    }
+
+    /// Also watch a file that may not have loaded, e.g. because it does not parse.
+    pub fn watch_file(&mut self, path: &Path) {
+        if !self.hashes.contains_key(path) {
+            self.hashes.insert(path.to_path_buf(), current_hash(path));
+        }
+    }
+
+    /// Watch a file the run starts from, along with the other .roc files in its directory. This
+    /// way, fixing an imported module that failed to parse still triggers a new run.
+    pub fn watch_root(&mut self, path: &Path) {
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+
+        let mut neighbors = Vec::new();
+        crate::find_all_roc_files(&dir, &mut neighbors);
+
+        self.watch_file(path);
+
+        for neighbor in neighbors.iter() {
+            self.watch_file(neighbor);
+        }
+    }
+
+    /// Block until the content of at least one watched file changes, and return the files that
+    /// changed. A file that was deleted counts as changed.
+    pub fn wait_for_changes(&mut self) -> MutSet<PathBuf> {
+        loop {
+            let changed = self.changed_files();
+
+            if !changed.is_empty() {
+                return changed;
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    fn changed_files(&mut self) -> MutSet<PathBuf> {
+        let mut changed = MutSet::default();
+
+        for (path, hash) in self.hashes.iter_mut() {
+            let new_hash = current_hash(path);
+
+            if *hash != new_hash {
+                *hash = new_hash;
+                changed.insert(path.clone());
+            }
+        }
+
+        changed
+    }
+}
+
+/// What `roc test --watch` keeps between runs.
+#[derive(Debug, Default)]
+pub struct TestSession {
+    pub watcher: Watcher,
+    /// The module graph of each file passed to `roc test`, as of its last run.
+    pub graphs: MutMap<PathBuf, ModuleGraph>,
+    /// What the last load of each file passed to `roc test` left for the next load to reuse, so
+    /// that only the changed modules and the modules that import them are type checked again.
+    pub load_caches: MutMap<PathBuf, LoadCache>,
+}
+
+/// The module graph of one file passed to `roc test --watch`, as of its last run, along with the
+/// results of the expects in each of its modules.
+///
+/// Kept between runs so that a change only re-runs the expects that could have been affected by
+/// it, and files that don't depend on the change are not loaded again at all.
+#[derive(Debug, Default)]
+pub struct ModuleGraph {
+    /// For each file in the graph, the files of the modules that import it.
+    importers: MutMap<PathBuf, MutSet<PathBuf>>,
+    /// The (failed, passed) counts of the expects in each file, from the last time they ran.
+    results: MutMap<PathBuf, (usize, usize)>,
+}
+
+impl ModuleGraph {
+    pub fn new(
+        sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+        imports: &MutMap<ModuleId, MutSet<ModuleId>>,
+    ) -> Self {
+        let mut importers: MutMap<PathBuf, MutSet<PathBuf>> = MutMap::default();
+
+        for (module_id, (path, _)) in sources.iter() {
+            importers.entry(path.clone()).or_default();
+
+            for imported in imports.get(module_id).into_iter().flatten() {
+                if let Some((imported_path, _)) = sources.get(imported) {
+                    importers
+                        .entry(imported_path.clone())
+                        .or_default()
+                        .insert(path.clone());
+                }
+            }
+        }
+
+        Self {
+            importers,
+            results: MutMap::default(),
+        }
+    }
+
+    /// The files whose expects may give a different result after `changed` changed: the changed
+    /// files themselves, and every file that imports one of them, directly or not.
+    pub fn affected_by(&self, changed: &MutSet<PathBuf>) -> MutSet<PathBuf> {
+        let mut affected = MutSet::default();
+        let mut stack: Vec<&PathBuf> = changed
+            .iter()
+            .filter(|path| self.importers.contains_key(*path))
+            .collect();
+
+        while let Some(path) = stack.pop() {
+            if affected.insert(path.clone()) {
+                stack.extend(self.importers.get(path).into_iter().flatten());
+            }
+        }
+
+        affected
+    }
+
+    pub fn record_result(&mut self, path: &Path, failed_count: usize, passed_count: usize) {
+        self.results
+            .insert(path.to_path_buf(), (failed_count, passed_count));
+    }
+
+    /// The (failed, passed) counts from the last time the expects in this file ran.
+    pub fn previous_result(&self, path: &Path) -> Option<(usize, usize)> {
+        self.results.get(path).copied()
+    }
+
+    /// The (failed, passed) counts of every expect in the graph.
+    pub fn total_results(&self) -> (usize, usize) {
+        self.results
+            .values()
+            .fold((0, 0), |(failed, passed), (f, p)| (failed + f, passed + p))
+    }
+}
+
+fn current_hash(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|contents| hash_contents(&contents))
+}
+
+/// Printed after each run, so it's clear the process is still alive.
+pub fn print_watching() {
+    println!("\nWatching for changes… (press Ctrl-C to stop)\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::write;
+    use tempfile::tempdir;
+
+    #[test]
+    fn reports_only_files_whose_content_changed() {
+        let dir = tempdir().unwrap();
+        let changed_path = dir.path().join("Changed.roc");
+        let touched_path = dir.path().join("Touched.roc");
+
+        write(&changed_path, "module []\n").unwrap();
+        write(&touched_path, "module []\n").unwrap();
+
+        let mut watcher = Watcher::default();
+        watcher.record([
+            (changed_path.as_path(), "module []\n"),
+            (touched_path.as_path(), "module []\n"),
+        ]);
+
+        assert!(watcher.changed_files().is_empty());
+
+        write(&changed_path, "module [x]\n\nx = 1\n").unwrap();
+        write(&touched_path, "module []\n").unwrap();
+
+        let changed = watcher.changed_files();
+        assert_eq!(changed, MutSet::from_iter([changed_path]));
+
+        // The change has been seen, so it is not reported again.
+        assert!(watcher.changed_files().is_empty());
+    }
+
+    #[test]
+    fn changes_affect_modules_that_import_them() {
+        let home = ModuleId::ATTR;
+        let a = ModuleId::STR;
+        let b = ModuleId::LIST;
+        let c = ModuleId::NUM;
+
+        let path = |name: &str| PathBuf::from(format!("{name}.roc"));
+        let sources = MutMap::from_iter([
+            (home, (path("Main"), "".into())),
+            (a, (path("A"), "".into())),
+            (b, (path("B"), "".into())),
+            (c, (path("C"), "".into())),
+        ]);
+        // Main imports A, A imports B. C imports nothing.
+        let imports = MutMap::from_iter([
+            (home, MutSet::from_iter([a, c])),
+            (a, MutSet::from_iter([b])),
+        ]);
+
+        let graph = ModuleGraph::new(&sources, &imports);
+
+        assert_eq!(
+            graph.affected_by(&MutSet::from_iter([path("B")])),
+            MutSet::from_iter([path("B"), path("A"), path("Main")])
+        );
+        assert_eq!(
+            graph.affected_by(&MutSet::from_iter([path("C")])),
+            MutSet::from_iter([path("C"), path("Main")])
+        );
+        assert!(graph
+            .affected_by(&MutSet::from_iter([path("Elsewhere")]))
+            .is_empty());
+    }
+}
@@ -11,7 +11,7 @@ mod cli_tests {
     use cli_test_utils::exec_cli::ExecCli;
     use cli_test_utils::helpers::{dir_from_root, file_from_root};
     use const_format::concatcp;
-    use roc_cli::{CMD_BUILD, CMD_CHECK, CMD_FORMAT, CMD_TEST};
+    use roc_cli::{CMD_BENCH, CMD_BUILD, CMD_CHECK, CMD_FORMAT, CMD_TEST};
 
     #[cfg(all(unix, not(target_os = "macos")))]
     const ALLOW_VALGRIND: bool = true;
@@ -252,6 +252,40 @@ mod cli_tests {
         insta::assert_snapshot!(cli_test_out.normalize_stdout_and_stderr());
     }
 
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    fn bench_with_constant_input() {
+        let cli_bench = ExecCli::new(
+            CMD_BENCH,
+            file_from_root("crates/cli/tests/test-projects/bench", "Bench.roc"),
+        );
+
+        let cli_bench_out = cli_bench.run();
+        cli_bench_out.assert_clean_success();
+
+        let mean_of = |name: &str| {
+            let prefix = format!("{name}: mean ");
+            let line = cli_bench_out
+                .stdout
+                .lines()
+                .find_map(|line| line.strip_prefix(&prefix))
+                .unwrap_or_else(|| panic!("{name} was not timed\n\n{cli_bench_out}"));
+
+            line.split(" ±").next().unwrap().to_string()
+        };
+
+        // Had its input been folded in at compile time, summing the squares of 1000 numbers
+        // would take no time at all.
+        let mean = mean_of("bench_sum_squares");
+        let too_fast = mean
+            .strip_suffix(" ns")
+            .is_some_and(|nanos| nanos.parse::<f64>().unwrap() < 10.0);
+
+        assert!(!too_fast, "bench_sum_squares was optimized away: {mean}");
+
+        mean_of("bench_concat");
+    }
+
     #[test]
     #[cfg_attr(windows, ignore)]
     fn multiple_exposed() {
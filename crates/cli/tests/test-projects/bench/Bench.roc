@@ -0,0 +1,15 @@
+module []
+
+# All of this is constant work, which LLVM would do at compile time if the
+# input of the benchmark didn't go through a black box.
+bench_sum_squares : List U64 -> U64
+bench_sum_squares = |numbers|
+    numbers
+    |> List.map(|n| n * n)
+    |> List.sum
+
+bench_sum_squares_input : List U64
+bench_sum_squares_input = List.range({ start: At 1, end: At 1000 })
+
+bench_concat : {} -> Str
+bench_concat = |{}| Str.concat("Hello, ", "World!")
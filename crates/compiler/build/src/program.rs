@@ -2,14 +2,16 @@ use crate::link::{link, preprocess_host_wasm32, rebuild_host, LinkType, LinkingS
 use bumpalo::collections::CollectIn;
 use bumpalo::Bump;
 use inkwell::memory_buffer::MemoryBuffer;
+use roc_collections::all::MutMap;
 use roc_error_macros::internal_error;
 use roc_gen_dev::AssemblyBackendMode;
 use roc_gen_llvm::llvm::build::{module_from_builtins, LlvmBackendMode};
-use roc_gen_llvm::llvm::externs::add_default_roc_externs;
+use roc_gen_llvm::llvm::externs::{add_default_roc_externs, add_dict_pseudo_seed_export};
 use roc_load::{
-    EntryPoint, ExecutionMode, ExpectMetadata, FunctionKind, LoadConfig, LoadMonomorphizedError,
-    LoadedModule, LoadingProblem, MonomorphizedModule, Threading,
+    EntryPoint, ExecutionMode, ExpectMetadata, FunctionKind, LoadCache, LoadConfig,
+    LoadMonomorphizedError, LoadedModule, LoadingProblem, MonomorphizedModule, Threading,
 };
+use roc_module::symbol::ModuleId;
 use roc_mono::ir::{OptLevel, SingleEntryPoint};
 use roc_packaging::cache::RocCacheDir;
 use roc_reporting::{
@@ -84,6 +86,9 @@ pub struct CodeGenOptions {
     pub emit_debug_info: bool,
     pub emit_llvm_ir: bool,
     pub fuzz: bool,
+    /// Whether to export the seed `Dict` hashes keys with as `roc_dict_pseudo_seed`, for hosts
+    /// that build or look up `Dict`s themselves. Only the LLVM backend supports this.
+    pub export_dict_seed: bool,
 }
 
 type GenFromMono<'a> = (CodeObject, CodeGenTiming, ExpectMetadata<'a>);
@@ -102,6 +107,7 @@ pub fn gen_from_mono_module<'a>(
     let debug = code_gen_options.emit_debug_info;
     let emit_llvm_ir = code_gen_options.emit_llvm_ir;
     let fuzz = code_gen_options.fuzz;
+    let export_dict_seed = code_gen_options.export_dict_seed;
     let opt = code_gen_options.opt_level;
 
     match code_gen_options.backend {
@@ -139,6 +145,7 @@ pub fn gen_from_mono_module<'a>(
             debug,
             emit_llvm_ir,
             fuzz,
+            export_dict_seed,
         ),
     }
 }
@@ -157,6 +164,7 @@ fn gen_from_mono_module_llvm<'a>(
     emit_debug_info: bool,
     emit_llvm_ir: bool,
     fuzz: bool,
+    export_dict_seed: bool,
 ) -> GenFromMono<'a> {
     use crate::target::{self, convert_opt_level};
     use inkwell::attributes::{Attribute, AttributeLoc};
@@ -258,6 +266,10 @@ fn gen_from_mono_module_llvm<'a>(
         &loaded.glue_layouts,
     );
 
+    if export_dict_seed {
+        add_dict_pseudo_seed_export(&env);
+    }
+
     // We are now finished building the LLVM IR.
     let generate_final_ir = all_code_gen_start.elapsed();
     let code_gen_object_start = Instant::now();
@@ -830,8 +842,11 @@ fn build_loaded_file<'a>(
         link_type,
     );
 
-    let dll_stub_symbols =
-        roc_linker::ExposedSymbols::from_exposed_to_host(&loaded.interns, &loaded.exposed_to_host);
+    let dll_stub_symbols = roc_linker::ExposedSymbols::from_exposed_to_host(
+        &loaded.interns,
+        &loaded.exposed_to_host,
+        code_gen_options.export_dict_seed,
+    );
 
     let built_host_opt =
         // Not sure if this is correct for all calls with LinkType::Dylib...
@@ -1327,7 +1342,10 @@ fn spawn_legacy_host_build_thread(
     })
 }
 
+/// Type check a file and the modules it imports. Modules that did not change since the load that
+/// produced `previous` are not type checked again; the returned cache is for the next check.
 #[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
 pub fn check_file<'a>(
     arena: &'a Bump,
     roc_file_path: PathBuf,
@@ -1335,7 +1353,16 @@ pub fn check_file<'a>(
     emit_timings: bool,
     roc_cache_dir: RocCacheDir<'_>,
     threading: Threading,
-) -> Result<(Problems, Duration), LoadingProblem<'a>> {
+    previous: &LoadCache,
+) -> Result<
+    (
+        Problems,
+        Duration,
+        MutMap<ModuleId, (PathBuf, Box<str>)>,
+        LoadCache,
+    ),
+    LoadingProblem<'a>,
+> {
     let compilation_start = Instant::now();
 
     // only used for generating errors. We don't do code generation, so hardcoding should be fine
@@ -1353,12 +1380,13 @@ pub fn check_file<'a>(
         threading,
         exec_mode: ExecutionMode::Check,
     };
-    let mut loaded = roc_load::load_and_typecheck(
+    let mut loaded = roc_load::load_and_typecheck_with_cache(
         arena,
         roc_file_path,
         opt_main_path,
         roc_cache_dir,
         load_config,
+        previous,
     )?;
 
     let buf = &mut String::with_capacity(1024);
@@ -1403,7 +1431,9 @@ pub fn check_file<'a>(
         println!("Finished checking in {} ms\n", compilation_end.as_millis(),);
     }
 
-    Ok((report_problems_typechecked(&mut loaded), compilation_end))
+    let problems = report_problems_typechecked(&mut loaded);
+
+    Ok((problems, compilation_end, loaded.sources, loaded.cache))
 }
 
 pub fn build_str_test<'a>(
@@ -1420,6 +1450,7 @@ pub fn build_str_test<'a>(
         emit_debug_info: false,
         emit_llvm_ir: false,
         fuzz: false,
+        export_dict_seed: false,
     };
 
     let emit_timings = false;
@@ -97,6 +97,10 @@ const NUMBERS = INTEGERS ++ FLOATS;
 comptime {
     exportNumFn(num.shiftRightZeroFillI128, "shift_right_zero_fill.i128");
     exportNumFn(num.shiftRightZeroFillU128, "shift_right_zero_fill.u128");
+    exportNumFn(num.shiftLeftByI128, "shift_left_by.i128");
+    exportNumFn(num.shiftLeftByU128, "shift_left_by.u128");
+    exportNumFn(num.shiftRightByI128, "shift_right_by.i128");
+    exportNumFn(num.shiftRightByU128, "shift_right_by.u128");
 
     exportNumFn(num.compareI128, "compare.i128");
     exportNumFn(num.compareU128, "compare.u128");
@@ -145,6 +149,9 @@ comptime {
         num.exportSubSaturatedInt(T, ROC_BUILTINS ++ "." ++ NUM ++ ".sub_saturated.");
         num.exportSubWrappedInt(T, ROC_BUILTINS ++ "." ++ NUM ++ ".sub_wrapped.");
 
+        num.exportAbsOrPanic(T, ROC_BUILTINS ++ "." ++ NUM ++ ".abs_or_panic.");
+        num.exportNegOrPanic(T, ROC_BUILTINS ++ "." ++ NUM ++ ".neg_or_panic.");
+
         num.exportMulWithOverflow(T, WIDEINTS[i], ROC_BUILTINS ++ "." ++ NUM ++ ".mul_with_overflow.");
         num.exportMulOrPanic(T, WIDEINTS[i], ROC_BUILTINS ++ "." ++ NUM ++ ".mul_or_panic.");
         num.exportMulSaturatedInt(T, WIDEINTS[i], ROC_BUILTINS ++ "." ++ NUM ++ ".mul_saturated.");
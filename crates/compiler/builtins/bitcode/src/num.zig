@@ -464,6 +464,34 @@ pub fn exportSubOrPanic(comptime T: type, comptime name: []const u8) void {
     @export(f, .{ .name = name ++ @typeName(T), .linkage = .strong });
 }
 
+pub fn exportAbsOrPanic(comptime T: type, comptime name: []const u8) void {
+    const f = struct {
+        fn func(self: T) callconv(.C) T {
+            if (@typeInfo(T).Int.signedness == .unsigned) {
+                return self;
+            } else if (self == std.math.minInt(T)) {
+                roc_panic("Integer absolute overflowed because its argument is the minimum value", 0);
+            } else {
+                return @intCast(@abs(self));
+            }
+        }
+    }.func;
+    @export(f, .{ .name = name ++ @typeName(T), .linkage = .strong });
+}
+
+pub fn exportNegOrPanic(comptime T: type, comptime name: []const u8) void {
+    const f = struct {
+        fn func(self: T) callconv(.C) T {
+            if (@typeInfo(T).Int.signedness == .signed and self == std.math.minInt(T)) {
+                roc_panic("Integer negation overflowed because its argument is the minimum value", 0);
+            } else {
+                return 0 -% self;
+            }
+        }
+    }.func;
+    @export(f, .{ .name = name ++ @typeName(T), .linkage = .strong });
+}
+
 fn mulWithOverflow(comptime T: type, comptime W: type, self: T, other: T) WithOverflow(T) {
     switch (@typeInfo(T)) {
         .Int => {
@@ -569,6 +597,10 @@ pub fn exportMulWrappedInt(comptime T: type, comptime name: []const u8) void {
 }
 
 pub fn shiftRightZeroFillI128(self: i128, other: u8) callconv(.C) i128 {
+    return @bitCast(shiftRightZeroFillU128(@bitCast(self), other));
+}
+
+pub fn shiftRightZeroFillU128(self: u128, other: u8) callconv(.C) u128 {
     if (other & 0b1000_0000 > 0) {
         return 0;
     } else {
@@ -576,14 +608,31 @@ pub fn shiftRightZeroFillI128(self: i128, other: u8) callconv(.C) i128 {
     }
 }
 
-pub fn shiftRightZeroFillU128(self: u128, other: u8) callconv(.C) u128 {
+pub fn shiftLeftByI128(self: i128, other: u8) callconv(.C) i128 {
+    return @bitCast(shiftLeftByU128(@bitCast(self), other));
+}
+
+pub fn shiftLeftByU128(self: u128, other: u8) callconv(.C) u128 {
     if (other & 0b1000_0000 > 0) {
         return 0;
+    } else {
+        return self << @as(u7, @intCast(other));
+    }
+}
+
+pub fn shiftRightByI128(self: i128, other: u8) callconv(.C) i128 {
+    if (other & 0b1000_0000 > 0) {
+        return self >> 127;
     } else {
         return self >> @as(u7, @intCast(other));
     }
 }
 
+// Like for the other widths, shifting right sign-extends, even for unsigned integers
+pub fn shiftRightByU128(self: u128, other: u8) callconv(.C) u128 {
+    return @bitCast(shiftRightByI128(@bitCast(self), other));
+}
+
 pub fn compareI128(self: i128, other: i128) callconv(.C) Ordering {
     if (self == other) {
         return Ordering.EQ;
@@ -98,6 +98,8 @@ macro_rules! map_symbol_to_lowlevel_and_arity {
                 LowLevel::LongJmp => unimplemented!(),
                 LowLevel::SetLongJmpBuffer => unimplemented!(),
 
+                LowLevel::BlackBox => unimplemented!(),
+
                 // these are not implemented, not sure why
                 LowLevel::StrFromInt => unimplemented!(),
                 LowLevel::StrFromFloat => unimplemented!(),
@@ -3328,6 +3328,110 @@ pub fn toplevel_expect_to_inline_expect_pure(mut loc_expr: Loc<Expr>) -> Loc<Exp
     loc_expr
 }
 
+/// The variables of a `{} -> a` function, as found in its solved type.
+#[derive(Debug, Clone, Copy)]
+pub struct BenchFunctionVars {
+    pub fn_var: Variable,
+    pub arg_var: Variable,
+    pub closure_var: Variable,
+    pub ret_var: Variable,
+    pub fx_var: Variable,
+}
+
+/// The symbols that [toplevel_bench_to_thunk] binds in the thunk's body.
+#[derive(Debug, Clone, Copy)]
+pub struct BenchThunkSymbols {
+    pub input: Symbol,
+    pub result: Symbol,
+    pub sink: Symbol,
+}
+
+/// Here we turn a benchmark function `bench_foo : input -> a` into the thunk
+///
+/// ```ignore
+/// input = black_box bench_foo_input
+/// result = bench_foo input
+/// sink = black_box result
+///
+/// {}
+/// ```
+///
+/// where `bench_foo_input` is the top-level constant with the benchmark's input, or `{}` if the
+/// benchmark takes `{}`.
+///
+/// Like a toplevel expect, this thunk has type `{}`, so the benchmark runner can call every
+/// benchmark the same way, and the result is dropped as usual, so that repeated runs don't leak.
+/// The input goes through [LowLevel::BlackBox], so that work on a constant input is not folded
+/// away at compile time, and so does the result, so that benchmarks are not optimized away
+/// because their result is unused.
+///
+/// This is supposed to happen just before monomorphization.
+pub fn toplevel_bench_to_thunk(
+    bench_symbol: Symbol,
+    input_symbol: Option<Symbol>,
+    symbols: BenchThunkSymbols,
+    vars: BenchFunctionVars,
+    region: Region,
+) -> Loc<Expr> {
+    let BenchFunctionVars {
+        fn_var,
+        arg_var,
+        closure_var,
+        ret_var,
+        fx_var,
+    } = vars;
+
+    let input = match input_symbol {
+        Some(input_symbol) => Expr::Var(input_symbol, arg_var),
+        None => Expr::EmptyRecord,
+    };
+
+    let call = Expr::Call(
+        Box::new((
+            fn_var,
+            Loc::at(region, Expr::Var(bench_symbol, fn_var)),
+            closure_var,
+            ret_var,
+            fx_var,
+        )),
+        vec![(arg_var, Loc::at(region, Expr::Var(symbols.input, arg_var)))],
+        CalledVia::Space,
+    );
+
+    let result = Expr::Var(symbols.result, ret_var);
+
+    let defs = [
+        (symbols.input, arg_var, black_box(arg_var, input)),
+        (symbols.result, ret_var, call),
+        (symbols.sink, ret_var, black_box(ret_var, result)),
+    ];
+
+    let mut loc_expr = Loc::at(region, Expr::EmptyRecord);
+
+    for (symbol, var, expr) in defs.into_iter().rev() {
+        let def = Def {
+            loc_pattern: Loc::at(region, Pattern::Identifier(symbol)),
+            loc_expr: Loc::at(region, expr),
+            expr_var: var,
+            pattern_vars: SendMap::from_iter([(symbol, var)]),
+            annotation: None,
+            kind: crate::def::DefKind::Let,
+        };
+
+        loc_expr = Loc::at(region, Expr::LetNonRec(Box::new(def), Box::new(loc_expr)));
+    }
+
+    loc_expr
+}
+
+fn black_box(var: Variable, expr: Expr) -> Expr {
+    Expr::RunLowLevel {
+        op: LowLevel::BlackBox,
+        args: vec![(var, expr)],
+        ret_var: var,
+    }
+}
+
 pub struct ExpectCollector {
     pub expects: VecMap<Region, Vec<ExpectLookup>>,
     pub has_dbgs: bool,
@@ -49,7 +49,7 @@ use roc_region::all::Loc;
 ///       x = 3
 ///       x + 1
 ///    )
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
 pub enum Parens {
     NotNeeded,
     InCollection,
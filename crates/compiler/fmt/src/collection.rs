@@ -42,11 +42,29 @@ pub fn fmt_collection<'a, 'buf, T: ExtractSpaces<'a> + Formattable + std::fmt::D
     newline: Newlines,
 ) where
     <T as ExtractSpaces<'a>>::Item: Formattable + std::fmt::Debug,
+{
+    let is_multiline = is_collection_multiline(&items);
+
+    fmt_collection_with_layout(buf, indent, braces, items, newline, is_multiline)
+}
+
+/// Like [fmt_collection], but puts every item on its own line when `is_multiline` is set,
+/// even if none of them were on their own line before.
+pub fn fmt_collection_with_layout<'a, 'buf, T: ExtractSpaces<'a> + Formattable + std::fmt::Debug>(
+    buf: &mut Buf<'buf>,
+
+    indent: u16,
+    braces: Braces,
+    items: Collection<'a, T>,
+    newline: Newlines,
+    is_multiline: bool,
+) where
+    <T as ExtractSpaces<'a>>::Item: Formattable + std::fmt::Debug,
 {
     let start = braces.start();
     let end = braces.end();
 
-    if is_collection_multiline(&items) {
+    if is_multiline {
         let braces_indent = indent;
         let item_indent = braces_indent + INDENT;
         if newline == Newlines::Yes {
@@ -112,8 +130,10 @@ pub fn fmt_collection<'a, 'buf, T: ExtractSpaces<'a> + Formattable + std::fmt::D
             buf.indent(item_indent);
             item.item.format(buf, item_indent);
 
-            buf.indent(item_indent);
-            buf.push(',');
+            if buf.config().trailing_commas || index + 1 < items.len() {
+                buf.indent(item_indent);
+                buf.push(',');
+            }
 
             last_after = item.after;
         }
@@ -24,6 +24,7 @@ use roc_parse::ast::{
 use roc_parse::expr::merge_spaces;
 use roc_parse::header::Keyword;
 use roc_region::all::Loc;
+use std::ops::Range;
 
 /// A Located formattable value is also formattable
 
@@ -949,6 +950,61 @@ pub fn fmt_defs(buf: &mut Buf, defs: &Defs, indent: u16) {
     defs.format(buf, indent);
 }
 
+/// Format only the top-level defs whose source overlaps one of `ranges`, and copy everything
+/// else from `src` as it is. `src` must be the source `defs` was parsed from, and `ranges` are
+/// byte offsets into it.
+///
+/// Returns where the formatted defs ended up in `buf`, so that the result can be formatted
+/// again with the same defs selected.
+pub fn fmt_defs_in_ranges<'a>(
+    buf: &mut Buf<'a>,
+    src: &str,
+    defs: &Defs<'a>,
+    ranges: &[Range<usize>],
+) -> std::vec::Vec<Range<usize>> {
+    let arena = buf.text.bump();
+    let mut formatted_ranges = std::vec::Vec::new();
+    let mut copied_up_to = 0;
+
+    for (index, def) in defs.defs().enumerate() {
+        let region = defs.regions[index];
+        let start = region.start().offset as usize;
+        let end = region.end().offset as usize;
+
+        if !ranges
+            .iter()
+            .any(|range| range.start < end && start < range.end)
+        {
+            continue;
+        }
+
+        // Format the def on its own, so the spaces around it are left alone
+        let mut single_def = Defs::default();
+
+        match def {
+            Ok(type_def) => single_def.push_type_def(*type_def, region, &[], &[]),
+            Err(value_def) => single_def.push_value_def(*value_def, region, &[], &[]),
+        }
+
+        let mut def_buf = Buf::new_with_config_in(arena, buf.flags(), buf.config());
+        fmt_defs(&mut def_buf, &single_def, 0);
+
+        let formatted = def_buf.into_bump_str().trim_start_matches('\n').trim_end();
+
+        buf.push_verbatim(&src[copied_up_to..start]);
+
+        let formatted_start = buf.text.len();
+        buf.push_verbatim(formatted);
+        formatted_ranges.push(formatted_start..buf.text.len());
+
+        copied_up_to = end;
+    }
+
+    buf.push_verbatim(&src[copied_up_to..]);
+
+    formatted_ranges
+}
+
 pub fn fmt_annotated_body_comment<'a>(
     buf: &mut Buf,
     indent: u16,
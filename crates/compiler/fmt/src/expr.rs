@@ -1,5 +1,5 @@
 use crate::annotation::{except_last, is_collection_multiline, Formattable, Newlines, Parens};
-use crate::collection::{fmt_collection, Braces};
+use crate::collection::{fmt_collection_with_layout, Braces};
 use crate::def::{fmt_defs, starts_with_block_string_literal, valdef_lift_spaces_before};
 use crate::node::Prec;
 use crate::pattern::{
@@ -191,30 +191,39 @@ fn format_expr_only(
             buf.push_str(string);
         }
         Expr::Record(fields) => {
+            let wrap = wraps(buf, indent, |buf| expr_only_width(buf, item, parens));
+
             fmt_record_like(
                 buf,
                 None,
                 prepare_expr_field_collection(buf.text.bump(), *fields),
                 indent,
                 assigned_field_to_spaces,
+                wrap,
             );
         }
         Expr::RecordUpdate { update, fields } => {
+            let wrap = wraps(buf, indent, |buf| expr_only_width(buf, item, parens));
+
             fmt_record_like(
                 buf,
                 Some(RecordPrefix::Update(update)),
                 prepare_expr_field_collection(buf.text.bump(), *fields),
                 indent,
                 assigned_field_to_spaces,
+                wrap,
             );
         }
         Expr::RecordBuilder { mapper, fields } => {
+            let wrap = wraps(buf, indent, |buf| expr_only_width(buf, item, parens));
+
             fmt_record_like(
                 buf,
                 Some(RecordPrefix::Mapper(mapper)),
                 prepare_expr_field_collection(buf.text.bump(), *fields),
                 indent,
                 assigned_field_to_spaces,
+                wrap,
             );
         }
         Expr::Closure(loc_patterns, loc_ret) => {
@@ -285,7 +294,10 @@ fn format_expr_only(
             final_else,
             indented_else: _,
         } => {
-            fmt_if(buf, branches, final_else, item.is_multiline(), indent);
+            let is_multiline =
+                item.is_multiline() || wraps(buf, indent, |buf| expr_only_width(buf, item, parens));
+
+            fmt_if(buf, branches, final_else, is_multiline, indent);
         }
         Expr::When(loc_condition, branches) => fmt_when(buf, loc_condition, branches, indent),
         Expr::Tuple(items) => fmt_expr_collection(buf, indent, Braces::Round, *items, Newlines::No),
@@ -660,7 +672,10 @@ fn fmt_expr_collection(
     let new_items =
         Collection::with_items_and_comments(arena, new_items.into_bump_slice(), final_comments);
 
-    fmt_collection(buf, indent, braces, new_items, newlines)
+    let is_multiline = is_collection_multiline(&new_items)
+        || (!items.is_empty() && wraps(buf, indent, |buf| collection_width(buf, items)));
+
+    fmt_collection_with_layout(buf, indent, braces, new_items, newlines, is_multiline)
 }
 
 fn requires_space_after_unary(item: &Expr<'_>) -> bool {
@@ -752,6 +767,9 @@ fn fmt_apply(
                 })
                 .unwrap_or_default());
 
+    let needs_indent = needs_indent
+        || (!should_reflow_outdentable && apply_overflows_line(loc_expr, loc_args, indent, buf));
+
     let arg_indent = if needs_indent {
         indent + INDENT
     } else {
@@ -811,6 +829,47 @@ fn fmt_apply(
     }
 }
 
+/// Whether a single-line application runs past the maximum line width, and so needs its
+/// arguments on their own lines. If the last argument is a collection, and everything before
+/// it fits, the collection is wrapped instead, as in `foo bar [` followed by the items.
+fn apply_overflows_line(
+    loc_expr: &Loc<Expr<'_>>,
+    loc_args: &[&Loc<Expr<'_>>],
+    indent: u16,
+    buf: &mut Buf<'_>,
+) -> bool {
+    if buf.config().max_line_width.is_none() {
+        return false;
+    }
+
+    let Some(width) = apply_width(buf, loc_expr, loc_args) else {
+        return false;
+    };
+
+    if !buf.overflows(indent, 0, width) {
+        return false;
+    }
+
+    match loc_args.last() {
+        Some(last) if is_wrappable_collection(&last.value) => {
+            let last_width =
+                sub_expr_width(buf, &last.value, Parens::InApplyLastArg).unwrap_or_default();
+
+            // Everything up to and including the opening brace
+            buf.overflows(indent, 0, width - last_width + 1)
+        }
+        _ => true,
+    }
+}
+
+fn is_wrappable_collection(expr: &Expr<'_>) -> bool {
+    match expr {
+        Expr::Tuple(items) | Expr::List(items) => !items.is_empty(),
+        Expr::Record(fields) => !fields.is_empty(),
+        _ => false,
+    }
+}
+
 fn is_outdentable_collection(expr: &Expr<'_>) -> bool {
     match expr {
         Expr::Tuple(items) => is_collection_multiline(items),
@@ -827,7 +886,7 @@ fn fmt_parens(sub_expr: &Expr<'_>, buf: &mut Buf<'_>, indent: u16) {
         | Expr::When(..)
         | Expr::SpaceAfter(Expr::Closure(..), ..)
         | Expr::DbgStmt { .. } => false,
-        _ => sub_expr.is_multiline(),
+        _ => is_multiline_at(buf, sub_expr, indent, 1),
     };
 
     buf.indent(indent);
@@ -1526,7 +1585,8 @@ fn fmt_binops<'a>(
     indent: u16,
 ) {
     let is_multiline = loc_right_side.value.is_multiline()
-        || lefts.iter().any(|(expr, _)| expr.value.is_multiline());
+        || lefts.iter().any(|(expr, _)| expr.value.is_multiline())
+        || wraps(buf, indent, |buf| binops_width(buf, lefts, loc_right_side));
 
     for (loc_left_side, loc_binop) in lefts {
         let binop = loc_binop.value;
@@ -1645,7 +1705,7 @@ fn is_when_patterns_multiline(when_branch: &WhenBranch) -> bool {
 }
 
 fn fmt_if_or_when_condition<'a>(buf: &mut Buf, loc_condition: &'a Loc<Expr<'a>>, indent: u16) {
-    let is_multiline_condition = loc_condition.is_multiline();
+    let is_multiline_condition = is_multiline_at(buf, &loc_condition.value, indent, 1);
 
     if is_multiline_condition {
         let condition = expr_lift_spaces(Parens::NotNeeded, buf.text.bump(), &loc_condition.value);
@@ -1818,6 +1878,7 @@ fn fmt_when<'a>(
         buf.push_str(" ->");
 
         let inner_indent = line_indent + INDENT;
+        let is_multiline_expr = is_multiline_expr || is_multiline_at(buf, &expr.value, indent, 1);
 
         let expr = expr_lift_spaces(Parens::NotNeeded, buf.text.bump(), &expr.value);
         fmt_spaces_no_blank_lines(buf, expr.before.iter(), inner_indent);
@@ -1873,19 +1934,23 @@ fn fmt_dbg_stmt<'a>(
     args.push(condition);
     args.extend_from_slice(extra_args);
 
+    // Allocated, rather than on the stack, because widths are remembered by address
+    let dbg = buf.text.bump().alloc(Loc::at_zero(Expr::Dbg));
+
     if pnc_style {
-        Expr::PncApply(
-            &Loc::at_zero(Expr::Dbg),
-            Collection::with_items(args.into_bump_slice()),
-        )
-        .format_with_options(buf, parens, Newlines::Yes, indent);
+        Expr::PncApply(dbg, Collection::with_items(args.into_bump_slice())).format_with_options(
+            buf,
+            parens,
+            Newlines::Yes,
+            indent,
+        );
     } else {
-        Expr::Apply(
-            &Loc::at_zero(Expr::Dbg),
-            args.into_bump_slice(),
-            called_via::CalledVia::Space,
-        )
-        .format_with_options(buf, parens, Newlines::Yes, indent);
+        Expr::Apply(dbg, args.into_bump_slice(), called_via::CalledVia::Space).format_with_options(
+            buf,
+            parens,
+            Newlines::Yes,
+            indent,
+        );
     }
 
     let cont_lifted = expr_lift_spaces(Parens::NotNeeded, buf.text.bump(), &continuation.value);
@@ -2086,7 +2151,7 @@ fn fmt_closure<'a>(
     buf.push_str("|");
     buf.spaces(1);
 
-    let is_multiline = loc_ret.value.is_multiline();
+    let is_multiline = is_multiline_at(buf, &loc_ret.value, indent, 0);
 
     // If the body is multiline, go down a line and indent.
     let body_indent = if is_multiline {
@@ -2136,6 +2201,7 @@ fn fmt_record_like<'a, 'b: 'a, Field, ToSpacesAround>(
     fields: Collection<'b, Loc<Field>>,
     indent: u16,
     to_space_around: ToSpacesAround,
+    wrap: bool,
 ) where
     Field: Formattable + std::fmt::Debug,
     ToSpacesAround: Fn(&'a Bump, &'b Field) -> Spaces<'a, Field>,
@@ -2170,7 +2236,8 @@ fn fmt_record_like<'a, 'b: 'a, Field, ToSpacesAround>(
             }
         }
 
-        let is_multiline = loc_fields.iter().any(|loc_field| loc_field.is_multiline())
+        let is_multiline = wrap
+            || loc_fields.iter().any(|loc_field| loc_field.is_multiline())
             || !final_comments.is_empty();
 
         if is_multiline {
@@ -2211,8 +2278,10 @@ fn fmt_record_like<'a, 'b: 'a, Field, ToSpacesAround>(
                     Newlines::No,
                     field_indent,
                 );
-                buf.indent(field_indent);
-                buf.push_str(",");
+                if buf.config().trailing_commas || iter + 1 < loc_fields.len() {
+                    buf.indent(field_indent);
+                    buf.push_str(",");
+                }
                 last_after = field_lifted.after;
             }
 
@@ -2317,3 +2386,291 @@ pub fn sub_expr_requests_parens(expr: &Expr<'_>) -> bool {
         _ => false,
     }
 }
+
+/// Whether something on a single line, as wide as `width` says, runs past the maximum line
+/// width when it's written at the current column. Widths are only worked out when there is a
+/// maximum.
+fn wraps<'a>(
+    buf: &mut Buf<'a>,
+    indent: u16,
+    width: impl FnOnce(&mut Buf<'a>) -> Option<usize>,
+) -> bool {
+    buf.config().max_line_width.is_some()
+        && width(buf).is_some_and(|width| buf.overflows(indent, 0, width))
+}
+
+/// Whether `expr` takes more than one line, either because it already does, or because it
+/// doesn't fit on the current line when it's written `offset` columns after the current one.
+/// Layouts that depend on whether a subexpression is multiline ask this, rather than
+/// [Formattable::is_multiline], so they already account for anything that will be wrapped.
+fn is_multiline_at(buf: &mut Buf<'_>, expr: &Expr<'_>, indent: u16, offset: usize) -> bool {
+    expr.is_multiline()
+        || (buf.config().max_line_width.is_some()
+            && flat_width(buf, expr, Parens::NotNeeded)
+                .is_some_and(|width| buf.overflows(indent, offset, width)))
+}
+
+/// How wide `expr` is when it's written on a single line by `format_with_options` with the
+/// given parens, or None if it takes more than one line.
+///
+/// This is worked out from the widths of the subexpressions, which the `Buf` remembers, so
+/// deciding how to lay out each part of a large expression doesn't measure its parts again.
+fn flat_width(buf: &mut Buf<'_>, expr: &Expr<'_>, parens: Parens) -> Option<usize> {
+    let in_apply = matches!(parens, Parens::InApply | Parens::InApplyLastArg);
+
+    // These are put in parens when their spaces are lifted, see `expr_lift_spaces`
+    match expr {
+        Expr::SpaceBefore(..) | Expr::SpaceAfter(..) => None,
+        Expr::Apply(_, args, _) if in_apply && !args.is_empty() => {
+            Some(flat_width(buf, expr, Parens::NotNeeded)? + 2)
+        }
+        Expr::Closure(..) if parens == Parens::InApply => {
+            Some(flat_width(buf, expr, Parens::NotNeeded)? + 2)
+        }
+        Expr::If { .. } | Expr::When(..) | Expr::Return(..) if in_apply => {
+            Some(flat_width(buf, expr, Parens::NotNeeded)? + 2)
+        }
+        Expr::Apply(_, [], _) => buf.flat_width(|buf| {
+            expr.format_with_options(buf, parens, Newlines::Yes, 0);
+        }),
+        _ => expr_only_width(buf, expr, parens),
+    }
+}
+
+/// Like [flat_width], for an expression another one refers to. Unlike the copies formatting
+/// makes when it lifts spaces, these live as long as the `Buf` does, so their widths can be
+/// remembered by their address.
+fn sub_expr_width(buf: &mut Buf<'_>, expr: &Expr<'_>, parens: Parens) -> Option<usize> {
+    let key = (expr as *const Expr<'_> as usize, parens);
+
+    if let Some(width) = buf.widths.get(&key) {
+        return *width;
+    }
+
+    let width = flat_width(buf, expr, parens);
+
+    buf.widths.insert(key, width);
+
+    width
+}
+
+/// How wide `item` is when it's written on a single line by `format_expr_only`, or None if it
+/// takes more than one line.
+fn expr_only_width(buf: &mut Buf<'_>, item: &Expr<'_>, parens: Parens) -> Option<usize> {
+    match item {
+        Expr::SpaceBefore(..) | Expr::SpaceAfter(..) => None,
+        Expr::ParensAround(sub_expr) => {
+            let width = sub_expr_width(buf, sub_expr, Parens::NotNeeded)?;
+
+            if parens == Parens::NotNeeded && !sub_expr_requests_parens(sub_expr) {
+                Some(width)
+            } else {
+                Some(width + 2)
+            }
+        }
+        Expr::PncApply(loc_expr, loc_args)
+            if !matches!(loc_expr.value, Expr::TrySuffix(_)) || !buf.flags().parens_and_commas =>
+        {
+            Some(
+                sub_expr_width(buf, &loc_expr.value, Parens::InApply)?
+                    + collection_width(buf, *loc_args)?,
+            )
+        }
+        Expr::Apply(loc_expr, loc_args, _) if !buf.flags().parens_and_commas => {
+            let width = apply_width(buf, loc_expr, loc_args)?;
+
+            match parens {
+                Parens::InApply | Parens::InApplyLastArg if !loc_args.is_empty() => Some(width + 2),
+                _ => Some(width),
+            }
+        }
+        Expr::List(items) | Expr::Tuple(items) => collection_width(buf, *items),
+        Expr::Record(fields) => record_width(buf, None, *fields),
+        Expr::RecordUpdate { update, fields } => record_width(buf, Some(("&", update)), *fields),
+        Expr::RecordBuilder { mapper, fields } => record_width(buf, Some(("<-", mapper)), *fields),
+        Expr::Closure(loc_patterns, loc_ret) => {
+            // The bars around the arguments, and a space after them
+            let mut width = 3;
+
+            for (index, loc_pattern) in loc_patterns.iter().enumerate() {
+                if loc_pattern.is_multiline() {
+                    return None;
+                }
+
+                width += buf.flat_width(|buf| {
+                    loc_pattern.format_with_options(buf, Parens::InClosurePattern, Newlines::No, 0);
+                })?;
+
+                if index > 0 {
+                    width += 2;
+                }
+            }
+
+            Some(width + sub_expr_width(buf, &loc_ret.value, Parens::NotNeeded)?)
+        }
+        Expr::BinOps(lefts, loc_right_side) => binops_width(buf, lefts, loc_right_side),
+        Expr::If {
+            if_thens: branches,
+            final_else,
+            ..
+        } => {
+            // `if`, `then` and `else`, and the spaces around them
+            let mut width = 6 + sub_expr_width(buf, &final_else.value, Parens::NotNeeded)?;
+
+            for (index, (loc_condition, loc_then)) in branches.iter().enumerate() {
+                width += 9
+                    + sub_expr_width(buf, &loc_condition.value, Parens::NotNeeded)?
+                    + sub_expr_width(buf, &loc_then.value, Parens::NotNeeded)?;
+
+                if index > 0 {
+                    width += 6;
+                }
+            }
+
+            Some(width)
+        }
+        Expr::RecordAccess(inner, key) => {
+            Some(sub_expr_width(buf, inner, Parens::InApply)? + 1 + label_width(buf, key))
+        }
+        Expr::TupleAccess(inner, key) => {
+            Some(sub_expr_width(buf, inner, Parens::InApply)? + 1 + key.chars().count())
+        }
+        Expr::TrySuffix(inner) => Some(sub_expr_width(buf, inner, Parens::InApply)? + 1),
+        _ if item.is_multiline() => None,
+        _ => buf.flat_width(|buf| format_expr_only(item, buf, parens, Newlines::Yes, 0)),
+    }
+}
+
+/// How wide an application is on a single line, without any parens around it.
+fn apply_width(
+    buf: &mut Buf<'_>,
+    loc_expr: &Loc<Expr<'_>>,
+    loc_args: &[&Loc<Expr<'_>>],
+) -> Option<usize> {
+    let mut width = sub_expr_width(buf, &loc_expr.value, Parens::InApply)?;
+
+    for (index, loc_arg) in loc_args.iter().enumerate() {
+        let parens = if index + 1 == loc_args.len() {
+            Parens::InApplyLastArg
+        } else {
+            Parens::InApply
+        };
+
+        // `implements` is always put in parens, since it would be a keyword otherwise
+        width += 1 + match loc_arg.value {
+            Expr::Var { module_name, ident } if module_name.is_empty() && ident == "implements" => {
+                ident.len() + 2
+            }
+            _ => sub_expr_width(buf, &loc_arg.value, parens)?,
+        };
+    }
+
+    Some(width)
+}
+
+/// How wide a chain of binary operators is on a single line. Left operands ending in a closure
+/// only get parens depending on where they start, so those are counted without them.
+fn binops_width(
+    buf: &mut Buf<'_>,
+    lefts: &[(Loc<Expr<'_>>, Loc<BinOp>)],
+    loc_right_side: &Loc<Expr<'_>>,
+) -> Option<usize> {
+    let mut width = binop_operand_width(buf, &loc_right_side.value)?;
+
+    for (loc_left_side, loc_binop) in lefts.iter() {
+        // The operator, with a space on either side
+        width += binop_operand_width(buf, &loc_left_side.value)?
+            + buf.flat_width(|buf| push_op(buf, loc_binop.value))?
+            + 2;
+    }
+
+    Some(width)
+}
+
+fn binop_operand_width(buf: &mut Buf<'_>, operand: &Expr<'_>) -> Option<usize> {
+    if matches!(operand, Expr::BinOps(..)) || starts_with_unary_minus(*operand) {
+        Some(sub_expr_width(buf, operand, Parens::NotNeeded)? + 2)
+    } else {
+        sub_expr_width(buf, operand, Parens::InOperator)
+    }
+}
+
+/// How wide a list, a tuple or the arguments of a function call are on a single line.
+fn collection_width(buf: &mut Buf<'_>, items: Collection<'_, &Loc<Expr<'_>>>) -> Option<usize> {
+    if !items.final_comments().is_empty() {
+        return None;
+    }
+
+    // The braces, and a comma and a space between the items
+    let mut width = 2;
+
+    for (index, item) in items.iter().enumerate() {
+        width += sub_expr_width(buf, &item.value, Parens::NotNeeded)?;
+
+        if index > 0 {
+            width += 2;
+        }
+    }
+
+    Some(width)
+}
+
+/// How wide a record is on a single line. The `prefix` is the operator after the record being
+/// updated, or the mapper of a record builder, and that expression.
+fn record_width(
+    buf: &mut Buf<'_>,
+    prefix: Option<(&str, &Loc<Expr<'_>>)>,
+    fields: Collection<'_, Loc<AssignedField<'_, Expr<'_>>>>,
+) -> Option<usize> {
+    if !fields.final_comments().is_empty() {
+        return None;
+    }
+
+    if fields.is_empty() && prefix.is_none() {
+        return Some(2);
+    }
+
+    // The braces, and the spaces inside them
+    let mut width = 4;
+
+    if let Some((operator, loc_expr)) = prefix {
+        width += sub_expr_width(buf, &loc_expr.value, Parens::NotNeeded)? + 2 + operator.len();
+    }
+
+    for (index, field) in fields.iter().enumerate() {
+        width += match field.value {
+            AssignedField::RequiredValue(name, [], value) => {
+                label_width(buf, name.value)
+                    + 2
+                    + sub_expr_width(buf, &value.value, Parens::NotNeeded)?
+            }
+            AssignedField::OptionalValue(name, [], value) => {
+                label_width(buf, name.value)
+                    + 3
+                    + sub_expr_width(buf, &value.value, Parens::NotNeeded)?
+            }
+            AssignedField::IgnoredValue(name, [], value) => {
+                label_width(buf, name.value)
+                    + 3
+                    + sub_expr_width(buf, &value.value, Parens::NotNeeded)?
+            }
+            AssignedField::LabelOnly(name) => label_width(buf, name.value),
+            _ => return None,
+        };
+
+        if index > 0 {
+            width += 2;
+        }
+    }
+
+    Some(width)
+}
+
+fn label_width(buf: &Buf<'_>, label: &str) -> usize {
+    if buf.flags().snakify {
+        buf.flat_width(|buf| snakify_camel_ident(buf, label))
+            .unwrap_or_default()
+    } else {
+        label.chars().count()
+    }
+}
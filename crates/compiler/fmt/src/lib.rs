@@ -12,7 +12,9 @@ pub mod node;
 pub mod pattern;
 pub mod spaces;
 
+use annotation::Parens;
 use bumpalo::{collections::String, Bump};
+use roc_collections::MutMap;
 
 #[derive(Debug)]
 pub struct Buf<'a> {
@@ -22,6 +24,10 @@ pub struct Buf<'a> {
     beginning_of_line: bool,
     line_indent: u16,
     flags: MigrationFlags,
+    config: FormatConfig,
+    /// How wide subexpressions are on a single line, by their address and the parens they are
+    /// written with. See `expr::flat_width`.
+    widths: MutMap<(usize, Parens), Option<usize>>,
 }
 
 #[derive(Debug, Copy, Clone, Default)]
@@ -36,8 +42,36 @@ impl MigrationFlags {
     }
 }
 
+/// Style options for `roc format`. The default is the standard Roc style.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FormatConfig {
+    /// When set, applications, records, lists and tuples that would run past this column are
+    /// split over multiple lines, and so is whatever they are part of, as if they had been
+    /// written over multiple lines to begin with. Formatting the output again changes nothing.
+    pub max_line_width: Option<u16>,
+    /// Whether the last item of a multiline collection or record is followed by a comma.
+    pub trailing_commas: bool,
+}
+
+impl Default for FormatConfig {
+    fn default() -> Self {
+        Self {
+            max_line_width: None,
+            trailing_commas: true,
+        }
+    }
+}
+
 impl<'a> Buf<'a> {
     pub fn new_in(arena: &'a Bump, flags: MigrationFlags) -> Buf<'a> {
+        Self::new_with_config_in(arena, flags, FormatConfig::default())
+    }
+
+    pub fn new_with_config_in(
+        arena: &'a Bump,
+        flags: MigrationFlags,
+        config: FormatConfig,
+    ) -> Buf<'a> {
         Buf {
             text: String::new_in(arena),
             line_indent: 0,
@@ -45,6 +79,8 @@ impl<'a> Buf<'a> {
             newlines_to_flush: 0,
             beginning_of_line: true,
             flags,
+            config,
+            widths: MutMap::default(),
         }
     }
 
@@ -52,6 +88,54 @@ impl<'a> Buf<'a> {
         self.flags
     }
 
+    pub fn config(&self) -> FormatConfig {
+        self.config
+    }
+
+    /// The column the next character will be written at, if it is indented by `indent`.
+    fn column(&self, indent: u16) -> usize {
+        if self.beginning_of_line {
+            indent as usize
+        } else if self.newlines_to_flush > 0 {
+            self.spaces_to_flush
+        } else {
+            let line_start = self.text.rfind('\n').map_or(0, |i| i + 1);
+
+            self.text[line_start..].chars().count() + self.spaces_to_flush
+        }
+    }
+
+    /// Whether something `width` columns wide, written at the column the next character will be
+    /// written at plus `offset`, runs past the maximum line width. Always false when no maximum
+    /// is configured.
+    pub fn overflows(&self, indent: u16, offset: usize, width: usize) -> bool {
+        match self.config.max_line_width {
+            Some(max) => self.column(indent) + offset + width > max as usize,
+            None => false,
+        }
+    }
+
+    /// How wide what `format` writes is, or None if it writes more than one line.
+    pub fn flat_width(&self, format: impl FnOnce(&mut Buf<'a>)) -> Option<usize> {
+        let config = FormatConfig {
+            max_line_width: None,
+            ..self.config
+        };
+        let mut scratch = Buf::new_with_config_in(self.text.bump(), self.flags, config);
+
+        // Indentation is the caller's business; we only measure what comes after it.
+        scratch.beginning_of_line = false;
+
+        format(&mut scratch);
+
+        let text = scratch.text.as_str();
+
+        match text.contains('\n') {
+            true => None,
+            false => Some(text.chars().count()),
+        }
+    }
+
     pub fn as_str(&'a self) -> &'a str {
         self.text.as_str()
     }
@@ -113,6 +197,18 @@ impl<'a> Buf<'a> {
         self.text.push_str(s);
     }
 
+    /// Append `text` exactly as it is, e.g. source code that is not being reformatted.
+    pub fn push_verbatim(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+
+        self.flush_spaces();
+
+        self.text.push_str(text);
+        self.beginning_of_line = text.ends_with('\n');
+    }
+
     pub fn push_char_literal(&mut self, c: char) {
         self.flush_spaces();
 
@@ -1327,11 +1327,13 @@ impl<
 
     fn build_num_abs(&mut self, dst: &Symbol, src: &Symbol, layout: &InLayout<'a>) {
         match self.interner().get_repr(*layout) {
-            LayoutRepr::Builtin(Builtin::Int(IntWidth::I64 | IntWidth::U64)) => {
-                let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
-                let src_reg = self.storage_manager.load_to_general_reg(&mut self.buf, src);
-                ASM::abs_reg64_reg64(&mut self.buf, dst_reg, src_reg);
-            }
+            LayoutRepr::Builtin(Builtin::Int(int_width)) => self.build_fn_call(
+                dst,
+                bitcode::NUM_ABS_OR_PANIC_INT[int_width].to_string(),
+                &[*src],
+                &[*layout],
+                layout,
+            ),
             LayoutRepr::Builtin(Builtin::Float(FloatWidth::F64)) => {
                 let dst_reg = self.storage_manager.claim_float_reg(&mut self.buf, dst);
                 let src_reg = self.storage_manager.load_to_float_reg(&mut self.buf, src);
@@ -1342,6 +1344,13 @@ impl<
                 let src_reg = self.storage_manager.load_to_float_reg(&mut self.buf, src);
                 ASM::abs_freg32_freg32(&mut self.buf, &mut self.relocs, dst_reg, src_reg);
             }
+            LayoutRepr::DEC => self.build_fn_call(
+                dst,
+                bitcode::DEC_ABS.to_string(),
+                &[*src],
+                &[Layout::DEC],
+                &Layout::DEC,
+            ),
             x => todo!("NumAbs: layout, {:?}", x),
         }
     }
@@ -1444,7 +1453,16 @@ impl<
     ) {
         let function_name = match self.interner().get_repr(*num_layout) {
             LayoutRepr::Builtin(Builtin::Int(width)) => &bitcode::NUM_ADD_CHECKED_INT[width],
-            LayoutRepr::Builtin(Builtin::Float(width)) => &bitcode::NUM_ADD_CHECKED_FLOAT[width],
+            LayoutRepr::Builtin(Builtin::Float(_)) => {
+                return self.build_float_checked(
+                    Self::build_num_add,
+                    dst,
+                    src1,
+                    src2,
+                    num_layout,
+                    return_layout,
+                );
+            }
             LayoutRepr::Builtin(Builtin::Decimal) => bitcode::DEC_ADD_WITH_OVERFLOW,
             x => internal_error!("NumAddChecked is not defined for {:?}", x),
         };
@@ -1492,7 +1510,16 @@ impl<
     ) {
         let function_name = match self.interner().get_repr(*num_layout) {
             LayoutRepr::Builtin(Builtin::Int(width)) => &bitcode::NUM_SUB_CHECKED_INT[width],
-            LayoutRepr::Builtin(Builtin::Float(width)) => &bitcode::NUM_SUB_CHECKED_FLOAT[width],
+            LayoutRepr::Builtin(Builtin::Float(_)) => {
+                return self.build_float_checked(
+                    Self::build_num_sub,
+                    dst,
+                    src1,
+                    src2,
+                    num_layout,
+                    return_layout,
+                );
+            }
             LayoutRepr::Builtin(Builtin::Decimal) => bitcode::DEC_SUB_WITH_OVERFLOW,
             x => internal_error!("NumSubChecked is not defined for {:?}", x),
         };
@@ -1621,7 +1648,16 @@ impl<
     ) {
         let function_name = match self.interner().get_repr(*num_layout) {
             LayoutRepr::Builtin(Builtin::Int(width)) => &bitcode::NUM_MUL_CHECKED_INT[width],
-            LayoutRepr::Builtin(Builtin::Float(width)) => &bitcode::NUM_MUL_CHECKED_FLOAT[width],
+            LayoutRepr::Builtin(Builtin::Float(_)) => {
+                return self.build_float_checked(
+                    Self::build_num_mul,
+                    dst,
+                    src1,
+                    src2,
+                    num_layout,
+                    return_layout,
+                );
+            }
             LayoutRepr::Builtin(Builtin::Decimal) => bitcode::DEC_MUL_WITH_OVERFLOW,
             x => internal_error!("NumMulChecked is not defined for {:?}", x),
         };
@@ -1765,11 +1801,13 @@ impl<
 
     fn build_num_neg(&mut self, dst: &Symbol, src: &Symbol, layout: &InLayout<'a>) {
         match self.layout_interner.get_repr(*layout) {
-            LayoutRepr::Builtin(Builtin::Int(quadword_and_smaller!())) => {
-                let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
-                let src_reg = self.storage_manager.load_to_general_reg(&mut self.buf, src);
-                ASM::neg_reg64_reg64(&mut self.buf, dst_reg, src_reg);
-            }
+            LayoutRepr::Builtin(Builtin::Int(int_width)) => self.build_fn_call(
+                dst,
+                bitcode::NUM_NEG_OR_PANIC_INT[int_width].to_string(),
+                &[*src],
+                &[*layout],
+                layout,
+            ),
             LayoutRepr::F32 => {
                 let dst_reg = self.storage_manager.claim_float_reg(&mut self.buf, dst);
                 let src_reg = self.storage_manager.load_to_float_reg(&mut self.buf, src);
@@ -4461,7 +4499,9 @@ impl<
         let buf = &mut self.buf;
 
         match int_width {
-            IntWidth::U128 | IntWidth::I128 => todo!(),
+            IntWidth::U128 | IntWidth::I128 => {
+                self.build_int128_bitwise(ASM::and_reg64_reg64_reg64, dst, src1, src2, int_width)
+            }
             _ => {
                 let dst_reg = self.storage_manager.claim_general_reg(buf, dst);
                 let src1_reg = self.storage_manager.load_to_general_reg(buf, src1);
@@ -4481,7 +4521,9 @@ impl<
         let buf = &mut self.buf;
 
         match int_width {
-            IntWidth::U128 | IntWidth::I128 => todo!(),
+            IntWidth::U128 | IntWidth::I128 => {
+                self.build_int128_bitwise(ASM::or_reg64_reg64_reg64, dst, src1, src2, int_width)
+            }
             _ => {
                 let dst_reg = self.storage_manager.claim_general_reg(buf, dst);
                 let src1_reg = self.storage_manager.load_to_general_reg(buf, src1);
@@ -4501,7 +4543,9 @@ impl<
         let buf = &mut self.buf;
 
         match int_width {
-            IntWidth::U128 | IntWidth::I128 => todo!(),
+            IntWidth::U128 | IntWidth::I128 => {
+                self.build_int128_bitwise(ASM::xor_reg64_reg64_reg64, dst, src1, src2, int_width)
+            }
             _ => {
                 let dst_reg = self.storage_manager.claim_general_reg(buf, dst);
                 let src1_reg = self.storage_manager.load_to_general_reg(buf, src1);
@@ -4521,7 +4565,17 @@ impl<
         let buf = &mut self.buf;
 
         match int_width {
-            IntWidth::U128 | IntWidth::I128 => todo!(),
+            IntWidth::U128 | IntWidth::I128 => {
+                let layout = Layout::from_int_width(int_width);
+
+                self.build_fn_call(
+                    dst,
+                    bitcode::NUM_SHIFT_LEFT_BY[int_width].to_string(),
+                    &[*src1, *src2],
+                    &[layout, Layout::U8],
+                    &layout,
+                );
+            }
             _ => {
                 let dst_reg = self.storage_manager.claim_general_reg(buf, dst);
                 let src1_reg = self.storage_manager.load_to_general_reg(buf, src1);
@@ -4548,7 +4602,17 @@ impl<
         let buf = &mut self.buf;
 
         match int_width {
-            IntWidth::U128 | IntWidth::I128 => todo!(),
+            IntWidth::U128 | IntWidth::I128 => {
+                let layout = Layout::from_int_width(int_width);
+
+                self.build_fn_call(
+                    dst,
+                    bitcode::NUM_SHIFT_RIGHT_BY[int_width].to_string(),
+                    &[*src1, *src2],
+                    &[layout, Layout::U8],
+                    &layout,
+                );
+            }
             _ => {
                 let dst_reg = self.storage_manager.claim_general_reg(buf, dst);
                 let src1_reg = self.storage_manager.load_to_general_reg(buf, src1);
@@ -4597,17 +4661,13 @@ impl<
 
         match int_width {
             IntWidth::U128 | IntWidth::I128 => {
-                let layout = match int_width {
-                    IntWidth::I128 => Layout::I128,
-                    IntWidth::U128 => Layout::U128,
-                    _ => unreachable!(),
-                };
+                let layout = Layout::from_int_width(int_width);
 
                 self.build_fn_call(
                     dst,
                     bitcode::NUM_SHIFT_RIGHT_ZERO_FILL[int_width].to_string(),
                     &[*src1, *src2],
-                    &[layout, layout],
+                    &[layout, Layout::U8],
                     &layout,
                 );
             }
@@ -4651,38 +4711,74 @@ impl<
         let buf = &mut self.buf;
 
         match (source, target) {
-            (U128, U64) => {
+            (I128 | U128, quadword_and_smaller!()) => {
                 let dst_reg = self.storage_manager.claim_general_reg(buf, dst);
 
                 let (offset, _size) = self.storage_manager.stack_offset_and_size(src);
 
-                ASM::mov_reg64_base32(buf, dst_reg, offset + 8);
+                // the lower 8 bytes hold the bits that are kept when truncating
+                ASM::mov_reg64_base32(buf, dst_reg, offset);
+
+                if let Some(width @ (RegisterWidth::W8 | RegisterWidth::W16 | RegisterWidth::W32)) =
+                    RegisterWidth::try_from_layout(LayoutRepr::Builtin(Builtin::Int(target)))
+                {
+                    ASM::movzx_reg_reg(buf, width, dst_reg, dst_reg);
+                }
 
                 return;
             }
-            (U64, U128) => {
+            (quadword_and_smaller!(), I128 | U128) => {
                 let src_reg = self.storage_manager.load_to_general_reg(buf, src);
 
                 let base_offset = self.storage_manager.claim_stack_area_layout(
                     self.layout_interner,
                     *dst,
-                    Layout::U128,
+                    Layout::from_int_width(target),
                 );
 
                 let tmp = Symbol::DEV_TMP;
                 let tmp_reg = self.storage_manager.claim_general_reg(buf, &tmp);
 
-                // move a zero into the lower 8 bytes
-                ASM::mov_reg64_imm64(buf, tmp_reg, 0x0);
-                ASM::mov_base32_reg64(buf, base_offset + 8, tmp_reg);
+                let width =
+                    RegisterWidth::try_from_layout(LayoutRepr::Builtin(Builtin::Int(source)))
+                        .unwrap();
+
+                if source.is_signed() && target.is_signed() {
+                    // sign-extend into the lower 8 bytes
+                    ASM::movsx_reg_reg(buf, width, tmp_reg, src_reg);
+                    ASM::mov_base32_reg64(buf, base_offset, tmp_reg);
 
-                ASM::mov_base32_reg64(buf, base_offset, src_reg);
+                    // the upper 8 bytes are all ones for a negative value, and all zeros otherwise
+                    let sign = Symbol::DEV_TMP2;
+                    let sign_reg = self.storage_manager.claim_general_reg(buf, &sign);
+                    ASM::mov_reg64_imm64(buf, sign_reg, 0x0);
+                    ASM::signed_compare_reg64(
+                        buf,
+                        RegisterWidth::W64,
+                        CompareOperation::LessThan,
+                        sign_reg,
+                        tmp_reg,
+                        sign_reg,
+                    );
+                    ASM::neg_reg64_reg64(buf, sign_reg, sign_reg);
+                    ASM::mov_base32_reg64(buf, base_offset + 8, sign_reg);
+
+                    self.free_symbol(&sign);
+                } else {
+                    // zero-extend into the lower 8 bytes
+                    ASM::movzx_reg_reg(buf, width, tmp_reg, src_reg);
+                    ASM::mov_base32_reg64(buf, base_offset, tmp_reg);
+
+                    // move a zero into the upper 8 bytes
+                    ASM::mov_reg64_imm64(buf, tmp_reg, 0x0);
+                    ASM::mov_base32_reg64(buf, base_offset + 8, tmp_reg);
+                }
 
                 self.free_symbol(&tmp);
 
                 return;
             }
-            (U128, I128) | (I128, U128) => {
+            (I128 | U128, I128 | U128) => {
                 let to_offset = self.storage_manager.claim_stack_area_layout(
                     self.layout_interner,
                     *dst,
@@ -4714,21 +4810,21 @@ impl<
         } else {
             match (source, target) {
                 // -- CASTING UP --
-                (I8 | U8, U16 | U32 | U64) => {
+                (I8 | U8, U16 | U32 | U64) | (U8, I16 | I32 | I64) => {
                     // zero  out the register
                     ASM::xor_reg64_reg64_reg64(buf, dst_reg, dst_reg, dst_reg);
 
                     // move the 8-bit integer
                     ASM::mov_reg_reg(buf, RegisterWidth::W8, dst_reg, src_reg);
                 }
-                (U16, U32 | U64) => {
+                (I16 | U16, U32 | U64) | (U16, I32 | I64) => {
                     // zero  out the register
                     ASM::xor_reg64_reg64_reg64(buf, dst_reg, dst_reg, dst_reg);
 
                     // move the 16-bit integer
                     ASM::mov_reg_reg(buf, RegisterWidth::W16, dst_reg, src_reg);
                 }
-                (U32, U64) => {
+                (I32 | U32, U64) | (U32, I64) => {
                     // zero  out the register
                     ASM::xor_reg64_reg64_reg64(buf, dst_reg, dst_reg, dst_reg);
 
@@ -4770,6 +4866,72 @@ impl<
         CC: CallConv<GeneralReg, FloatReg, ASM>,
     > Backend64Bit<'a, 'r, GeneralReg, FloatReg, ASM, CC>
 {
+    /// 128-bit integers live on the stack, and a bitwise operation on one is the same
+    /// operation on each of its 8-byte halves.
+    fn build_int128_bitwise(
+        &mut self,
+        op: fn(&mut Vec<'a, u8>, GeneralReg, GeneralReg, GeneralReg),
+        dst: &Symbol,
+        src1: &Symbol,
+        src2: &Symbol,
+        int_width: IntWidth,
+    ) {
+        let buf = &mut self.buf;
+
+        let (src1_offset, _) = self.storage_manager.stack_offset_and_size(src1);
+        let (src2_offset, _) = self.storage_manager.stack_offset_and_size(src2);
+
+        let dst_offset = self.storage_manager.claim_stack_area_layout(
+            self.layout_interner,
+            *dst,
+            Layout::from_int_width(int_width),
+        );
+
+        let tmp1 = Symbol::DEV_TMP;
+        let tmp1_reg = self.storage_manager.claim_general_reg(buf, &tmp1);
+        let tmp2 = Symbol::DEV_TMP2;
+        let tmp2_reg = self.storage_manager.claim_general_reg(buf, &tmp2);
+
+        for half in [0, 8] {
+            ASM::mov_reg64_base32(buf, tmp1_reg, src1_offset + half);
+            ASM::mov_reg64_base32(buf, tmp2_reg, src2_offset + half);
+            op(buf, tmp1_reg, tmp1_reg, tmp2_reg);
+            ASM::mov_base32_reg64(buf, dst_offset + half, tmp1_reg);
+        }
+
+        self.free_symbol(&tmp1);
+        self.free_symbol(&tmp2);
+    }
+
+    /// Float arithmetic doesn't trap, so a checked float operation is the plain operation,
+    /// which has overflowed if its answer isn't finite. This is done inline because the zig
+    /// builtins return a float and a bool together, partly in a float register, which
+    /// `build_fn_call` can't receive.
+    fn build_float_checked(
+        &mut self,
+        build_op: fn(&mut Self, &Symbol, &Symbol, &Symbol, &InLayout<'a>),
+        dst: &Symbol,
+        src1: &Symbol,
+        src2: &Symbol,
+        num_layout: &InLayout<'a>,
+        return_layout: &InLayout<'a>,
+    ) {
+        let value = self.debug_symbol("checked_value");
+        let is_finite = self.debug_symbol("checked_is_finite");
+        let has_overflowed = self.debug_symbol("checked_has_overflowed");
+
+        build_op(self, &value, src1, src2, num_layout);
+        self.build_num_is_finite(&is_finite, &value, num_layout);
+        self.build_not(&has_overflowed, &is_finite, &Layout::BOOL);
+
+        let fields = self.env.arena.alloc([value, has_overflowed]);
+        self.create_struct(dst, return_layout, fields);
+
+        self.free_symbol(&value);
+        self.free_symbol(&is_finite);
+        self.free_symbol(&has_overflowed);
+    }
+
     fn build_fn_call_stack_return<const N: usize>(
         &mut self,
         function_name: String,
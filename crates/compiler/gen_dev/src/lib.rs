@@ -30,6 +30,8 @@ mod object_builder;
 pub use object_builder::build_module;
 use roc_target::Target;
 mod run_roc;
+mod support;
+pub use support::supports_procedures;
 
 #[derive(Debug, Clone, Copy)]
 pub enum AssemblyBackendMode {
@@ -277,6 +279,9 @@ impl<'a> LastSeenMap<'a> {
 
             Stmt::Dbg { .. } => todo!("dbg not implemented in the dev backend"),
             Stmt::Expect { .. } => todo!("expect is not implemented in the dev backend"),
+            Stmt::Coverage { remainder, .. } => {
+                self.scan_ast_help(remainder);
+            }
 
             Stmt::Crash(msg, _crash_tag) => {
                 self.set_last_seen(*msg, stmt);
@@ -705,6 +710,8 @@ trait Backend<'a> {
                 self.free_symbols(stmt);
             }
             Stmt::Crash(msg, crash_tag) => self.roc_panic(*msg, *crash_tag),
+            // coverage is only collected by the LLVM backend
+            Stmt::Coverage { remainder, .. } => self.build_stmt(layout_ids, remainder, ret_layout),
             x => todo!("the statement, {:?}", x),
         }
     }
@@ -1906,6 +1913,45 @@ trait Backend<'a> {
 
                 self.build_num_int_cast(sym, &args[0], source_width, target_width)
             }
+            LowLevel::NumToIntChecked => {
+                // the return layout is { value: N, out_of_bounds: Bool }
+                let source_width = arg_layouts[0].try_int_width().unwrap();
+
+                let target_width = match self.interner().get_repr(*ret_layout) {
+                    LayoutRepr::Struct(field_layouts) if field_layouts.len() == 2 => {
+                        field_layouts[0].try_int_width().unwrap()
+                    }
+                    other => internal_error!("NumToIntChecked returns a struct, found {other:?}"),
+                };
+
+                // an unsigned int fits in any wider int, and a signed int in any wider signed int
+                let always_fits = source_width == target_width
+                    || (source_width.stack_size() < target_width.stack_size()
+                        && (!source_width.is_signed() || target_width.is_signed()));
+
+                if always_fits {
+                    let value = self.debug_symbol("value");
+                    let out_of_bounds = self.debug_symbol("out_of_bounds");
+
+                    self.build_num_int_cast(&value, &args[0], source_width, target_width);
+                    self.load_literal(&out_of_bounds, &Layout::BOOL, &Literal::Bool(false));
+
+                    let fields = self.env().arena.alloc([value, out_of_bounds]);
+                    self.create_struct(sym, ret_layout, fields);
+
+                    self.free_symbol(&value);
+                    self.free_symbol(&out_of_bounds);
+                } else {
+                    // an unsigned int can only be too big, but a signed one can also be too small
+                    let intrinsic = if source_width.is_signed() {
+                        &bitcode::NUM_INT_TO_INT_CHECKING_MAX_AND_MIN[target_width][source_width]
+                    } else {
+                        &bitcode::NUM_INT_TO_INT_CHECKING_MAX[target_width][source_width]
+                    };
+
+                    self.build_fn_call(sym, intrinsic.to_string(), args, arg_layouts, ret_layout);
+                }
+            }
             LowLevel::NumIsMultipleOf => {
                 let int_width = arg_layouts[0].try_int_width().unwrap();
                 let intrinsic = bitcode::NUM_IS_MULTIPLE_OF[int_width].to_string();
@@ -0,0 +1,101 @@
+//! Checks, before building anything, whether the dev backend can build a set of procedures.
+//!
+//! The dev backend panics on things it doesn't implement yet. Callers that have another backend
+//! to fall back on, like the REPL, use [supports_procedures] to pick one up front instead.
+
+use roc_builtins::bitcode::IntWidth;
+use roc_collections::all::MutMap;
+use roc_module::low_level::{LowLevel, LowLevelWrapperType};
+use roc_module::symbol::Symbol;
+use roc_mono::ir::{CallType, Expr, Proc, ProcLayout, Stmt};
+use roc_mono::layout::{Builtin, InLayout, LayoutInterner, LayoutRepr, STLayoutInterner};
+
+/// Whether [crate::build_module] can build all of these procedures.
+///
+/// This errs on the side of saying no: anything the dev backend has a `todo!` for is reported
+/// as unsupported, even when the particular layouts involved might be fine.
+pub fn supports_procedures<'a>(
+    layout_interner: &STLayoutInterner<'a>,
+    procedures: &MutMap<(Symbol, ProcLayout<'a>), Proc<'a>>,
+) -> bool {
+    procedures
+        .values()
+        .all(|proc| supports_stmt(layout_interner, &proc.body))
+}
+
+fn supports_stmt<'a>(layout_interner: &STLayoutInterner<'a>, stmt: &Stmt<'a>) -> bool {
+    match stmt {
+        Stmt::Let(_, expr, layout, following) => {
+            supports_expr(layout_interner, expr, *layout)
+                && supports_stmt(layout_interner, following)
+        }
+        Stmt::Switch {
+            branches,
+            default_branch,
+            ..
+        } => {
+            branches
+                .iter()
+                .all(|(_, _, branch)| supports_stmt(layout_interner, branch))
+                && supports_stmt(layout_interner, default_branch.1)
+        }
+        Stmt::Refcounting(_, following) => supports_stmt(layout_interner, following),
+        Stmt::Join {
+            body, remainder, ..
+        } => supports_stmt(layout_interner, body) && supports_stmt(layout_interner, remainder),
+        Stmt::Coverage { remainder, .. } => supports_stmt(layout_interner, remainder),
+        Stmt::Ret(_) | Stmt::Jump(_, _) | Stmt::Crash(_, _) => true,
+        Stmt::Dbg { .. } | Stmt::Expect { .. } => false,
+    }
+}
+
+fn supports_expr<'a>(
+    layout_interner: &STLayoutInterner<'a>,
+    expr: &Expr<'a>,
+    layout: InLayout<'a>,
+) -> bool {
+    match expr {
+        Expr::Call(call) => match &call.call_type {
+            CallType::ByName {
+                name, ret_layout, ..
+            } => match LowLevelWrapperType::from_symbol(name.name()) {
+                LowLevelWrapperType::CanBeReplacedBy(lowlevel) => {
+                    supports_low_level(layout_interner, lowlevel, *ret_layout)
+                }
+                LowLevelWrapperType::NotALowLevelWrapper => true,
+            },
+            CallType::LowLevel { op, .. } => supports_low_level(layout_interner, *op, layout),
+            CallType::ByPointer { .. } => false,
+            CallType::Foreign { .. } | CallType::HigherOrder(_) => true,
+        },
+        Expr::FunctionPointer { .. } | Expr::ErasedMake { .. } | Expr::ErasedLoad { .. } => false,
+        _ => true,
+    }
+}
+
+/// Mirrors the low levels that `build_run_low_level` handles, and the layouts it handles them for.
+fn supports_low_level<'a>(
+    layout_interner: &STLayoutInterner<'a>,
+    lowlevel: LowLevel,
+    ret_layout: InLayout<'a>,
+) -> bool {
+    match lowlevel {
+        LowLevel::BlackBox
+        | LowLevel::BoxExpr
+        | LowLevel::Hash
+        | LowLevel::ListGetCapacity
+        | LowLevel::ListIsUnique
+        | LowLevel::ListSortWith
+        | LowLevel::NumToFloatChecked
+        | LowLevel::StrFromFloat
+        | LowLevel::StrFromInt
+        | LowLevel::StrReleaseExcessCapacity
+        | LowLevel::UnboxExpr
+        | LowLevel::Unreachable => false,
+        LowLevel::NumDivTruncUnchecked | LowLevel::NumRemUnchecked => !matches!(
+            layout_interner.get_repr(ret_layout),
+            LayoutRepr::Builtin(Builtin::Int(IntWidth::I128 | IntWidth::U128))
+        ),
+        _ => true,
+    }
+}
@@ -45,6 +45,7 @@ use roc_debug_flags::dbg_do;
 use roc_debug_flags::ROC_PRINT_LLVM_FN_VERIFICATION;
 use roc_error_macros::{internal_error, todo_lambda_erasure};
 use roc_module::symbol::{Interns, ModuleId, Symbol};
+use roc_mono::coverage::CoveragePoint;
 use roc_mono::ir::{
     BranchInfo, CallType, CrashTag, EntryPoint, GlueLayouts, HostExposedLambdaSet,
     HostExposedLambdaSets, ListLiteralElement, ModifyRc, OptLevel, ProcLayout, SingleEntryPoint,
@@ -3487,6 +3488,32 @@ pub(crate) fn build_exp_stmt<'a, 'ctx>(
             )
         }
 
+        Coverage { point, remainder } => {
+            let i64_type = env.context.i64_type();
+            let counter = get_coverage_counter_ptr(env, point);
+
+            let count = env
+                .builder
+                .new_build_load(i64_type, counter, "coverage_count")
+                .into_int_value();
+            let count = env.builder.new_build_int_add(
+                count,
+                i64_type.const_int(1, false),
+                "coverage_count_inc",
+            );
+            env.builder.new_build_store(counter, count);
+
+            build_exp_stmt(
+                env,
+                layout_interner,
+                layout_ids,
+                func_spec_solutions,
+                scope,
+                parent,
+                remainder,
+            )
+        }
+
         Expect {
             condition: cond_symbol,
             region,
@@ -5013,6 +5040,24 @@ pub fn get_panic_tag_ptr<'ctx>(env: &Env<'_, 'ctx, '_>) -> PointerValue<'ctx> {
     global.as_pointer_value()
 }
 
+/// Pointer to the hit counter of a coverage point.
+/// The test runner looks these up by name once the expects have run.
+fn get_coverage_counter_ptr<'ctx>(
+    env: &Env<'_, 'ctx, '_>,
+    point: &CoveragePoint,
+) -> PointerValue<'ctx> {
+    let i64_typ = env.context.i64_type();
+
+    let global_name = point.counter_name();
+    let global = env.module.get_global(&global_name).unwrap_or_else(|| {
+        let global = env.module.add_global(i64_typ, None, &global_name);
+        global.set_initializer(&i64_typ.const_zero());
+        global
+    });
+
+    global.as_pointer_value()
+}
+
 fn set_jump_and_catch_long_jump<'a, 'ctx>(
     env: &Env<'a, 'ctx, '_>,
     layout_interner: &STLayoutInterner<'a>,
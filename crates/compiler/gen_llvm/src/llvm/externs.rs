@@ -1,5 +1,5 @@
 use crate::debug_info_init;
-use crate::llvm::bitcode::call_void_bitcode_fn;
+use crate::llvm::bitcode::{call_bitcode_fn, call_void_bitcode_fn};
 use crate::llvm::build::{add_func, get_panic_msg_ptr, get_panic_tag_ptr, BuilderExt, C_CALL_CONV};
 use crate::llvm::build::{CCReturn, Env, FunctionSpec};
 use crate::llvm::convert::zig_str_type;
@@ -210,6 +210,43 @@ pub fn add_default_roc_externs(env: &Env<'_, '_, '_>) {
     }
 }
 
+/// Define roc_dict_pseudo_seed, which gives hosts that build or look up Dicts themselves
+/// (like roc_std's RocDict) the seed the app's Dict hashes keys with.
+pub fn add_dict_pseudo_seed_export(env: &Env<'_, '_, '_>) {
+    let builder = env.builder;
+
+    let fn_spec = FunctionSpec::cconv(
+        env,
+        CCReturn::Return,
+        Some(env.context.i64_type().into()),
+        &[],
+    );
+    let fn_val = add_func(
+        env.context,
+        env.module,
+        "roc_dict_pseudo_seed",
+        fn_spec,
+        Linkage::External,
+    );
+
+    let subprogram = env.new_subprogram("roc_dict_pseudo_seed");
+    fn_val.set_subprogram(subprogram);
+
+    debug_info_init!(env, fn_val);
+
+    let entry = env.context.append_basic_block(fn_val, "entry");
+
+    builder.position_at_end(entry);
+
+    let seed = call_bitcode_fn(env, &[], bitcode::UTILS_DICT_PSEUDO_SEED);
+
+    builder.new_build_return(Some(&seed));
+
+    if cfg!(debug_assertions) {
+        crate::llvm::build::verify_fn(fn_val);
+    }
+}
+
 fn unreachable_function(env: &Env, name: &str) {
     // The type of this function (but not the implementation) should have
     // already been defined by the builtins, which rely on it.
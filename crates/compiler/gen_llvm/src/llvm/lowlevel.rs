@@ -1441,6 +1441,28 @@ pub(crate) fn run_low_level<'a, 'ctx>(
             call_bitcode_fn(env, &[], bitcode::UTILS_DICT_PSEUDO_SEED)
         }
 
+        BlackBox => {
+            // A volatile store can not be removed, and it makes the value (and any memory it
+            // points to) escape, so LLVM has to compute it. LLVM can't assume anything about
+            // what a volatile load gives back, so it can't constant-fold what uses the result.
+            arguments!(value);
+
+            let ptr = create_entry_block_alloca(env, value.get_type(), "black_box");
+            env.builder
+                .new_build_store(ptr, value)
+                .set_volatile(true)
+                .unwrap();
+
+            let result = env.builder.new_build_load(value.get_type(), ptr, "black_box");
+            result
+                .as_instruction_value()
+                .unwrap()
+                .set_volatile(true)
+                .unwrap();
+
+            result
+        }
+
         ListIncref | ListDecref | SetJmp | LongJmp | SetLongJmpBuffer => {
             unreachable!("only inserted in dev backend codegen")
         }
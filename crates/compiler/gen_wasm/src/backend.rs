@@ -607,6 +607,8 @@ impl<'a, 'r> WasmBackend<'a, 'r> {
 
             Stmt::Dbg { .. } => todo!("dbg is not implemented in the wasm backend"),
             Stmt::Expect { .. } => todo!("expect is not implemented in the wasm backend"),
+            // coverage is only collected by the LLVM backend
+            Stmt::Coverage { remainder, .. } => self.stmt(remainder),
 
             Stmt::Crash(sym, tag) => self.stmt_crash(*sym, *tag),
         }
@@ -2253,6 +2253,8 @@ impl<'a> LowLevelCall<'a> {
             SetJmp | LongJmp | SetLongJmpBuffer => {
                 unreachable!("only inserted in dev backend codegen")
             }
+
+            BlackBox => unreachable!("only inserted for `roc bench`, which uses the LLVM backend"),
         }
     }
 
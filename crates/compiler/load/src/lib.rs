@@ -19,10 +19,12 @@ const SKIP_SUBS_CACHE: bool = {
 pub use roc_load_internal::docs;
 pub use roc_load_internal::file::{
     ExecutionMode, ExpectMetadata, LoadConfig, LoadResult, LoadStart, LoadingProblem, Phase,
-    Threading,
+    TestOptions, Threading,
 };
+pub use roc_load_internal::hash_contents;
 pub use roc_load_internal::module::{
-    CheckedModule, EntryPoint, Expectations, ExposedToHost, LoadedModule, MonomorphizedModule,
+    CheckedModule, EntryPoint, Expectations, ExposedToHost, LoadCache, LoadedModule,
+    MonomorphizedModule,
 };
 pub use roc_solve::FunctionKind;
 
@@ -126,16 +128,37 @@ pub fn load_and_monomorphize<'a>(
     opt_main_path: Option<PathBuf>,
     roc_cache_dir: RocCacheDir<'_>,
     load_config: LoadConfig,
+) -> Result<MonomorphizedModule<'a>, LoadMonomorphizedError<'a>> {
+    load_and_monomorphize_with_cache(
+        arena,
+        filename,
+        opt_main_path,
+        roc_cache_dir,
+        load_config,
+        &LoadCache::default(),
+    )
+}
+
+/// Like [load_and_monomorphize], but modules that did not change since the load that produced
+/// `previous` are not type checked again.
+pub fn load_and_monomorphize_with_cache<'a>(
+    arena: &'a Bump,
+    filename: PathBuf,
+    opt_main_path: Option<PathBuf>,
+    roc_cache_dir: RocCacheDir<'_>,
+    load_config: LoadConfig,
+    previous: &LoadCache,
 ) -> Result<MonomorphizedModule<'a>, LoadMonomorphizedError<'a>> {
     use LoadResult::*;
 
-    let load_start = LoadStart::from_path(
+    let load_start = LoadStart::from_path_with_cache(
         arena,
         filename,
         opt_main_path,
         load_config.render,
         roc_cache_dir,
         load_config.palette,
+        previous,
     )?;
 
     let exposed_types = ExposedByModule::default();
@@ -152,16 +175,37 @@ pub fn load_and_typecheck<'a>(
     opt_main_path: Option<PathBuf>,
     roc_cache_dir: RocCacheDir<'_>,
     load_config: LoadConfig,
+) -> Result<LoadedModule, LoadingProblem<'a>> {
+    load_and_typecheck_with_cache(
+        arena,
+        filename,
+        opt_main_path,
+        roc_cache_dir,
+        load_config,
+        &LoadCache::default(),
+    )
+}
+
+/// Like [load_and_typecheck], but modules that did not change since the load that produced
+/// `previous` are not type checked again.
+pub fn load_and_typecheck_with_cache<'a>(
+    arena: &'a Bump,
+    filename: PathBuf,
+    opt_main_path: Option<PathBuf>,
+    roc_cache_dir: RocCacheDir<'_>,
+    load_config: LoadConfig,
+    previous: &LoadCache,
 ) -> Result<LoadedModule, LoadingProblem<'a>> {
     use LoadResult::*;
 
-    let load_start = LoadStart::from_path(
+    let load_start = LoadStart::from_path_with_cache(
         arena,
         filename,
         opt_main_path,
         load_config.render,
         roc_cache_dir,
         load_config.palette,
+        previous,
     )?;
 
     let exposed_types = ExposedByModule::default();
@@ -0,0 +1,261 @@
+//! Doc tests: the fenced `roc` code blocks in `##` doc comments, which `roc test --doc` runs as
+//! top-level expects of the module they are in.
+
+use bumpalo::Bump;
+use roc_parse::ast::{CommentOrNewline, Defs, ValueDef};
+use roc_parse::expr::test_parse_expr;
+use roc_parse::parser::SyntaxError;
+use roc_parse::state::State;
+use roc_region::all::{Position, Region};
+
+const FENCE: &str = "```";
+
+/// A fenced `roc` code block in a doc comment.
+#[derive(Debug, PartialEq, Eq)]
+struct DocTestBlock {
+    /// The code of the block at the same byte offsets as in the module's source, with
+    /// everything else (including the `##` at the start of each line) replaced by whitespace.
+    /// That way, the regions of the parsed code point at the doc comment.
+    masked_src: String,
+    region: Region,
+}
+
+/// Parse the `roc` code blocks in the doc comments of the module, and add each of them to `defs`
+/// as a top-level `expect`. Like the body of an `expect`, a block may start with defs, and must
+/// end in a `Bool`.
+///
+/// The doc comments are the ones `roc docs` shows: those before the module header, and those
+/// before each top-level def.
+pub(crate) fn add_doc_tests<'a>(
+    arena: &'a Bump,
+    src: &str,
+    header_comments: &[CommentOrNewline<'_>],
+    defs: &mut Defs<'a>,
+) -> Result<(), SyntaxError<'a>> {
+    for block in doc_test_blocks(src, header_comments, defs) {
+        let masked_src = arena.alloc_str(&block.masked_src);
+
+        let condition = test_parse_expr(0, arena, State::new(masked_src.as_bytes()))
+            .map_err(|fail| SyntaxError::Expr(fail, block.region.start()))?;
+
+        let expect = ValueDef::Expect {
+            condition: arena.alloc(condition),
+            preceding_comment: Region::new(block.region.start(), block.region.start()),
+        };
+
+        defs.push_value_def(expect, block.region, &[], &[]);
+    }
+
+    Ok(())
+}
+
+fn doc_test_blocks(
+    src: &str,
+    header_comments: &[CommentOrNewline<'_>],
+    defs: &Defs<'_>,
+) -> Vec<DocTestBlock> {
+    let comments_before_defs =
+        (0..defs.len()).map(|index| &defs.spaces[defs.space_before[index].indices()]);
+
+    std::iter::once(header_comments)
+        .chain(comments_before_defs)
+        .flat_map(|comments| doc_test_blocks_in_comments(src, comments))
+        .collect()
+}
+
+fn doc_test_blocks_in_comments(src: &str, comments: &[CommentOrNewline<'_>]) -> Vec<DocTestBlock> {
+    let mut blocks = Vec::new();
+
+    // The start and end offsets of each line of code in the block we are in, and whether the
+    // block is a `roc` block. A block that its doc comment doesn't close is not a doc test.
+    let mut open_block: Option<(Vec<(usize, usize)>, bool)> = None;
+
+    for comment in comments {
+        let (content, content_start) = match comment {
+            CommentOrNewline::DocComment(content) => match offset_in(src, content) {
+                Some(content_start) => (*content, content_start),
+                None => {
+                    open_block = None;
+                    continue;
+                }
+            },
+            CommentOrNewline::LineComment(_) | CommentOrNewline::Newline => {
+                open_block = None;
+                continue;
+            }
+        };
+
+        match open_block.take() {
+            None => {
+                if let Some(info) = content.trim_start().strip_prefix(FENCE) {
+                    open_block = Some((Vec::new(), info.trim() == "roc"));
+                }
+            }
+            Some((code_lines, is_roc)) => {
+                if content.trim_start().starts_with(FENCE) {
+                    if is_roc && !code_lines.is_empty() {
+                        blocks.push(mask(src, &code_lines));
+                    }
+                } else {
+                    let mut code_lines = code_lines;
+                    code_lines.push((content_start, content_start + content.len()));
+
+                    open_block = Some((code_lines, is_roc));
+                }
+            }
+        }
+    }
+
+    blocks
+}
+
+/// The offset in `src` of a doc comment that the parser sliced out of it.
+fn offset_in(src: &str, doc_comment: &str) -> Option<usize> {
+    let offset = (doc_comment.as_ptr() as usize).checked_sub(src.as_ptr() as usize)?;
+
+    (offset + doc_comment.len() <= src.len()).then_some(offset)
+}
+
+fn mask(src: &str, code_lines: &[(usize, usize)]) -> DocTestBlock {
+    let (start, _) = code_lines[0];
+    let (_, end) = code_lines[code_lines.len() - 1];
+
+    let mut masked: Vec<u8> = src.as_bytes()[..end]
+        .iter()
+        .map(|&byte| if byte == b'\n' { b'\n' } else { b' ' })
+        .collect();
+
+    for &(code_start, code_end) in code_lines {
+        masked[code_start..code_end].copy_from_slice(&src.as_bytes()[code_start..code_end]);
+    }
+
+    DocTestBlock {
+        // Only whole lines of code were copied over, so this is still valid UTF-8.
+        masked_src: String::from_utf8(masked).unwrap(),
+        region: Region::new(Position::new(start as u32), Position::new(end as u32)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use roc_parse::header::{parse_header, parse_module_defs};
+
+    fn parse<'a>(arena: &'a Bump, src: &'a str) -> (&'a [CommentOrNewline<'a>], Defs<'a>) {
+        let (header, state) = parse_header(arena, State::new(src.as_bytes())).unwrap();
+        let defs = parse_module_defs(arena, state, Defs::default()).unwrap();
+
+        (header.before, defs)
+    }
+
+    #[test]
+    fn finds_roc_blocks_in_doc_comments() {
+        let src = indoc!(
+            r#"
+            module [add]
+
+            ## Adds two numbers.
+            ##
+            ## ```roc
+            ## add 1 2 == 3
+            ## ```
+            ##
+            ## ```sh
+            ## roc test --doc
+            ## ```
+            add = \a, b -> a + b
+
+            # ```roc
+            # 1 == 2
+            # ```
+            "#
+        );
+
+        let arena = Bump::new();
+        let (header_comments, defs) = parse(&arena, src);
+
+        let blocks = doc_test_blocks(src, header_comments, &defs);
+        assert_eq!(blocks.len(), 1);
+
+        let block = &blocks[0];
+        let start = src.find("add 1 2").unwrap();
+        let end = start + "add 1 2 == 3".len();
+
+        assert_eq!(
+            block.region,
+            Region::new(Position::new(start as u32), Position::new(end as u32))
+        );
+        assert_eq!(&block.masked_src[start..end], "add 1 2 == 3");
+        assert_eq!(block.masked_src.trim(), "add 1 2 == 3");
+        assert_eq!(
+            block.masked_src.matches('\n').count(),
+            src[..end].matches('\n').count()
+        );
+    }
+
+    #[test]
+    fn finds_roc_blocks_in_module_docs() {
+        let src = indoc!(
+            r#"
+            ## The answer:
+            ##
+            ## ```roc
+            ## answer == 42
+            ## ```
+            module [answer]
+
+            answer = 42
+            "#
+        );
+
+        let arena = Bump::new();
+        let (header_comments, defs) = parse(&arena, src);
+
+        let blocks = doc_test_blocks(src, header_comments, &defs);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].masked_src.trim(), "answer == 42");
+    }
+
+    #[test]
+    fn parses_blocks_with_defs_at_their_source_region() {
+        let src = indoc!(
+            r#"
+            module [double]
+
+            ## ```roc
+            ## x = double 2
+            ##
+            ## x == 4
+            ## ```
+            double = \n -> n * 2
+
+            ## ```roc
+            ## unclosed == 1
+            "#
+        );
+
+        let arena = Bump::new();
+        let (header_comments, mut defs) = parse(&arena, src);
+        let def_count = defs.len();
+
+        add_doc_tests(&arena, src, header_comments, &mut defs).unwrap();
+
+        assert_eq!(defs.len(), def_count + 1);
+
+        let start = src.find("x = double").unwrap() as u32;
+        let end = (src.find("x == 4").unwrap() + "x == 4".len()) as u32;
+
+        assert_eq!(
+            defs.regions[def_count],
+            Region::new(Position::new(start), Position::new(end))
+        );
+
+        match defs.value_defs[defs.value_defs.len() - 1] {
+            ValueDef::Expect { condition, .. } => {
+                assert_eq!(condition.region.start(), Position::new(start));
+            }
+            ref other => panic!("expected an expect, got {other:?}"),
+        }
+    }
+}
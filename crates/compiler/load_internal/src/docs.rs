@@ -7,6 +7,7 @@ use roc_module::symbol::{IdentIds, ModuleId, ModuleIds, Symbol};
 use roc_parse::ast::{self, ExtractSpaces, TypeHeader, TypeVar};
 use roc_parse::ast::{AssignedField, FunctionArrow};
 use roc_parse::ast::{CommentOrNewline, TypeDef, ValueDef};
+use roc_region::all::Region;
 
 // Documentation generation requirements
 
@@ -43,6 +44,8 @@ pub struct DocDef {
     pub type_vars: Vec<String>,
     pub type_annotation: TypeAnnotation,
     pub docs: Option<String>,
+    /// Where the def is in its module's source, not including its doc comment.
+    pub region: Region,
 }
 
 #[derive(Debug, Clone)]
@@ -114,6 +117,7 @@ pub struct AbilityMember {
     pub type_annotation: TypeAnnotation,
     pub able_variables: Vec<(String, Vec<TypeAnnotation>)>,
     pub docs: Option<String>,
+    pub region: Region,
 }
 
 #[derive(Debug, Clone)]
@@ -209,6 +213,7 @@ fn generate_entry_docs(
         scratchpad.extend(spaces_before);
 
         let docs = comments_or_new_lines_to_docs(&scratchpad);
+        let region = defs.regions[index];
 
         match either_index.split() {
             Err(value_index) => match &defs.value_defs[value_index.index()] {
@@ -223,6 +228,7 @@ fn generate_entry_docs(
                                 type_annotation: type_to_docs(false, loc_ann.value),
                                 type_vars: Vec::new(),
                                 docs,
+                                region,
                             };
                             doc_entries.push(DocEntry::DocDef(doc_def));
                         }
@@ -243,6 +249,7 @@ fn generate_entry_docs(
                                 type_vars: Vec::new(),
                                 symbol: Symbol::new(home, ident_id),
                                 docs,
+                                region,
                             };
                             doc_entries.push(DocEntry::DocDef(doc_def));
                         }
@@ -259,6 +266,7 @@ fn generate_entry_docs(
                                 type_vars: Vec::new(),
                                 symbol: Symbol::new(home, ident_id),
                                 docs,
+                                region,
                             };
                             doc_entries.push(DocEntry::DocDef(doc_def));
                         }
@@ -297,6 +305,7 @@ fn generate_entry_docs(
                                 type_vars: Vec::new(),
                                 symbol: Symbol::new(home, ident_id),
                                 docs,
+                                region,
                             };
                             doc_entries.push(DocEntry::DocDef(doc_def));
                         }
@@ -335,6 +344,7 @@ fn generate_entry_docs(
                         type_vars,
                         docs,
                         symbol: Symbol::new(home, ident_id),
+                        region,
                     };
                     doc_entries.push(DocEntry::DocDef(doc_def));
                 }
@@ -358,6 +368,7 @@ fn generate_entry_docs(
                         type_vars,
                         docs,
                         symbol: Symbol::new(home, ident_id),
+                        region,
                     };
                     doc_entries.push(DocEntry::DocDef(doc_def));
                 }
@@ -387,6 +398,7 @@ fn generate_entry_docs(
                                 type_annotation,
                                 able_variables,
                                 docs: comments_or_new_lines_to_docs(extracted.before),
+                                region: Region::span_across(&mem.name.region, &mem.typ.region),
                             }
                         })
                         .collect();
@@ -398,6 +410,7 @@ fn generate_entry_docs(
                         symbol: Symbol::new(home, ident_id),
                         type_vars,
                         docs,
+                        region,
                     };
                     doc_entries.push(DocEntry::DocDef(doc_def));
                 }
@@ -1,9 +1,10 @@
 #![allow(clippy::too_many_arguments)]
 
+use crate::doc_tests;
 use crate::docs::ModuleDocumentation;
 use crate::module::{
     CheckedModule, ConstrainedModule, EntryPoint, Expectations, ExposedToHost,
-    FoundSpecializationsModule, LateSpecializationsModule, LoadedModule, ModuleHeader,
+    FoundSpecializationsModule, LateSpecializationsModule, LoadCache, LoadedModule, ModuleHeader,
     ModuleTiming, MonomorphizedModule, ParsedModule, ToplevelExpects, TypeCheckedModule,
 };
 use crate::module_cache::ModuleCache;
@@ -16,7 +17,9 @@ use roc_builtins::roc::module_source;
 use roc_can::abilities::{AbilitiesStore, PendingAbilitiesStore, ResolvedImpl};
 use roc_can::constraint::{Constraint as ConstraintSoa, Constraints, TypeOrVar};
 use roc_can::env::Env;
-use roc_can::expr::{Declarations, ExpectLookup, PendingDerives};
+use roc_can::expr::{
+    BenchFunctionVars, BenchThunkSymbols, Declarations, ExpectLookup, PendingDerives,
+};
 use roc_can::module::{
     canonicalize_module_defs, ExposedByModule, ExposedForModule, ExposedModuleTypes, Module,
     ModuleParams, ResolvedImplementations, TypeState,
@@ -104,6 +107,13 @@ const DEFAULT_MAIN_NAME: &str = "main.roc";
 
 const EXPANDED_STACK_SIZE: usize = 8 * 1024 * 1024;
 
+/// Top-level functions of the root module whose name starts with this are benchmarks
+const BENCH_PREFIX: &str = "bench_";
+
+/// A benchmark `bench_foo` that takes an argument gets it from the top-level constant
+/// `bench_foo_input`
+const BENCH_INPUT_SUFFIX: &str = "_input";
+
 macro_rules! log {
     ($($arg:tt)*) => (dbg_do!(ROC_PRINT_LOAD_LOG, println!($($arg)*)))
 }
@@ -126,7 +136,20 @@ pub enum ExecutionMode {
     ExecutableIfCheck,
     /// Test is like [`ExecutionMode::ExecutableIfCheck`], but rather than producing a proper
     /// executable, run tests.
-    Test,
+    Test(TestOptions),
+    /// Like [`ExecutionMode::Test`], but rather than the top-level expects, build the benchmark
+    /// functions of the root module, so `roc bench` can time them.
+    Bench,
+}
+
+/// What `roc test` does besides running the top-level expects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TestOptions {
+    /// Instrument the modules under test to record which of their defs and branches the tests
+    /// executed.
+    pub coverage: bool,
+    /// Also run the `roc` code blocks in the doc comments of the modules under test, as expects.
+    pub doc_tests: bool,
 }
 
 impl ExecutionMode {
@@ -135,12 +158,28 @@ impl ExecutionMode {
 
         match self {
             Executable => Phase::MakeSpecializations,
-            Check | ExecutableIfCheck | Test => Phase::SolveTypes,
+            Check | ExecutableIfCheck | Test(_) | Bench => Phase::SolveTypes,
         }
     }
 
     fn build_if_checks(&self) -> bool {
-        matches!(self, Self::ExecutableIfCheck | Self::Test)
+        matches!(self, Self::ExecutableIfCheck | Self::Test(_) | Self::Bench)
+    }
+
+    fn runs_tests(&self) -> bool {
+        matches!(self, Self::Test(_))
+    }
+
+    fn collects_coverage(&self) -> bool {
+        matches!(self, Self::Test(options) if options.coverage)
+    }
+
+    fn runs_doc_tests(&self) -> bool {
+        matches!(self, Self::Test(options) if options.doc_tests)
+    }
+
+    fn runs_benches(&self) -> bool {
+        matches!(self, Self::Bench)
     }
 }
 
@@ -199,12 +238,21 @@ fn start_phase<'a>(
                 // parse the file
                 let header = state.module_cache.headers.remove(&module_id).unwrap();
 
+                // only the modules of the package under test have their doc tests run
+                let add_doc_tests = state.exec_mode.runs_doc_tests() && {
+                    let modules = state.arc_modules.lock();
+                    modules
+                        .package_eq(module_id, state.root_id)
+                        .expect("root or this module is not yet known - that's a bug!")
+                };
+
                 BuildTask::Parse {
                     header,
                     arc_shorthands: Arc::clone(&state.arc_shorthands),
                     module_ids: Arc::clone(&state.arc_modules),
                     ident_ids_by_module: Arc::clone(&state.ident_ids_by_module),
                     root_type: state.root_type.clone(),
+                    add_doc_tests,
                 }
             }
             Phase::SoloCanonicalize => {
@@ -315,6 +363,31 @@ fn start_phase<'a>(
                     }
                 }
 
+                // A module can take its types from the previous load if its source is unchanged,
+                // and every module it imports took its types from the previous load as well.
+                // Imports are canonicalized first, so they have all been decided on by now.
+                if matches!(
+                    parsed.header_type,
+                    HeaderType::Module { .. } | HeaderType::Hosted { .. }
+                ) {
+                    let hash = state
+                        .module_cache
+                        .add_module_content_hash(module_id, parsed.src);
+
+                    let unchanged = state.previous_hashes.get(&module_id) == Some(&hash)
+                        && parsed.available_modules.keys().all(|imported| {
+                            imported.is_builtin() || state.reused_modules.contains(imported)
+                        });
+
+                    if unchanged {
+                        state.reused_modules.insert(module_id);
+                    }
+                }
+
+                if !module_id.is_builtin() && !state.reused_modules.contains(&module_id) {
+                    state.cached_types.lock().remove(&module_id);
+                }
+
                 let skip_constraint_gen = {
                     // Give this its own scope to make sure that the Guard from the lock() is dropped
                     // immediately after contains_key returns
@@ -431,8 +504,8 @@ fn start_phase<'a>(
 
                 let derived_module = SharedDerivedModule::clone(&state.derived_module);
 
-                let build_expects =
-                    matches!(state.exec_mode, ExecutionMode::Test) && expectations.is_some();
+                let build_expects = state.exec_mode.runs_tests() && expectations.is_some();
+                let build_benches = state.exec_mode.runs_benches() && module_id == state.root_id;
 
                 BuildTask::BuildPendingSpecializations {
                     layout_cache,
@@ -449,6 +522,7 @@ fn start_phase<'a>(
                     derived_module,
                     expectations,
                     build_expects,
+                    build_benches,
                 }
             }
             Phase::MakeSpecializations => {
@@ -776,9 +850,16 @@ struct State<'a> {
 
     make_specializations_pass: MakeSpecializationsPass,
 
-    // cached types (used for builtin modules, could include packages in the future too)
+    // cached types (used for builtin modules, and for modules a previous load already solved)
     cached_types: CachedTypeState,
 
+    /// The source hashes of the modules a previous load left types for in `cached_types`.
+    previous_hashes: MutMap<ModuleId, String>,
+    /// The modules that take their types from the previous load, rather than being solved again.
+    reused_modules: MutSet<ModuleId>,
+    /// The types of the modules that were solved without problems, for the next load to reuse.
+    solved_for_cache: MutMap<ModuleId, TypeState>,
+
     layout_interner: GlobalLayoutInterner<'a>,
 }
 
@@ -789,6 +870,24 @@ impl<'a> State<'a> {
         self.exec_mode.goal_phase()
     }
 
+    /// What this load leaves for the next load of the same root to reuse.
+    fn take_load_cache(&mut self) -> LoadCache {
+        let mut cache = LoadCache::default();
+
+        cache.record_module_names(&self.arc_modules.lock());
+        cache.solved = std::mem::take(&mut self.solved_for_cache)
+            .into_iter()
+            .filter_map(|(module_id, types)| {
+                let hash = self.module_cache.content_hashes.get(&module_id)?;
+
+                Some((module_id, (hash.clone(), types)))
+            })
+            .collect();
+        cache.reused = std::mem::take(&mut self.reused_modules);
+
+        cache
+    }
+
     fn new(
         root_id: ModuleId,
         root_path: PathBuf,
@@ -800,7 +899,8 @@ impl<'a> State<'a> {
         arc_modules: Arc<Mutex<PackageModuleIds<'a>>>,
         ident_ids_by_module: SharedIdentIdsByModule,
         arc_shorthands: Arc<Mutex<MutMap<&'a str, ShorthandPath>>>,
-        cached_types: MutMap<ModuleId, TypeState>,
+        mut cached_types: MutMap<ModuleId, TypeState>,
+        previous: LoadCache,
         render: RenderTarget,
         palette: Palette,
         number_of_workers: usize,
@@ -809,6 +909,12 @@ impl<'a> State<'a> {
         let cache_dir = roc_packaging::cache::roc_cache_packages_dir();
         let dependencies = Dependencies::new(exec_mode.goal_phase());
 
+        let mut previous_hashes = MutMap::default();
+        for (module_id, (hash, types)) in previous.solved {
+            previous_hashes.insert(module_id, hash);
+            cached_types.insert(module_id, types);
+        }
+
         Self {
             root_id,
             root_path,
@@ -838,6 +944,9 @@ impl<'a> State<'a> {
             timings: MutMap::default(),
             layout_caches: std::vec::Vec::with_capacity(number_of_workers),
             cached_types: Arc::new(Mutex::new(cached_types)),
+            previous_hashes,
+            reused_modules: MutSet::default(),
+            solved_for_cache: MutMap::default(),
             render,
             palette,
             exec_mode,
@@ -908,6 +1017,7 @@ enum BuildTask<'a> {
         module_ids: Arc<Mutex<PackageModuleIds<'a>>>,
         ident_ids_by_module: SharedIdentIdsByModule,
         root_type: RootType,
+        add_doc_tests: bool,
     },
     SoloCanonicalize {
         parsed: ParsedModule<'a>,
@@ -960,6 +1070,7 @@ enum BuildTask<'a> {
         derived_module: SharedDerivedModule,
         expectations: Option<Expectations>,
         build_expects: bool,
+        build_benches: bool,
     },
     MakeSpecializations {
         module_id: ModuleId,
@@ -1175,6 +1286,7 @@ pub struct LoadStart<'a> {
     root_type: RootType,
     opt_platform_shorthand: Option<&'a str>,
     src_dir: PathBuf,
+    previous: LoadCache,
 }
 
 #[derive(Debug, Clone)]
@@ -1192,7 +1304,29 @@ impl<'a> LoadStart<'a> {
         roc_cache_dir: RocCacheDir<'_>,
         palette: Palette,
     ) -> Result<Self, LoadingProblem<'a>> {
-        let arc_modules = Arc::new(Mutex::new(PackageModuleIds::default()));
+        Self::from_path_with_cache(
+            arena,
+            filename,
+            opt_main_path,
+            render,
+            roc_cache_dir,
+            palette,
+            &LoadCache::default(),
+        )
+    }
+
+    /// Like [LoadStart::from_path], but reuses what an earlier load of the same root left in
+    /// `previous` for the modules that did not change since.
+    pub fn from_path_with_cache(
+        arena: &'a Bump,
+        filename: PathBuf,
+        opt_main_path: Option<PathBuf>,
+        render: RenderTarget,
+        roc_cache_dir: RocCacheDir<'_>,
+        palette: Palette,
+        previous: &LoadCache,
+    ) -> Result<Self, LoadingProblem<'a>> {
+        let arc_modules = Arc::new(Mutex::new(previous.module_ids(arena)));
         let arc_shorthands = Arc::new(Mutex::new(MutMap::default()));
         let root_exposed_ident_ids = IdentIds::exposed_builtins(0);
         let ident_ids_by_module = Arc::new(Mutex::new(root_exposed_ident_ids));
@@ -1255,6 +1389,7 @@ impl<'a> LoadStart<'a> {
             root_msg: header_output.msg,
             root_type,
             opt_platform_shorthand: header_output.opt_platform_shorthand,
+            previous: previous.clone(),
         })
     }
 
@@ -1311,6 +1446,7 @@ impl<'a> LoadStart<'a> {
             root_msg,
             root_type,
             opt_platform_shorthand: opt_platform_id,
+            previous: LoadCache::default(),
         })
     }
 }
@@ -1604,7 +1740,7 @@ pub fn load_single_threaded<'a>(
         root_type,
         src_dir,
         opt_platform_shorthand,
-        ..
+        previous,
     } = load_start;
 
     let (msg_tx, msg_rx) = bounded(1024);
@@ -1626,6 +1762,7 @@ pub fn load_single_threaded<'a>(
         ident_ids_by_module,
         arc_shorthands,
         cached_types,
+        previous,
         render,
         palette,
         number_of_workers,
@@ -1985,7 +2122,7 @@ fn load_multi_threaded<'a>(
         root_type,
         src_dir,
         opt_platform_shorthand,
-        ..
+        previous,
     } = load_start;
 
     let (msg_tx, msg_rx) = bounded(1024);
@@ -2022,6 +2159,7 @@ fn load_multi_threaded<'a>(
         ident_ids_by_module,
         arc_shorthands,
         cached_types,
+        previous,
         render,
         palette,
         num_workers,
@@ -2521,6 +2659,30 @@ fn update<'a>(
             log!("solved types for {:?}", module_id);
             module_timing.end_time = Instant::now();
 
+            // Keep the types of modules that were solved without problems, so that the next load
+            // can reuse them. Symbols of the derived module are numbered in whatever order modules
+            // happen to ask for them, so types that mention them can't be reused.
+            let reusable = state.module_cache.content_hashes.contains_key(&module_id)
+                && solved_module.problems.is_empty()
+                && !state.module_cache.module_params.contains_key(&module_id)
+                && !solved_subs
+                    .inner()
+                    .symbol_names
+                    .iter()
+                    .any(|symbol| symbol.module_id() == ModuleId::DERIVED_GEN);
+
+            if reusable {
+                state.solved_for_cache.insert(
+                    module_id,
+                    TypeState {
+                        subs: solved_subs.inner().clone(),
+                        exposed_vars_by_symbol: solved_module.exposed_vars_by_symbol.clone(),
+                        abilities: abilities_store.clone(),
+                        solved_implementations: solved_module.solved_implementations.clone(),
+                    },
+                );
+            }
+
             state
                 .module_cache
                 .type_problems
@@ -2530,12 +2692,15 @@ fn update<'a>(
                 .exposes
                 .insert(module_id, solved_module.exposed_vars_by_symbol.clone());
 
-            let should_include_expects = (!loc_expects.is_empty() || has_dbgs) && {
-                let modules = state.arc_modules.lock();
-                modules
-                    .package_eq(module_id, state.root_id)
-                    .expect("root or this module is not yet known - that's a bug!")
-            };
+            // with coverage, every module of the root package reports on its defs, even if it
+            // has no expects of its own
+            let should_include_expects =
+                (!loc_expects.is_empty() || has_dbgs || state.exec_mode.collects_coverage()) && {
+                    let modules = state.arc_modules.lock();
+                    modules
+                        .package_eq(module_id, state.root_id)
+                        .expect("root or this module is not yet known - that's a bug!")
+                };
 
             let opt_expectations = if should_include_expects {
                 let (path, _) = state.module_cache.sources.get(&module_id).unwrap();
@@ -2545,6 +2710,7 @@ fn update<'a>(
                     subs: solved_subs.clone().into_inner(),
                     path: path.to_owned(),
                     ident_ids: ident_ids.clone(),
+                    coverage: state.exec_mode.collects_coverage().then(Vec::new),
                 })
             } else {
                 None
@@ -2560,8 +2726,8 @@ fn update<'a>(
             };
 
             let add_to_host_exposed = is_host_exposed &&
-                // During testing, we don't need to expose anything to the host.
-                !matches!(state.exec_mode, ExecutionMode::Test);
+                // During testing and benchmarking, we don't need to expose anything to the host.
+                !state.exec_mode.runs_tests() && !state.exec_mode.runs_benches();
 
             if add_to_host_exposed {
                 state.exposed_to_host.top_level_values.extend(
@@ -2724,7 +2890,7 @@ fn update<'a>(
 
             let subs = solved_subs.into_inner();
 
-            if !toplevel_expects.pure.is_empty() {
+            if !toplevel_expects.pure.is_empty() || !toplevel_expects.benches.is_empty() {
                 state.toplevel_expects.insert(module_id, toplevel_expects);
             }
 
@@ -3203,7 +3369,7 @@ fn log_layout_stats(module_id: ModuleId, layout_cache: &LayoutCache) {
 
 fn finish_specialization<'a>(
     arena: &'a Bump,
-    state: State<'a>,
+    mut state: State<'a>,
     subs: Subs,
     layout_interner: STLayoutInterner<'a>,
     exposed_to_host: ExposedToHost,
@@ -3215,6 +3381,7 @@ fn finish_specialization<'a>(
             roc_types::types::get_type_clone_count()
         );
     }
+    let cache = state.take_load_cache();
     let module_ids = Arc::try_unwrap(state.arc_modules)
         .unwrap_or_else(|_| panic!("There were still outstanding Arc references to module_ids"))
         .into_inner()
@@ -3239,7 +3406,7 @@ fn finish_specialization<'a>(
     let entry_point = {
         let interns: &mut Interns = &mut interns;
         match state.exec_mode {
-            ExecutionMode::Test => Ok(EntryPoint::Test),
+            ExecutionMode::Test(_) | ExecutionMode::Bench => Ok(EntryPoint::Test),
             ExecutionMode::Executable | ExecutionMode::ExecutableIfCheck => {
                 use PlatformPath::*;
 
@@ -3316,6 +3483,7 @@ fn finish_specialization<'a>(
         type_problems,
         can_problems,
         sources,
+        imports,
         ..
     } = module_cache;
 
@@ -3345,10 +3513,12 @@ fn finish_specialization<'a>(
         host_exposed_lambda_sets,
         entry_point,
         sources,
+        imports,
         timings: state.timings,
         toplevel_expects,
         glue_layouts: GlueLayouts { getters: vec![] },
         needs_prebuilt_host,
+        cache,
     })
 }
 
@@ -3384,6 +3554,7 @@ fn finish(
     //
     #[cfg(debug_assertions)] checkmate: Option<roc_checkmate::Collector>,
 ) -> LoadedModule {
+    let cache = state.take_load_cache();
     let module_ids = Arc::try_unwrap(state.arc_modules)
         .unwrap_or_else(|_| panic!("There were still outstanding Arc references to module_ids"))
         .into_inner()
@@ -3441,6 +3612,7 @@ fn finish(
         exposed_imports: state.module_cache.exposed_imports,
         imports: state.module_cache.imports,
         exposes: state.module_cache.exposes,
+        cache,
     }
 }
 
@@ -4923,58 +5095,41 @@ fn run_solve<'a>(
     let has_dbgs = module.has_dbgs;
     let module = module;
 
-    let solve_result = {
-        if module_id.is_builtin() {
-            match cached_types.lock().remove(&module_id) {
-                None => run_solve_solve(
-                    exposed_for_module,
-                    types,
-                    constraints,
-                    constraint,
-                    function_kind,
-                    pending_derives,
-                    var_store,
-                    module,
-                    derived_module,
-                    is_host_exposed,
-                    //
-                    #[cfg(debug_assertions)]
-                    checkmate,
-                ),
-                Some(TypeState {
-                    subs,
-                    exposed_vars_by_symbol,
-                    abilities,
-                    solved_implementations,
-                }) => SolveResult {
-                    solved: Solved(subs),
-                    solved_implementations,
-                    exposed_vars_by_symbol,
-                    problems: vec![],
-                    abilities_store: abilities,
-                    imported_modules_with_params: vec![],
+    // Release the lock before solving, so other modules can be solved in the meantime
+    let cached = cached_types.lock().remove(&module_id);
 
-                    #[cfg(debug_assertions)]
-                    checkmate: None,
-                },
-            }
-        } else {
-            run_solve_solve(
-                exposed_for_module,
-                types,
-                constraints,
-                constraint,
-                function_kind,
-                pending_derives,
-                var_store,
-                module,
-                derived_module,
-                is_host_exposed,
-                //
-                #[cfg(debug_assertions)]
-                checkmate,
-            )
-        }
+    let solve_result = match cached {
+        None => run_solve_solve(
+            exposed_for_module,
+            types,
+            constraints,
+            constraint,
+            function_kind,
+            pending_derives,
+            var_store,
+            module,
+            derived_module,
+            is_host_exposed,
+            //
+            #[cfg(debug_assertions)]
+            checkmate,
+        ),
+        Some(TypeState {
+            subs,
+            exposed_vars_by_symbol,
+            abilities,
+            solved_implementations,
+        }) => SolveResult {
+            solved: Solved(subs),
+            solved_implementations,
+            exposed_vars_by_symbol,
+            problems: vec![],
+            abilities_store: abilities,
+            imported_modules_with_params: vec![],
+
+            #[cfg(debug_assertions)]
+            checkmate: None,
+        },
     };
 
     let SolveResult {
@@ -5002,7 +5157,10 @@ fn run_solve<'a>(
         ExecutionMode::Check => {
             // Params are not lowered in check mode
         }
-        ExecutionMode::Executable | ExecutionMode::ExecutableIfCheck | ExecutionMode::Test => {
+        ExecutionMode::Executable
+        | ExecutionMode::ExecutableIfCheck
+        | ExecutionMode::Test(_)
+        | ExecutionMode::Bench => {
             roc_lower_params::type_error::remove_module_param_arguments(
                 &mut problems,
                 home_has_params,
@@ -5307,7 +5465,10 @@ fn canonicalize_and_constrain<'a>(
             // No need to lower params for `roc check` and lang server
             // If we did, we'd have to update the language server to exclude the extra arguments
         }
-        ExecutionMode::Executable | ExecutionMode::ExecutableIfCheck | ExecutionMode::Test => {
+        ExecutionMode::Executable
+        | ExecutionMode::ExecutableIfCheck
+        | ExecutionMode::Test(_)
+        | ExecutionMode::Bench => {
             // We need to lower params only if the current module has any or imports at least one with params
             if module_output.module_params.is_some() || !imported_module_params.is_empty() {
                 roc_lower_params::lower::lower(
@@ -5422,6 +5583,7 @@ fn parse<'a>(
     module_ids: Arc<Mutex<PackageModuleIds<'a>>>,
     ident_ids_by_module: SharedIdentIdsByModule,
     root_type: RootType,
+    add_doc_tests: bool,
 ) -> Result<Msg<'a>, LoadingProblem<'a>> {
     let mut module_timing = header.module_timing;
     let parse_start = Instant::now();
@@ -5431,7 +5593,7 @@ fn parse<'a>(
     let header_import_defs =
         roc_parse::ast::Header::header_imports_to_defs(arena, header.header_imports);
 
-    let parsed_defs = match parse_module_defs(arena, parse_state.clone(), header_import_defs) {
+    let mut parsed_defs = match parse_module_defs(arena, parse_state.clone(), header_import_defs) {
         Ok(success) => success,
         Err(fail) => {
             return Err(LoadingProblem::ParsingFailed(
@@ -5445,6 +5607,16 @@ fn parse<'a>(
     // we'd have bailed out before now.
     let src = unsafe { from_utf8_unchecked(source) };
 
+    if add_doc_tests {
+        if let Err(fail) =
+            doc_tests::add_doc_tests(arena, src, header.header_comments, &mut parsed_defs)
+        {
+            return Err(LoadingProblem::ParsingFailed(
+                fail.into_file_error(header.module_path, &parse_state),
+            ));
+        }
+    }
+
     // Record the parse end time once, to avoid checking the time a second time
     // immediately afterward (for the beginning of canonicalization).
     let parse_end = Instant::now();
@@ -5735,6 +5907,7 @@ fn make_specializations<'a>(
 ) -> Msg<'a> {
     let make_specializations_start = Instant::now();
     let mut update_mode_ids = UpdateModeIds::new();
+    let coverage = expectations.as_ref().is_some_and(|e| e.coverage.is_some());
 
     // do the thing
     let mut mono_env = roc_mono::ir::Env {
@@ -5751,6 +5924,7 @@ fn make_specializations<'a>(
         exposed_by_module,
         derived_module: &derived_module,
         struct_indexing: UsageTrackingMap::default(),
+        coverage,
     };
 
     let mut procs = Procs::new_in(arena);
@@ -5821,12 +5995,21 @@ fn build_pending_specializations<'a>(
     derived_module: SharedDerivedModule,
     mut expectations: Option<Expectations>,
     build_expects: bool,
+    build_benches: bool,
 ) -> Msg<'a> {
     let find_specializations_start = Instant::now();
 
     let mut module_thunks = bumpalo::collections::Vec::new_in(arena);
     let mut toplevel_expects = ToplevelExpects::default();
 
+    let coverage = match expectations.as_mut().and_then(|e| e.coverage.as_mut()) {
+        Some(sites) => {
+            sites.extend(roc_mono::coverage::coverage_sites(home, &declarations));
+            true
+        }
+        None => false,
+    };
+
     let mut procs_base = ProcsBase {
         partial_procs: BumpMap::default(),
         module_thunks: &[],
@@ -5854,6 +6037,7 @@ fn build_pending_specializations<'a>(
         exposed_by_module,
         derived_module: &derived_module,
         struct_indexing: UsageTrackingMap::default(),
+        coverage,
     };
 
     let layout_cache_snapshot = layout_cache.snapshot();
@@ -6008,6 +6192,23 @@ fn build_pending_specializations<'a>(
                     body,
                     is_recursive,
                 );
+
+                if build_benches {
+                    let region = declarations.symbols[index].region;
+
+                    if let Some(thunk_symbol) = register_bench_thunk(
+                        &mut mono_env,
+                        &mut procs_base,
+                        &mut layout_cache,
+                        &declarations,
+                        symbol,
+                        expr_var,
+                        region,
+                    ) {
+                        module_thunks.push(thunk_symbol);
+                        toplevel_expects.benches.insert(thunk_symbol, symbol);
+                    }
+                }
             }
             Destructure(d_index) => {
                 let loc_pattern = &declarations.destructs[d_index.index()].loc_pattern;
@@ -6183,6 +6384,96 @@ fn build_pending_specializations<'a>(
     }
 }
 
+/// Benchmarks are the top-level functions of the root module that are named `bench_...`. A
+/// benchmark `bench_foo` takes `{}`, or its input, which is the top-level constant
+/// `bench_foo_input`. Each one gets a thunk of type `{}` that calls it, which is exposed like a
+/// top-level expect.
+fn register_bench_thunk<'a>(
+    mono_env: &mut roc_mono::ir::Env<'a, '_>,
+    procs_base: &mut ProcsBase<'a>,
+    layout_cache: &mut LayoutCache<'a>,
+    declarations: &Declarations,
+    bench_symbol: Symbol,
+    fn_var: Variable,
+    region: Region,
+) -> Option<Symbol> {
+    use roc_can::expr::DeclarationTag;
+    use roc_types::subs::{Content, FlatType};
+
+    let bench_name = mono_env
+        .ident_ids
+        .get_name(bench_symbol.ident_id())
+        .filter(|name| name.starts_with(BENCH_PREFIX))?;
+
+    let input_symbol = mono_env
+        .ident_ids
+        .get_id(&format!("{bench_name}{BENCH_INPUT_SUFFIX}"))
+        .map(|ident_id| Symbol::new(bench_symbol.module_id(), ident_id))
+        .filter(|input_symbol| {
+            declarations
+                .symbols
+                .iter()
+                .zip(declarations.declarations.iter())
+                .any(|(symbol, tag)| {
+                    symbol.value == *input_symbol && matches!(tag, DeclarationTag::Value)
+                })
+        });
+
+    let vars = match mono_env.subs.get_content_without_compacting(fn_var) {
+        Content::Structure(FlatType::Func(arg_vars, closure_var, ret_var, fx_var))
+            if arg_vars.len() == 1 =>
+        {
+            BenchFunctionVars {
+                fn_var,
+                arg_var: mono_env.subs[arg_vars.into_iter().next()?],
+                closure_var: *closure_var,
+                ret_var: *ret_var,
+                fx_var: *fx_var,
+            }
+        }
+        _ => return None,
+    };
+
+    if input_symbol.is_none() {
+        match layout_cache.from_var(mono_env.arena, vars.arg_var, mono_env.subs) {
+            Ok(layout) if layout == Layout::UNIT => {}
+            _ => return None,
+        }
+    }
+
+    let thunk_symbol = mono_env.unique_symbol();
+    let symbols = BenchThunkSymbols {
+        input: mono_env.unique_symbol(),
+        result: mono_env.unique_symbol(),
+        sink: mono_env.unique_symbol(),
+    };
+    let body =
+        roc_can::expr::toplevel_bench_to_thunk(bench_symbol, input_symbol, symbols, vars, region);
+
+    procs_base.host_specializations.insert_host_exposed(
+        mono_env.subs,
+        LambdaName::no_niche(thunk_symbol),
+        None,
+        Variable::EMPTY_RECORD,
+    );
+
+    let proc = PartialProc {
+        annotation: Variable::EMPTY_RECORD,
+        // This is a 0-arity thunk, so it has no arguments.
+        pattern_symbols: &[],
+        // This is a top-level definition, so it cannot capture anything
+        captured_symbols: CapturedSymbols::None,
+        body: body.value,
+        body_var: Variable::EMPTY_RECORD,
+        // This is a 0-arity thunk, so it cannot be recursive
+        is_self_recursive: false,
+    };
+
+    procs_base.partial_procs.insert(thunk_symbol, proc);
+
+    Some(thunk_symbol)
+}
+
 fn register_toplevel_function_into_procs_base<'a>(
     mono_env: &mut roc_mono::ir::Env<'a, '_>,
     procs_base: &mut ProcsBase<'a>,
@@ -6263,6 +6554,8 @@ fn load_derived_partial_procs<'a>(
             exposed_by_module,
             derived_module,
             struct_indexing: UsageTrackingMap::default(),
+            // Derived implementations are not part of the user's source.
+            coverage: false,
         };
 
         let partial_proc = match derived_expr {
@@ -6352,6 +6645,7 @@ fn run_task<'a>(
             module_ids,
             ident_ids_by_module,
             root_type,
+            add_doc_tests,
         } => parse(
             arena,
             header,
@@ -6359,6 +6653,7 @@ fn run_task<'a>(
             module_ids,
             ident_ids_by_module,
             root_type,
+            add_doc_tests,
         ),
         SoloCanonicalize { parsed } => {
             let module_id = parsed.module_id;
@@ -6451,6 +6746,7 @@ fn run_task<'a>(
             derived_module,
             expectations,
             build_expects,
+            build_benches,
         } => Ok(build_pending_specializations(
             arena,
             solved_subs,
@@ -6467,6 +6763,7 @@ fn run_task<'a>(
             derived_module,
             expectations,
             build_expects,
+            build_benches,
         )),
         MakeSpecializations {
             module_id,
@@ -4,11 +4,14 @@
 #![allow(clippy::large_enum_variant)]
 
 use roc_module::symbol::ModuleId;
+mod doc_tests;
 pub mod docs;
 pub mod file;
 pub mod module;
 mod module_cache;
 
+pub use module_cache::hash_contents;
+
 #[cfg(target_family = "wasm")]
 mod wasm_instant;
 
@@ -1,16 +1,19 @@
 use crate::docs::ModuleDocumentation;
+use bumpalo::Bump;
 use roc_can::constraint::{Constraint as ConstraintSoa, Constraints};
 use roc_can::expr::ExpectLookup;
 use roc_can::{
     abilities::AbilitiesStore,
     expr::{Declarations, PendingDerives},
-    module::{Module, ResolvedImplementations},
+    module::{Module, ResolvedImplementations, TypeState},
 };
 use roc_collections::{MutMap, MutSet, VecMap};
-use roc_module::ident::Ident;
+use roc_module::ident::{Ident, ModuleName};
 use roc_module::symbol::{
-    IdentIds, IdentIdsByModule, Interns, ModuleId, PQModuleName, PackageQualified, Symbol,
+    IdentIds, IdentIdsByModule, Interns, ModuleId, PQModuleName, PackageModuleIds,
+    PackageQualified, Symbol,
 };
+use roc_mono::coverage::CoverageSite;
 use roc_mono::ir::{GlueLayouts, HostExposedLambdaSets, LambdaSetId, Proc, ProcLayout, ProcsBase};
 use roc_mono::layout::{LayoutCache, STLayoutInterner};
 use roc_parse::ast::{CommentOrNewline, Defs, TypeAnnotation};
@@ -52,6 +55,7 @@ pub struct LoadedModule {
     pub imports: MutMap<ModuleId, MutSet<ModuleId>>,
     pub exposed_imports: MutMap<ModuleId, MutMap<Symbol, Region>>,
     pub exposes: MutMap<ModuleId, Vec<(Symbol, Variable)>>,
+    pub cache: LoadCache,
 }
 
 impl LoadedModule {
@@ -91,6 +95,60 @@ impl LoadedModule {
     }
 }
 
+/// What a load keeps in memory for the next load of the same root, e.g. in watch mode.
+///
+/// A module whose source is unchanged, and whose imports were all reused as well, is given the
+/// types solved for it last time instead of being solved again. Editing a module therefore only
+/// solves that module and the modules that import it, directly or not.
+#[derive(Debug, Clone, Default)]
+pub struct LoadCache {
+    /// The name of every module the last load knew about, by module id. The next load gives
+    /// these modules the same ids, so that the symbols in the cached types stay valid.
+    pub(crate) module_names: Vec<(Option<Box<str>>, ModuleName)>,
+    /// The source hash and solved types of every module that type checked without problems.
+    pub(crate) solved: MutMap<ModuleId, (String, TypeState)>,
+    /// The modules whose types the last load took from the load before it.
+    pub(crate) reused: MutSet<ModuleId>,
+}
+
+impl LoadCache {
+    /// Whether the load that produced this cache reused the types of this module, rather than
+    /// solving it again.
+    pub fn reused(&self, module_id: ModuleId) -> bool {
+        self.reused.contains(&module_id)
+    }
+
+    /// Module ids that give every module the last load knew about the same id again.
+    pub(crate) fn module_ids<'a>(&self, arena: &'a Bump) -> PackageModuleIds<'a> {
+        let mut module_ids = PackageModuleIds::default();
+
+        for (shorthand, module_name) in self.module_names.iter() {
+            let name = match shorthand {
+                Some(shorthand) => {
+                    PackageQualified::Qualified(&*arena.alloc_str(shorthand), module_name.clone())
+                }
+                None => PackageQualified::Unqualified(module_name.clone()),
+            };
+
+            module_ids.get_or_insert(&name);
+        }
+
+        module_ids
+    }
+
+    pub(crate) fn record_module_names(&mut self, module_ids: &PackageModuleIds) {
+        self.module_names = module_ids
+            .available_modules()
+            .map(|name| {
+                (
+                    name.package_shorthand().map(Box::from),
+                    name.as_inner().clone(),
+                )
+            })
+            .collect();
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct ModuleHeader<'a> {
     pub(crate) module_id: ModuleId,
@@ -167,6 +225,8 @@ pub(crate) struct LateSpecializationsModule<'a> {
 #[derive(Debug, Default)]
 pub struct ToplevelExpects {
     pub pure: VecMap<Symbol, Region>,
+    /// The thunk that runs each benchmark, and the benchmark function it calls
+    pub benches: VecMap<Symbol, Symbol>,
 }
 
 #[derive(Debug)]
@@ -183,10 +243,12 @@ pub struct MonomorphizedModule<'a> {
     pub entry_point: EntryPoint<'a>,
     pub exposed_to_host: ExposedToHost,
     pub sources: MutMap<ModuleId, (PathBuf, Box<str>)>,
+    pub imports: MutMap<ModuleId, MutSet<ModuleId>>,
     pub timings: MutMap<ModuleId, ModuleTiming>,
     pub expectations: VecMap<ModuleId, Expectations>,
     pub needs_prebuilt_host: bool,
     pub glue_layouts: GlueLayouts<'a>,
+    pub cache: LoadCache,
 }
 
 #[derive(Debug, Clone)]
@@ -224,6 +286,8 @@ pub struct Expectations {
     pub path: PathBuf,
     pub expectations: VecMap<Region, Vec<ExpectLookup>>,
     pub ident_ids: IdentIds,
+    /// Every point `roc test --coverage` reports on, or [None] if coverage is not being collected.
+    pub coverage: Option<Vec<CoverageSite>>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -47,7 +47,6 @@ pub(crate) struct ModuleCache<'a> {
     pub(crate) type_problems: MutMap<ModuleId, Vec<TypeError>>,
 
     pub(crate) sources: MutMap<ModuleId, (PathBuf, &'a str)>,
-    #[allow(dead_code)]
     pub(crate) content_hashes: MutMap<ModuleId, String>,
 }
 
@@ -70,18 +69,17 @@ impl<'a> ModuleCache<'a> {
         self.has_can_errors() || self.has_type_errors()
     }
 
-    #[allow(dead_code)]
     pub fn add_module_content_hash(&mut self, module_id: ModuleId, contents: &str) -> String {
-        let hash = Self::hash_contents(contents);
+        let hash = hash_contents(contents);
         self.content_hashes.insert(module_id, hash.clone());
 
         hash
     }
+}
 
-    #[allow(dead_code)]
-    pub fn hash_contents(contents: &str) -> String {
-        base64_url::encode(blake3::hash(contents.as_bytes()).as_bytes())
-    }
+/// A hash of a module's source, used to tell whether it changed between two loads.
+pub fn hash_contents(contents: &str) -> String {
+    base64_url::encode(blake3::hash(contents.as_bytes()).as_bytes())
 }
 
 impl Default for ModuleCache<'_> {
@@ -19,9 +19,9 @@ use bumpalo::Bump;
 use roc_can::module::ExposedByModule;
 use roc_load_internal::docs::DocDef;
 use roc_load_internal::file::{
-    ExecutionMode, LoadConfig, LoadResult, LoadStart, LoadingProblem, Threading,
+    ExecutionMode, LoadConfig, LoadResult, LoadStart, LoadingProblem, TestOptions, Threading,
 };
-use roc_load_internal::module::LoadedModule;
+use roc_load_internal::module::{LoadCache, LoadedModule};
 use roc_module::ident::ModuleName;
 use roc_module::symbol::{Interns, ModuleId};
 use roc_packaging::cache::RocCacheDir;
@@ -45,6 +45,7 @@ fn load_and_typecheck(
     exposed_types: ExposedByModule,
     target: Target,
     function_kind: FunctionKind,
+    exec_mode: ExecutionMode,
 ) -> Result<LoadedModule, LoadingProblem> {
     use LoadResult::*;
 
@@ -62,7 +63,7 @@ fn load_and_typecheck(
         render: RenderTarget::Generic,
         palette: DEFAULT_PALETTE,
         threading: Threading::Single,
-        exec_mode: ExecutionMode::Check,
+        exec_mode,
     };
 
     match roc_load_internal::file::load(
@@ -78,6 +79,43 @@ fn load_and_typecheck(
     }
 }
 
+fn load_with_cache(arena: &Bump, filename: PathBuf, previous: &LoadCache) -> LoadedModule {
+    use LoadResult::*;
+
+    let load_start = LoadStart::from_path_with_cache(
+        arena,
+        filename,
+        None,
+        RenderTarget::Generic,
+        RocCacheDir::Disallowed,
+        DEFAULT_PALETTE,
+        previous,
+    )
+    .unwrap();
+    let load_config = LoadConfig {
+        target: TARGET,
+        function_kind: FunctionKind::LambdaSet,
+        render: RenderTarget::Generic,
+        palette: DEFAULT_PALETTE,
+        threading: Threading::AllAvailable,
+        exec_mode: ExecutionMode::Check,
+    };
+
+    match roc_load_internal::file::load(
+        arena,
+        load_start,
+        Default::default(),
+        Default::default(),
+        RocCacheDir::Disallowed,
+        load_config,
+    )
+    .unwrap()
+    {
+        Monomorphized(_) => unreachable!(""),
+        TypeChecked(module) => module,
+    }
+}
+
 const TARGET: Target = Target::LinuxX64;
 
 // HELPERS
@@ -136,10 +174,18 @@ fn format_type_problems(
 }
 
 fn multiple_modules(subdir: &str, files: Vec<(&str, &str)>) -> Result<LoadedModule, String> {
+    multiple_modules_in_mode(subdir, ExecutionMode::Check, files)
+}
+
+fn multiple_modules_in_mode(
+    subdir: &str,
+    exec_mode: ExecutionMode,
+    files: Vec<(&str, &str)>,
+) -> Result<LoadedModule, String> {
     let arena = Bump::new();
     let arena = &arena;
 
-    match multiple_modules_help(subdir, arena, files) {
+    match multiple_modules_help(subdir, arena, exec_mode, files) {
         Err(io_error) => panic!("IO trouble: {io_error:?}"),
         Ok(Err(LoadingProblem::FormattedReport(buf, _))) => Err(buf),
         Ok(Err(loading_problem)) => Err(format!("{loading_problem:?}")),
@@ -180,6 +226,7 @@ fn multiple_modules(subdir: &str, files: Vec<(&str, &str)>) -> Result<LoadedModu
 fn multiple_modules_help<'a>(
     subdir: &str,
     arena: &'a Bump,
+    exec_mode: ExecutionMode,
     mut files: Vec<(&str, &str)>,
 ) -> Result<Result<LoadedModule, roc_load_internal::file::LoadingProblem<'a>>, std::io::Error> {
     use std::fs::{self, File};
@@ -224,6 +271,7 @@ fn multiple_modules_help<'a>(
             Default::default(),
             TARGET,
             FunctionKind::LambdaSet,
+            exec_mode,
         )
     };
 
@@ -244,6 +292,7 @@ fn load_fixture(
         subs_by_module,
         TARGET,
         FunctionKind::LambdaSet,
+        ExecutionMode::Check,
     );
     let mut loaded_module = match loaded {
         Ok(x) => x,
@@ -426,6 +475,7 @@ fn module_with_deps() {
         subs_by_module,
         TARGET,
         FunctionKind::LambdaSet,
+        ExecutionMode::Check,
     );
 
     let mut loaded_module = loaded.expect("Test module failed to load");
@@ -2183,3 +2233,160 @@ fn roc_package_depends_on_other_package() {
 
     assert!(result.is_ok());
 }
+
+#[test]
+fn doc_test_type_error_points_at_doc_comment() {
+    let modules = vec![(
+        "Main.roc",
+        indoc!(
+            r#"
+            module [add]
+
+            ## Adds two numbers.
+            ##
+            ## ```roc
+            ## add 1 2 == "three"
+            ## ```
+            add : I64, I64 -> I64
+            add = \a, b -> a + b
+            "#
+        ),
+    )];
+
+    let exec_mode = ExecutionMode::Test(TestOptions {
+        doc_tests: true,
+        ..TestOptions::default()
+    });
+    let err = multiple_modules_in_mode("doc_test_type_error", exec_mode, modules).unwrap_err();
+    assert_eq!(
+        err,
+        indoc!(
+            r#"
+            ── TYPE MISMATCH in tmp/doc_test_type_error/Main.roc ───────────────────────────
+
+            This 2nd argument to == has an unexpected type:
+
+            6│  ## add 1 2 == "three"
+                              ^^^^^^^
+
+            The argument is a string of type:
+
+                Str
+
+            But == needs its 2nd argument to be:
+
+                Int Signed64
+            "#
+        )
+    );
+}
+
+#[test]
+fn reload_reuses_types_of_unchanged_modules() {
+    use std::fs;
+
+    let dir = TmpDir::new("tmp/reload_reuses_types_of_unchanged_modules");
+    let write = |name: &str, source: &str| fs::write(dir.path().join(name), source).unwrap();
+
+    write(
+        "Dep.roc",
+        indoc!(
+            r#"
+            module [one]
+
+            one : U8
+            one = 1
+            "#
+        ),
+    );
+    write(
+        "Mid.roc",
+        indoc!(
+            r#"
+            module [two]
+
+            import Dep
+
+            two = Dep.one + 1
+            "#
+        ),
+    );
+    write(
+        "Other.roc",
+        indoc!(
+            r#"
+            module [name]
+
+            name = "other"
+            "#
+        ),
+    );
+    write(
+        "Top.roc",
+        indoc!(
+            r#"
+            module [three, name]
+
+            import Mid
+            import Other
+
+            three = Mid.two + 1
+
+            name = Other.name
+            "#
+        ),
+    );
+
+    let top_path = dir.path().join("Top.roc");
+    let module_id = |loaded: &LoadedModule, name: &str| {
+        loaded
+            .interns
+            .module_ids
+            .get_id(&ModuleName::from(name))
+            .unwrap()
+    };
+
+    let arena = Bump::new();
+    let mut loaded = load_with_cache(&arena, top_path.clone(), &LoadCache::default());
+    let cache = std::mem::take(&mut loaded.cache);
+
+    assert!(!cache.reused(loaded.module_id));
+    expect_types(loaded, hashmap! { "three" => "U8", "name" => "Str" });
+
+    // Nothing changed, so every module takes its types from the previous load.
+    let arena = Bump::new();
+    let mut loaded = load_with_cache(&arena, top_path.clone(), &cache);
+    let cache = std::mem::take(&mut loaded.cache);
+
+    for name in ["Dep", "Mid", "Other", "Top"] {
+        assert!(
+            cache.reused(module_id(&loaded, name)),
+            "{name} was solved again"
+        );
+    }
+    expect_types(loaded, hashmap! { "three" => "U8", "name" => "Str" });
+
+    // Mid changed, so it and Top, which imports it, are solved again.
+    write(
+        "Mid.roc",
+        indoc!(
+            r#"
+            module [two]
+
+            import Dep
+
+            two = Num.to_u16 Dep.one + 1
+            "#
+        ),
+    );
+
+    let arena = Bump::new();
+    let mut loaded = load_with_cache(&arena, top_path, &cache);
+    let cache = std::mem::take(&mut loaded.cache);
+
+    assert!(cache.reused(module_id(&loaded, "Dep")));
+    assert!(cache.reused(module_id(&loaded, "Other")));
+    assert!(!cache.reused(module_id(&loaded, "Mid")));
+    assert!(!cache.reused(module_id(&loaded, "Top")));
+    expect_types(loaded, hashmap! { "three" => "U16", "name" => "Str" });
+}
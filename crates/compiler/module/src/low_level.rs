@@ -137,6 +137,10 @@ pub enum LowLevel {
     SetJmp,
     LongJmp,
     SetLongJmpBuffer,
+    /// Returns its argument, but keeps the optimizer from removing its computation, e.g. the call
+    /// to a benchmark whose result is otherwise unused, or from making use of its value, e.g. the
+    /// input of a benchmark.
+    BlackBox,
 }
 
 macro_rules! higher_order {
@@ -244,6 +248,8 @@ macro_rules! map_symbol_to_lowlevel {
                 LowLevel::LongJmp => unimplemented!(),
                 LowLevel::SetLongJmpBuffer => unimplemented!(),
 
+                LowLevel::BlackBox => unimplemented!(),
+
                 // these are not implemented, not sure why
                 LowLevel::StrFromInt => unimplemented!(),
                 LowLevel::StrFromFloat => unimplemented!(),
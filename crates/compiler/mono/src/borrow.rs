@@ -279,6 +279,9 @@ impl<'state, 'a> State<'state, 'a> {
                 // based on my reading of inc_dec.rs, expect borrows the symbol
                 self.inspect_stmt(interner, borrow_signatures, remainder);
             }
+            Stmt::Coverage { remainder, .. } => {
+                self.inspect_stmt(interner, borrow_signatures, remainder);
+            }
             Stmt::Join {
                 id,
                 parameters,
@@ -487,6 +490,7 @@ impl<'a> CallInfo<'a> {
 
                 Dbg { remainder, .. } => stack.push(remainder),
                 Expect { remainder, .. } => stack.push(remainder),
+                Coverage { remainder, .. } => stack.push(remainder),
 
                 Refcounting(_, _) => unreachable!("these have not been introduced yet"),
 
@@ -0,0 +1,135 @@
+//! Source-level code coverage for `roc test --coverage`.
+//!
+//! When coverage is enabled, specialization wraps the body of every top-level def, `when` branch
+//! and `if` arm in a [`Stmt::Coverage`](crate::ir::Stmt::Coverage) marker. A backend that supports
+//! coverage turns each marker into a hit counter named by [`CoveragePoint::counter_name`], which
+//! the test runner reads back once the expects have run.
+//!
+//! Code that is never specialized has no counter at all, so the set of points a module *could*
+//! hit is collected separately from the canonical declarations by [`coverage_sites`].
+
+use roc_can::expr::{Declarations, Expr, WhenBranch};
+use roc_can::traverse::{walk_decl, walk_expr, DeclarationInfo, Visitor};
+use roc_module::symbol::{ModuleId, Symbol};
+use roc_region::all::{Loc, Region};
+use roc_types::subs::Variable;
+
+/// A location in the source whose execution is counted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CoveragePoint {
+    /// The body of the top-level def with this name was entered.
+    Def(Symbol),
+    /// The `when` branch or `if` arm whose body has this region was taken.
+    Branch(ModuleId, Region),
+}
+
+impl CoveragePoint {
+    /// The name of the global counter a backend emits for this point.
+    ///
+    /// Every specialization of the same source location shares one counter.
+    pub fn counter_name(&self) -> String {
+        match self {
+            CoveragePoint::Def(symbol) => {
+                format!("roc__coverage_def_{}", symbol.as_u64())
+            }
+            CoveragePoint::Branch(module_id, region) => {
+                format!(
+                    "roc__coverage_branch_{}_{}_{}",
+                    module_id.to_zero_indexed(),
+                    region.start().offset,
+                    region.end().offset
+                )
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CoverageKind {
+    Def,
+    WhenBranch,
+    IfArm,
+}
+
+/// A coverage point together with what to report about it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CoverageSite {
+    pub point: CoveragePoint,
+    pub kind: CoverageKind,
+    /// For defs this spans the name and the body, so that reports point at the name.
+    pub region: Region,
+}
+
+/// Collect every coverage point in a module's top-level declarations, in source order.
+///
+/// Top-level `expect`s are test code, and are not reported on.
+pub fn coverage_sites(home: ModuleId, decls: &Declarations) -> Vec<CoverageSite> {
+    let mut collector = CoverageCollector {
+        home,
+        sites: Vec::new(),
+    };
+
+    collector.visit_decls(decls);
+
+    collector.sites.sort_by_key(|site| site.region.start());
+
+    collector.sites
+}
+
+struct CoverageCollector {
+    home: ModuleId,
+    sites: Vec<CoverageSite>,
+}
+
+impl CoverageCollector {
+    fn add_branch(&mut self, kind: CoverageKind, region: Region) {
+        self.sites.push(CoverageSite {
+            point: CoveragePoint::Branch(self.home, region),
+            kind,
+            region,
+        });
+    }
+}
+
+impl Visitor for CoverageCollector {
+    fn visit_decl(&mut self, decl: DeclarationInfo<'_>) {
+        match &decl {
+            DeclarationInfo::Expectation { .. } => return,
+            DeclarationInfo::Value { loc_symbol, .. }
+            | DeclarationInfo::Function { loc_symbol, .. } => {
+                self.sites.push(CoverageSite {
+                    point: CoveragePoint::Def(loc_symbol.value),
+                    kind: CoverageKind::Def,
+                    region: decl.region(),
+                });
+            }
+            DeclarationInfo::Destructure { .. } => {}
+        }
+
+        walk_decl(self, decl);
+    }
+
+    fn visit_expr(&mut self, expr: &Expr, _region: Region, var: Variable) {
+        match expr {
+            Expr::When { branches, .. } => {
+                for WhenBranch { value, .. } in branches {
+                    self.add_branch(CoverageKind::WhenBranch, value.region);
+                }
+            }
+            Expr::If {
+                branches,
+                final_else,
+                ..
+            } => {
+                for (_, Loc { region, .. }) in branches {
+                    self.add_branch(CoverageKind::IfArm, *region);
+                }
+
+                self.add_branch(CoverageKind::IfArm, final_else.region);
+            }
+            _ => {}
+        }
+
+        walk_expr(self, expr, var);
+    }
+}
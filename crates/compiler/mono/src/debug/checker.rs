@@ -345,6 +345,9 @@ impl<'a, 'r> Ctx<'a, 'r> {
             &Stmt::Dbg { remainder, .. } => {
                 self.check_stmt(remainder);
             }
+            &Stmt::Coverage { remainder, .. } => {
+                self.check_stmt(remainder);
+            }
             &Stmt::Expect {
                 condition,
                 region: _,
@@ -610,6 +610,16 @@ fn specialize_drops_stmt<'a, 'i>(
                 remainder,
             ),
         }),
+        Stmt::Coverage { point, remainder } => arena.alloc(Stmt::Coverage {
+            point: *point,
+            remainder: specialize_drops_stmt(
+                arena,
+                layout_interner,
+                ident_ids,
+                environment,
+                remainder,
+            ),
+        }),
         Stmt::Dbg {
             source_location,
             source,
@@ -1614,6 +1624,7 @@ fn low_level_no_rc(lowlevel: &LowLevel) -> RC {
         | NumDecFromBits => RC::NoRc,
         NumWithoutDecimalPoint | NumWithDecimalPoint => RC::NoRc,
         DictPseudoSeed => RC::NoRc,
+        BlackBox => RC::NoRc,
         StrStartsWith | StrEndsWith => RC::NoRc,
         StrFromUtf8 => RC::Rc,
         StrFromUtf8Lossy => RC::Rc,
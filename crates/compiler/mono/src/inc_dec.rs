@@ -184,7 +184,9 @@ impl<'a, 'i> SymbolRcTypesEnv<'a, 'i> {
             Stmt::Refcounting(_, _) => unreachable!(
                 "Refcounting operations should not be present in the AST at this point."
             ),
-            Stmt::Expect { remainder, .. } | Stmt::Dbg { remainder, .. } => {
+            Stmt::Expect { remainder, .. }
+            | Stmt::Dbg { remainder, .. }
+            | Stmt::Coverage { remainder, .. } => {
                 self.insert_symbols_rc_type_stmt(remainder);
             }
             Stmt::Join {
@@ -693,6 +695,14 @@ fn insert_refcount_operations_stmt<'v, 'a>(
                 remainder: newer_remainder,
             })
         }
+        Stmt::Coverage { point, remainder } => {
+            let new_remainder = insert_refcount_operations_stmt(arena, environment, remainder);
+
+            arena.alloc(Stmt::Coverage {
+                point: *point,
+                remainder: new_remainder,
+            })
+        }
         Stmt::Dbg {
             source_location,
             source,
@@ -1260,6 +1270,7 @@ pub(crate) fn lowlevel_borrow_signature(op: LowLevel) -> &'static [Ownership] {
     match op {
         Unreachable => &[IRRELEVANT],
         DictPseudoSeed => &[IRRELEVANT],
+        BlackBox => &[OWNED],
         ListLenU64 | ListLenUsize | StrIsEmpty | StrCountUtf8Bytes | ListGetCapacity => &[BORROWED],
         ListWithCapacity | StrWithCapacity => &[IRRELEVANT],
         ListReplaceUnsafe => &[OWNED, IRRELEVANT, IRRELEVANT],
@@ -1,5 +1,6 @@
 #![allow(clippy::manual_map)]
 
+use crate::coverage::CoveragePoint;
 use crate::ir::erased::{build_erased_function, ResolvedErasedLambda};
 use crate::ir::literal::{make_num_literal, IntOrFloatValue};
 use crate::layout::{
@@ -1373,6 +1374,8 @@ pub struct Env<'a, 'i> {
     pub exposed_by_module: &'i ExposedByModule,
     pub derived_module: &'i SharedDerivedModule,
     pub struct_indexing: UsageTrackingMap<(Symbol, u64), Symbol>,
+    /// Whether to emit [Stmt::Coverage] markers, for `roc test --coverage`.
+    pub coverage: bool,
 }
 
 impl<'a, 'i> Env<'a, 'i> {
@@ -1387,6 +1390,18 @@ impl<'a, 'i> Env<'a, 'i> {
         Symbol::new(self.home, ident_id)
     }
 
+    /// Mark `stmt` as reaching `point`, if we are collecting coverage.
+    fn with_coverage(&self, point: CoveragePoint, stmt: Stmt<'a>) -> Stmt<'a> {
+        if self.coverage {
+            Stmt::Coverage {
+                point,
+                remainder: self.arena.alloc(stmt),
+            }
+        } else {
+            stmt
+        }
+    }
+
     pub fn next_update_mode_id(&mut self) -> UpdateModeId {
         self.update_mode_ids.next_id()
     }
@@ -1543,6 +1558,12 @@ pub enum Stmt<'a> {
         /// What happens after the dbg
         remainder: &'a Stmt<'a>,
     },
+    /// Count that execution reached a point in the source, for `roc test --coverage`
+    Coverage {
+        point: CoveragePoint,
+        /// what happens after the point is counted
+        remainder: &'a Stmt<'a>,
+    },
     /// a join point `join f <params> = <continuation> in remainder`
     Join {
         id: JoinPointId,
@@ -2256,6 +2277,11 @@ impl<'a> Stmt<'a> {
                 .append(alloc.hardline())
                 .append(remainder.to_doc(alloc, interner, pretty)),
 
+            Coverage { remainder, .. } => alloc
+                .text("coverage;")
+                .append(alloc.hardline())
+                .append(remainder.to_doc(alloc, interner, pretty)),
+
             Expect {
                 condition,
                 remainder,
@@ -3554,7 +3580,9 @@ fn specialize_proc_help<'a>(
     let body = partial_proc.body.clone();
     let body_var = partial_proc.body_var;
 
-    let mut specialized_body = from_can(env, body_var, body, procs, layout_cache);
+    let specialized_body = from_can(env, body_var, body, procs, layout_cache);
+    let mut specialized_body =
+        env.with_coverage(CoveragePoint::Def(lambda_name.name()), specialized_body);
 
     let specialized_proc = match specialized {
         SpecializedLayout::FunctionPointerBody {
@@ -4671,6 +4699,7 @@ pub fn with_hole<'a>(
                     if is_terminated {
                         let terminator = hole;
 
+                        let else_point = CoveragePoint::Branch(env.home, final_else.region);
                         let mut stmt = with_hole(
                             env,
                             final_else.value,
@@ -4680,10 +4709,12 @@ pub fn with_hole<'a>(
                             assigned,
                             terminator,
                         );
+                        stmt = env.with_coverage(else_point, stmt);
 
                         for (loc_cond, loc_then) in branches.into_iter().rev() {
                             let branching_symbol = env.unique_symbol();
 
+                            let then_point = CoveragePoint::Branch(env.home, loc_then.region);
                             let then = with_hole(
                                 env,
                                 loc_then.value,
@@ -4693,6 +4724,7 @@ pub fn with_hole<'a>(
                                 assigned,
                                 terminator,
                             );
+                            let then = env.with_coverage(then_point, then);
 
                             stmt = cond(env, branching_symbol, cond_layout, then, stmt, ret_layout);
 
@@ -4716,6 +4748,7 @@ pub fn with_hole<'a>(
                             .arena
                             .alloc(Stmt::Jump(id, env.arena.alloc([assigned_in_jump])));
 
+                        let else_point = CoveragePoint::Branch(env.home, final_else.region);
                         let mut stmt = with_hole(
                             env,
                             final_else.value,
@@ -4725,6 +4758,7 @@ pub fn with_hole<'a>(
                             assigned_in_jump,
                             terminator,
                         );
+                        stmt = env.with_coverage(else_point, stmt);
 
                         for (loc_cond, loc_then) in branches.into_iter().rev() {
                             let branching_symbol = possible_reuse_symbol_or_specialize(
@@ -4735,6 +4769,7 @@ pub fn with_hole<'a>(
                                 cond_var,
                             );
 
+                            let then_point = CoveragePoint::Branch(env.home, loc_then.region);
                             let then = with_hole(
                                 env,
                                 loc_then.value,
@@ -4744,6 +4779,7 @@ pub fn with_hole<'a>(
                                 assigned_in_jump,
                                 terminator,
                             );
+                            let then = env.with_coverage(then_point, then);
 
                             stmt = cond(env, branching_symbol, cond_layout, then, stmt, ret_layout);
 
@@ -7076,7 +7112,9 @@ pub fn from_can<'a>(
                 "invalid condition type in if expression"
             );
 
+            let else_point = CoveragePoint::Branch(env.home, final_else.region);
             let mut stmt = from_can(env, branch_var, final_else.value, procs, layout_cache);
+            stmt = env.with_coverage(else_point, stmt);
 
             for (loc_cond, loc_then) in branches.into_iter().rev() {
                 let branching_symbol = possible_reuse_symbol_or_specialize(
@@ -7086,7 +7124,9 @@ pub fn from_can<'a>(
                     &loc_cond.value,
                     cond_var,
                 );
+                let then_point = CoveragePoint::Branch(env.home, loc_then.region);
                 let then = from_can(env, branch_var, loc_then.value, procs, layout_cache);
+                let then = env.with_coverage(then_point, then);
 
                 stmt = cond(env, branching_symbol, cond_layout, then, stmt, ret_layout);
 
@@ -7255,7 +7295,7 @@ fn to_opt_branches<'a>(
 ) -> std::vec::Vec<(
     Pattern<'a>,
     Option<Loc<roc_can::expr::Expr>>,
-    roc_can::expr::Expr,
+    Loc<roc_can::expr::Expr>,
 )> {
     debug_assert!(!branches.is_empty());
 
@@ -7301,14 +7341,21 @@ fn to_opt_branches<'a>(
                     };
 
                     // TODO remove clone?
-                    opt_branches.push((mono_pattern, when_branch.guard.clone(), loc_expr.value));
+                    opt_branches.push((
+                        mono_pattern,
+                        when_branch.guard.clone(),
+                        Loc::at(when_branch.value.region, loc_expr.value),
+                    ));
                 }
                 Err(runtime_error) => {
                     // TODO remove clone?
                     opt_branches.push((
                         Pattern::Underscore,
                         when_branch.guard.clone(),
-                        roc_can::expr::Expr::RuntimeError(runtime_error),
+                        Loc::at(
+                            when_branch.value.region,
+                            roc_can::expr::Expr::RuntimeError(runtime_error),
+                        ),
                     ));
                 }
             }
@@ -7321,7 +7368,9 @@ fn to_opt_branches<'a>(
         opt_branches.push((
             Pattern::Underscore,
             None,
-            roc_can::expr::Expr::RuntimeError(roc_problem::can::RuntimeError::NonExhaustivePattern),
+            Loc::at_zero(roc_can::expr::Expr::RuntimeError(
+                roc_problem::can::RuntimeError::NonExhaustivePattern,
+            )),
         ));
     }
 
@@ -7362,7 +7411,7 @@ fn from_can_when<'a>(
     let arena = env.arena;
     let it = opt_branches
         .into_iter()
-        .filter_map(|(pattern, opt_guard, can_expr)| {
+        .filter_map(|(pattern, opt_guard, loc_can_expr)| {
             // If the pattern has a void layout we can drop it; however, we must still perform the
             // work of building the body, because that may contain specializations we must
             // discover for use elsewhere. See
@@ -7379,6 +7428,11 @@ fn from_can_when<'a>(
                 None
             };
 
+            let Loc {
+                region: branch_region,
+                value: can_expr,
+            } = loc_can_expr;
+
             let branch_stmt = match join_point {
                 None => from_can(env, expr_var, can_expr, procs, layout_cache),
                 Some(id) => {
@@ -7390,6 +7444,13 @@ fn from_can_when<'a>(
                 }
             };
 
+            // the branch added for a non-exhaustive `when` has no region in the source
+            let branch_stmt = if branch_region.is_empty() {
+                branch_stmt
+            } else {
+                env.with_coverage(CoveragePoint::Branch(env.home, branch_region), branch_stmt)
+            };
+
             use decision_tree::Guard;
             let result = if let Some(loc_expr) = opt_guard {
                 let guard_spec = GuardStmtSpec {
@@ -7683,6 +7744,15 @@ fn substitute_in_stmt_help<'a>(
             Some(arena.alloc(expect))
         }
 
+        Coverage { point, remainder } => {
+            substitute_in_stmt_help(arena, remainder, subs).map(|remainder| {
+                &*arena.alloc(Coverage {
+                    point: *point,
+                    remainder,
+                })
+            })
+        }
+
         Expect {
             condition,
             region,
@@ -625,6 +625,22 @@ fn insert_reset_reuse_operations_stmt<'a, 'i>(
                 remainder: new_remainder,
             })
         }
+        Stmt::Coverage { point, remainder } => {
+            let new_remainder = insert_reset_reuse_operations_stmt(
+                arena,
+                layout_interner,
+                home,
+                ident_ids,
+                update_mode_ids,
+                environment,
+                remainder,
+            );
+
+            arena.alloc(Stmt::Coverage {
+                point: *point,
+                remainder: new_remainder,
+            })
+        }
         Stmt::Dbg {
             source_location,
             source,
@@ -337,6 +337,21 @@ fn insert_jumps<'a>(
             }
         }
 
+        Coverage { point, remainder } => match insert_jumps(
+            arena,
+            remainder,
+            goal_id,
+            needle,
+            needle_arguments,
+            needle_result,
+        ) {
+            Some(cont) => Some(arena.alloc(Coverage {
+                point: *point,
+                remainder: cont,
+            })),
+            None => None,
+        },
+
         Dbg {
             source_location,
             source,
@@ -535,7 +550,9 @@ fn trmc_candidates_help(
             }
         }
         Stmt::Refcounting(_, next) => trmc_candidates_help(function_name, next, candidates),
-        Stmt::Expect { remainder, .. } | Stmt::Dbg { remainder, .. } => {
+        Stmt::Expect { remainder, .. }
+        | Stmt::Dbg { remainder, .. }
+        | Stmt::Coverage { remainder, .. } => {
             trmc_candidates_help(function_name, remainder, candidates)
         }
         Stmt::Join {
@@ -994,6 +1011,10 @@ impl<'a> TrmcEnv<'a> {
                 variables,
                 remainder: arena.alloc(self.walk_stmt(env, remainder)),
             },
+            Stmt::Coverage { point, remainder } => Stmt::Coverage {
+                point: *point,
+                remainder: arena.alloc(self.walk_stmt(env, remainder)),
+            },
             Stmt::Dbg {
                 source_location,
                 source,
@@ -1100,6 +1121,7 @@ fn stmt_contains_symbol_nonrec(stmt: &Stmt, needle: Symbol) -> bool {
             condition, lookups, ..
         } => needle == *condition || lookups.contains(&needle),
         Stmt::Dbg { symbol, .. } => needle == *symbol,
+        Stmt::Coverage { .. } => false,
         Stmt::Join { .. } => false,
         Stmt::Jump(_, arguments) => arguments.contains(&needle),
         Stmt::Crash(symbol, _) => needle == *symbol,
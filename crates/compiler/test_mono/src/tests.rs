@@ -17,6 +17,7 @@ use roc_load::ExecutionMode;
 use roc_load::FunctionKind;
 use roc_load::LoadConfig;
 use roc_load::LoadMonomorphizedError;
+use roc_load::TestOptions;
 use roc_load::Threading;
 use roc_module::symbol::Interns;
 use roc_module::symbol::Symbol;
@@ -130,7 +131,8 @@ fn compiles_to_ir(test_name: &str, src: &str, mode: &str, allow_type_errors: boo
 
     let exec_mode = match mode {
         "exec" => ExecutionMode::Executable,
-        "test" => ExecutionMode::Test,
+        "test" => ExecutionMode::Test(TestOptions::default()),
+        "bench" => ExecutionMode::Bench,
         _ => panic!("Invalid test_mono exec mode {mode}"),
     };
 
@@ -2358,6 +2360,21 @@ fn issue_4705() {
     )
 }
 
+#[mono_test(mode = "bench")]
+fn bench_thunk() {
+    indoc!(
+        r"
+        interface Test exposes [] imports []
+
+        bench_sum : {} -> U64
+        bench_sum = \{} -> List.sum [1, 2, 3]
+
+        bench_needs_an_argument : U64 -> U64
+        bench_needs_an_argument = \n -> n + 1
+        "
+    )
+}
+
 #[mono_test(mode = "test", large_stack = "true")]
 fn issue_4749() {
     &formatdoc!(
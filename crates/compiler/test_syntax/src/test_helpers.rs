@@ -8,7 +8,7 @@ use roc_can_solo::env::SoloEnv;
 use roc_can_solo::scope::SoloScope;
 use roc_error_macros::set_panic_not_exit;
 use roc_fmt::migrate::{MigrateError, Suffix};
-use roc_fmt::{annotation::Formattable, header::fmt_header, MigrationFlags};
+use roc_fmt::{annotation::Formattable, header::fmt_header, FormatConfig, MigrationFlags};
 use roc_module::ident::QualifiedModuleName;
 use roc_module::symbol::{IdentIds, Interns, ModuleIds, PackageModuleIds, Symbol};
 use roc_parse::ast::ValueDef;
@@ -111,8 +111,12 @@ pub enum Output<'a> {
 
 impl<'a> Output<'a> {
     pub fn format(&self, flags: MigrationFlags) -> InputOwned {
+        self.format_with_config(flags, FormatConfig::default())
+    }
+
+    pub fn format_with_config(&self, flags: MigrationFlags, config: FormatConfig) -> InputOwned {
         let arena = Bump::new();
-        let mut buf = Buf::new_in(&arena, flags);
+        let mut buf = Buf::new_with_config_in(&arena, flags, config);
         match self {
             Output::Header(header) => {
                 fmt_header(&mut buf, header);
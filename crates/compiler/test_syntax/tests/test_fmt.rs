@@ -4,9 +4,9 @@ extern crate indoc;
 #[cfg(test)]
 mod test_fmt {
     use bumpalo::Bump;
-    use roc_fmt::def::fmt_defs;
+    use roc_fmt::def::{fmt_defs, fmt_defs_in_ranges};
     use roc_fmt::header::fmt_header;
-    use roc_fmt::{Buf, MigrationFlags};
+    use roc_fmt::{Buf, FormatConfig, MigrationFlags};
     use roc_parse::ast::{Defs, Header, SpacesBefore};
     use roc_parse::header::{self, parse_module_defs};
     use roc_parse::state::State;
@@ -63,6 +63,29 @@ mod test_fmt {
         )
     }
 
+    /// Formats `input` once with `config`, the way `roc format` does, and checks that formatting
+    /// the output again doesn't change it.
+    fn expr_formats_to_with_config(input: &str, expected: &str, config: FormatConfig) {
+        let flags = MigrationFlags::default();
+        let format = |src: &str| {
+            let arena = Bump::new();
+            let ast = Input::Expr(src).parse_in(&arena).unwrap_or_else(|err| {
+                panic!("Formatting with {config:?} produced code that doesn't parse:\n\n{src}\n\n{err:?}");
+            });
+            let formatted = ast.format_with_config(flags, config);
+
+            formatted.as_ref().as_str().trim().to_string()
+        };
+
+        let output = format(input.trim());
+
+        assert_multiline_str_eq!(expected.trim(), output.as_str());
+        assert_multiline_str_eq!(output.as_str(), format(&output).as_str());
+
+        // The wrapped code must mean the same thing, and keep its layout under the default style.
+        Input::Expr(&output).check_invariants(|_| {}, true, Some(false));
+    }
+
     fn fmt_module_and_defs<'a>(
         arena: &Bump,
         src: &str,
@@ -166,6 +189,110 @@ mod test_fmt {
         module_formats_to(input, input);
     }
 
+    /// Formats the defs that overlap the lines containing one of `changed`, and checks that
+    /// formatting the output again changes nothing.
+    fn defs_on_lines_format_to(input: &str, changed: &[&str], expected: &str) {
+        let arena = Bump::new();
+        let (_, state) = header::parse_header(&arena, State::new(input.as_bytes())).unwrap();
+        let defs_start = state.pos().offset as usize;
+        let defs_src = &input[defs_start..];
+
+        let ranges: Vec<_> = changed
+            .iter()
+            .map(|line| {
+                let start = defs_src.find(line).unwrap();
+                start..start + line.len()
+            })
+            .collect();
+
+        let defs =
+            parse_module_defs(&arena, State::new(defs_src.as_bytes()), Defs::default()).unwrap();
+        let mut buf = Buf::new_in(&arena, MigrationFlags::default());
+        let formatted_ranges = fmt_defs_in_ranges(&mut buf, defs_src, &defs, &ranges);
+        let output = format!("{}{}", &input[..defs_start], buf.as_str());
+
+        assert_multiline_str_eq!(expected, output.as_str());
+
+        let output_defs_src = &output[defs_start..];
+        let reparsed = parse_module_defs(
+            &arena,
+            State::new(output_defs_src.as_bytes()),
+            Defs::default(),
+        )
+        .unwrap();
+        let mut reformatted_buf = Buf::new_in(&arena, MigrationFlags::default());
+        fmt_defs_in_ranges(
+            &mut reformatted_buf,
+            output_defs_src,
+            &reparsed,
+            &formatted_ranges,
+        );
+
+        assert_multiline_str_eq!(output_defs_src, reformatted_buf.as_str());
+    }
+
+    #[test]
+    fn format_only_changed_defs() {
+        defs_on_lines_format_to(
+            indoc!(
+                r#"
+                module [a, b, c]
+
+                a   =   1
+
+                # b is nice
+                b : U64
+                b =     a+1
+
+                c =   [ 1,2 ]
+                "#
+            ),
+            &["b =     a+1"],
+            indoc!(
+                r#"
+                module [a, b, c]
+
+                a   =   1
+
+                # b is nice
+                b : U64
+                b = a + 1
+
+                c =   [ 1,2 ]
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    fn format_changed_defs_keeps_spacing_between_defs() {
+        defs_on_lines_format_to(
+            indoc!(
+                r#"
+                module [a, b]
+                a =   1
+
+
+
+                b =   2
+                # trailing comment
+                "#
+            ),
+            &["a =   1", "b =   2"],
+            indoc!(
+                r#"
+                module [a, b]
+                a = 1
+
+
+
+                b = 2
+                # trailing comment
+                "#
+            ),
+        );
+    }
+
     // STRING LITERALS
 
     #[test]
@@ -7160,4 +7287,192 @@ mod test_fmt {
     fn pattern_tag_apply_with_pnc_multi_arg() {
         pattern_formats_same(indoc!("Ok(a, b)"));
     }
+
+    #[test]
+    fn wraps_long_list() {
+        expr_formats_to_with_config(
+            "[first_item, second_item, third_item]",
+            indoc!(
+                r"
+                [
+                    first_item,
+                    second_item,
+                    third_item,
+                ]
+                "
+            ),
+            FormatConfig {
+                max_line_width: Some(30),
+                ..FormatConfig::default()
+            },
+        );
+    }
+
+    #[test]
+    fn short_list_is_not_wrapped() {
+        expr_formats_to_with_config(
+            "[first_item, second_item]",
+            "[first_item, second_item]",
+            FormatConfig {
+                max_line_width: Some(30),
+                ..FormatConfig::default()
+            },
+        );
+    }
+
+    #[test]
+    fn wraps_long_record_without_trailing_comma() {
+        expr_formats_to_with_config(
+            "{ name: \"Roc\", version: 1, homepage: \"roc-lang.org\" }",
+            indoc!(
+                r#"
+                {
+                    name: "Roc",
+                    version: 1,
+                    homepage: "roc-lang.org"
+                }
+                "#
+            ),
+            FormatConfig {
+                max_line_width: Some(40),
+                trailing_commas: false,
+            },
+        );
+    }
+
+    #[test]
+    fn wraps_long_application() {
+        expr_formats_to_with_config(
+            "some_function first_argument second_argument",
+            indoc!(
+                r"
+                some_function
+                    first_argument
+                    second_argument
+                "
+            ),
+            FormatConfig {
+                max_line_width: Some(30),
+                ..FormatConfig::default()
+            },
+        );
+    }
+
+    #[test]
+    fn wraps_last_collection_argument_of_application() {
+        expr_formats_to_with_config(
+            "List.sum [first_item, second_item, third_item]",
+            indoc!(
+                r"
+                List.sum [
+                    first_item,
+                    second_item,
+                    third_item,
+                ]
+                "
+            ),
+            FormatConfig {
+                max_line_width: Some(30),
+                ..FormatConfig::default()
+            },
+        );
+    }
+
+    #[test]
+    fn wraps_nested_collections_once_parent_is_wrapped() {
+        expr_formats_to_with_config(
+            "[[first_item, second_item], [third_item, fourth_item, fifth_item]]",
+            indoc!(
+                r"
+                [
+                    [first_item, second_item],
+                    [
+                        third_item,
+                        fourth_item,
+                        fifth_item,
+                    ],
+                ]
+                "
+            ),
+            FormatConfig {
+                max_line_width: Some(32),
+                ..FormatConfig::default()
+            },
+        );
+    }
+
+    #[test]
+    fn wrapped_when_branch_goes_on_its_own_line() {
+        expr_formats_to_with_config(
+            indoc!(
+                r"
+                when x is
+                    A -> [first_item, second_item, third_item]
+                    B -> foo(bar, baz, qux)
+                "
+            ),
+            indoc!(
+                r"
+                when x is
+                    A ->
+                        [
+                            first_item,
+                            second_item,
+                            third_item,
+                        ]
+
+                    B -> foo(bar, baz, qux)
+                "
+            ),
+            FormatConfig {
+                max_line_width: Some(30),
+                ..FormatConfig::default()
+            },
+        );
+    }
+
+    #[test]
+    fn if_that_overflows_is_multiline() {
+        expr_formats_to_with_config(
+            "if condition then [first_item, second_item, third_item] else foo(bar, baz, [qux, quux])",
+            indoc!(
+                r"
+                if condition then
+                    [first_item, second_item, third_item]
+                else
+                    foo(bar, baz, [qux, quux])
+                "
+            ),
+            FormatConfig {
+                max_line_width: Some(50),
+                ..FormatConfig::default()
+            },
+        );
+    }
+
+    #[test]
+    fn no_trailing_comma_in_multiline_list() {
+        expr_formats_to_with_config(
+            indoc!(
+                r"
+                [
+                    1,
+                    2,
+                ]
+                "
+            ),
+            indoc!(
+                r"
+                [
+                    1,
+                    2
+                ]
+                "
+            ),
+            FormatConfig {
+                max_line_width: None,
+                trailing_commas: false,
+            },
+        );
+    }
 }
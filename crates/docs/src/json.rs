@@ -0,0 +1,412 @@
+//! The JSON output of `roc docs --format=json`, for tools that render Roc docs themselves.
+//!
+//! The types in this module are the schema of that output. `docs.schema.json` next to this
+//! crate's Cargo.toml is generated from them, and [SCHEMA_VERSION] is bumped on every change
+//! that could break a consumer of the JSON.
+use roc_collections::VecSet;
+use roc_load::docs::{self, DocEntry, ModuleDocumentation};
+use roc_load::LoadedModule;
+use roc_module::symbol::{ModuleId, Symbol};
+use roc_parse::ast::FunctionArrow;
+use roc_region::all::{LineInfo, Region};
+use schemars::{schema::RootSchema, schema_for, JsonSchema};
+use serde::Serialize;
+
+/// The version of the schema the JSON follows.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The documentation of a package.
+#[derive(Serialize, JsonSchema, Debug)]
+pub struct PackageDocs {
+    /// The version of the schema this JSON follows. It goes up on every change that could break
+    /// a consumer of the JSON.
+    pub schema_version: u32,
+    pub package_name: String,
+    /// The modules the package exposes, in the order it exposes them.
+    pub modules: Vec<ModuleDocs>,
+}
+
+impl PackageDocs {
+    pub fn schema() -> RootSchema {
+        schema_for!(Self)
+    }
+}
+
+#[derive(Serialize, JsonSchema, Debug)]
+pub struct ModuleDocs {
+    pub name: String,
+    /// The module's source file, relative to the directory of the package's main .roc file.
+    pub source_path: String,
+    /// The doc comment at the top of the module, in Markdown.
+    pub docs: Option<String>,
+    /// The exposed defs of the module and its detached doc comments, in source order.
+    pub entries: Vec<Entry>,
+}
+
+#[derive(Serialize, JsonSchema, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Entry {
+    Def {
+        name: String,
+        /// The type variables of a type alias, opaque type or ability.
+        type_vars: Vec<String>,
+        /// Absent for defs without an annotation, and for aliases of unexposed types.
+        type_annotation: Option<Type>,
+        /// The doc comment of the def, in Markdown.
+        docs: Option<String>,
+        region: SourceRegion,
+    },
+    /// A doc comment that isn't attached to a def.
+    Detached { docs: String },
+}
+
+/// A type annotation, as written in the source.
+#[derive(Serialize, JsonSchema, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Type {
+    TagUnion {
+        tags: Vec<Tag>,
+        /// Absent for closed tag unions.
+        extension: Option<Box<Type>>,
+    },
+    Function {
+        args: Vec<Type>,
+        /// Whether the arrow is `=>` rather than `->`.
+        effectful: bool,
+        output: Box<Type>,
+    },
+    ObscuredTagUnion,
+    ObscuredRecord,
+    Variable {
+        name: String,
+    },
+    /// A named type, like `List Str`. The name includes the module, if it was qualified.
+    Apply {
+        name: String,
+        args: Vec<Type>,
+    },
+    Record {
+        fields: Vec<RecordField>,
+        /// Absent for closed records.
+        extension: Option<Box<Type>>,
+    },
+    Tuple {
+        elems: Vec<Type>,
+        /// Absent for closed tuples.
+        extension: Option<Box<Type>>,
+    },
+    Ability {
+        members: Vec<AbilityMember>,
+    },
+    /// `*`
+    Wildcard,
+    /// `_`, or a part of the annotation that couldn't be parsed.
+    Inferred,
+    Where {
+        #[serde(rename = "type")]
+        type_: Box<Type>,
+        implements: Vec<ImplementsClause>,
+    },
+    As {
+        #[serde(rename = "type")]
+        type_: Box<Type>,
+        name: String,
+        vars: Vec<String>,
+    },
+}
+
+#[derive(Serialize, JsonSchema, Debug)]
+pub struct Tag {
+    pub name: String,
+    pub payload: Vec<Type>,
+}
+
+#[derive(Serialize, JsonSchema, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RecordField {
+    Required {
+        name: String,
+        #[serde(rename = "type")]
+        type_: Type,
+    },
+    Optional {
+        name: String,
+        #[serde(rename = "type")]
+        type_: Type,
+    },
+    /// A field in a record pattern without a type, like `{ name }`.
+    LabelOnly { name: String },
+}
+
+#[derive(Serialize, JsonSchema, Debug)]
+pub struct AbilityMember {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: Type,
+    pub implements: Vec<ImplementsClause>,
+    pub docs: Option<String>,
+    pub region: SourceRegion,
+}
+
+/// `a implements Hash & Eq`
+#[derive(Serialize, JsonSchema, Debug)]
+pub struct ImplementsClause {
+    pub variable: String,
+    pub abilities: Vec<Type>,
+}
+
+/// Where something is in its module's source file.
+#[derive(Serialize, JsonSchema, Debug, PartialEq, Eq)]
+pub struct SourceRegion {
+    pub start: SourcePosition,
+    pub end: SourcePosition,
+}
+
+/// Lines and columns are zero-based, like in the Language Server Protocol.
+#[derive(Serialize, JsonSchema, Debug, PartialEq, Eq)]
+pub struct SourcePosition {
+    /// The offset in bytes from the start of the file.
+    pub offset: u32,
+    pub line: u32,
+    pub column: u32,
+}
+
+pub fn package_docs(
+    package_name: &str,
+    exposed_module_docs: &[(ModuleId, ModuleDocumentation)],
+    loaded_module: &LoadedModule,
+) -> PackageDocs {
+    let modules = exposed_module_docs
+        .iter()
+        .map(|(module_id, module)| {
+            let (path, src) = &loaded_module.sources[module_id];
+            let line_info = LineInfo::new(src);
+
+            module_docs(
+                module,
+                crate::relative_source_path(loaded_module, path),
+                &module.exposed_symbols,
+                &line_info,
+            )
+        })
+        .collect();
+
+    PackageDocs {
+        schema_version: SCHEMA_VERSION,
+        package_name: package_name.to_string(),
+        modules,
+    }
+}
+
+fn module_docs(
+    module: &ModuleDocumentation,
+    source_path: String,
+    exposed_symbols: &VecSet<Symbol>,
+    line_info: &LineInfo,
+) -> ModuleDocs {
+    let mut module_doc = None;
+    let mut entries = Vec::with_capacity(module.entries.len());
+
+    for entry in module.entries.iter() {
+        match entry {
+            DocEntry::DocDef(doc_def) => {
+                // Only include entries that are exposed, like the HTML docs do
+                if exposed_symbols.contains(&doc_def.symbol) {
+                    entries.push(Entry::Def {
+                        name: doc_def.name.clone(),
+                        type_vars: doc_def.type_vars.clone(),
+                        type_annotation: type_annotation(&doc_def.type_annotation, line_info),
+                        docs: doc_def.docs.clone(),
+                        region: source_region(doc_def.region, line_info),
+                    });
+                }
+            }
+            DocEntry::ModuleDoc(docs) => {
+                module_doc = Some(docs.clone());
+            }
+            DocEntry::DetachedDoc(docs) => {
+                entries.push(Entry::Detached { docs: docs.clone() });
+            }
+        }
+    }
+
+    ModuleDocs {
+        name: module.name.clone(),
+        source_path,
+        docs: module_doc,
+        entries,
+    }
+}
+
+/// `None` for a missing annotation, which at the top level means that the def has none.
+fn type_annotation(ann: &docs::TypeAnnotation, line_info: &LineInfo) -> Option<Type> {
+    use docs::TypeAnnotation::*;
+
+    let to_type = |ann: &docs::TypeAnnotation| type_or_inferred(ann, line_info);
+    let to_types = |anns: &[docs::TypeAnnotation]| anns.iter().map(to_type).collect();
+    let to_extension = |ext: &docs::TypeAnnotation| type_annotation(ext, line_info).map(Box::new);
+
+    let typ = match ann {
+        NoTypeAnn => return None,
+        TagUnion { tags, extension } => Type::TagUnion {
+            tags: tags
+                .iter()
+                .map(|tag| Tag {
+                    name: tag.name.clone(),
+                    payload: to_types(&tag.values),
+                })
+                .collect(),
+            extension: to_extension(extension),
+        },
+        Function {
+            args,
+            arrow,
+            output,
+        } => Type::Function {
+            args: to_types(args),
+            effectful: matches!(arrow, FunctionArrow::Effectful),
+            output: Box::new(to_type(output)),
+        },
+        ObscuredTagUnion => Type::ObscuredTagUnion,
+        ObscuredRecord => Type::ObscuredRecord,
+        BoundVariable(name) => Type::Variable { name: name.clone() },
+        Apply { name, parts } => Type::Apply {
+            name: name.clone(),
+            args: to_types(parts),
+        },
+        Record { fields, extension } => Type::Record {
+            fields: fields
+                .iter()
+                .map(|field| match field {
+                    docs::RecordField::RecordField {
+                        name,
+                        type_annotation,
+                    } => RecordField::Required {
+                        name: name.clone(),
+                        type_: to_type(type_annotation),
+                    },
+                    docs::RecordField::OptionalField {
+                        name,
+                        type_annotation,
+                    } => RecordField::Optional {
+                        name: name.clone(),
+                        type_: to_type(type_annotation),
+                    },
+                    docs::RecordField::LabelOnly { name } => {
+                        RecordField::LabelOnly { name: name.clone() }
+                    }
+                })
+                .collect(),
+            extension: to_extension(extension),
+        },
+        Tuple { elems, extension } => Type::Tuple {
+            elems: to_types(elems),
+            extension: to_extension(extension),
+        },
+        Ability { members } => Type::Ability {
+            members: members
+                .iter()
+                .map(|member| AbilityMember {
+                    name: member.name.clone(),
+                    type_: to_type(&member.type_annotation),
+                    implements: member
+                        .able_variables
+                        .iter()
+                        .map(|(variable, abilities)| ImplementsClause {
+                            variable: variable.clone(),
+                            abilities: to_types(abilities),
+                        })
+                        .collect(),
+                    docs: member.docs.clone(),
+                    region: source_region(member.region, line_info),
+                })
+                .collect(),
+        },
+        Wildcard => Type::Wildcard,
+        Where { ann, implements } => Type::Where {
+            type_: Box::new(to_type(ann)),
+            implements: implements
+                .iter()
+                .map(|clause| ImplementsClause {
+                    variable: clause.name.clone(),
+                    abilities: to_types(&clause.abilities),
+                })
+                .collect(),
+        },
+        As { ann, name, vars } => Type::As {
+            type_: Box::new(to_type(ann)),
+            name: name.clone(),
+            vars: vars.clone(),
+        },
+    };
+
+    Some(typ)
+}
+
+fn type_or_inferred(ann: &docs::TypeAnnotation, line_info: &LineInfo) -> Type {
+    type_annotation(ann, line_info).unwrap_or(Type::Inferred)
+}
+
+fn source_region(region: Region, line_info: &LineInfo) -> SourceRegion {
+    let position = |pos: roc_region::all::Position| {
+        let line_column = line_info.convert_pos(pos);
+
+        SourcePosition {
+            offset: pos.offset,
+            line: line_column.line,
+            column: line_column.column,
+        }
+    };
+
+    SourceRegion {
+        start: position(region.start()),
+        end: position(region.end()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn schema_is_up_to_date() {
+        let schema = serde_json::to_string_pretty(&PackageDocs::schema()).unwrap() + "\n";
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("docs.schema.json");
+
+        if std::fs::read_to_string(&path).ok().as_deref() != Some(schema.as_str()) {
+            std::fs::write(&path, schema).unwrap();
+
+            panic!(
+                "{} was out of date, and has been regenerated. If the change could break consumers of the JSON, bump SCHEMA_VERSION.",
+                path.display()
+            );
+        }
+    }
+
+    #[test]
+    fn regions_are_zero_based() {
+        let src = "module [x]\n\nx = 1\n";
+        let start = src.find("x = 1").unwrap() as u32;
+        let region = Region::new(
+            roc_region::all::Position::new(start),
+            roc_region::all::Position::new(start + 5),
+        );
+
+        assert_eq!(
+            source_region(region, &LineInfo::new(src)),
+            SourceRegion {
+                start: SourcePosition {
+                    offset: start,
+                    line: 2,
+                    column: 0,
+                },
+                end: SourcePosition {
+                    offset: start + 5,
+                    line: 2,
+                    column: 5,
+                },
+            }
+        );
+    }
+}
@@ -6,7 +6,7 @@ use bumpalo::Bump;
 use roc_can::scope::Scope;
 use roc_collections::VecSet;
 use roc_highlight::highlight_roc_code_inline;
-use roc_load::docs::{DocEntry, TypeAnnotation};
+use roc_load::docs::{DocDef, DocEntry, TypeAnnotation};
 use roc_load::docs::{ModuleDocumentation, RecordField};
 use roc_load::{ExecutionMode, LoadConfig, LoadedModule, LoadingProblem, Threading};
 use roc_module::symbol::{Interns, ModuleId, Symbol};
@@ -16,19 +16,38 @@ use roc_parse::ident::{parse_ident, Accessor, Ident};
 use roc_parse::keyword;
 use roc_parse::state::State;
 use roc_problem::Severity;
-use roc_region::all::Region;
+use roc_region::all::{LineInfo, Region};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+pub mod json;
+mod man;
+mod markdown;
+mod search;
+
 const LINK_SVG: &str = include_str!("./static/link.svg");
 
-pub fn generate_docs_html(root_file: PathBuf, build_dir: &Path, maybe_root_dir: Option<String>) {
+/// The directory in each module's docs with its highlighted source.
+const SOURCE_DIR: &str = "source";
+
+// TODO get these from the platform's source file rather than hardcoding them!
+// github.com/roc-lang/roc/issues/5712
+const PACKAGE_NAME: &str = "Documentation";
+
+/// `source_url_template` is where the "Source" link of each entry points, with `{path}` replaced by
+/// the path of the module relative to `root_file`, and `{line}` by the line of the entry. Without
+/// one, the links point to highlighted copies of the sources, which are generated alongside the
+/// docs.
+pub fn generate_docs_html(
+    root_file: PathBuf,
+    build_dir: &Path,
+    maybe_root_dir: Option<String>,
+    source_url_template: Option<String>,
+) {
     let mut loaded_module = load_module_for_docs(root_file);
     let exposed_module_docs = get_exposed_module_docs(&mut loaded_module);
 
-    // TODO get these from the platform's source file rather than hardcoding them!
-    // github.com/roc-lang/roc/issues/5712
-    let package_name = "Documentation".to_string();
+    let package_name = PACKAGE_NAME.to_string();
 
     // Clear out the generated-docs dir (we'll create a fresh one at the end)
     if build_dir.exists() {
@@ -192,19 +211,120 @@ pub fn generate_docs_html(root_file: PathBuf, build_dir: &Path, maybe_root_dir:
                     &loaded_module,
                     &all_exposed_symbols,
                     maybe_root_dir.as_deref(),
+                    source_url_template.as_deref(),
                 )
                 .as_str(),
             );
 
         fs::write(module_dir.join("index.html"), rendered_module)
             .expect("TODO gracefully handle failing to write index.html inside module's dir");
+
+        if source_url_template.is_none() {
+            let source_dir = module_dir.join(SOURCE_DIR);
+            let (_, src) = &loaded_module.sources[module_id];
+
+            fs::create_dir_all(&source_dir)
+                .expect("TODO gracefully handle not being able to create the source dir");
+
+            let rendered_source = template_html
+                .replace(
+                    "<!-- Page title -->",
+                    page_title(package_name.as_str(), module_name).as_str(),
+                )
+                .replace(
+                    "<!-- Package Name -->",
+                    render_name_link(package_name.as_str(), maybe_root_dir.as_deref()).as_str(),
+                )
+                .replace("<!-- Package Name String -->", package_name.as_str())
+                .replace(
+                    "<!-- Module Docs -->",
+                    render_source(module_name, src).as_str(),
+                );
+
+            fs::write(source_dir.join("index.html"), rendered_source)
+                .expect("TODO gracefully handle failing to write the highlighted source");
+        }
+    }
+
+    println!("🎉 Docs generated in {}", build_dir.display());
+}
+
+/// Writes the docs of the package as `docs.json` in `build_dir`, following the schema in [json].
+pub fn generate_docs_json(root_file: PathBuf, build_dir: &Path) {
+    let mut loaded_module = load_module_for_docs(root_file);
+    let exposed_module_docs = get_exposed_module_docs(&mut loaded_module);
+
+    let package_docs = json::package_docs(PACKAGE_NAME, &exposed_module_docs, &loaded_module);
+
+    fs::create_dir_all(build_dir).expect("TODO gracefully handle being unable to create build dir");
+
+    let path = build_dir.join("docs.json");
+    let contents = serde_json::to_string_pretty(&package_docs)
+        .expect("the docs JSON types should always serialize successfully");
+
+    write_docs_file(&path, contents);
+
+    println!("🎉 Docs generated in {}", path.display());
+}
+
+/// Writes the docs of the package to `build_dir` as an `index.md` and a Markdown file per module.
+pub fn generate_docs_markdown(root_file: PathBuf, build_dir: &Path) {
+    let mut loaded_module = load_module_for_docs(root_file);
+    let exposed_module_docs = get_exposed_module_docs(&mut loaded_module);
+
+    fs::create_dir_all(build_dir).expect("TODO gracefully handle being unable to create build dir");
+
+    write_docs_file(
+        &build_dir.join("index.md"),
+        markdown::render_index(
+            PACKAGE_NAME,
+            exposed_module_docs.iter().map(|(_, docs)| docs),
+        ),
+    );
+
+    for (_, module_docs) in exposed_module_docs.iter() {
+        write_docs_file(
+            &build_dir.join(markdown::module_file_name(&module_docs.name)),
+            markdown::render_module(module_docs),
+        );
     }
 
     println!("🎉 Docs generated in {}", build_dir.display());
 }
 
+/// Writes a man page per module of the package to the `man3` directory in `build_dir`, so
+/// `man -M <build_dir> <module>` shows them.
+pub fn generate_docs_man(root_file: PathBuf, build_dir: &Path) {
+    let mut loaded_module = load_module_for_docs(root_file);
+    let exposed_module_docs = get_exposed_module_docs(&mut loaded_module);
+
+    let man_dir = build_dir.join(man::MAN_DIR);
+
+    fs::create_dir_all(&man_dir)
+        .expect("TODO gracefully handle being unable to create the man page dir");
+
+    for (_, module_docs) in exposed_module_docs.iter() {
+        write_docs_file(
+            &man_dir.join(man::module_file_name(&module_docs.name)),
+            man::render_module(PACKAGE_NAME, module_docs),
+        );
+    }
+
+    println!("🎉 Docs generated in {}", man_dir.display());
+}
+
+fn write_docs_file(path: &Path, contents: impl AsRef<[u8]>) {
+    fs::write(path, contents).unwrap_or_else(|error| {
+        panic!(
+            "Attempted to write {} but failed with this error: {}",
+            path.display(),
+            error
+        )
+    });
+}
+
 /// Gives only the module docs for modules that are exposed by the platform or package.
-fn get_exposed_module_docs(
+pub fn get_exposed_module_docs(
     loaded_module: &mut LoadedModule,
 ) -> Vec<(ModuleId, ModuleDocumentation)> {
     let mut exposed_docs = Vec::with_capacity(loaded_module.exposed_modules.len());
@@ -280,9 +400,12 @@ fn render_module_documentation(
     root_module: &LoadedModule,
     all_exposed_symbols: &VecSet<Symbol>,
     maybe_root_dir: Option<&str>,
+    source_url_template: Option<&str>,
 ) -> String {
     let mut buf = String::new();
     let module_name = module.name.as_str();
+    let (source_path, src) = &root_module.sources[&module_id];
+    let line_info = LineInfo::new(src);
 
     push_html(&mut buf, "h2", [("class", "module-name")], {
         let mut link_buf = String::new();
@@ -302,30 +425,29 @@ fn render_module_documentation(
                     let def_name = doc_def.name.as_str();
                     let href = format!("{module_name}#{def_name}");
                     let mut content = String::new();
-                    let mut anno_buf = String::new();
+                    let anno_buf = entry_signature(doc_def);
 
                     push_html(&mut content, "a", [("href", href.as_str())], LINK_SVG);
-                    // push_html(&mut content, "strong", [], def_name);
-                    anno_buf.push_str(def_name);
-
-                    for type_var in &doc_def.type_vars {
-                        anno_buf.push(' ');
-                        anno_buf.push_str(type_var.as_str());
-                    }
-
-                    let type_ann = &doc_def.type_annotation;
-
-                    if !matches!(type_ann, TypeAnnotation::NoTypeAnn) {
-                        // Ability declarations don't have ":" after the name, just `implements`
-                        if !matches!(type_ann, TypeAnnotation::Ability { .. }) {
-                            anno_buf.push_str(" :");
-                        }
+                    content.push_str(highlight_roc_code_inline(anno_buf.as_str()).as_str());
 
-                        anno_buf.push(' ');
+                    let line = line_info.convert_pos(doc_def.region.start()).line + 1;
+                    let source_href = source_href(
+                        source_url_template,
+                        module_name,
+                        &relative_source_path(&root_module.filename, source_path),
+                        line,
+                    );
 
-                        type_annotation_to_html(0, &mut anno_buf, type_ann, false);
-                    }
-                    content.push_str(highlight_roc_code_inline(anno_buf.as_str()).as_str());
+                    push_html(
+                        &mut content,
+                        "a",
+                        [
+                            ("href", source_href.as_str()),
+                            ("class", "source-link"),
+                            ("title", "View source"),
+                        ],
+                        "Source",
+                    );
 
                     push_html(
                         &mut buf,
@@ -377,6 +499,115 @@ fn render_module_documentation(
     buf
 }
 
+/// The name of a documented entry with its type variables and type annotation, as plain text.
+fn entry_signature(doc_def: &DocDef) -> String {
+    let mut buf = String::new();
+
+    buf.push_str(doc_def.name.as_str());
+
+    for type_var in &doc_def.type_vars {
+        buf.push(' ');
+        buf.push_str(type_var.as_str());
+    }
+
+    let type_ann = &doc_def.type_annotation;
+
+    if !matches!(type_ann, TypeAnnotation::NoTypeAnn) {
+        // Ability declarations don't have ":" after the name, just `implements`
+        if !matches!(type_ann, TypeAnnotation::Ability { .. }) {
+            buf.push_str(" :");
+        }
+
+        buf.push(' ');
+
+        type_annotation_to_html(0, &mut buf, type_ann, false);
+    }
+
+    buf
+}
+
+/// The highlighted source of a module, with a numbered anchor on each line for the "Source" links
+/// to point to.
+fn render_source(module_name: &str, src: &str) -> String {
+    let mut buf = String::new();
+
+    push_html(&mut buf, "h2", [("class", "module-name")], {
+        let mut link_buf = String::new();
+
+        push_html(
+            &mut link_buf,
+            "a",
+            [("href", module_name.replace('.', "/").as_str())],
+            module_name,
+        );
+
+        link_buf
+    });
+
+    let mut lines_buf = String::new();
+
+    for (index, line) in roc_highlight::highlight_lines(src).iter().enumerate() {
+        let line_number = (index + 1).to_string();
+        let id = format!("L{line_number}");
+        let mut line_buf = String::new();
+
+        push_html(
+            &mut line_buf,
+            "a",
+            [
+                ("href", format!("#{id}").as_str()),
+                ("class", "line-number"),
+            ],
+            line_number.as_str(),
+        );
+        line_buf.push_str(line);
+
+        push_html(
+            &mut lines_buf,
+            "span",
+            [("id", id.as_str()), ("class", "source-line")],
+            line_buf,
+        );
+        lines_buf.push('\n');
+    }
+
+    push_html(&mut buf, "pre", [("class", "source")], {
+        let mut samp_buf = String::new();
+
+        push_html(&mut samp_buf, "samp", [], lines_buf);
+
+        samp_buf
+    });
+
+    buf
+}
+
+/// Where the "Source" link of an entry on `line` of a module points: `source_url_template` with
+/// `{path}` and `{line}` filled in, or else that line of the module's highlighted source page.
+fn source_href(
+    source_url_template: Option<&str>,
+    module_name: &str,
+    relative_path: &str,
+    line: u32,
+) -> String {
+    match source_url_template {
+        Some(template) => template
+            .replace("{path}", relative_path)
+            .replace("{line}", &line.to_string()),
+        None => format!("{}/{SOURCE_DIR}#L{line}", module_name.replace('.', "/")),
+    }
+}
+
+/// The path of a module's source file, relative to the directory of the package's main .roc file.
+fn relative_source_path(root_file: &Path, path: &Path) -> String {
+    let root_dir = root_file.parent().unwrap_or(Path::new(""));
+
+    path.strip_prefix(root_dir)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
 fn push_html<'a, 'b, I>(buf: &mut String, tag_name: &str, attrs: I, content: impl AsRef<str>)
 where
     I: IntoIterator<Item = (&'a str, &'b str)>,
@@ -579,7 +810,15 @@ fn render_search_type_ahead<'a, I: Iterator<Item = &'a ModuleDocumentation>>(mod
                         &entry_contents_buf,
                     );
 
-                    push_html(&mut buf, "li", [("role", "option")], &anchor_buf);
+                    match search::search_signature(&doc_def.type_annotation) {
+                        Some(signature) => push_html(
+                            &mut buf,
+                            "li",
+                            [("role", "option"), ("data-signature", signature.as_str())],
+                            &anchor_buf,
+                        ),
+                        None => push_html(&mut buf, "li", [("role", "option")], &anchor_buf),
+                    }
                 }
             }
         }
@@ -676,6 +915,15 @@ fn llm_docs<'a, I: Iterator<Item = &'a ModuleDocumentation>>(modules: I) -> Stri
     buf
 }
 
+/// Renders a type annotation on a single line, like in `llms.txt`.
+pub fn type_annotation_to_string(type_ann: &TypeAnnotation) -> String {
+    let mut buf = String::new();
+
+    type_annotation_to_html(0, &mut buf, type_ann, false);
+
+    compact_type_annotation(&buf)
+}
+
 // Helper function to llm_docs
 fn compact_type_annotation(annotation: &str) -> String {
     if annotation.contains("implements\n") {
@@ -1481,3 +1729,53 @@ fn report_markdown_link_problem(
 
     report.render_color_terminal(&mut buf, &alloc, &palette);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn source_url_template() {
+        assert_eq!(
+            source_href(
+                Some("https://example.com/blob/main/{path}#L{line}"),
+                "Json.Decode",
+                "Json/Decode.roc",
+                12,
+            ),
+            "https://example.com/blob/main/Json/Decode.roc#L12"
+        );
+    }
+
+    #[test]
+    fn source_page_link() {
+        assert_eq!(
+            source_href(None, "Json.Decode", "Json/Decode.roc", 12),
+            "Json/Decode/source#L12"
+        );
+    }
+
+    #[test]
+    fn relative_source_path_of_nested_module() {
+        assert_eq!(
+            relative_source_path(Path::new("pkg/main.roc"), Path::new("pkg/Json/Decode.roc")),
+            "Json/Decode.roc"
+        );
+        assert_eq!(
+            relative_source_path(Path::new("main.roc"), Path::new("Json/Decode.roc")),
+            "Json/Decode.roc"
+        );
+    }
+
+    #[test]
+    fn source_lines_are_numbered() {
+        let html = render_source("Json.Decode", "x = 1\ny = 2\n");
+
+        // The trailing newline doesn't start a third line.
+        assert_eq!(html.matches("class=\"source-line\"").count(), 2);
+        assert!(html.contains(concat!(
+            r##"<span id="L2" class="source-line" ><a href="#L2" class="line-number" >2</a>"##,
+            r#"<span class="lowerident">y</span><span class="kw"> =</span><span class="literal"> 2</span></span>"#
+        )));
+    }
+}
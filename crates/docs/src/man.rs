@@ -0,0 +1,268 @@
+//! The output of `roc docs --format=man`: a man page (in section 3, for libraries) per module, for
+//! reading docs in a terminal.
+use crate::entry_signature;
+use pulldown_cmark::{Event, Tag};
+use roc_collections::VecSet;
+use roc_load::docs::{DocEntry, ModuleDocumentation};
+use roc_module::symbol::Symbol;
+
+/// The directory the man pages go in, so `man -M <docs dir> <module>` finds them.
+pub(crate) const MAN_DIR: &str = "man3";
+
+pub(crate) fn module_file_name(module_name: &str) -> String {
+    format!("{module_name}.3")
+}
+
+pub(crate) fn render_module(package_name: &str, module: &ModuleDocumentation) -> String {
+    render_entries(
+        package_name,
+        &module.name,
+        &module.entries,
+        &module.exposed_symbols,
+    )
+}
+
+fn render_entries(
+    package_name: &str,
+    module_name: &str,
+    entries: &[DocEntry],
+    exposed_symbols: &VecSet<Symbol>,
+) -> String {
+    let mut roff = Roff::default();
+
+    roff.request(&format!(
+        ".TH \"{}\" 3 \"\" \"{}\" \"Roc Documentation\"",
+        escape_quoted(module_name),
+        escape_quoted(package_name)
+    ));
+    roff.request(".SH NAME");
+    roff.text(module_name);
+    roff.request(".SH DESCRIPTION");
+
+    for entry in entries {
+        match entry {
+            DocEntry::DocDef(doc_def) => {
+                // Only render entries that are exposed
+                if exposed_symbols.contains(&doc_def.symbol) {
+                    roff.request(&format!(".SS \"{}\"", escape_quoted(&doc_def.name)));
+                    roff.preformatted(&entry_signature(doc_def));
+
+                    if let Some(docs) = &doc_def.docs {
+                        roff.markdown(docs);
+                    }
+                }
+            }
+            DocEntry::ModuleDoc(docs) | DocEntry::DetachedDoc(docs) => {
+                roff.markdown(docs);
+            }
+        }
+    }
+
+    roff.buf
+}
+
+/// Builds a page of roff, the markup language of man pages.
+#[derive(Default)]
+struct Roff {
+    buf: String,
+}
+
+impl Roff {
+    fn at_line_start(&self) -> bool {
+        self.buf.is_empty() || self.buf.ends_with('\n')
+    }
+
+    /// Writes a request like `.PP` on a line of its own.
+    fn request(&mut self, request: &str) {
+        if !self.at_line_start() {
+            self.buf.push('\n');
+        }
+
+        self.buf.push_str(request);
+        self.buf.push('\n');
+    }
+
+    /// Writes text that roff fills into lines, escaping anything roff would interpret.
+    fn text(&mut self, text: &str) {
+        for (index, line) in text.split('\n').enumerate() {
+            if index > 0 {
+                self.buf.push('\n');
+            }
+
+            // A `.` or `'` at the start of a line would make it a request.
+            if self.at_line_start() && (line.starts_with('.') || line.starts_with('\'')) {
+                self.buf.push_str("\\&");
+            }
+
+            self.buf.push_str(&line.replace('\\', "\\e"));
+        }
+    }
+
+    /// Writes code, keeping its line breaks and indentation.
+    fn preformatted(&mut self, code: &str) {
+        self.request(".PP");
+        self.request(".RS 4");
+        self.request(".nf");
+        self.text(code.trim_end_matches('\n'));
+        self.request(".fi");
+        self.request(".RE");
+    }
+
+    fn markdown(&mut self, markdown: &str) {
+        let mut list_numbers: Vec<Option<u64>> = Vec::new();
+        let mut code_block: Option<String> = None;
+        let mut at_item_start = false;
+
+        for event in pulldown_cmark::Parser::new(markdown) {
+            match event {
+                Event::Start(Tag::Paragraph) => {
+                    // The first paragraph of a list item goes right after its bullet.
+                    if !at_item_start {
+                        self.request(if list_numbers.is_empty() {
+                            ".PP"
+                        } else {
+                            ".IP"
+                        });
+                    }
+                }
+                Event::Start(Tag::Heading(..)) => self.request(".SS"),
+                Event::Start(Tag::BlockQuote) => self.request(".RS 4"),
+                Event::End(Tag::BlockQuote) => self.request(".RE"),
+                Event::Start(Tag::CodeBlock(_)) => code_block = Some(String::new()),
+                Event::End(Tag::CodeBlock(_)) => {
+                    if let Some(code) = code_block.take() {
+                        self.preformatted(&code);
+                    }
+                }
+                Event::Start(Tag::List(first_number)) => {
+                    if !list_numbers.is_empty() {
+                        self.request(".RS 4");
+                    }
+
+                    list_numbers.push(first_number);
+                }
+                Event::End(Tag::List(_)) => {
+                    list_numbers.pop();
+
+                    if !list_numbers.is_empty() {
+                        self.request(".RE");
+                    }
+                }
+                Event::Start(Tag::Item) => {
+                    match list_numbers.last_mut() {
+                        Some(Some(number)) => {
+                            self.request(&format!(".IP {number}. 4"));
+                            *number += 1;
+                        }
+                        _ => self.request(".IP \\(bu 2"),
+                    }
+
+                    at_item_start = true;
+                    continue;
+                }
+                Event::Start(Tag::Emphasis) => self.buf.push_str("\\fI"),
+                Event::Start(Tag::Strong) => self.buf.push_str("\\fB"),
+                Event::End(Tag::Emphasis | Tag::Strong) => self.buf.push_str("\\fR"),
+                Event::End(Tag::Link(_, url, _)) => {
+                    if !url.is_empty() {
+                        self.text(&format!(" <{url}>"));
+                    }
+                }
+                Event::Code(code) => {
+                    self.buf.push_str("\\fB");
+                    self.text(&code);
+                    self.buf.push_str("\\fR");
+                }
+                Event::Text(text) => match code_block.as_mut() {
+                    Some(code) => code.push_str(&text),
+                    None => self.text(&text),
+                },
+                Event::Html(html) => self.text(&html),
+                Event::SoftBreak => self.buf.push('\n'),
+                Event::HardBreak => self.request(".br"),
+                Event::Rule => self.request(".PP"),
+                _ => {}
+            }
+
+            at_item_start = false;
+        }
+    }
+}
+
+/// Escapes an argument of a request, which is in double quotes.
+fn escape_quoted(text: &str) -> String {
+    text.replace('\\', "\\e").replace('"', "\\(dq")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    fn roff_from_markdown(markdown: &str) -> String {
+        let mut roff = Roff::default();
+
+        roff.markdown(markdown);
+
+        roff.buf
+    }
+
+    #[test]
+    fn paragraphs_and_inline_markup() {
+        assert_eq!(
+            roff_from_markdown(indoc!(
+                r#"
+                Joins *all* the strings with `sep`.
+
+                .5 is **not** a \n valid [number](https://roc-lang.org).
+                "#
+            )),
+            indoc!(
+                r#"
+                .PP
+                Joins \fIall\fR the strings with \fBsep\fR.
+                .PP
+                \&.5 is \fBnot\fR a \en valid number <https://roc-lang.org>."#
+            )
+        );
+    }
+
+    #[test]
+    fn lists_and_code_blocks() {
+        assert_eq!(
+            roff_from_markdown(indoc!(
+                r#"
+                Steps:
+
+                1. First
+                2. Second
+                    - nested
+
+                ```roc
+                Str.concat "a" "b"
+                ```
+                "#
+            )),
+            indoc!(
+                r#"
+                .PP
+                Steps:
+                .IP 1. 4
+                First
+                .IP 2. 4
+                Second
+                .RS 4
+                .IP \(bu 2
+                nested
+                .RE
+                .PP
+                .RS 4
+                .nf
+                Str.concat "a" "b"
+                .fi
+                .RE
+                "#
+            )
+        );
+    }
+}
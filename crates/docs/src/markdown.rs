@@ -0,0 +1,142 @@
+//! The output of `roc docs --format=markdown`: one Markdown file per module, for wikis and other
+//! places that render Markdown themselves.
+use crate::entry_signature;
+use roc_collections::VecSet;
+use roc_load::docs::{DocEntry, ModuleDocumentation};
+use roc_module::symbol::Symbol;
+
+/// The file name of a module's Markdown docs.
+pub(crate) fn module_file_name(module_name: &str) -> String {
+    format!("{module_name}.md")
+}
+
+/// A list of links to the docs of each module.
+pub(crate) fn render_index<'a>(
+    package_name: &str,
+    modules: impl Iterator<Item = &'a ModuleDocumentation>,
+) -> String {
+    let mut buf = format!("# {package_name}\n\n## Exposed Modules\n\n");
+
+    for module in modules {
+        let name = module.name.as_str();
+
+        buf.push_str(&format!("- [{name}]({})\n", module_file_name(name)));
+    }
+
+    buf
+}
+
+pub(crate) fn render_module(module: &ModuleDocumentation) -> String {
+    render_entries(&module.name, &module.entries, &module.exposed_symbols)
+}
+
+fn render_entries(
+    module_name: &str,
+    entries: &[DocEntry],
+    exposed_symbols: &VecSet<Symbol>,
+) -> String {
+    let mut buf = format!("# {module_name}\n");
+
+    for entry in entries {
+        match entry {
+            DocEntry::DocDef(doc_def) => {
+                // Only render entries that are exposed
+                if exposed_symbols.contains(&doc_def.symbol) {
+                    buf.push_str(&format!(
+                        "\n## {}\n\n```roc\n{}\n```\n",
+                        doc_def.name,
+                        entry_signature(doc_def)
+                    ));
+
+                    if let Some(docs) = &doc_def.docs {
+                        push_docs(&mut buf, docs);
+                    }
+                }
+            }
+            DocEntry::ModuleDoc(docs) | DocEntry::DetachedDoc(docs) => {
+                push_docs(&mut buf, docs);
+            }
+        }
+    }
+
+    buf
+}
+
+/// Doc comments are Markdown already, so they only need to be separated from what's around them.
+fn push_docs(buf: &mut String, docs: &str) {
+    buf.push('\n');
+    buf.push_str(docs.trim_end());
+    buf.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use roc_load::docs::{DocDef, TypeAnnotation};
+    use roc_region::all::Region;
+
+    #[test]
+    fn renders_exposed_entries() {
+        let doc_def = |name: &str, symbol, docs: Option<&str>| {
+            DocEntry::DocDef(DocDef {
+                name: name.to_string(),
+                symbol,
+                type_vars: Vec::new(),
+                type_annotation: TypeAnnotation::Function {
+                    args: vec![TypeAnnotation::Apply {
+                        name: "Str".to_string(),
+                        parts: Vec::new(),
+                    }],
+                    arrow: roc_parse::ast::FunctionArrow::Pure,
+                    output: Box::new(TypeAnnotation::Apply {
+                        name: "U64".to_string(),
+                        parts: Vec::new(),
+                    }),
+                },
+                docs: docs.map(str::to_string),
+                region: Region::zero(),
+            })
+        };
+
+        let entries = [
+            DocEntry::ModuleDoc("Functions on strings.\n".to_string()),
+            doc_def(
+                "count_utf8_bytes",
+                Symbol::STR_COUNT_UTF8_BYTES,
+                Some("Counts the bytes.\n\n```roc\nStr.count_utf8_bytes \"a\"\n```\n"),
+            ),
+            doc_def("is_empty", Symbol::STR_IS_EMPTY, None),
+            DocEntry::DetachedDoc("## Conversions\n".to_string()),
+        ];
+
+        let mut exposed_symbols = VecSet::default();
+        exposed_symbols.insert(Symbol::STR_COUNT_UTF8_BYTES);
+
+        assert_eq!(
+            render_entries("Str", &entries, &exposed_symbols),
+            indoc!(
+                r#"
+                # Str
+
+                Functions on strings.
+
+                ## count_utf8_bytes
+
+                ```roc
+                count_utf8_bytes : Str -> U64
+                ```
+
+                Counts the bytes.
+
+                ```roc
+                Str.count_utf8_bytes "a"
+                ```
+
+                ## Conversions
+                "#
+            )
+            .trim_start()
+        );
+    }
+}
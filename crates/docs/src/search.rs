@@ -0,0 +1,144 @@
+//! The index behind searching the generated docs by type signature, e.g. for
+//! `List a, (a -> b) -> List b`.
+//!
+//! Each function in the search type-ahead gets a `data-signature` attribute with its type in a
+//! simplified, single-line form of Roc's type syntax, which `search.js` parses with the same
+//! parser it uses for the query. The matching itself (type variables up to renaming, arguments in
+//! any order) happens in `search.js`.
+use roc_load::docs::TypeAnnotation;
+
+/// The signature to match type queries against, or `None` if the annotation isn't a function.
+pub(crate) fn search_signature(type_ann: &TypeAnnotation) -> Option<String> {
+    match type_ann {
+        TypeAnnotation::Function { .. } => {
+            let mut buf = String::new();
+
+            push_type(&mut buf, type_ann);
+
+            Some(buf)
+        }
+        TypeAnnotation::Where { ann, .. } | TypeAnnotation::As { ann, .. } => search_signature(ann),
+        _ => None,
+    }
+}
+
+fn push_type(buf: &mut String, type_ann: &TypeAnnotation) {
+    match type_ann {
+        TypeAnnotation::Function { args, output, .. } => {
+            for (index, arg) in args.iter().enumerate() {
+                if index > 0 {
+                    buf.push_str(", ");
+                }
+
+                push_arg(buf, arg);
+            }
+
+            // Searching doesn't distinguish effectful functions from pure ones.
+            buf.push_str(" -> ");
+
+            push_arg(buf, output);
+        }
+        TypeAnnotation::Apply { name, parts } => {
+            // Queries rarely qualify their types, so neither does the index.
+            buf.push_str(name.rsplit('.').next().unwrap_or(name));
+
+            for part in parts {
+                buf.push(' ');
+
+                match part {
+                    TypeAnnotation::Apply { parts, .. } if !parts.is_empty() => {
+                        push_parenthesized(buf, part)
+                    }
+                    _ => push_arg(buf, part),
+                }
+            }
+        }
+        TypeAnnotation::BoundVariable(name) => buf.push_str(name),
+        // Records, tag unions and tuples only match their own kind, whatever is in them.
+        TypeAnnotation::Record { .. } | TypeAnnotation::ObscuredRecord => buf.push_str("{}"),
+        TypeAnnotation::TagUnion { .. } | TypeAnnotation::ObscuredTagUnion => buf.push_str("[]"),
+        TypeAnnotation::Tuple { .. } => buf.push_str("()"),
+        TypeAnnotation::Where { ann, .. } | TypeAnnotation::As { ann, .. } => push_type(buf, ann),
+        TypeAnnotation::Wildcard | TypeAnnotation::NoTypeAnn | TypeAnnotation::Ability { .. } => {
+            buf.push('*')
+        }
+    }
+}
+
+fn push_arg(buf: &mut String, type_ann: &TypeAnnotation) {
+    match type_ann {
+        TypeAnnotation::Function { .. } => push_parenthesized(buf, type_ann),
+        _ => push_type(buf, type_ann),
+    }
+}
+
+fn push_parenthesized(buf: &mut String, type_ann: &TypeAnnotation) {
+    buf.push('(');
+    push_type(buf, type_ann);
+    buf.push(')');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use roc_parse::ast::FunctionArrow;
+
+    fn apply(name: &str, parts: Vec<TypeAnnotation>) -> TypeAnnotation {
+        TypeAnnotation::Apply {
+            name: name.to_string(),
+            parts,
+        }
+    }
+
+    fn var(name: &str) -> TypeAnnotation {
+        TypeAnnotation::BoundVariable(name.to_string())
+    }
+
+    fn function(args: Vec<TypeAnnotation>, output: TypeAnnotation) -> TypeAnnotation {
+        TypeAnnotation::Function {
+            args,
+            arrow: FunctionArrow::Pure,
+            output: Box::new(output),
+        }
+    }
+
+    #[test]
+    fn list_map() {
+        let map = function(
+            vec![
+                apply("List", vec![var("a")]),
+                function(vec![var("a")], var("b")),
+            ],
+            apply("List", vec![var("b")]),
+        );
+
+        assert_eq!(
+            search_signature(&map).as_deref(),
+            Some("List a, (a -> b) -> List b")
+        );
+    }
+
+    #[test]
+    fn nested_applies_and_qualified_names() {
+        let walk = function(
+            vec![
+                apply("Dict.Dict", vec![var("k"), apply("List", vec![var("v")])]),
+                TypeAnnotation::Record {
+                    fields: Vec::new(),
+                    extension: Box::new(TypeAnnotation::NoTypeAnn),
+                },
+            ],
+            apply("Num.U64", Vec::new()),
+        );
+
+        assert_eq!(
+            search_signature(&walk).as_deref(),
+            Some("Dict k (List v), {} -> U64")
+        );
+    }
+
+    #[test]
+    fn values_are_not_indexed() {
+        assert_eq!(search_signature(&apply("Str", Vec::new())), None);
+    }
+}
@@ -28,6 +28,245 @@ const setupSidebarNav = () => {
   });
 };
 
+// Searching by type signature, like `List a, (a -> b) -> List b`. The docs
+// generator puts the signature of every function in the type-ahead in a
+// `data-signature` attribute, in the same syntax as queries, so both go
+// through `parseType`.
+
+const isTypeQuery = (text) => text.includes("->") || text.includes("=>");
+
+const parseType = (text) => {
+  const tokens =
+    text.match(/->|=>|[A-Za-z_][A-Za-z0-9_.]*|\*|[(){}[\],]/g) ?? [];
+  let pos = 0;
+
+  const peek = () => tokens[pos];
+  const next = () => tokens[pos++];
+  const fail = () => {
+    throw new Error(`Unexpected ${peek() ?? "end of input"} in type`);
+  };
+
+  // We only match records, tag unions and tuples by their kind, so we skip
+  // what's in them.
+  const skipBalanced = () => {
+    let depth = 1;
+    while (depth > 0) {
+      const token = next();
+      if (token === undefined) {
+        fail();
+      } else if ("{[(".includes(token)) {
+        depth++;
+      } else if ("}])".includes(token)) {
+        depth--;
+      }
+    }
+  };
+
+  const startsAtom = (token) =>
+    token !== undefined && (/^[A-Za-z_]/.test(token) || "({[*".includes(token));
+
+  const parseAtom = () => {
+    const token = next();
+
+    if (token === "(") {
+      const inner = parseFunction(true);
+      if (next() !== ")") {
+        fail();
+      }
+      return inner;
+    } else if (token === "{") {
+      skipBalanced();
+      return { kind: "record" };
+    } else if (token === "[") {
+      skipBalanced();
+      return { kind: "tag" };
+    } else if (token === "*" || token === "_") {
+      return { kind: "any" };
+    } else if (token !== undefined && /^[A-Za-z]/.test(token)) {
+      // Queries rarely qualify their types, so neither does the index.
+      const name = token.split(".").pop();
+      return /^[A-Z]/.test(name)
+        ? { kind: "app", name, args: [] }
+        : { kind: "var", name };
+    } else {
+      pos--;
+      fail();
+    }
+  };
+
+  const parseApply = () => {
+    const head = parseAtom();
+    if (head.kind === "app") {
+      while (startsAtom(peek())) {
+        head.args.push(parseAtom());
+      }
+    }
+    return head;
+  };
+
+  const parseFunction = (inParens) => {
+    const args = [parseApply()];
+    while (peek() === ",") {
+      next();
+      args.push(parseApply());
+    }
+
+    if (peek() === "->" || peek() === "=>") {
+      // Effectful functions match pure ones and vice versa.
+      next();
+      return { kind: "fn", args, ret: parseApply() };
+    } else if (args.length === 1) {
+      return args[0];
+    } else if (inParens) {
+      return { kind: "tuple" };
+    } else {
+      fail();
+    }
+  };
+
+  try {
+    const type = parseFunction(false);
+    return pos === tokens.length ? type : null;
+  } catch {
+    return null;
+  }
+};
+
+// What a type looks like with its type variables left out, so arguments can be
+// paired up by shape instead of trying every order of them.
+const shapeOf = (type) => {
+  switch (type.kind) {
+    case "var":
+    case "any":
+      return "_";
+    case "app":
+      return `${type.name}(${type.args.map(shapeOf).join(",")})`;
+    case "fn":
+      return `(${type.args.map(shapeOf).sort().join(",")}->${shapeOf(type.ret)})`;
+    default:
+      return type.kind;
+  }
+};
+
+// Puts the signature's arguments in the order of the query's, pairing each
+// query argument with a signature argument of the same shape, or returns null
+// if the arguments don't have the same shapes. A `*` argument on either side
+// pairs with whatever is left over. Arguments of the same shape are paired in
+// the order they appear.
+const pairArgs = (queryArgs, signatureArgs) => {
+  const byShape = new Map();
+  const signatureWildcards = [];
+
+  for (const arg of signatureArgs) {
+    if (arg.kind === "any") {
+      signatureWildcards.push(arg);
+    } else {
+      const shape = shapeOf(arg);
+      byShape.set(shape, [...(byShape.get(shape) ?? []), arg]);
+    }
+  }
+
+  const paired = queryArgs.map((arg) =>
+    arg.kind === "any"
+      ? undefined
+      : (byShape.get(shapeOf(arg))?.shift() ?? signatureWildcards.shift()),
+  );
+
+  const isUnpaired = (arg, index) =>
+    arg === undefined && queryArgs[index].kind !== "any";
+
+  if (paired.some(isUnpaired)) {
+    return null;
+  }
+
+  const leftovers = [...byShape.values()].flat().concat(signatureWildcards);
+
+  return paired.map((arg) => arg ?? leftovers.shift());
+};
+
+// Returns the type variable renaming under which `query` matches `signature`,
+// or null if there is none. Function arguments may be in any order.
+const matchType = (query, signature, renaming) => {
+  if (query.kind === "any" || signature.kind === "any") {
+    return renaming;
+  }
+
+  switch (query.kind) {
+    case "var": {
+      if (signature.kind !== "var") {
+        return null;
+      }
+
+      const renamedQuery = renaming.queryToSignature.get(query.name);
+      const renamedSignature = renaming.signatureToQuery.get(signature.name);
+
+      if (renamedQuery === undefined && renamedSignature === undefined) {
+        return {
+          queryToSignature: new Map(renaming.queryToSignature).set(
+            query.name,
+            signature.name,
+          ),
+          signatureToQuery: new Map(renaming.signatureToQuery).set(
+            signature.name,
+            query.name,
+          ),
+        };
+      }
+
+      return renamedQuery === signature.name && renamedSignature === query.name
+        ? renaming
+        : null;
+    }
+    case "app":
+      return signature.kind === "app" &&
+        signature.name === query.name &&
+        signature.args.length === query.args.length
+        ? matchTypes(query.args, signature.args, renaming)
+        : null;
+    case "fn":
+      if (
+        signature.kind !== "fn" ||
+        signature.args.length !== query.args.length
+      ) {
+        return null;
+      }
+
+      const args = pairArgs(query.args, signature.args);
+      const argsRenaming = args && matchTypes(query.args, args, renaming);
+
+      return argsRenaming && matchType(query.ret, signature.ret, argsRenaming);
+    default:
+      return query.kind === signature.kind ? renaming : null;
+  }
+};
+
+const matchTypes = (queries, signatures, renaming) =>
+  queries.reduce(
+    (renaming, query, index) =>
+      renaming && matchType(query, signatures[index], renaming),
+    renaming,
+  );
+
+const parsedSignatures = new Map();
+
+const matchesTypeQuery = (query, signatureText) => {
+  if (signatureText === undefined) {
+    return false;
+  }
+
+  if (!parsedSignatures.has(signatureText)) {
+    parsedSignatures.set(signatureText, parseType(signatureText));
+  }
+
+  const signature = parsedSignatures.get(signatureText);
+  const renaming = {
+    queryToSignature: new Map(),
+    signatureToQuery: new Map(),
+  };
+
+  return signature !== null && matchType(query, signature, renaming) !== null;
+};
+
 const setupSearch = () => {
   let searchTypeAhead = document.getElementById("search-type-ahead");
   let searchBox = document.getElementById("module-search");
@@ -116,6 +355,9 @@ const setupSearch = () => {
     function search() {
       topSearchResultListItem = undefined;
       let text = searchBox.value.toLowerCase(); // Search is case-insensitive.
+      // Type names are capitalized, so type signature search is case-sensitive.
+      // While the query doesn't parse yet (e.g. `List a ->`), search by name.
+      let typeQuery = isTypeQuery(text) ? parseType(searchBox.value) : null;
 
       if (text === "") {
         searchTypeAhead.classList.add("hidden");
@@ -136,10 +378,13 @@ const setupSearch = () => {
 
           const qualifiedEntryName = `${entryModule}.${entryName}`;
 
-          if (
-            qualifiedEntryName.includes(text) ||
-            entrySignature?.includes(text.replace(/\s+/g, ""))
-          ) {
+          const isMatch =
+            typeQuery !== null
+              ? matchesTypeQuery(typeQuery, entry.dataset.signature)
+              : qualifiedEntryName.includes(text) ||
+                entrySignature?.includes(text.replace(/\s+/g, ""));
+
+          if (isMatch) {
             totalResults++;
             entry.classList.remove("hidden");
             if (topSearchResultListItem === undefined) {
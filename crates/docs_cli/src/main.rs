@@ -23,6 +23,7 @@ fn main() -> io::Result<()> {
         matches.get_one::<PathBuf>(ROC_FILE).unwrap().to_owned(),
         &PathBuf::from("./generated-docs"),
         std::env::var("ROC_DOCS_URL_ROOT").ok(),
+        std::env::var("ROC_DOCS_SOURCE_URL").ok(),
     );
 
     Ok(())
@@ -1,32 +1,242 @@
 app [make_glue] { pf: platform "../platform/main.roc" }
 
 import pf.Types exposing [Types]
-# import pf.Shape exposing [Shape, RocFn]
+import pf.Shape exposing [Shape, RocStructFields]
 import pf.File exposing [File]
-# import pf.TypeId exposing [TypeId]
+import pf.TypeId exposing [TypeId]
+import pf.Target exposing [Architecture]
 
-## generate placeholder glue for now that only works for our one C test
+## generate placeholder glue for now that only works for entry points which take no arguments
+## and return a number or a record of numbers
 make_glue : List Types -> Result (List File) Str
-make_glue = \_types_by_arch ->
-    Ok([{ name: "roc_app.h", content: placeholder_glue }])
+make_glue = \types_by_arch ->
+    when types_by_arch is
+        [first, ..] ->
+            # Every architecture has the same entry points, only their layouts differ.
+            entry_points = Types.entry_points(first)
+            # A struct's fields have smaller TypeIds than the struct, so walking the shapes in
+            # order declares every struct before it's used.
+            type_declarations = Types.walk_shapes(first, "", \buf, shape, _ -> generate_type_declaration(buf, first, shape))
+            declarations = List.walk(entry_points, "", \buf, T(name, id) -> generate_entry_point(buf, first, name, id))
+            layout_checks = List.walk(types_by_arch, "", generate_layout_checks)
 
-placeholder_glue =
+            Ok([{ name: "roc_app.h", content: header(type_declarations, layout_checks, declarations) }])
+
+        [] ->
+            Err("roc glue didn't provide the types for any architecture.")
+
+header = \type_declarations, layout_checks, declarations ->
     """
     #ifndef ROC_APP_H
     #define ROC_APP_H
 
+    #include <stdbool.h>
+    #include <stddef.h>
     #include <stdint.h>
 
-    extern void roc__main_for_host_1_exposed_generic(uint8_t *ret);
+    ${type_declarations}${layout_checks}${declarations}
+    #endif
+    """
 
-    uint8_t roc_main_for_host()
-    {
-        uint8_t ret;
+generate_entry_point : Str, Types, Str, TypeId -> Str
+generate_entry_point = \buf, types, name, id ->
+    ret_type = c_type(types, entry_point_ret(types, name, id))
 
-        roc__main_for_host_1_exposed_generic(&ret);
+    Str.concat(
+        buf,
+        """
+        extern void roc__${name}_1_exposed_generic(${ret_type} *ret);
 
-        return ret;
-    }
+        ${ret_type} roc_${name}()
+        {
+            ${ret_type} ret;
 
-    #endif
+            roc__${name}_1_exposed_generic(&ret);
+
+            return ret;
+        }
+
+
+        """,
+    )
+
+generate_type_declaration : Str, Types, Shape -> Str
+generate_type_declaration = \buf, types, shape ->
+    when shape is
+        Struct({ name, fields }) ->
+            escaped_name = escape_kw(name)
+            field_declarations =
+                List.walk(struct_fields(name, fields), "", \accum, { name: field_name, id: field_id } ->
+                    field_type = c_type(types, field_id)
+                    escaped_field_name = escape_kw(field_name)
+
+                    Str.concat(accum, "    ${field_type} ${escaped_field_name};\n"))
+
+            Str.concat(buf, "typedef struct ${escaped_name} {\n${field_declarations}} ${escaped_name};\n\n")
+
+        _ ->
+            # Every other type the C glue supports is a builtin C type.
+            buf
+
+## Asserts at compile time that the C compiler lays each type out the way Roc does on each
+## architecture, so a mismatch fails the build instead of corrupting memory at runtime.
+generate_layout_checks : Str, Types -> Str
+generate_layout_checks = \buf, types ->
+    arch = (Types.target(types)).architecture
+    arch_str = arch_name(arch)
+    condition = arch_condition(arch)
+
+    struct_checks =
+        Types.walk_shapes(types, "", \accum, shape, id ->
+            when shape is
+                Struct({ name, fields }) -> Str.concat(accum, generate_struct_checks(types, id, name, fields, arch_str))
+                _ -> accum)
+
+    checks =
+        List.walk(Types.entry_points(types), struct_checks, \accum, T(name, id) ->
+            ret_id = entry_point_ret(types, name, id)
+            ret_type = c_type(types, ret_id)
+            size_str = Num.to_str(Types.size(types, ret_id))
+            align_str = Num.to_str(Types.alignment(types, ret_id))
+
+            Str.concat(
+                accum,
+                """
+                _Static_assert(sizeof(${ret_type}) == ${size_str}, "roc_${name} returns ${size_str} bytes on ${arch_str}");
+                _Static_assert(_Alignof(${ret_type}) == ${align_str}, "roc_${name} returns a value aligned to ${align_str} bytes on ${arch_str}");
+
+                """,
+            ))
+
+    Str.concat(buf, "#if ${condition}\n${checks}#endif\n\n")
+
+## Roc puts a struct's fields in the order they appear in its shape, each at the next offset that
+## is a multiple of the field's alignment.
+generate_struct_checks : Types, TypeId, Str, RocStructFields, Str -> Str
+generate_struct_checks = \types, id, name, fields, arch_str ->
+    escaped_name = escape_kw(name)
+    size_str = Num.to_str(Types.size(types, id))
+    align_str = Num.to_str(Types.alignment(types, id))
+
+    offset_checks =
+        List.walk(struct_fields(name, fields), { checks: "", offset: 0 }, \{ checks, offset }, { name: field_name, id: field_id } ->
+            field_offset = next_multiple_of(offset, Types.alignment(types, field_id))
+            field_offset_str = Num.to_str(field_offset)
+            escaped_field_name = escape_kw(field_name)
+
+            {
+                checks: Str.concat(checks, "_Static_assert(offsetof(${escaped_name}, ${escaped_field_name}) == ${field_offset_str}, \"${name}.${field_name} is at offset ${field_offset_str} on ${arch_str}\");\n"),
+                offset: field_offset + Types.size(types, field_id),
+            })
+        |> .checks
+
+    """
+    _Static_assert(sizeof(${escaped_name}) == ${size_str}, "${name} is ${size_str} bytes on ${arch_str}");
+    _Static_assert(_Alignof(${escaped_name}) == ${align_str}, "${name} is aligned to ${align_str} bytes on ${arch_str}");
+    ${offset_checks}
     """
+
+struct_fields = \name, fields ->
+    when fields is
+        HasNoClosure(xs) -> xs
+        HasClosure(_) -> crash("The C glue doesn't support records that contain functions, like ${name}, yet.")
+
+entry_point_ret : Types, Str, TypeId -> TypeId
+entry_point_ret = \types, name, id ->
+    when Types.shape(types, id) is
+        Function(_) -> crash("The C glue doesn't support entry points that take arguments, like ${name}, yet.")
+        _ -> id
+
+c_type : Types, TypeId -> Str
+c_type = \types, id ->
+    when Types.shape(types, id) is
+        Bool -> "bool"
+        Num(U8) -> "uint8_t"
+        Num(U16) -> "uint16_t"
+        Num(U32) -> "uint32_t"
+        Num(U64) -> "uint64_t"
+        Num(I8) -> "int8_t"
+        Num(I16) -> "int16_t"
+        Num(I32) -> "int32_t"
+        Num(I64) -> "int64_t"
+        Num(F32) -> "float"
+        Num(F64) -> "double"
+        Struct({ name }) -> escape_kw(name)
+        _ -> crash("The C glue only supports Bools, numbers up to 64 bits, and records of those, for now.")
+
+## The predefined macros that C compilers (including MSVC) set for each architecture.
+arch_condition : Architecture -> Str
+arch_condition = \arch ->
+    when arch is
+        Aarch32 -> "defined(__arm__) || defined(_M_ARM)"
+        Aarch64 -> "defined(__aarch64__) || defined(_M_ARM64)"
+        Wasm32 -> "defined(__wasm32__)"
+        X86x32 -> "defined(__i386__) || defined(_M_IX86)"
+        X86x64 -> "defined(__x86_64__) || defined(_M_X64)"
+
+arch_name = \arch ->
+    when arch is
+        Aarch32 ->
+            "arm"
+
+        Aarch64 ->
+            "aarch64"
+
+        Wasm32 ->
+            "wasm32"
+
+        X86x32 ->
+            "x86"
+
+        X86x64 ->
+            "x86_64"
+
+next_multiple_of = \lhs, rhs ->
+    when lhs % rhs is
+        0 -> lhs
+        r -> lhs + (rhs - r)
+
+escape_kw = \input ->
+    # C has no raw identifiers, so add an underscore to names that are reserved keywords.
+    if Set.contains(reserved_keywords, input) then
+        "${input}_"
+    else
+        input
+
+reserved_keywords = Set.from_list([
+    "auto",
+    "bool",
+    "break",
+    "case",
+    "char",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extern",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "register",
+    "restrict",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "struct",
+    "switch",
+    "typedef",
+    "union",
+    "unsigned",
+    "void",
+    "volatile",
+    "while",
+])
@@ -0,0 +1,574 @@
+app [make_glue] { pf: platform "../platform/main.roc" }
+
+import pf.Types exposing [Types]
+import pf.Shape exposing [Shape, RocStructFields]
+import pf.File exposing [File]
+import pf.TypeId exposing [TypeId]
+import "../static/python/roc_std.py" as roc_std_py : Str
+import "../static/python/roc_host.c" as roc_host_c : Str
+
+## Generates `roc_app`, a Python package that calls into a Roc shared library (built with
+## `roc build --lib`) through ctypes.
+##
+## It has a module with the platform's types and entry points for each architecture, and
+## `roc_app/__init__.py` picks the right one when it's imported. `roc_host.c` has the functions
+## the shared library needs from its host; its comment says how to build it.
+make_glue : List Types -> Result (List File) Str
+make_glue = \types_by_arch ->
+    native_types = List.keep_if(types_by_arch, \types -> is_native((Types.target(types)).architecture))
+
+    native_types
+    |> List.map(convert_types_to_file)
+    |> List.append({ name: "roc_app/__init__.py", content: init_file(native_types) })
+    |> List.concat(static_files)
+    |> Ok
+
+## These are always included, and don't depend on the specifics of the app.
+static_files : List File
+static_files = [
+    { name: "roc_app/roc_std.py", content: roc_std_py },
+    { name: "roc_host.c", content: roc_host_c },
+]
+
+## Python can't load a wasm32 library, so there's no module for it.
+is_native = \arch ->
+    when arch is
+        Wasm32 -> Bool.false
+        _ -> Bool.true
+
+init_file : List Types -> Str
+init_file = \native_types ->
+    branches =
+        List.walk_with_index(native_types, "", \buf, types, index ->
+            arch = (Types.target(types)).architecture
+            arch_str = arch_name(arch)
+            machines = machine_names(arch)
+            keyword = if index == 0 then "if" else "elif"
+
+            Str.concat(buf, "${keyword} _machine in (${machines}):\n    from .${arch_str} import *  # noqa: F401, F403\n"))
+
+    """
+    # ⚠️ GENERATED CODE ⚠️
+    #
+    # This package is generated by the `roc glue` CLI command.
+    #
+    # Building Dicts and Sets from Python is out of scope: their `from_python` raises TypeError,
+    # because building one needs Roc's hashing. Pass a list to Roc and use Dict.from_list or
+    # Set.from_list there instead.
+
+    import platform
+
+    from .roc_std import Tag, live_allocations, load, owned, release  # noqa: F401
+
+    _machine = platform.machine().lower()
+
+    ${branches}else:
+        raise ImportError(f"roc_app has no module for the {_machine} architecture")
+
+    """
+
+## What `platform.machine()` returns on each architecture, in lowercase.
+machine_names = \arch ->
+    when arch is
+        Aarch32 -> "\"arm\", \"armv6l\", \"armv7l\""
+        Aarch64 -> "\"aarch64\", \"arm64\""
+        Wasm32 -> "\"wasm32\""
+        X86x32 -> "\"i386\", \"i686\", \"x86\""
+        X86x64 -> "\"amd64\", \"x86_64\""
+
+convert_types_to_file : Types -> File
+convert_types_to_file = \types ->
+    arch_str = arch_name((Types.target(types)).architecture)
+
+    declared =
+        Types.walk_shapes(types, { buf: file_header(arch_str), declared: Set.empty({}) }, \state, _shape, id ->
+            declare(state, types, id))
+
+    content =
+        List.walk(Types.entry_points(types), declared.buf, \buf, T(name, id) ->
+            generate_entry_point(buf, types, name, id))
+
+    { name: "roc_app/${arch_str}.py", content }
+
+## Python needs a class to be defined before another class can use it in its fields,
+## so this declares the types a type depends on before the type itself.
+declare : { buf : Str, declared : Set TypeId }, Types, TypeId -> { buf : Str, declared : Set TypeId }
+declare = \state, types, id ->
+    if Set.contains(state.declared, id) then
+        state
+    else
+        shape = Types.shape(types, id)
+        marked = { state & declared: Set.insert(state.declared, id) }
+        with_dependencies = List.walk(dependencies(shape), marked, \accum, dependency -> declare(accum, types, dependency))
+
+        { with_dependencies & buf: Str.concat(with_dependencies.buf, declaration(types, id, shape)) }
+
+dependencies : Shape -> List TypeId
+dependencies = \shape ->
+    when shape is
+        RocList(elem) | RocSet(elem) | RocBox(elem) -> [elem]
+        RocDict(key, value) -> [key, value]
+        RocResult(ok, err) -> [ok, err]
+        Struct({ fields }) | TagUnionPayload({ fields }) -> List.map(struct_fields(fields), \{ id } -> id)
+        TagUnion(NonRecursive({ tags })) -> List.keep_oks(tags, \{ payload } -> payload_id(payload))
+        TagUnion(SingleTagStruct({ payload: HasNoClosure(fields) })) -> List.map(fields, \{ id } -> id)
+        _ -> []
+
+declaration : Types, TypeId, Shape -> Str
+declaration = \types, id, shape ->
+    when shape is
+        Struct({ name, fields }) ->
+            record_fields = struct_fields(fields)
+
+            generate_struct(types, id, name, "RocStruct", record_fields, List.map(record_fields, \{ name: field_name } -> field_name), [])
+
+        TagUnionPayload({ name, fields }) ->
+            payload_fields =
+                struct_fields(fields)
+                |> List.map(\{ name: index, id: field_id } -> { name: "f${index}", id: field_id })
+
+            # The fields are in the order they're stored in, but a payload's values should be in
+            # the order they're written in.
+            in_order =
+                List.map_with_index(payload_fields, \_, index ->
+                    index_str = Num.to_str(index)
+
+                    "f${index_str}")
+
+            generate_struct(types, id, name, "RocTagPayload", payload_fields, in_order, [])
+
+        TagUnion(Enumeration({ name, tags, size })) ->
+            generate_enumeration(name, tags, size)
+
+        TagUnion(NonRecursive({ name, tags, discriminant_size, discriminant_offset })) ->
+            if List.is_empty(tags) then
+                ""
+            else
+                generate_tag_union(types, id, name, tags, discriminant_size, discriminant_offset)
+
+        TagUnion(SingleTagStruct({ name, tag_name, payload: HasNoClosure(fields) })) ->
+            payload_fields =
+                List.map_with_index(fields, \{ id: field_id }, index ->
+                    index_str = Num.to_str(index)
+
+                    { name: "f${index_str}", id: field_id })
+
+            generate_struct(types, id, name, "RocSingleTagUnion", payload_fields, List.map(payload_fields, \{ name: field_name } -> field_name), ["    _roc_tag_ = \"${tag_name}\""])
+
+        TagUnion(SingleTagStruct({ name })) ->
+            unsupported("single-tag unions with functions in them, like ${name}")
+
+        TagUnion(Recursive({ name })) | TagUnion(NullableWrapped({ name })) | TagUnion(NullableUnwrapped({ name })) | TagUnion(NonNullableUnwrapped({ name })) ->
+            unsupported("recursive tag unions, like ${name}")
+
+        Function(roc_fn) ->
+            if roc_fn.is_toplevel then
+                # Entry points become functions at the end of the module.
+                ""
+            else
+                function_name = roc_fn.function_name
+
+                unsupported("functions other than entry points, like ${function_name}")
+
+        Unit
+        | Unsized
+        | EmptyTagUnion
+        | Num(_)
+        | Bool
+        | RocResult(_, _)
+        | RocStr
+        | RocDict(_, _)
+        | RocSet(_)
+        | RocList(_)
+        | RocBox(_)
+        | RecursivePointer(_) ->
+            # These are all in roc_std.py, or (for RecursivePointer) part of an unsupported type.
+            ""
+
+struct_fields : RocStructFields -> List { name : Str, id : TypeId }
+struct_fields = \fields ->
+    when fields is
+        HasNoClosure(no_closure_fields) -> no_closure_fields
+        HasClosure(_) -> unsupported("records with functions in them")
+
+payload_id : [Some TypeId, None] -> Result TypeId [NoPayload]
+payload_id = \payload ->
+    when payload is
+        Some(id) -> Ok(id)
+        None -> Err(NoPayload)
+
+unsupported = \what ->
+    crash("The Python glue doesn't support ${what} yet.")
+
+## A ctypes structure, with explicit padding wherever Roc puts some: Roc aligns some types
+## (like 128-bit integers) more than ctypes does, so ctypes can't be left to work it out.
+generate_struct : Types, TypeId, Str, Str, List { name : Str, id : TypeId }, List Str, List Str -> Str
+generate_struct = \types, id, name, base, fields, field_names, extra_lines ->
+    layout =
+        List.walk(fields, { lines: [], offset: 0 }, \{ lines, offset }, field ->
+            field_offset = next_multiple_of(offset, Types.alignment(types, field.id))
+            field_type = type_name(types, field.id)
+
+            {
+                lines: lines
+                |> pad(offset, field_offset)
+                |> List.append("        (\"${field.name}\", ${field_type}),"),
+                offset: field_offset + Types.size(types, field.id),
+            })
+
+    class_lines(name, base, pad(layout.lines, layout.offset, Types.size(types, id)))
+    |> List.concat([
+        "    _roc_fields_ = ${python_tuple(List.map(field_names, quoted))}",
+        "    _roc_alignment_ = ${alignment_str(types, id)}",
+        "    _roc_refcounted_ = ${python_bool(is_refcounted(types, Types.shape(types, id)))}",
+    ])
+    |> List.concat(extra_lines)
+    |> python_block
+
+generate_enumeration : Str, List Str, U32 -> Str
+generate_enumeration = \name, tags, size ->
+    class_name = escape_kw(name)
+    int_type = unsigned_int_type(size)
+
+    python_block([
+        "class ${class_name}(roc_std.RocEnumeration, ${int_type}):",
+        "    _roc_tags_ = ${python_tuple(List.map(tags, quoted))}",
+    ])
+
+## A ctypes structure with a ctypes union of the payloads, followed by the discriminant.
+generate_tag_union : Types, TypeId, Str, List { name : Str, payload : [Some TypeId, None] }, U32, U32 -> Str
+generate_tag_union = \types, id, name, tags, discriminant_size, discriminant_offset ->
+    payload_union_name = "_${name}Payload"
+    discriminant_type = unsigned_int_type(discriminant_size)
+    discriminant_end = discriminant_offset + discriminant_size
+
+    payload_lines =
+        List.walk(tags, [], \lines, { name: tag_name, payload } ->
+            when payload is
+                Some(payload_type_id) ->
+                    payload_type = type_name(types, payload_type_id)
+
+                    List.append(lines, "        (\"${tag_name}\", ${payload_type}),")
+
+                None ->
+                    lines)
+        # The union has to be as big as Roc's, for the discriminant to come right after it.
+        |> pad(0, discriminant_offset)
+
+    tag_entries =
+        List.map(tags, \{ name: tag_name, payload } ->
+            payload_type =
+                when payload is
+                    Some(payload_type_id) -> type_name(types, payload_type_id)
+                    None -> "None"
+
+            "(\"${tag_name}\", ${payload_type})")
+
+    union_block =
+        ["class ${payload_union_name}(ctypes.Union):", "    _fields_ = ["]
+        |> List.concat(payload_lines)
+        |> List.append("    ]")
+        |> python_block
+
+    tag_union_block =
+        class_lines(name, "RocTagUnion", pad(["        (\"payload\", ${payload_union_name}),", "        (\"discriminant\", ${discriminant_type}),"], discriminant_end, Types.size(types, id)))
+        |> List.concat([
+            "    _roc_tags_ = ${python_tuple(tag_entries)}",
+            "    _roc_alignment_ = ${alignment_str(types, id)}",
+            "    _roc_refcounted_ = ${python_bool(is_refcounted(types, Types.shape(types, id)))}",
+        ])
+        |> python_block
+
+    Str.concat(union_block, tag_union_block)
+
+class_lines : Str, Str, List Str -> List Str
+class_lines = \name, base, field_lines ->
+    class_name = escape_kw(name)
+
+    ["class ${class_name}(roc_std.${base}):", "    _fields_ = ["]
+    |> List.concat(field_lines)
+    |> List.append("    ]")
+
+## Adds a padding field, if the next field's offset is past the end of the previous field.
+pad : List Str, U32, U32 -> List Str
+pad = \lines, end, next_offset ->
+    if next_offset > end then
+        count_str = Num.to_str(List.len(lines))
+        bytes_str = Num.to_str((next_offset - end))
+
+        List.append(lines, "        (\"_padding${count_str}\", ctypes.c_uint8 * ${bytes_str}),")
+    else
+        lines
+
+generate_entry_point : Str, Types, Str, TypeId -> Str
+generate_entry_point = \buf, types, name, id ->
+    (arg_ids, ret_id) =
+        when Types.shape(types, id) is
+            Function(roc_fn) ->
+                # `{}` arguments aren't passed at all.
+                (List.drop_if(roc_fn.args, \arg_id -> is_unit(Types.shape(types, arg_id))), roc_fn.ret)
+
+            _ ->
+                ([], id)
+
+    function_name = escape_kw(name)
+    ret_type =
+        when Types.shape(types, ret_id) is
+            Function(_) -> unsupported("entry points that return functions, like ${name}")
+            _ -> type_name(types, ret_id)
+
+    params =
+        List.map_with_index(arg_ids, \_, index ->
+            index_str = Num.to_str(index)
+
+            "arg${index_str}")
+
+    arg_types =
+        List.map(arg_ids, \arg_id ->
+            arg_type = type_name(types, arg_id)
+
+            if passed_by_value(types, arg_id) then
+                arg_type
+            else
+                "ctypes.POINTER(${arg_type})")
+
+    arg_values =
+        List.map_with_index(arg_ids, \arg_id, index ->
+            arg_type = type_name(types, arg_id)
+            index_str = Num.to_str(index)
+
+            if passed_by_value(types, arg_id) then
+                "roc_std.from_python(${arg_type}, arg${index_str})"
+            else
+                "ctypes.byref(roc_std.from_python(${arg_type}, arg${index_str}))")
+
+    params_str = Str.join_with(params, ", ")
+    arg_types_str = Str.join_with(List.prepend(arg_types, "ctypes.POINTER(${ret_type})"), ", ")
+    arg_values_str = Str.join_with(List.prepend(arg_values, "ctypes.byref(ret)"), ", ")
+
+    Str.concat(
+        buf,
+        python_block([
+            "def ${function_name}(${params_str}):",
+            "    \"\"\"Calls the Roc entry point `${name}`, converting to and from Python values.\"\"\"",
+            "    function = roc_std.library().roc__${name}_1_exposed_generic",
+            "    function.argtypes = [${arg_types_str}]",
+            "    function.restype = None",
+            "",
+            "    # Roc takes ownership of the arguments, and Python of what Roc returns.",
+            "    ret = ${ret_type}()",
+            "    function(${arg_values_str})",
+            "",
+            "    return roc_std.take(ret)",
+        ]),
+    )
+
+## Values without anything reference counted in them are passed by value, and others by pointer.
+passed_by_value : Types, TypeId -> Bool
+passed_by_value = \types, id ->
+    !(is_refcounted(types, Types.shape(types, id)))
+
+is_refcounted : Types, Shape -> Bool
+is_refcounted = \types, shape ->
+    when shape is
+        RocStr | RocList(_) | RocDict(_, _) | RocSet(_) | RocBox(_) | Unsized -> Bool.true
+        RocResult(ok_id, err_id) ->
+            is_refcounted(types, Types.shape(types, ok_id))
+            or is_refcounted(types, Types.shape(types, err_id))
+
+        Struct({ fields }) | TagUnionPayload({ fields }) ->
+            List.any(struct_fields(fields), \{ id } -> is_refcounted(types, Types.shape(types, id)))
+
+        TagUnion(NonRecursive({ tags })) ->
+            List.any(List.keep_oks(tags, \{ payload } -> payload_id(payload)), \id -> is_refcounted(types, Types.shape(types, id)))
+
+        TagUnion(SingleTagStruct({ payload: HasNoClosure(fields) })) ->
+            List.any(fields, \{ id } -> is_refcounted(types, Types.shape(types, id)))
+
+        TagUnion(Enumeration(_)) -> Bool.false
+        # The other tag unions are recursive, so they're on the heap.
+        TagUnion(_) -> Bool.true
+        RecursivePointer(_) | Function(_) -> Bool.true
+        Unit | EmptyTagUnion | Bool | Num(_) -> Bool.false
+
+is_unit : Shape -> Bool
+is_unit = \shape ->
+    when shape is
+        Unit -> Bool.true
+        _ -> Bool.false
+
+type_name : Types, TypeId -> Str
+type_name = \types, id ->
+    when Types.shape(types, id) is
+        Unit | EmptyTagUnion -> "roc_std.RocUnit"
+        RocStr -> "roc_std.RocStr"
+        Bool -> "ctypes.c_bool"
+        Num(U8) -> "ctypes.c_uint8"
+        Num(U16) -> "ctypes.c_uint16"
+        Num(U32) -> "ctypes.c_uint32"
+        Num(U64) -> "ctypes.c_uint64"
+        Num(U128) -> "roc_std.RocU128"
+        Num(I8) -> "ctypes.c_int8"
+        Num(I16) -> "ctypes.c_int16"
+        Num(I32) -> "ctypes.c_int32"
+        Num(I64) -> "ctypes.c_int64"
+        Num(I128) -> "roc_std.RocI128"
+        Num(F32) -> "ctypes.c_float"
+        Num(F64) -> "ctypes.c_double"
+        Num(Dec) -> "roc_std.RocDec"
+        RocList(elem) ->
+            elem_name = type_name(types, elem)
+
+            "roc_std.RocList(${elem_name})"
+
+        RocSet(elem) ->
+            elem_name = type_name(types, elem)
+
+            "roc_std.RocSet(${elem_name})"
+
+        RocBox(elem) ->
+            elem_name = type_name(types, elem)
+
+            "roc_std.RocBox(${elem_name})"
+
+        RocDict(key, value) ->
+            key_name = type_name(types, key)
+            value_name = type_name(types, value)
+
+            "roc_std.RocDict(${key_name}, ${value_name})"
+
+        RocResult(ok, err) ->
+            ok_name = type_name(types, ok)
+            err_name = type_name(types, err)
+
+            "roc_std.RocResult(${ok_name}, ${err_name})"
+
+        Struct({ name }) -> escape_kw(name)
+        TagUnionPayload({ name }) -> escape_kw(name)
+        TagUnion(NonRecursive({ name })) -> escape_kw(name)
+        TagUnion(Recursive({ name })) -> escape_kw(name)
+        TagUnion(Enumeration({ name })) -> escape_kw(name)
+        TagUnion(NullableWrapped({ name })) -> escape_kw(name)
+        TagUnion(NullableUnwrapped({ name })) -> escape_kw(name)
+        TagUnion(NonNullableUnwrapped({ name })) -> escape_kw(name)
+        TagUnion(SingleTagStruct({ name })) -> escape_kw(name)
+        Function({ function_name }) -> unsupported("functions other than entry points, like ${function_name}")
+        RecursivePointer(_) -> unsupported("recursive types")
+        Unsized -> unsupported("unsized types")
+
+unsigned_int_type : U32 -> Str
+unsigned_int_type = \size ->
+    when size is
+        1 -> "ctypes.c_uint8"
+        2 -> "ctypes.c_uint16"
+        4 -> "ctypes.c_uint32"
+        _ -> "ctypes.c_uint64"
+
+alignment_str : Types, TypeId -> Str
+alignment_str = \types, id ->
+    Num.to_str(Types.alignment(types, id))
+
+python_bool : Bool -> Str
+python_bool = \bool ->
+    if bool then "True" else "False"
+
+quoted : Str -> Str
+quoted = \str -> "\"${str}\""
+
+python_tuple : List Str -> Str
+python_tuple = \items ->
+    when items is
+        [only] -> "(${only},)"
+        _ ->
+            joined = Str.join_with(items, ", ")
+
+            "(${joined})"
+
+## Top-level definitions are separated by two blank lines, as PEP 8 recommends.
+python_block : List Str -> Str
+python_block = \lines ->
+    joined = Str.join_with(lines, "\n")
+
+    "\n\n${joined}\n"
+
+file_header = \arch_str ->
+    """
+    # ⚠️ GENERATED CODE ⚠️
+    #
+    # This module is generated by the `roc glue` CLI command, for ${arch_str}.
+
+    import ctypes
+
+    from . import roc_std
+
+    """
+
+arch_name = \arch ->
+    when arch is
+        Aarch32 ->
+            "arm"
+
+        Aarch64 ->
+            "aarch64"
+
+        Wasm32 ->
+            "wasm32"
+
+        X86x32 ->
+            "x86"
+
+        X86x64 ->
+            "x86_64"
+
+next_multiple_of = \lhs, rhs ->
+    if rhs == 0 then
+        lhs
+    else
+        when lhs % rhs is
+            0 -> lhs
+            r -> lhs + (rhs - r)
+
+reserved_keywords = Set.from_list([
+    "False",
+    "None",
+    "True",
+    "and",
+    "as",
+    "assert",
+    "async",
+    "await",
+    "break",
+    "class",
+    "continue",
+    "def",
+    "del",
+    "elif",
+    "else",
+    "except",
+    "finally",
+    "for",
+    "from",
+    "global",
+    "if",
+    "import",
+    "in",
+    "is",
+    "lambda",
+    "nonlocal",
+    "not",
+    "or",
+    "pass",
+    "raise",
+    "return",
+    "try",
+    "while",
+    "with",
+    "yield",
+])
+
+escape_kw = \input ->
+    # Add an underscore after names Python reserves, like PEP 8 suggests.
+    if Set.contains(reserved_keywords, input) then
+        "${input}_"
+    else
+        input
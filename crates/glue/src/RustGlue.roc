@@ -7,7 +7,10 @@ import pf.TypeId exposing [TypeId]
 import "../static/Cargo.toml" as roc_app_cargo_toml : Str
 import "../../roc_std/Cargo.toml" as roc_std_cargo_toml : Str
 import "../../roc_std/src/lib.rs" as roc_std_lib : Str
+import "../../roc_std/src/inspect.rs" as roc_std_inspect : Str
 import "../../roc_std/src/roc_box.rs" as roc_std_box : Str
+import "../../roc_std/src/roc_dict.rs" as roc_std_dict : Str
+import "../../roc_std/src/roc_hash.rs" as roc_std_hash : Str
 import "../../roc_std/src/roc_list.rs" as roc_std_list : Str
 import "../../roc_std/src/roc_str.rs" as roc_std_str : Str
 import "../../roc_std/src/storage.rs" as roc_std_storage : Str
@@ -42,7 +45,10 @@ static_files = [
     { name: "roc_app/Cargo.toml", content: roc_app_cargo_toml },
     { name: "roc_std/Cargo.toml", content: roc_std_cargo_toml },
     { name: "roc_std/src/lib.rs", content: roc_std_lib },
+    { name: "roc_std/src/inspect.rs", content: roc_std_inspect },
     { name: "roc_std/src/roc_box.rs", content: roc_std_box },
+    { name: "roc_std/src/roc_dict.rs", content: roc_std_dict },
+    { name: "roc_std/src/roc_hash.rs", content: roc_std_hash },
     { name: "roc_std/src/roc_list.rs", content: roc_std_list },
     { name: "roc_std/src/roc_str.rs", content: roc_std_str },
     { name: "roc_std/src/storage.rs", content: roc_std_storage },
@@ -61,6 +67,7 @@ convert_types_to_file = \types ->
 
                 TagUnion(Enumeration({ name, tags, size })) ->
                     generate_enumeration(buf, types, type, name, tags, size)
+                    |> generate_layout_checks(types, id, escape_kw(name), [])
 
                 TagUnion(NonRecursive({ name, tags, discriminant_size, discriminant_offset })) ->
                     if !(List.is_empty(tags)) then
@@ -82,10 +89,10 @@ convert_types_to_file = \types ->
                     generate_nullable_unwrapped(buf, types, id, name, null_tag, non_null_tag, non_null_payload, which_tag_is_null)
 
                 TagUnion(SingleTagStruct({ name, tag_name, payload })) ->
-                    generate_single_tag_struct(buf, types, name, tag_name, payload)
+                    generate_single_tag_struct(buf, types, id, name, tag_name, payload)
 
                 TagUnion(NonNullableUnwrapped({ name, tag_name, payload })) ->
-                    generate_non_nullable_unwrapped(buf, types, name, tag_name, payload, 0, 0, None)
+                    generate_non_nullable_unwrapped(buf, types, id, name, tag_name, payload, 0, 0, None)
 
                 Function(roc_fn) ->
                     if roc_fn.is_toplevel then
@@ -318,11 +325,70 @@ generate_struct = \buf, types, id, name, struct_fields, visibility ->
 
     buf
     |> generate_derive_str(types, struct_type, IncludeDebug)
+    |> \b ->
+        when struct_type is
+            Struct(_) -> generate_serde_derive_str(b, types, struct_type)
+            _ -> b
     |> Str.concat("#[repr(${repr})]\n${pub}struct ${escaped_name} {\n")
     |> generate_struct_fields(types, Public, struct_fields)
     |> Str.concat("}\n\n")
+    |> generate_layout_checks(types, id, escaped_name, field_ids(struct_fields))
+    |> \b ->
+        if can_support_inspect_serde(types, struct_type) then
+            when struct_type is
+                TagUnionPayload(_) ->
+                    b
+                    |> generate_serde_tag_union_payload(types, escaped_name, field_ids(struct_fields))
+                    |> generate_inspect_impls(escaped_name, inspect_tag_union_payload(field_ids(struct_fields)))
+
+                _ -> generate_inspect_impls(b, escaped_name, inspect_record(field_ids(struct_fields)))
+        else
+            b
+    |> \b ->
+        if can_support_roc_hash(types, struct_type) then
+            when struct_type is
+                TagUnionPayload(_) -> generate_roc_hash_impl(b, escaped_name, roc_hash_tag_union_payload(field_ids(struct_fields)))
+                _ -> generate_roc_hash_impl(b, escaped_name, roc_hash_record(field_ids(struct_fields)))
+        else
+            b
     |> generate_roc_refcounted(types, struct_type, escaped_name)
 
+field_ids = \struct_fields ->
+    when struct_fields is
+        HasNoClosure(fields) -> fields
+        HasClosure(fields) -> List.map(fields, \{ name, id } -> { name, id })
+
+## Asserts at compile time that Rust lays the type out the way Roc does on this architecture,
+## so a mismatch fails the build instead of corrupting memory at runtime.
+generate_layout_checks : Str, Types, TypeId, Str, List { name : Str, id : TypeId } -> Str
+generate_layout_checks = \buf, types, id, escaped_name, fields ->
+    size_of_self = Num.to_str(Types.size(types, id))
+    align_of_self = Num.to_str(Types.alignment(types, id))
+
+    offset_checks =
+        List.walk(fields, { checks: "", offset: 0 }, \{ checks, offset }, { name: field_name, id: field_id } ->
+            field_align = Types.alignment(types, field_id)
+            # Zero-sized fields have no alignment.
+            field_offset = if field_align == 0 then offset else next_multiple_of(offset, field_align)
+            field_offset_str = Num.to_str(field_offset)
+            escaped_field_name = escape_kw(field_name)
+
+            {
+                checks: Str.concat(checks, "const _OFFSET_CHECK_${escaped_name}_${field_name}: () = assert!(core::mem::offset_of!(${escaped_name}, ${escaped_field_name}) == ${field_offset_str});\n"),
+                offset: field_offset + Types.size(types, field_id),
+            })
+        |> .checks
+
+    Str.concat(
+        buf,
+        """
+        const _SIZE_CHECK_${escaped_name}: () = assert!(core::mem::size_of::<${escaped_name}>() == ${size_of_self});
+        const _ALIGN_CHECK_${escaped_name}: () = assert!(core::mem::align_of::<${escaped_name}>() == ${align_of_self});
+        ${offset_checks}
+
+        """,
+    )
+
 generate_struct_fields = \buf, types, visibility, struct_fields ->
     when struct_fields is
         HasNoClosure(fields) ->
@@ -362,6 +428,7 @@ generate_enumeration = \buf, types, enum_type, name, tags, tag_bytes ->
 
     buf
     |> generate_derive_str(types, enum_type, ExcludeDebug)
+    |> generate_serde_derive_str(types, enum_type)
     |> Str.concat("#[repr(u${repr_bits})]\npub enum ${escaped_name} {\n")
     |> \b -> List.walk_with_index(tags, b, generate_enum_tags)
     |>
@@ -378,6 +445,8 @@ generate_enumeration = \buf, types, enum_type, name, tags, tag_bytes ->
     )
     |> \b -> List.walk(tags, b, generate_enum_tags_debug(name))
     |> Str.concat("${indent}${indent}}\n${indent}}\n}\n\n")
+    |> generate_inspect_impls(escaped_name, inspect_enumeration(tags))
+    |> generate_roc_hash_impl(escaped_name, roc_hash_enumeration(tags))
     |> generate_roc_refcounted(types, enum_type, escaped_name)
 
 generate_enum_tags = \accum, name, index ->
@@ -569,6 +638,284 @@ derive_hash_tag_union = \buf, types, shape, tag_union_type, tags ->
     else
         buf
 
+## Implements `RocInspect`, which formats values the way Roc's `Inspect.to_str` does, and then
+## `Display` in terms of it. The body is the lines of `RocInspect::inspect`.
+generate_inspect_impls : Str, Str, List Str -> Str
+generate_inspect_impls = \buf, escaped_name, body_lines ->
+    body = body_lines |> indent_lines |> indent_lines |> Str.join_with("\n")
+
+    """
+    ${buf}
+
+    impl roc_std::RocInspect for ${escaped_name} {
+        fn inspect(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    ${body}
+        }
+    }
+
+    impl core::fmt::Display for ${escaped_name} {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            self.inspect(f)
+        }
+    }
+
+
+    """
+
+inspect_record : List { name : Str, id : TypeId } -> List Str
+inspect_record = \fields ->
+    # Roc shows a record's fields in alphabetical order, not in the order they're stored in.
+    sorted_fields = List.sort_with(fields, \a, b -> compare_str(a.name, b.name))
+
+    if List.is_empty(sorted_fields) then
+        ["f.write_str(\"{}\")"]
+    else
+        sorted_fields
+        |> List.map_with_index(\{ name }, index ->
+            separator = if index == 0 then "{" else ", "
+            escaped_field_name = escape_kw(name)
+
+            [
+                "f.write_str(\"${separator}${name}: \")?;",
+                "self.${escaped_field_name}.inspect(f)?;",
+            ])
+        |> List.join
+        |> List.append("f.write_str(\"}\")")
+
+## A tag union payload's fields, separated by spaces, for the tag union to put after the tag's name.
+inspect_tag_union_payload : List { name : Str, id : TypeId } -> List Str
+inspect_tag_union_payload = \fields ->
+    fields
+    |> List.map_with_index(\{ name }, index ->
+        if index == 0 then
+            ["self.${name}.inspect(f)?;"]
+        else
+            ["f.write_str(\" \")?;", "self.${name}.inspect(f)?;"])
+    |> List.join
+    |> List.append("Ok(())")
+
+inspect_tag : Str, List Str -> List Str
+inspect_tag = \tag_name, payload_exprs ->
+    if List.is_empty(payload_exprs) then
+        ["f.write_str(\"${tag_name}\")"]
+    else
+        payload_exprs
+        |> List.map(\expr -> "${expr}.inspect(f)?;")
+        |> List.intersperse("f.write_str(\" \")?;")
+        |> List.prepend("f.write_str(\"(${tag_name} \")?;")
+        |> List.append("f.write_str(\")\")")
+
+inspect_enumeration : List Str -> List Str
+inspect_enumeration = \tags ->
+    tags
+    |> List.map(\tag_name -> "${indent}Self::${tag_name} => f.write_str(\"${tag_name}\"),")
+    |> List.prepend("match self {")
+    |> List.append("}")
+
+inspect_non_recursive : Str, List { name : Str, payload : [Some TypeId, None] } -> List Str
+inspect_non_recursive = \tag_union_type, tags ->
+    arms =
+        List.map(tags, \{ name: tag_name, payload } ->
+            lines =
+                when payload is
+                    Some(_) -> inspect_tag(tag_name, ["self.payload.${tag_name}"])
+                    None -> inspect_tag(tag_name, [])
+
+            when lines is
+                [line] -> ["discriminant_${tag_union_type}::${tag_name} => ${line},"]
+                _ ->
+                    lines
+                    |> indent_lines
+                    |> List.prepend("discriminant_${tag_union_type}::${tag_name} => {")
+                    |> List.append("}"))
+        |> List.join
+
+    ["unsafe {", "${indent}match self.discriminant {"]
+    |> List.concat(arms |> indent_lines |> indent_lines)
+    |> List.concat(["${indent}}", "}"])
+
+## Implements `RocHash`, which hashes values the way Roc's `Hash` ability does, so that they can
+## be keys of a `RocDict` or elements of a `RocSet`. The body is the lines of `RocHash::roc_hash`.
+generate_roc_hash_impl : Str, Str, List Str -> Str
+generate_roc_hash_impl = \buf, escaped_name, body_lines ->
+    hasher = if List.is_empty(body_lines) then "_hasher" else "hasher"
+    body = body_lines |> indent_lines |> indent_lines |> Str.join_with("\n")
+
+    """
+    ${buf}
+
+    impl roc_std::RocHash for ${escaped_name} {
+        fn roc_hash(&self, ${hasher}: &mut roc_std::RocHasher) {
+    ${body}
+        }
+    }
+
+
+    """
+
+roc_hash_record : List { name : Str, id : TypeId } -> List Str
+roc_hash_record = \fields ->
+    # Like Inspect, Roc's Hash visits a record's fields in alphabetical order.
+    fields
+    |> List.sort_with(\a, b -> compare_str(a.name, b.name))
+    |> List.map(\{ name } ->
+        escaped_field_name = escape_kw(name)
+
+        "self.${escaped_field_name}.roc_hash(hasher);")
+
+## The fields are named `f0`, `f1`, etc. and are stored sorted by alignment, but Roc hashes them
+## in the order they appear in the tag.
+roc_hash_tag_union_payload : List { name : Str, id : TypeId } -> List Str
+roc_hash_tag_union_payload = \fields ->
+    payload_index = \{ name } ->
+        name
+        |> Str.drop_prefix("f")
+        |> Str.to_u64
+        |> Result.with_default(0)
+
+    fields
+    |> List.sort_with(\a, b -> Num.compare(payload_index(a), payload_index(b)))
+    |> List.map(\{ name } -> "self.${name}.roc_hash(hasher);")
+
+roc_hash_enumeration : List Str -> List Str
+roc_hash_enumeration = \tags ->
+    tag_count = List.len(tags)
+
+    # Roc hashes which tag it is with the smallest integer that fits every tag,
+    # and doesn't hash anything for a tag union with only one tag.
+    if tag_count == 1 then
+        []
+    else if tag_count <= 255 then
+        ["hasher.add_u8(*self as u8);"]
+    else
+        ["hasher.add_u16(*self as u16);"]
+
+roc_hash_non_recursive : Str, List { name : Str, payload : [Some TypeId, None] } -> List Str
+roc_hash_non_recursive = \tag_union_type, tags ->
+    arms =
+        List.map(tags, \{ name: tag_name, payload } ->
+            when payload is
+                Some(_) -> "discriminant_${tag_union_type}::${tag_name} => self.payload.${tag_name}.roc_hash(hasher),"
+                None -> "discriminant_${tag_union_type}::${tag_name} => {}")
+
+    ["self.discriminant.roc_hash(hasher);", "", "unsafe {", "${indent}match self.discriminant {"]
+    |> List.concat(arms |> indent_lines |> indent_lines)
+    |> List.concat(["${indent}}", "}"])
+
+## Tag unions serialize the way serde serializes a Rust enum, e.g. `{"Circle":[1.0,2.0]}` or
+## `"Empty"` in JSON. They go through an enum that derives the serde traits, so that they support
+## every format serde does. `to_tagged` is the lines of an expression that borrows `self` as that
+## enum, and `construct` builds `Self` from the bindings `f0`, `f1`, etc.
+generate_serde_tag_union : Str, Str, List Str, List { tag_name : Str, field_types : List Str, construct : Str } -> Str
+generate_serde_tag_union = \buf, escaped_name, to_tagged, tags ->
+    variant = \tag_name, field_types ->
+        if List.is_empty(field_types) then
+            "${tag_name},"
+        else
+            fields_str = Str.join_with(field_types, ", ")
+
+            "${tag_name}(${fields_str}),"
+
+    lifetime = if List.any(tags, \{ field_types } -> !(List.is_empty(field_types))) then "<'a>" else ""
+
+    borrowed_variants =
+        tags
+        |> List.map(\{ tag_name, field_types } -> variant(tag_name, List.map(field_types, \field_type -> "&'a ${field_type}")))
+        |> Str.join_with("\n${indent}${indent}${indent}")
+
+    owned_variants =
+        tags
+        |> List.map(\{ tag_name, field_types } -> variant(tag_name, field_types))
+        |> Str.join_with("\n${indent}${indent}${indent}")
+
+    arms =
+        tags
+        |> List.map(\{ tag_name, field_types, construct } ->
+            if List.is_empty(field_types) then
+                "Tagged::${tag_name} => ${construct},"
+            else
+                bindings =
+                    field_types
+                    |> List.map_with_index(\_, index ->
+                        index_str = Num.to_str(index)
+
+                        "f${index_str}")
+                    |> Str.join_with(", ")
+
+                "Tagged::${tag_name}(${bindings}) => ${construct},")
+        |> Str.join_with("\n${indent}${indent}${indent}")
+
+    tagged_expr = Str.join_with(to_tagged, "\n${indent}${indent}")
+
+    """
+    ${buf}
+
+    #[cfg(feature = "serde")]
+    impl serde::Serialize for ${escaped_name} {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+            #[derive(serde::Serialize)]
+            #[serde(rename = "${escaped_name}")]
+            enum Tagged${lifetime} {
+                ${borrowed_variants}
+            }
+
+            let tagged = ${tagged_expr};
+
+            serde::Serialize::serialize(&tagged, serializer)
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl<'de> serde::Deserialize<'de> for ${escaped_name} {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+            #[derive(serde::Deserialize)]
+            #[serde(rename = "${escaped_name}")]
+            enum Tagged {
+                ${owned_variants}
+            }
+
+            let tagged: Tagged = serde::Deserialize::deserialize(deserializer)?;
+
+            Ok(match tagged {
+                ${arms}
+            })
+        }
+    }
+
+
+    """
+
+## A tag union payload serializes as a tuple of its fields (or as its only field), so that its
+## tag union serializes like a Rust enum whose variants hold those fields.
+generate_serde_tag_union_payload : Str, Types, Str, List { name : Str, id : TypeId } -> Str
+generate_serde_tag_union_payload = \buf, types, escaped_name, fields ->
+    refs = fields |> List.map(\{ name } -> "&self.${name}") |> as_rust_tuple
+    bindings = fields |> List.map(.name) |> as_rust_tuple
+    tuple_type = fields |> List.map(\{ id } -> type_name(types, id)) |> as_rust_tuple
+    field_names = fields |> List.map(.name) |> Str.join_with(", ")
+
+    """
+    ${buf}
+
+    #[cfg(feature = "serde")]
+    impl serde::Serialize for ${escaped_name} {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+            serde::Serialize::serialize(&${refs}, serializer)
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl<'de> serde::Deserialize<'de> for ${escaped_name} {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+            let ${bindings} = <${tuple_type} as serde::Deserialize>::deserialize(deserializer)?;
+
+            Ok(Self { ${field_names} })
+        }
+    }
+
+
+    """
+
 generate_constructor_functions : Str, Types, Str, List { name : Str, payload : [Some TypeId, None] } -> Str
 generate_constructor_functions = \buf, types, tag_union_type, tags ->
     buf
@@ -711,14 +1058,6 @@ generate_non_recursive_tag_union = \buf, types, id, name, tags, discriminant_siz
 
     align_of_union_str = Num.to_str(align_of_union)
 
-    size_of_union_str =
-        List.walk(tags, 1, \accum, { payload } ->
-            when payload is
-                Some(payload_id) -> max(accum, Types.size(types, payload_id))
-                None -> accum)
-        |> next_multiple_of(align_of_union)
-        |> Num.to_str
-
     size_of_self = Num.to_str(Types.size(types, id))
     align_of_self = Num.to_str(Types.alignment(types, id))
     shape = Types.shape(types, id)
@@ -741,8 +1080,8 @@ generate_non_recursive_tag_union = \buf, types, id, name, tags, discriminant_siz
         """
         }
 
-        // TODO(@roc-lang): See https://github.com/roc-lang/roc/issues/6012
-        // const _SIZE_CHECK_${union_name}: () = assert!(core::mem::size_of::<${union_name}>() == ${size_of_union_str});
+        // Roc puts the discriminant right after the payloads, rounded up to their alignment.
+        const _SIZE_CHECK_${union_name}: () = assert!(core::mem::size_of::<${union_name}>() == ${discriminant_offset_str});
         const _ALIGN_CHECK_${union_name}: () = assert!(core::mem::align_of::<${union_name}>() == ${align_of_union_str});
 
         const _SIZE_CHECK_${escaped_name}: () = assert!(core::mem::size_of::<${escaped_name}>() == ${size_of_self});
@@ -778,6 +1117,9 @@ generate_non_recursive_tag_union = \buf, types, id, name, tags, discriminant_siz
             payload: union_${escaped_name},
             discriminant: discriminant_${escaped_name},
         }
+
+        const _OFFSET_CHECK_${escaped_name}_payload: () = assert!(core::mem::offset_of!(${escaped_name}, payload) == 0);
+        const _OFFSET_CHECK_${escaped_name}_discriminant: () = assert!(core::mem::offset_of!(${escaped_name}, discriminant) == ${discriminant_offset_str});
         """,
     )
     |> derive_clone_tag_union(escaped_name, tags)
@@ -787,6 +1129,35 @@ generate_non_recursive_tag_union = \buf, types, id, name, tags, discriminant_siz
     |> derive_ord_tag_union(types, shape, escaped_name)
     |> derive_partial_ord_tag_union(types, shape, escaped_name, tags)
     |> derive_hash_tag_union(types, shape, escaped_name, tags)
+    |> \b ->
+        if can_support_inspect_serde(types, shape) then
+            serde_tags =
+                List.map(tags, \{ name: tag_name, payload } ->
+                    when payload is
+                        Some(payload_id) -> { tag_name, field_types: [type_name(types, payload_id)], construct: "Self::${tag_name}(f0)" }
+                        None -> { tag_name, field_types: [], construct: "Self::${tag_name}()" })
+
+            to_tagged_arms =
+                List.map(tags, \{ name: tag_name, payload } ->
+                    when payload is
+                        Some(_) -> "${indent}${indent}${discriminant_name}::${tag_name} => Tagged::${tag_name}(&self.payload.${tag_name}),"
+                        None -> "${indent}${indent}${discriminant_name}::${tag_name} => Tagged::${tag_name},")
+
+            to_tagged =
+                ["unsafe {", "${indent}match self.discriminant {"]
+                |> List.concat(to_tagged_arms)
+                |> List.concat(["${indent}}", "}"])
+
+            b
+            |> generate_serde_tag_union(escaped_name, to_tagged, serde_tags)
+            |> generate_inspect_impls(escaped_name, inspect_non_recursive(escaped_name, tags))
+        else
+            b
+    |> \b ->
+        if can_support_roc_hash(types, shape) then
+            generate_roc_hash_impl(b, escaped_name, roc_hash_non_recursive(escaped_name, tags))
+        else
+            b
     |> generate_destructor_functions(types, escaped_name, tags)
     |> generate_constructor_functions(types, escaped_name, tags)
     |> \b ->
@@ -814,7 +1185,7 @@ generate_non_recursive_tag_union = \buf, types, id, name, tags, discriminant_siz
             b
     |> generate_roc_refcounted(types, union_type, escaped_name)
 
-generate_non_nullable_unwrapped = \buf, types, name, tag_name, payload, discriminant_size, _discriminant_offset, _null_tag_index ->
+generate_non_nullable_unwrapped = \buf, types, id, name, tag_name, payload, discriminant_size, _discriminant_offset, _null_tag_index ->
     escaped_name = escape_kw(name)
     discriminant_name = "discriminant_${escaped_name}"
 
@@ -871,10 +1242,29 @@ generate_non_nullable_unwrapped = \buf, types, name, tag_name, payload, discrimi
             f.debug_tuple("${escaped_name}::${tag_name}")${debug_fields}.finish()
         }
     }
+
     """
+    |> generate_layout_checks(types, id, escaped_name, [])
+    |> \b ->
+        if can_support_inspect_serde(types, union_type) then
+            field_names = List.map_with_index(payload_fields, \_, i ->
+                n = Num.to_str(i)
+                "f${n}")
+            refs = field_names |> List.map(\field_name -> "&self.0.${field_name}") |> Str.join_with(", ")
+            serde_tag = {
+                tag_name,
+                field_types: List.map(payload_fields, \id -> type_name(types, id)),
+                construct: "Self::${tag_name}(${payload_field_names})",
+            }
+
+            b
+            |> generate_serde_tag_union(escaped_name, ["Tagged::${tag_name}(${refs})"], [serde_tag])
+            |> generate_inspect_impls(escaped_name, inspect_tag(tag_name, List.map(field_names, \field_name -> "self.0.${field_name}")))
+        else
+            b
     |> generate_roc_refcounted(types, union_type, escaped_name)
 
-generate_recursive_tag_union = \buf, types, id, tag_union_name, tags, discriminant_size, _discriminant_offset, null_tag_index ->
+generate_recursive_tag_union = \buf, types, id, tag_union_name, tags, discriminant_size, discriminant_offset, null_tag_index ->
     escaped_name = escape_kw(tag_union_name)
     discriminant_name = "discriminant_${escaped_name}"
     tag_names = List.map(tags, \{ name: n } -> n)
@@ -1210,6 +1600,15 @@ generate_recursive_tag_union = \buf, types, id, tag_union_name, tags, discrimina
 
     size_of_self = Num.to_str(Types.size(types, id))
     align_of_self = Num.to_str(Types.alignment(types, id))
+    # The discriminant is stored in the pointer, but Roc still computes where it would go
+    # after the payloads, which is the size of the union they're stored in.
+    size_of_union_str = Num.to_str(discriminant_offset)
+    align_of_union_str =
+        List.walk(tags, 1, \accum, { payload } ->
+            when payload is
+                Some(payload_id) -> Num.max(accum, Types.alignment(types, payload_id))
+                None -> accum)
+        |> Num.to_str
 
     buf
     |> generate_discriminant(types, discriminant_name, tag_names, discriminant_size)
@@ -1296,7 +1695,16 @@ generate_recursive_tag_union = \buf, types, id, tag_union_name, tags, discrimina
         """,
     )
     |> \b -> List.walk(tags, b, generate_union_field(types))
-    |> Str.concat("}\n\n")
+    |> Str.concat(
+        """
+        }
+
+        const _SIZE_CHECK_${union_name}: () = assert!(core::mem::size_of::<${union_name}>() == ${size_of_union_str});
+        const _ALIGN_CHECK_${union_name}: () = assert!(core::mem::align_of::<${union_name}>() == ${align_of_union_str});
+
+
+        """,
+    )
     |> generate_roc_refcounted(types, union_type, escaped_name)
 
 generate_tag_union_drop_payload = \buf, types, self_mut, tags, discriminant_name, discriminant_size, indents ->
@@ -1557,7 +1965,7 @@ generate_nullable_unwrapped = \buf, types, tag_unionid, name, null_tag, non_null
     """
     |> generate_roc_refcounted(types, union_type, name)
 
-generate_single_tag_struct = \buf, types, name, tag_name, payload ->
+generate_single_tag_struct = \buf, types, id, name, tag_name, payload ->
     # Store single-tag unions as structs rather than enums,
     # because they have only one alternative. However, still
     # offer the usual tag union APIs.
@@ -1594,6 +2002,35 @@ generate_single_tag_struct = \buf, types, name, tag_name, payload ->
                     generate_zero_element_single_tag_struct(b, escaped_name, tag_name)
                 else
                     generate_multi_element_single_tag_struct(b, types, escaped_name, tag_name, fields, as_struct_fields)
+                    |> generate_layout_checks(types, id, escaped_name, field_ids(as_struct_fields))
+            |> \b ->
+                if can_support_inspect_serde(types, TagUnion(SingleTagStruct({ name, tag_name, payload }))) then
+                    field_names = List.map_with_index(fields, \_, index ->
+                        index_str = Num.to_str(index)
+
+                        "f${index_str}")
+                    field_types = List.map(fields, \{ id: field_id } -> type_name(types, field_id))
+
+                    (to_tagged, construct) =
+                        if List.is_empty(fields) then
+                            ("Tagged::${tag_name}", "Self::${tag_name}")
+                        else
+                            refs = field_names |> List.map(\field_name -> "&self.${field_name}") |> Str.join_with(", ")
+                            args = Str.join_with(field_names, ", ")
+
+                            ("Tagged::${tag_name}(${refs})", "Self::${tag_name}(${args})")
+
+                    b
+                    |> generate_serde_tag_union(escaped_name, [to_tagged], [{ tag_name, field_types, construct }])
+                    |> generate_inspect_impls(escaped_name, inspect_tag(tag_name, List.map(field_names, \field_name -> "self.${field_name}")))
+                else
+                    b
+            |> \b ->
+                if can_support_roc_hash(types, TagUnion(SingleTagStruct({ name, tag_name, payload }))) then
+                    # A tag union with only one tag doesn't hash its discriminant, just the payload.
+                    generate_roc_hash_impl(b, escaped_name, roc_hash_tag_union_payload(field_ids(as_struct_fields)))
+                else
+                    b
             |> generate_roc_refcounted(types, TagUnion(SingleTagStruct({ name, tag_name, payload })), escaped_name)
 
         HasClosure(_) ->
@@ -1795,6 +2232,13 @@ generate_derive_str = \buf, types, type, include_debug ->
     |> cond_write(can_support_eq_hash_ord(types, type), "Eq, Ord, Hash, ")
     |> Str.concat(")]\n")
 
+## The serde traits are only used when the host enables roc_app's `serde` feature.
+generate_serde_derive_str = \buf, types, type ->
+    if can_support_inspect_serde(types, type) then
+        Str.concat(buf, "#[cfg_attr(feature = \"serde\", derive(serde::Serialize, serde::Deserialize))]\n")
+    else
+        buf
+
 can_support_eq_hash_ord : Types, Shape -> Bool
 can_support_eq_hash_ord = \types, type ->
     !(has_float(types, type)) and (can_support_partial_eq_ord(types, type))
@@ -1809,15 +2253,22 @@ can_support_partial_eq_ord = \types, type ->
         Unsized -> Bool.false
         Unit | EmptyTagUnion | Bool | Num(_) | TagUnion(Enumeration(_)) -> Bool.true
         RocStr -> Bool.true
-        RocList(inner) | RocSet(inner) | RocBox(inner) ->
+        RocList(inner) | RocBox(inner) ->
             inner_type = Types.shape(types, inner)
             can_support_partial_eq_ord(types, inner_type)
 
+        # Comparing dicts and sets looks up their keys, which uses RocHash.
+        RocSet(inner) ->
+            inner_type = Types.shape(types, inner)
+            can_support_partial_eq_ord(types, inner_type) and can_support_roc_hash(types, inner_type)
+
         RocDict(k, v) ->
             k_type = Types.shape(types, k)
             v_type = Types.shape(types, v)
 
-            can_support_partial_eq_ord(types, k_type) and can_support_partial_eq_ord(types, v_type)
+            can_support_partial_eq_ord(types, k_type)
+            and can_support_roc_hash(types, k_type)
+            and can_support_partial_eq_ord(types, v_type)
 
         TagUnion(Recursive({ tags })) ->
             List.all(tags, \{ payload } ->
@@ -1862,6 +2313,77 @@ can_support_partial_eq_ord = \types, type ->
         Struct({ fields: HasClosure(fields) }) | TagUnionPayload({ fields: HasClosure(fields) }) ->
             List.all(fields, \{ id } -> can_support_partial_eq_ord(types, Types.shape(types, id)))
 
+## Whether the glue implements `RocInspect`, `Display` and the serde traits for a type, which it
+## does when everything the type contains implements them too.
+can_support_inspect_serde : Types, Shape -> Bool
+can_support_inspect_serde = \types, type ->
+    when type is
+        Unit | Bool | Num(_) | RocStr | TagUnion(Enumeration(_)) -> Bool.true
+        Unsized | EmptyTagUnion | Function(_) -> Bool.false
+        RocList(id) | RocSet(id) | RocBox(id) ->
+            can_support_inspect_serde(types, Types.shape(types, id))
+
+        RocDict(key_id, val_id) | RocResult(key_id, val_id) ->
+            can_support_inspect_serde(types, Types.shape(types, key_id))
+            and can_support_inspect_serde(types, Types.shape(types, val_id))
+
+        Struct({ fields: HasNoClosure(fields) }) | TagUnionPayload({ fields: HasNoClosure(fields) }) ->
+            List.all(fields, \{ id } -> can_support_inspect_serde(types, Types.shape(types, id)))
+
+        Struct({ fields: HasClosure(_) }) | TagUnionPayload({ fields: HasClosure(_) }) -> Bool.false
+        TagUnion(SingleTagStruct({ payload: HasNoClosure(fields) })) ->
+            List.all(fields, \{ id } -> can_support_inspect_serde(types, Types.shape(types, id)))
+
+        TagUnion(SingleTagStruct({ payload: HasClosure(_) })) -> Bool.false
+        TagUnion(NonRecursive({ tags })) ->
+            List.all(tags, \{ payload } ->
+                when payload is
+                    Some(id) -> can_support_inspect_serde(types, Types.shape(types, id))
+                    None -> Bool.true)
+
+        TagUnion(NonNullableUnwrapped({ payload })) ->
+            can_support_inspect_serde(types, Types.shape(types, payload))
+
+        # Of the recursive tag unions, only the ones without a null pointer get these impls so far.
+        RecursivePointer(id) ->
+            when Types.shape(types, id) is
+                TagUnion(NonNullableUnwrapped(_)) -> Bool.true
+                _ -> Bool.false
+
+        TagUnion(Recursive(_)) | TagUnion(NullableWrapped(_)) | TagUnion(NullableUnwrapped(_)) -> Bool.false
+
+## Whether the glue implements `RocHash` for a type. Like Roc's `Hash` ability, that leaves out
+## floats and functions, and the glue doesn't implement it for boxes or recursive tag unions yet.
+can_support_roc_hash : Types, Shape -> Bool
+can_support_roc_hash = \types, type ->
+    when type is
+        Unit | Bool | RocStr | TagUnion(Enumeration(_)) -> Bool.true
+        Num(F32) | Num(F64) -> Bool.false
+        Num(_) -> Bool.true
+        Unsized | EmptyTagUnion | Function(_) | RocBox(_) -> Bool.false
+        RocList(id) | RocSet(id) ->
+            can_support_roc_hash(types, Types.shape(types, id))
+
+        RocDict(key_id, val_id) | RocResult(key_id, val_id) ->
+            can_support_roc_hash(types, Types.shape(types, key_id))
+            and can_support_roc_hash(types, Types.shape(types, val_id))
+
+        Struct({ fields: HasNoClosure(fields) }) | TagUnionPayload({ fields: HasNoClosure(fields) }) ->
+            List.all(fields, \{ id } -> can_support_roc_hash(types, Types.shape(types, id)))
+
+        Struct({ fields: HasClosure(_) }) | TagUnionPayload({ fields: HasClosure(_) }) -> Bool.false
+        TagUnion(SingleTagStruct({ payload: HasNoClosure(fields) })) ->
+            List.all(fields, \{ id } -> can_support_roc_hash(types, Types.shape(types, id)))
+
+        TagUnion(SingleTagStruct({ payload: HasClosure(_) })) -> Bool.false
+        TagUnion(NonRecursive({ tags })) ->
+            List.all(tags, \{ payload } ->
+                when payload is
+                    Some(id) -> can_support_roc_hash(types, Types.shape(types, id))
+                    None -> Bool.true)
+
+        RecursivePointer(_) | TagUnion(Recursive(_)) | TagUnion(NullableWrapped(_)) | TagUnion(NullableUnwrapped(_)) | TagUnion(NonNullableUnwrapped(_)) -> Bool.false
+
 cannot_support_copy : Types, Shape -> Bool
 cannot_support_copy = \types, type ->
     !(can_derive_copy(types, type))
@@ -1907,9 +2429,7 @@ cannot_support_default = \types, type ->
             cannot_support_default(types, Types.shape(types, id))
 
         TagUnionPayload({ fields: HasClosure(_) }) -> Bool.true
-        RocDict(key_id, val_id) ->
-            cannot_support_copy(types, Types.shape(types, key_id))
-            or cannot_support_copy(types, Types.shape(types, val_id))
+        RocDict(_, _) -> Bool.false
 
         Struct({ fields: HasClosure(_) }) -> Bool.true
         Struct({ fields: HasNoClosure(fields) }) | TagUnionPayload({ fields: HasNoClosure(fields) }) ->
@@ -2203,17 +2723,17 @@ type_name = \types, id ->
         Num(I128) -> "i128"
         Num(F32) -> "f32"
         Num(F64) -> "f64"
-        Num(Dec) -> "roc_std:RocDec"
-        RocDict(_key, _value) ->
-            # key_name = type_name(types, key)
-            # value_name = type_name(types, value)
-            # "roc_std::RocDict<${key_name}, ${value_name}>"
-            crash("RocDict is not yet supported in rust")
-
-        RocSet(_elem) ->
-            # elem_name = type_name(types, elem)
-            # "roc_std::RocSet<${elem_name}>"
-            crash("RocSet is not yet supported in rust")
+        Num(Dec) -> "roc_std::RocDec"
+        RocDict(key, value) ->
+            key_name = type_name(types, key)
+            value_name = type_name(types, value)
+
+            "roc_std::RocDict<${key_name}, ${value_name}>"
+
+        RocSet(elem) ->
+            elem_name = type_name(types, elem)
+
+            "roc_std::RocSet<${elem_name}>"
 
         RocList(elem) ->
             elem_name = type_name(types, elem)
@@ -2283,6 +2803,8 @@ file_header =
     #![allow(clippy::non_canonical_partial_ord_impl)]
 
 
+    use roc_std::RocHash;
+    use roc_std::RocInspect;
     use roc_std::RocRefcounted;
     use roc_std::roc_refcounted_noop_impl;
 
@@ -2360,6 +2882,20 @@ next_multiple_of = \lhs, rhs ->
         0 -> lhs
         r -> lhs + (rhs - r)
 
+indent_lines : List Str -> List Str
+indent_lines = \lines ->
+    List.map(lines, \line -> "${indent}${line}")
+
+## Orders strings by their UTF-8 bytes, like Rust's `str` (and Roc's record fields) do.
+compare_str : Str, Str -> [LT, EQ, GT]
+compare_str = \a, b ->
+    a_bytes = Str.to_utf8(a)
+    b_bytes = Str.to_utf8(b)
+
+    List.map2(a_bytes, b_bytes, Num.compare)
+    |> List.find_first(\order -> order != EQ)
+    |> Result.with_default(Num.compare(List.len(a_bytes), List.len(b_bytes)))
+
 is_unit : Shape -> Bool
 is_unit = \shape ->
     when shape is
@@ -0,0 +1,503 @@
+app [make_glue] { pf: platform "../platform/main.roc" }
+
+import pf.Types exposing [Types]
+import pf.Shape exposing [Shape, RocStructFields]
+import pf.File exposing [File]
+import pf.TypeId exposing [TypeId]
+import "../static/typescript/roc_std.js" as roc_std_js : Str
+import "../static/typescript/roc_std.d.ts" as roc_std_d_ts : Str
+
+## Generates `roc_app.js`, which calls the entry points of a Roc app compiled with
+## `--target=wasm32`, reading and writing their arguments and return values in the app's
+## linear memory. `roc_app.d.ts` has their TypeScript types.
+make_glue : List Types -> Result (List File) Str
+make_glue = \types_by_arch ->
+    when List.find_first(types_by_arch, \types -> (Types.target(types)).architecture == Wasm32) is
+        Ok(types) ->
+            declared =
+                Types.walk_shapes(types, { js: js_header, dts: dts_header, declared: Set.empty({}) }, \state, _shape, id ->
+                    declare(state, types, id))
+
+            entry_points = Types.entry_points(types)
+
+            Ok([
+                { name: "roc_app.js", content: Str.concat(declared.js, generate_bind_js(types, entry_points)) },
+                { name: "roc_app.d.ts", content: Str.concat(declared.dts, generate_bind_dts(types, entry_points)) },
+                { name: "roc_std.js", content: roc_std_js },
+                { name: "roc_std.d.ts", content: roc_std_d_ts },
+            ])
+
+        Err(NotFound) ->
+            Err("The TypeScript glue needs the types for wasm32, but roc glue didn't provide them.")
+
+js_header =
+    """
+    // ⚠️ GENERATED CODE ⚠️
+    //
+    // This module is generated by the `roc glue` CLI command, for wasm32.
+
+    import * as roc from "./roc_std.js";
+
+    """
+
+dts_header =
+    """
+    // ⚠️ GENERATED CODE ⚠️
+    //
+    // This module is generated by the `roc glue` CLI command, for wasm32.
+
+    import type { Codec, RocMemory, Tag } from "./roc_std.js";
+
+    """
+
+## A codec has to be defined before another codec can use it, so this declares the types a
+## type depends on before the type itself.
+declare : { js : Str, dts : Str, declared : Set TypeId }, Types, TypeId -> { js : Str, dts : Str, declared : Set TypeId }
+declare = \state, types, id ->
+    if Set.contains(state.declared, id) then
+        state
+    else
+        shape = Types.shape(types, id)
+        marked = { state & declared: Set.insert(state.declared, id) }
+        with_dependencies = List.walk(dependencies(shape), marked, \accum, dependency -> declare(accum, types, dependency))
+
+        when declaration(types, id, shape) is
+            Ok({ js, dts }) ->
+                { with_dependencies &
+                    js: Str.concat(with_dependencies.js, js),
+                    dts: Str.concat(with_dependencies.dts, dts),
+                }
+
+            Err(Builtin) ->
+                with_dependencies
+
+dependencies : Shape -> List TypeId
+dependencies = \shape ->
+    when shape is
+        RocList(elem) | RocSet(elem) | RocBox(elem) -> [elem]
+        RocDict(key, value) -> [key, value]
+        RocResult(ok, err) -> [ok, err]
+        Struct({ fields }) | TagUnionPayload({ fields }) -> List.map(struct_fields(fields), \{ id } -> id)
+        TagUnion(NonRecursive({ tags })) -> List.keep_oks(tags, \{ payload } -> payload_id(payload))
+        TagUnion(SingleTagStruct({ payload: HasNoClosure(fields) })) -> List.map(fields, \{ id } -> id)
+        _ -> []
+
+## The codec in roc_app.js and the types in roc_app.d.ts for a type, or `Err(Builtin)` for
+## types that roc_std.js already has.
+declaration : Types, TypeId, Shape -> Result { js : Str, dts : Str } [Builtin]
+declaration = \types, id, shape ->
+    when shape is
+        Struct({ name, fields }) ->
+            record_fields = struct_fields(fields)
+            dts_fields =
+                List.map(record_fields, \{ name: field_name, id: field_id } ->
+                    field_type = ts_type(types, field_id)
+
+                    "  ${field_name}: ${field_type};")
+                |> Str.join_with("\n")
+
+            Ok({
+                js: generate_codec(name, "record", layout_lines(types, id, List.map(record_fields, \{ name: field_name, id: field_id } -> { key: quoted(field_name), id: field_id }))),
+                dts: ts_block(name, "interface ${escape_kw(name)} {\n${dts_fields}\n}"),
+            })
+
+        TagUnionPayload({ name, fields }) ->
+            payload_fields = struct_fields(fields)
+
+            # The fields are in the order they're stored in, and named after their positions.
+            positions = List.map(payload_fields, \{ name: position, id: field_id } -> { key: position, id: field_id })
+            in_order =
+                List.sort_with(payload_fields, \a, b -> Num.compare(position_of(a.name), position_of(b.name)))
+                |> List.map(\{ id: field_id } -> ts_type(types, field_id))
+                |> Str.join_with(", ")
+
+            Ok({
+                js: generate_codec(name, "tagPayload", layout_lines(types, id, positions)),
+                dts: ts_block(name, "type ${escape_kw(name)} = [${in_order}];"),
+            })
+
+        TagUnion(Enumeration({ name, tags, size })) ->
+            size_str = Num.to_str(size)
+            tag_names = List.map(tags, quoted)
+            tags_str = Str.join_with(tag_names, ", ")
+            union_str = Str.join_with(tag_names, " | ")
+
+            Ok({
+                js: generate_codec(name, "enumeration", ["  size: ${size_str},", "  tags: [${tags_str}],"]),
+                dts: ts_block(name, "type ${escape_kw(name)} = ${union_str};"),
+            })
+
+        TagUnion(NonRecursive({ name, tags, discriminant_size, discriminant_offset })) ->
+            if List.is_empty(tags) then
+                Err(Builtin)
+            else
+                Ok(generate_tag_union(types, id, name, tags, discriminant_size, discriminant_offset))
+
+        TagUnion(SingleTagStruct({ name, tag_name, payload: HasNoClosure(fields) })) ->
+            positions =
+                List.map_with_index(fields, \{ id: field_id }, index ->
+                    { key: Num.to_str(index), id: field_id })
+            payload_type =
+                when fields is
+                    [] -> ""
+                    [{ id: field_id }] -> ", ${ts_type(types, field_id)}"
+                    _ ->
+                        field_types =
+                            List.map(fields, \{ id: field_id } -> ts_type(types, field_id))
+                            |> Str.join_with(", ")
+
+                        ", [${field_types}]"
+
+            Ok({
+                js: generate_codec(name, "singleTag", List.concat(layout_lines(types, id, positions), ["  tag: ${quoted(tag_name)},"])),
+                dts: ts_block(name, "type ${escape_kw(name)} = Tag<${quoted(tag_name)}${payload_type}>;"),
+            })
+
+        TagUnion(SingleTagStruct({ name })) ->
+            unsupported("single-tag unions with functions in them, like ${name}")
+
+        TagUnion(Recursive({ name })) | TagUnion(NullableWrapped({ name })) | TagUnion(NullableUnwrapped({ name })) | TagUnion(NonNullableUnwrapped({ name })) ->
+            unsupported("recursive tag unions, like ${name}")
+
+        Function(roc_fn) ->
+            if roc_fn.is_toplevel then
+                # Entry points are wrapped by `bind`, at the end of the module.
+                Err(Builtin)
+            else
+                function_name = roc_fn.function_name
+
+                unsupported("functions other than entry points, like ${function_name}")
+
+        Unit
+        | Unsized
+        | EmptyTagUnion
+        | Num(_)
+        | Bool
+        | RocResult(_, _)
+        | RocStr
+        | RocDict(_, _)
+        | RocSet(_)
+        | RocList(_)
+        | RocBox(_)
+        | RecursivePointer(_) ->
+            # These are all in roc_std.js, or (for RecursivePointer) part of an unsupported type.
+            Err(Builtin)
+
+struct_fields : RocStructFields -> List { name : Str, id : TypeId }
+struct_fields = \fields ->
+    when fields is
+        HasNoClosure(no_closure_fields) -> no_closure_fields
+        HasClosure(_) -> unsupported("records with functions in them")
+
+payload_id : [Some TypeId, None] -> Result TypeId [NoPayload]
+payload_id = \payload ->
+    when payload is
+        Some(id) -> Ok(id)
+        None -> Err(NoPayload)
+
+position_of : Str -> U64
+position_of = \name ->
+    Str.to_u64(name) |> Result.with_default(0)
+
+unsupported = \what ->
+    crash("The TypeScript glue doesn't support ${what} yet.")
+
+## The size, alignment and fields of a record-like codec. Each field has its offset, which
+## comes from the layout Roc uses on wasm32.
+layout_lines : Types, TypeId, List { key : Str, id : TypeId } -> List Str
+layout_lines = \types, id, fields ->
+    field_lines =
+        List.walk(fields, { lines: [], offset: 0 }, \{ lines, offset }, field ->
+            field_offset = next_multiple_of(offset, Types.alignment(types, field.id))
+            offset_str = Num.to_str(field_offset)
+            codec = codec_name(types, field.id)
+
+            {
+                lines: List.append(lines, "    [${field.key}, ${offset_str}, ${codec}],"),
+                offset: field_offset + Types.size(types, field.id),
+            })
+        |> .lines
+
+    size_lines(types, id)
+    |> List.append("  fields: [")
+    |> List.concat(field_lines)
+    |> List.append("  ],")
+
+size_lines : Types, TypeId -> List Str
+size_lines = \types, id ->
+    size_str = Num.to_str(Types.size(types, id))
+    align_str = Num.to_str(Types.alignment(types, id))
+
+    ["  size: ${size_str},", "  align: ${align_str},"]
+
+generate_tag_union : Types, TypeId, Str, List { name : Str, payload : [Some TypeId, None] }, U32, U32 -> { js : Str, dts : Str }
+generate_tag_union = \types, id, name, tags, discriminant_size, discriminant_offset ->
+    offset_str = Num.to_str(discriminant_offset)
+    discriminant_size_str = Num.to_str(discriminant_size)
+
+    tag_lines =
+        List.map(tags, \{ name: tag_name, payload } ->
+            codec =
+                when payload is
+                    Some(payload_type_id) -> codec_name(types, payload_type_id)
+                    None -> "null"
+
+            "    [${quoted(tag_name)}, ${codec}],")
+
+    ts_tags =
+        List.map(tags, \{ name: tag_name, payload } ->
+            when payload is
+                Some(payload_type_id) -> "Tag<${quoted(tag_name)}, ${ts_type(types, payload_type_id)}>"
+                None -> "Tag<${quoted(tag_name)}>")
+        |> Str.join_with(" | ")
+
+    js_lines =
+        size_lines(types, id)
+        |> List.concat(["  discriminantOffset: ${offset_str},", "  discriminantSize: ${discriminant_size_str},", "  tags: ["])
+        |> List.concat(tag_lines)
+        |> List.append("  ],")
+
+    {
+        js: generate_codec(name, "tagUnion", js_lines),
+        dts: ts_block(name, "type ${escape_kw(name)} = ${ts_tags};"),
+    }
+
+generate_codec : Str, Str, List Str -> Str
+generate_codec = \name, constructor, lines ->
+    const_name = escape_kw(name)
+    body = Str.join_with(lines, "\n")
+
+    "\nexport const ${const_name} = roc.${constructor}({\n${body}\n});\n"
+
+## The type of a value, and the type of its codec, which has the same name.
+ts_block : Str, Str -> Str
+ts_block = \name, type_declaration ->
+    const_name = escape_kw(name)
+
+    "\nexport ${type_declaration}\nexport declare const ${const_name}: Codec<${const_name}>;\n"
+
+## Each entry point becomes a method of the object `bind` returns.
+generate_bind_js : Types, List [T Str TypeId] -> Str
+generate_bind_js = \types, entry_points ->
+    methods =
+        List.map(entry_points, \T(name, id) ->
+            { arg_ids, ret_id } = entry_point_signature(types, name, id)
+            params = arg_names(arg_ids)
+            arg_codecs =
+                List.map(arg_ids, \arg_id -> codec_name(types, arg_id))
+                |> Str.join_with(", ")
+            ret_codec = codec_name(types, ret_id)
+
+            """
+
+                ${name}(${params}) {
+                  return roc.call(memory, "roc__${name}_1_exposed_generic", ${ret_codec}, [${arg_codecs}], [${params}]);
+                },
+            """)
+        |> Str.join_with("")
+
+    """
+
+    /** Wraps the entry points of an instantiated Roc wasm module. */
+    export function bind(exports) {
+      const memory = new roc.RocMemory(exports);
+
+      return {
+        memory,${methods}
+      };
+    }
+
+    """
+
+generate_bind_dts : Types, List [T Str TypeId] -> Str
+generate_bind_dts = \types, entry_points ->
+    methods =
+        List.map(entry_points, \T(name, id) ->
+            { arg_ids, ret_id } = entry_point_signature(types, name, id)
+            params =
+                List.map_with_index(arg_ids, \arg_id, index ->
+                    index_str = Num.to_str(index)
+                    arg_type = ts_type(types, arg_id)
+
+                    "arg${index_str}: ${arg_type}")
+                |> Str.join_with(", ")
+            ret_type = ts_type(types, ret_id)
+
+            "\n  ${name}(${params}): ${ret_type};")
+        |> Str.join_with("")
+
+    """
+
+    /** The entry points of the Roc app, which take ownership of their arguments. */
+    export interface RocApp {
+      memory: RocMemory;${methods}
+    }
+
+    /**
+     * Wraps the entry points of an instantiated Roc wasm module. Besides its entry points, the
+     * module has to export `memory`, `roc_alloc` and `roc_dealloc`.
+     */
+    export declare function bind(exports: WebAssembly.Exports): RocApp;
+
+    """
+
+entry_point_signature : Types, Str, TypeId -> { arg_ids : List TypeId, ret_id : TypeId }
+entry_point_signature = \types, name, id ->
+    when Types.shape(types, id) is
+        Function(roc_fn) ->
+            when Types.shape(types, roc_fn.ret) is
+                Function(_) -> unsupported("entry points that return functions, like ${name}")
+                _ ->
+                    # `{}` arguments aren't passed at all.
+                    { arg_ids: List.drop_if(roc_fn.args, \arg_id -> is_unit(Types.shape(types, arg_id))), ret_id: roc_fn.ret }
+
+        _ ->
+            { arg_ids: [], ret_id: id }
+
+arg_names : List TypeId -> Str
+arg_names = \arg_ids ->
+    List.map_with_index(arg_ids, \_, index ->
+        index_str = Num.to_str(index)
+
+        "arg${index_str}")
+    |> Str.join_with(", ")
+
+is_unit : Shape -> Bool
+is_unit = \shape ->
+    when shape is
+        Unit -> Bool.true
+        _ -> Bool.false
+
+codec_name : Types, TypeId -> Str
+codec_name = \types, id ->
+    when Types.shape(types, id) is
+        Unit | EmptyTagUnion -> "roc.Unit"
+        RocStr -> "roc.Str"
+        Bool -> "roc.Bool"
+        Num(U8) -> "roc.U8"
+        Num(U16) -> "roc.U16"
+        Num(U32) -> "roc.U32"
+        Num(U64) -> "roc.U64"
+        Num(U128) -> "roc.U128"
+        Num(I8) -> "roc.I8"
+        Num(I16) -> "roc.I16"
+        Num(I32) -> "roc.I32"
+        Num(I64) -> "roc.I64"
+        Num(I128) -> "roc.I128"
+        Num(F32) -> "roc.F32"
+        Num(F64) -> "roc.F64"
+        Num(Dec) -> "roc.Dec"
+        RocList(elem) -> "roc.List(${codec_name(types, elem)})"
+        RocSet(elem) -> "roc.Set(${codec_name(types, elem)})"
+        RocBox(elem) -> "roc.Box(${codec_name(types, elem)})"
+        RocDict(key, value) -> "roc.Dict(${codec_name(types, key)}, ${codec_name(types, value)})"
+        RocResult(ok, err) -> "roc.Result(${codec_name(types, ok)}, ${codec_name(types, err)})"
+        _ -> escape_kw(named_type(types, id))
+
+ts_type : Types, TypeId -> Str
+ts_type = \types, id ->
+    when Types.shape(types, id) is
+        Unit -> "null"
+        EmptyTagUnion -> "never"
+        RocStr -> "string"
+        Bool -> "boolean"
+        Num(U8) | Num(U16) | Num(U32) | Num(I8) | Num(I16) | Num(I32) | Num(F32) | Num(F64) -> "number"
+        Num(U64) | Num(U128) | Num(I64) | Num(I128) -> "bigint"
+        Num(Dec) -> "string"
+        RocList(elem) -> "Array<${ts_type(types, elem)}>"
+        RocSet(elem) -> "Set<${ts_type(types, elem)}>"
+        RocBox(elem) -> ts_type(types, elem)
+        RocDict(key, value) -> "Map<${ts_type(types, key)}, ${ts_type(types, value)}>"
+        RocResult(ok, err) -> "Tag<\"Ok\", ${ts_type(types, ok)}> | Tag<\"Err\", ${ts_type(types, err)}>"
+        _ -> escape_kw(named_type(types, id))
+
+named_type : Types, TypeId -> Str
+named_type = \types, id ->
+    when Types.shape(types, id) is
+        Struct({ name }) -> name
+        TagUnionPayload({ name }) -> name
+        TagUnion(NonRecursive({ name })) -> name
+        TagUnion(Recursive({ name })) -> name
+        TagUnion(Enumeration({ name })) -> name
+        TagUnion(NullableWrapped({ name })) -> name
+        TagUnion(NullableUnwrapped({ name })) -> name
+        TagUnion(NonNullableUnwrapped({ name })) -> name
+        TagUnion(SingleTagStruct({ name })) -> name
+        Function({ function_name }) -> unsupported("functions other than entry points, like ${function_name}")
+        RecursivePointer(_) -> unsupported("recursive types")
+        Unsized -> unsupported("unsized types")
+        _ -> crash("named_type was called on a builtin type")
+
+quoted : Str -> Str
+quoted = \str -> "\"${str}\""
+
+next_multiple_of = \lhs, rhs ->
+    if rhs == 0 then
+        lhs
+    else
+        when lhs % rhs is
+            0 -> lhs
+            r -> lhs + (rhs - r)
+
+reserved_keywords = Set.from_list([
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+    # These would shadow the types of the same names in roc_app.d.ts.
+    "Array",
+    "Codec",
+    "Map",
+    "RocApp",
+    "RocMemory",
+    "Set",
+    "Tag",
+])
+
+escape_kw = \input ->
+    if Set.contains(reserved_keywords, input) then
+        "${input}_"
+    else
+        input
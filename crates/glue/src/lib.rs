@@ -7,6 +7,7 @@ pub mod load;
 pub mod roc_type;
 pub mod structs;
 pub mod types;
+pub mod verify;
 
 pub use load::generate;
 
@@ -42,6 +42,7 @@ pub fn generate(
     backend: CodeGenBackend,
     link_type: LinkType,
     linking_strategy: LinkingStrategy,
+    verify: bool,
 ) -> io::Result<i32> {
     let target = Triple::host().into();
     // TODO: Add verification around the paths. Make sure they have the correct file extension and what not.
@@ -61,6 +62,7 @@ pub fn generate(
                 emit_debug_info: false,
                 emit_llvm_ir: false,
                 fuzz: false,
+                export_dict_seed: false,
             };
 
             let load_config = standard_load_config(
@@ -186,7 +188,11 @@ pub fn generate(
                         output_path.display()
                     );
 
-                    Ok(0)
+                    if verify {
+                        verify_glue(output_path)
+                    } else {
+                        Ok(0)
+                    }
                 }
                 Err(BuildFileError::ErrorModule { module, total_time }) => {
                     handle_error_module(module, total_time)
@@ -217,6 +223,22 @@ pub fn generate(
     }
 }
 
+fn verify_glue(output_path: &Path) -> io::Result<i32> {
+    match crate::verify::verify(output_path) {
+        Ok(true) => {
+            println!("\n✅ The host's compiler lays out the glue's types the way Roc does.");
+
+            Ok(0)
+        }
+        Ok(false) => Ok(1),
+        Err(err) => {
+            eprintln!("Unable to verify the glue: {err}");
+
+            Ok(1)
+        }
+    }
+}
+
 fn call_roc_make_glue(
     lib: &Library,
     backend: CodeGenBackend,
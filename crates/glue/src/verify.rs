@@ -0,0 +1,127 @@
+//! `roc glue --verify`: compiles a small probe of the generated glue with the host's own
+//! compiler. The glue asserts at compile time that each type has the size, alignment and field
+//! offsets Roc uses, so the probe compiling means the host lays the types out the way Roc does.
+use std::ffi::OsString;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Generated glue that `--verify` knows how to compile.
+enum Probe {
+    /// The `roc_app` crate that RustGlue.roc generates.
+    RustCrate { manifest: PathBuf },
+    /// A C header, like the one CGlue.roc generates.
+    CHeader { header: PathBuf },
+}
+
+/// Compiles every probe for the glue in `glue_dir`, returning whether they all compiled.
+pub fn verify(glue_dir: &Path) -> io::Result<bool> {
+    let probes = find_probes(glue_dir)?;
+
+    if probes.is_empty() {
+        return Err(io::Error::new(
+            ErrorKind::NotFound,
+            format!(
+                "There's no glue that `--verify` knows how to compile in {}. It can compile a `roc_app` Rust crate and C headers.",
+                glue_dir.display()
+            ),
+        ));
+    }
+
+    // Build outputs go here, so they don't end up next to the glue.
+    let scratch_dir = tempfile::tempdir()?;
+    let mut all_compiled = true;
+
+    for probe in probes {
+        let mut command = probe_command(&probe, scratch_dir.path())?;
+
+        println!("Verifying the layouts in {}…", probe.path().display());
+
+        if !command.status()?.success() {
+            eprintln!(
+                "\n{} doesn't compile with the host's compiler, so its layouts don't match Roc's. The failed assertions above say which types differ.\n",
+                probe.path().display()
+            );
+
+            all_compiled = false;
+        }
+    }
+
+    Ok(all_compiled)
+}
+
+impl Probe {
+    fn path(&self) -> &Path {
+        match self {
+            Probe::RustCrate { manifest } => manifest.parent().unwrap_or(manifest),
+            Probe::CHeader { header } => header,
+        }
+    }
+}
+
+fn find_probes(glue_dir: &Path) -> io::Result<Vec<Probe>> {
+    let mut probes = Vec::new();
+    let manifest = glue_dir.join("roc_app").join("Cargo.toml");
+
+    if manifest.is_file() {
+        probes.push(Probe::RustCrate { manifest });
+    }
+
+    let mut headers = Vec::new();
+
+    for entry in std::fs::read_dir(glue_dir)? {
+        let path = entry?.path();
+
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "h") {
+            headers.push(path);
+        }
+    }
+
+    // read_dir's order depends on the file system.
+    headers.sort();
+    probes.extend(headers.into_iter().map(|header| Probe::CHeader { header }));
+
+    Ok(probes)
+}
+
+fn probe_command(probe: &Probe, scratch_dir: &Path) -> io::Result<Command> {
+    match probe {
+        Probe::RustCrate { manifest } => {
+            let mut command = Command::new(env_or("CARGO", "cargo"));
+
+            command
+                .arg("check")
+                .arg("--quiet")
+                .arg("--manifest-path")
+                .arg(manifest)
+                .arg("--target-dir")
+                .arg(scratch_dir.join("target"));
+
+            Ok(command)
+        }
+        Probe::CHeader { header } => {
+            // Including the header in an otherwise empty file is enough for the compiler to
+            // check its assertions.
+            let header = header.canonicalize()?;
+            let probe_file = scratch_dir.join("probe.c");
+
+            std::fs::write(&probe_file, c_probe_source(&header))?;
+
+            let mut command = Command::new(env_or("CC", "cc"));
+
+            command.arg("-std=c11").arg("-fsyntax-only").arg(probe_file);
+
+            Ok(command)
+        }
+    }
+}
+
+fn c_probe_source(header: &Path) -> String {
+    let include_path = header.to_string_lossy().replace('\\', "\\\\");
+
+    format!("#include \"{include_path}\"\n")
+}
+
+fn env_or(var: &str, default: &str) -> OsString {
+    std::env::var_os(var).unwrap_or_else(|| default.into())
+}
@@ -0,0 +1,96 @@
+// The functions a Roc shared library expects its host to provide, for loading it from Python.
+//
+// Compile this into a shared library of its own, for example with
+//
+//     cc -shared -fPIC -o libroc_host.so roc_host.c
+//
+// and pass its path to `roc_app.load` along with the path of the Roc library.
+
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#ifndef _WIN32
+#include <sys/mman.h>
+#include <sys/stat.h>
+#include <fcntl.h>
+#include <unistd.h>
+#endif
+
+#ifdef _WIN32
+#define ROC_HOST_EXPORT __declspec(dllexport)
+#else
+#define ROC_HOST_EXPORT
+#endif
+
+struct RocStr {
+    char *bytes;
+    size_t length;
+    size_t capacity;
+};
+
+// Allocations that haven't been freed yet, so Python can check for leaks.
+static size_t live_allocations = 0;
+
+ROC_HOST_EXPORT size_t roc_host_live_allocations(void) { return live_allocations; }
+
+ROC_HOST_EXPORT void *roc_alloc(size_t size, unsigned int alignment) {
+    live_allocations++;
+
+    return malloc(size);
+}
+
+ROC_HOST_EXPORT void *roc_realloc(void *ptr, size_t new_size, size_t old_size, unsigned int alignment) {
+    return realloc(ptr, new_size);
+}
+
+ROC_HOST_EXPORT void roc_dealloc(void *ptr, unsigned int alignment) {
+    live_allocations--;
+
+    free(ptr);
+}
+
+static void print_roc_str(FILE *file, struct RocStr *str) {
+    // Small strings are stored inline, with their length in the last byte.
+    if ((intptr_t)str->capacity < 0) {
+        size_t length = ((unsigned char *)str)[sizeof(struct RocStr) - 1] ^ 0x80;
+
+        fwrite(str, 1, length, file);
+    } else {
+        fwrite(str->bytes, 1, str->length & (SIZE_MAX >> 1), file);
+    }
+}
+
+// Roc can't unwind through Python, so a panic ends the process.
+ROC_HOST_EXPORT void roc_panic(struct RocStr *msg, unsigned int tag_id) {
+    fprintf(stderr, tag_id == 0 ? "Roc standard library hit a panic: " : "Application hit a panic: ");
+    print_roc_str(stderr, msg);
+    fprintf(stderr, "\n");
+
+    exit(1);
+}
+
+ROC_HOST_EXPORT void roc_dbg(struct RocStr *loc, struct RocStr *msg, struct RocStr *src) {
+    fprintf(stderr, "[");
+    print_roc_str(stderr, loc);
+    fprintf(stderr, "] ");
+    print_roc_str(stderr, src);
+    fprintf(stderr, " = ");
+    print_roc_str(stderr, msg);
+    fprintf(stderr, "\n");
+}
+
+ROC_HOST_EXPORT void *roc_memset(void *dst, int c, size_t n) { return memset(dst, c, n); }
+
+#ifdef _WIN32
+ROC_HOST_EXPORT int roc_getppid(void) { return 0; }
+#else
+ROC_HOST_EXPORT int roc_getppid(void) { return getppid(); }
+
+ROC_HOST_EXPORT int roc_shm_open(char *name, int oflag, int mode) { return shm_open(name, oflag, mode); }
+
+ROC_HOST_EXPORT void *roc_mmap(void *addr, size_t length, int prot, int flags, int fd, off_t offset) {
+    return mmap(addr, length, prot, flags, fd, offset);
+}
+#endif
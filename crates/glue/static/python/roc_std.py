@@ -0,0 +1,870 @@
+"""Python representations of Roc's builtin types, built on ctypes.
+
+The modules that `roc glue` generates with PythonGlue.roc describe a platform's types in
+terms of these. Each Roc type is a ctypes type with the same memory layout, so values can
+be passed to and returned from the Roc shared library as they are.
+
+Every Roc type also has two conversions:
+
+- `from_python(value)` (a classmethod) builds a Roc value from a Python value. Anything it
+  allocates is allocated with `roc_alloc`, so it can be handed to Roc, which takes
+  ownership of it.
+- `to_python()` copies a Roc value into plain Python values: `str`, `int`, `float`, `bool`,
+  `list`, `dict`, `set`, and `Tag` for tag unions.
+
+Building dictionaries and sets from Python is out of scope: finding their entries needs
+Roc's hashing, so `RocDict.from_python` and `RocSet.from_python` raise `TypeError`. To pass
+one to Roc, pass a list instead, and build the dictionary or set with `Dict.from_list` or
+`Set.from_list` in Roc. Dictionaries and sets that Roc returns convert to Python with
+`to_python()` as usual.
+
+A Roc value that Python owns (like one returned by Roc, or one built with `from_python` that
+wasn't handed to Roc) has to be given back with `release(value)` once it's no longer needed,
+which decrements its reference counts and frees whatever is no longer referenced. Using it as
+`with owned(value):` does that at the end of the block. Roc values don't release themselves
+in `__del__`, because ctypes copies a value's pointers whenever it's stored or read, so a
+value being garbage collected doesn't mean nothing else points at its allocations. The
+generated entry point wrappers do all of this, so most code never needs to.
+"""
+
+import contextlib
+import ctypes
+import decimal
+from typing import Any, NamedTuple
+
+_POINTER_SIZE = ctypes.sizeof(ctypes.c_void_p)
+
+# The high bit of a Str or List's length marks it as a seamless slice of another allocation.
+_SEAMLESS_SLICE_BIT = 1 << (_POINTER_SIZE * 8 - 1)
+_POINTER_MASK = (1 << (_POINTER_SIZE * 8)) - 1
+
+# A reference count of 0 marks a constant that's never freed, like a string literal.
+_REFCOUNT_CONSTANT = 0
+_REFCOUNT_ONE = 1
+
+_host = None
+_library = None
+
+
+def load(library_path, host_path):
+    """Loads a Roc shared library (built with `roc build --lib`) and the host functions it needs.
+
+    `host_path` is the shared library compiled from the `roc_host.c` that `roc glue` generates
+    next to this package. It has to be loaded first, and globally, because the Roc library
+    calls its `roc_alloc`, `roc_dealloc`, `roc_panic` and so on.
+    """
+    global _host, _library
+
+    _host = ctypes.CDLL(host_path, mode=ctypes.RTLD_GLOBAL)
+    _host.roc_alloc.argtypes = [ctypes.c_size_t, ctypes.c_uint32]
+    _host.roc_alloc.restype = ctypes.c_void_p
+    _host.roc_dealloc.argtypes = [ctypes.c_void_p, ctypes.c_uint32]
+    _host.roc_dealloc.restype = None
+    _host.roc_host_live_allocations.argtypes = []
+    _host.roc_host_live_allocations.restype = ctypes.c_size_t
+
+    _library = ctypes.CDLL(library_path)
+
+    return _library
+
+
+def library():
+    """The Roc shared library passed to `load`."""
+    if _library is None:
+        raise RuntimeError("No Roc library has been loaded yet. Call `load` first.")
+
+    return _library
+
+
+def live_allocations():
+    """How many allocations made by `roc_alloc` haven't been freed yet, to check for leaks."""
+    return _host.roc_host_live_allocations()
+
+
+class Tag(NamedTuple):
+    """A tag of a tag union, like `Rectangle 2.0 3.5` or `Ok "done"`.
+
+    `payload` is `None` for a tag without a payload, the value itself for a tag with one,
+    and a tuple of the values for a tag with more than one.
+    """
+
+    name: str
+    payload: Any = None
+
+
+def to_python(value):
+    """Copies a Roc value into plain Python values."""
+    convert = getattr(value, "to_python", None)
+
+    if convert is None:
+        # ctypes already converts numbers and Bools when they're read.
+        return value
+
+    return convert()
+
+
+def from_python(roc_type, value):
+    """Builds a value of the given Roc type from a Python value."""
+    convert = getattr(roc_type, "from_python", None)
+
+    if convert is None:
+        return roc_type(value)
+
+    return convert(value)
+
+
+def release(value):
+    """Gives back a Roc value that Python owns, freeing whatever is no longer referenced."""
+    release_value = getattr(value, "release", None)
+
+    if release_value is not None:
+        release_value()
+
+
+@contextlib.contextmanager
+def owned(value):
+    """Gives back a Roc value that Python owns at the end of a `with` block.
+
+    ```python
+    with owned(RocStr.from_python("hello")) as greeting:
+        library().shout(greeting)
+    ```
+    """
+    try:
+        yield value
+    finally:
+        release(value)
+
+
+def take(value):
+    """Converts a Roc value that Python owns to Python values, then releases it."""
+    result = to_python(value)
+
+    release(value)
+
+    return result
+
+
+def alignment(roc_type):
+    """The alignment Roc uses for a type, which can be larger than the one ctypes uses."""
+    return getattr(roc_type, "_roc_alignment_", None) or ctypes.alignment(roc_type)
+
+
+def is_refcounted(roc_type):
+    """Whether values of the type (or values inside them) are reference counted."""
+    return getattr(roc_type, "_roc_refcounted_", False)
+
+
+def _read_usize(address):
+    return ctypes.c_size_t.from_address(address).value
+
+
+def _write_usize(address, value):
+    ctypes.c_size_t.from_address(address).value = value
+
+
+def _allocate_refcounted(data_bytes, element_alignment, elements_refcounted, element_count):
+    """Allocates `data_bytes` with a reference count of 1 in front, like Roc's builtins do.
+
+    Returns the address of the data.
+    """
+    alloc_alignment = max(_POINTER_SIZE, element_alignment)
+    required_space = 2 * _POINTER_SIZE if elements_refcounted else _POINTER_SIZE
+    extra_bytes = max(required_space, element_alignment)
+
+    allocation = _host.roc_alloc(extra_bytes + data_bytes, alloc_alignment)
+
+    if not allocation:
+        raise MemoryError("roc_alloc returned a null pointer")
+
+    data = allocation + extra_bytes
+    _write_usize(data - _POINTER_SIZE, _REFCOUNT_ONE)
+
+    if elements_refcounted:
+        # Seamless slices of the allocation read the element count from here to free it.
+        _write_usize(data - 2 * _POINTER_SIZE, element_count)
+
+    return data
+
+
+def _decrement(refcount_address, free):
+    """Decrements a reference count, calling `free` if it was the last reference."""
+    refcount = ctypes.c_ssize_t.from_address(refcount_address)
+
+    if refcount.value == _REFCOUNT_CONSTANT:
+        return
+
+    if refcount.value == _REFCOUNT_ONE:
+        free()
+    else:
+        refcount.value -= 1
+
+
+class RocUnit(ctypes.Structure):
+    """`{}`, and other types that don't take up any memory."""
+
+    _fields_ = []
+
+    def to_python(self):
+        return None
+
+    @classmethod
+    def from_python(cls, value):
+        return cls()
+
+
+class RocStr(ctypes.Structure):
+    """A Roc `Str`."""
+
+    _fields_ = [
+        ("bytes", ctypes.c_void_p),
+        ("length", ctypes.c_size_t),
+        ("capacity_or_alloc_ptr", ctypes.c_size_t),
+    ]
+    _roc_refcounted_ = True
+
+    def _is_small(self):
+        # Small strings are stored inline, with their length in the last byte and its high
+        # bit set, which makes the capacity negative.
+        return self.capacity_or_alloc_ptr & _SEAMLESS_SLICE_BIT != 0
+
+    def _is_seamless_slice(self):
+        return self.length & _SEAMLESS_SLICE_BIT != 0
+
+    def __len__(self):
+        if self._is_small():
+            return ctypes.string_at(ctypes.addressof(self), ctypes.sizeof(self))[-1] ^ 0x80
+
+        return self.length & ~_SEAMLESS_SLICE_BIT
+
+    def to_bytes(self):
+        if self._is_small():
+            return ctypes.string_at(ctypes.addressof(self), len(self))
+
+        if not self.bytes:
+            return b""
+
+        return ctypes.string_at(self.bytes, len(self))
+
+    def to_python(self):
+        return self.to_bytes().decode("utf-8")
+
+    def __str__(self):
+        return self.to_python()
+
+    def __repr__(self):
+        return f"RocStr({self.to_python()!r})"
+
+    @classmethod
+    def from_python(cls, value):
+        utf8 = value.encode("utf-8") if isinstance(value, str) else bytes(value)
+        size = ctypes.sizeof(cls)
+
+        if len(utf8) < size:
+            inline = bytearray(size)
+            inline[: len(utf8)] = utf8
+            inline[-1] = len(utf8) | 0x80
+
+            return cls.from_buffer_copy(inline)
+
+        data = _allocate_refcounted(len(utf8), 1, False, 0)
+        ctypes.memmove(data, utf8, len(utf8))
+
+        return cls(data, len(utf8), len(utf8))
+
+    def release(self):
+        if self._is_small() or not self.bytes:
+            return
+
+        if self._is_seamless_slice():
+            allocation_data = (self.capacity_or_alloc_ptr << 1) & _POINTER_MASK
+        else:
+            allocation_data = self.bytes
+
+        refcount_address = allocation_data - _POINTER_SIZE
+
+        _decrement(refcount_address, lambda: _host.roc_dealloc(refcount_address, 1))
+
+
+class _RocListBase(ctypes.Structure):
+    _fields_ = [
+        ("elements", ctypes.c_void_p),
+        ("length", ctypes.c_size_t),
+        # The address of the allocation's elements (shifted right by 1) for seamless slices.
+        ("capacity_or_ref_ptr", ctypes.c_size_t),
+    ]
+    _roc_refcounted_ = True
+    _element_type_ = None
+
+    def __len__(self):
+        return self.length & ~_SEAMLESS_SLICE_BIT
+
+    def __getitem__(self, index):
+        return self.elements_view()[index]
+
+    def elements_view(self):
+        """The elements as a ctypes array that points into the list's memory."""
+        if not self.elements:
+            return (self._element_type_ * 0)()
+
+        return (self._element_type_ * len(self)).from_address(self.elements)
+
+    def to_python(self):
+        return [to_python(element) for element in self.elements_view()]
+
+    def __repr__(self):
+        return f"{type(self).__name__}({self.to_python()!r})"
+
+    @classmethod
+    def from_python(cls, values):
+        values = list(values)
+
+        if not values:
+            return cls()
+
+        element_type = cls._element_type_
+        data = _allocate_refcounted(
+            len(values) * ctypes.sizeof(element_type),
+            alignment(element_type),
+            is_refcounted(element_type),
+            len(values),
+        )
+        elements = (element_type * len(values)).from_address(data)
+
+        for index, value in enumerate(values):
+            elements[index] = from_python(element_type, value)
+
+        return cls(data, len(values), len(values))
+
+    def release(self):
+        if not self.elements:
+            return
+
+        element_type = self._element_type_
+        elements_refcounted = is_refcounted(element_type)
+        element_alignment = alignment(element_type)
+
+        if self.length & _SEAMLESS_SLICE_BIT:
+            allocation_data = (self.capacity_or_ref_ptr << 1) & _POINTER_MASK
+            element_count = (
+                _read_usize(allocation_data - 2 * _POINTER_SIZE) if elements_refcounted else 0
+            )
+        else:
+            allocation_data = self.elements
+            element_count = len(self)
+
+        required_space = 2 * _POINTER_SIZE if elements_refcounted else _POINTER_SIZE
+        allocation = allocation_data - max(required_space, element_alignment)
+
+        def free():
+            if elements_refcounted:
+                for element in (element_type * element_count).from_address(allocation_data):
+                    release(element)
+
+            _host.roc_dealloc(allocation, max(_POINTER_SIZE, element_alignment))
+
+        _decrement(allocation_data - _POINTER_SIZE, free)
+
+
+def _type_name(roc_type):
+    return getattr(roc_type, "__name__", repr(roc_type))
+
+
+_list_types = {}
+
+
+def RocList(element_type):
+    """The type of a Roc `List` of the given element type."""
+    list_type = _list_types.get(element_type)
+
+    if list_type is None:
+        list_type = type(
+            f"RocList({_type_name(element_type)})",
+            (_RocListBase,),
+            {"_element_type_": element_type},
+        )
+        _list_types[element_type] = list_type
+
+    return list_type
+
+
+def _lay_out(fields):
+    """Lays out (name, type) pairs the way Roc does, returning `_fields_` and the alignment.
+
+    Roc puts fields with larger alignments first, and keeps the given order otherwise. Padding
+    is explicit because Roc aligns some types (like 128-bit integers) more than ctypes does.
+    """
+    ordered = sorted(fields, key=lambda field: -alignment(field[1]))
+    struct_alignment = max((alignment(field_type) for _, field_type in ordered), default=1)
+    layout = []
+    offset = 0
+
+    def pad(to_alignment):
+        nonlocal offset
+        padding = -offset % to_alignment
+
+        if padding:
+            layout.append((f"_padding{len(layout)}", ctypes.c_uint8 * padding))
+            offset += padding
+
+    for name, field_type in ordered:
+        pad(alignment(field_type))
+        layout.append((name, field_type))
+        offset += ctypes.sizeof(field_type)
+
+    pad(struct_alignment)
+
+    return layout, struct_alignment
+
+
+class RocStruct(ctypes.Structure):
+    """A Roc record. `to_python` makes a `dict` of its fields."""
+
+    # The names of the fields, without the padding between them.
+    _roc_fields_ = ()
+
+    def to_python(self):
+        return {name: to_python(getattr(self, name)) for name in self._roc_fields_}
+
+    def __repr__(self):
+        return f"{type(self).__name__}({self.to_python()!r})"
+
+    @classmethod
+    def from_python(cls, value):
+        missing = [name for name in cls._roc_fields_ if name not in value]
+
+        if missing:
+            raise ValueError(f"{cls.__name__} is missing the fields {', '.join(missing)}")
+
+        result = cls()
+
+        for name, field_type in cls._fields_:
+            if name in cls._roc_fields_:
+                setattr(result, name, from_python(field_type, value[name]))
+
+        return result
+
+    def release(self):
+        for name in self._roc_fields_:
+            release(getattr(self, name))
+
+
+class RocTagPayload(RocStruct):
+    """The payload of a tag with more than one value. `to_python` makes a tuple of them."""
+
+    def to_python(self):
+        return tuple(to_python(getattr(self, name)) for name in self._roc_fields_)
+
+    @classmethod
+    def from_python(cls, value):
+        values = tuple(value)
+
+        if len(values) != len(cls._roc_fields_):
+            raise ValueError(
+                f"{cls.__name__} has {len(cls._roc_fields_)} values, but got {len(values)}"
+            )
+
+        return super().from_python(dict(zip(cls._roc_fields_, values)))
+
+
+class RocSingleTagUnion(RocStruct):
+    """A tag union with one tag, like `[Wrapper Str U64]`. `to_python` makes a `Tag`."""
+
+    # The name of the tag.
+    _roc_tag_ = ""
+
+    def to_python(self):
+        values = tuple(to_python(getattr(self, name)) for name in self._roc_fields_)
+
+        if not values:
+            return Tag(self._roc_tag_)
+
+        return Tag(self._roc_tag_, values[0] if len(values) == 1 else values)
+
+    def __repr__(self):
+        return f"{type(self).__name__}({self.to_python()!r})"
+
+    @classmethod
+    def from_python(cls, value):
+        name, payload = _tag(value)
+
+        if name != cls._roc_tag_:
+            raise ValueError(f"{name} is not the tag of {cls.__name__}, {cls._roc_tag_}")
+
+        values = (payload,) if len(cls._roc_fields_) == 1 else tuple(payload or ())
+
+        if len(values) != len(cls._roc_fields_):
+            raise ValueError(
+                f"{cls.__name__} has {len(cls._roc_fields_)} values, but got {len(values)}"
+            )
+
+        return super().from_python(dict(zip(cls._roc_fields_, values)))
+
+
+def _tag(value):
+    """Accepts a `Tag`, a `(name, payload)` pair, or just the name of a tag without a payload."""
+    if isinstance(value, str):
+        return Tag(value)
+
+    name, payload = value
+
+    return Tag(name, payload)
+
+
+class RocEnumeration:
+    """A tag union whose tags have no payloads, like `[Red, Green, Blue]`.
+
+    Mixed into the ctypes integer type of the right size. `to_python` gives the tag's name.
+    """
+
+    # The names of the tags, in the order of their discriminants.
+    _roc_tags_ = ()
+
+    def to_python(self):
+        return self._roc_tags_[self.value]
+
+    def __repr__(self):
+        return f"{type(self).__name__}.{self.to_python()}"
+
+    @classmethod
+    def from_python(cls, value):
+        name = _tag(value).name
+
+        try:
+            return cls(cls._roc_tags_.index(name))
+        except ValueError:
+            raise ValueError(f"{name} is not a tag of {cls.__name__}") from None
+
+
+class RocTagUnion(ctypes.Structure):
+    """A tag union with payloads. `to_python` gives a `Tag`.
+
+    Its fields are `payload`, a ctypes union with a field for each tag that has a payload, and
+    `discriminant`, which says which tag it is.
+    """
+
+    # (name, payload type or None) for each tag, in the order of their discriminants.
+    _roc_tags_ = ()
+
+    def to_python(self):
+        name, payload_type = self._roc_tags_[self.discriminant]
+
+        if payload_type is None:
+            return Tag(name)
+
+        return Tag(name, to_python(getattr(self.payload, name)))
+
+    def __repr__(self):
+        return f"{type(self).__name__}({self.to_python()!r})"
+
+    @classmethod
+    def from_python(cls, value):
+        tag = _tag(value)
+
+        for discriminant, (name, payload_type) in enumerate(cls._roc_tags_):
+            if name == tag.name:
+                result = cls()
+                result.discriminant = discriminant
+
+                if payload_type is not None:
+                    setattr(result.payload, name, from_python(payload_type, tag.payload))
+
+                return result
+
+        raise ValueError(f"{tag.name} is not a tag of {cls.__name__}")
+
+    def release(self):
+        name, payload_type = self._roc_tags_[self.discriminant]
+
+        if payload_type is not None:
+            release(getattr(self.payload, name))
+
+
+_result_types = {}
+
+
+def RocResult(ok_type, err_type):
+    """The type of a Roc `Result` with the given payload types."""
+    key = (ok_type, err_type)
+    result_type = _result_types.get(key)
+
+    if result_type is None:
+        payload_alignment = max(alignment(ok_type), alignment(err_type))
+        payload_size = max(ctypes.sizeof(ok_type), ctypes.sizeof(err_type))
+        payload_size += -payload_size % payload_alignment
+        payload_type = type(
+            f"RocResultPayload({_type_name(ok_type)}, {_type_name(err_type)})",
+            (ctypes.Union,),
+            {
+                "_fields_": [
+                    ("Err", err_type),
+                    ("Ok", ok_type),
+                    # Makes the union as big as Roc's, which aligns some types more than ctypes.
+                    ("_bytes", ctypes.c_uint8 * payload_size),
+                ]
+            },
+        )
+        fields = [("payload", payload_type), ("discriminant", ctypes.c_uint8)]
+        padding = -(payload_size + 1) % payload_alignment
+
+        if padding:
+            fields.append(("_padding", ctypes.c_uint8 * padding))
+
+        result_type = type(
+            f"RocResult({_type_name(ok_type)}, {_type_name(err_type)})",
+            (RocTagUnion,),
+            {
+                "_fields_": fields,
+                "_roc_alignment_": payload_alignment,
+                "_roc_refcounted_": is_refcounted(ok_type) or is_refcounted(err_type),
+                "_roc_tags_": (("Err", err_type), ("Ok", ok_type)),
+            },
+        )
+        _result_types[key] = result_type
+
+    return result_type
+
+
+_box_types = {}
+
+
+class _RocBoxBase(ctypes.Structure):
+    _fields_ = [("contents", ctypes.c_void_p)]
+    _roc_refcounted_ = True
+    _contents_type_ = None
+
+    def to_python(self):
+        return to_python(self._contents_type_.from_address(self.contents))
+
+    def __repr__(self):
+        return f"{type(self).__name__}({self.to_python()!r})"
+
+    @classmethod
+    def from_python(cls, value):
+        contents_type = cls._contents_type_
+        contents = _allocate_refcounted(
+            ctypes.sizeof(contents_type), alignment(contents_type), False, 0
+        )
+        roc_value = from_python(contents_type, value)
+        ctypes.memmove(contents, ctypes.addressof(roc_value), ctypes.sizeof(contents_type))
+
+        return cls(contents)
+
+    def release(self):
+        if not self.contents:
+            return
+
+        contents_type = self._contents_type_
+        contents_alignment = alignment(contents_type)
+
+        def free():
+            release(contents_type.from_address(self.contents))
+            _host.roc_dealloc(
+                self.contents - max(_POINTER_SIZE, contents_alignment),
+                max(_POINTER_SIZE, contents_alignment),
+            )
+
+        _decrement(self.contents - _POINTER_SIZE, free)
+
+
+def RocBox(contents_type):
+    """The type of a Roc `Box` of the given type."""
+    box_type = _box_types.get(contents_type)
+
+    if box_type is None:
+        box_type = type(
+            f"RocBox({_type_name(contents_type)})",
+            (_RocBoxBase,),
+            {"_contents_type_": contents_type},
+        )
+        _box_types[contents_type] = box_type
+
+    return box_type
+
+
+class _Bucket(ctypes.Structure):
+    _fields_ = [("data_index", ctypes.c_uint32), ("dist_and_fingerprint", ctypes.c_uint32)]
+
+
+class _RocDictBase(RocStruct):
+    """A Roc `Dict`. Python can't build one: `from_python` raises `TypeError`."""
+
+    _roc_fields_ = ("buckets", "data", "max_bucket_capacity", "max_load_factor", "shifts")
+    _roc_refcounted_ = True
+
+    def items(self):
+        """The (key, value) pairs, converted to Python values, in insertion order."""
+        return [to_python(pair) for pair in self.data.elements_view()]
+
+    def to_python(self):
+        return dict(self.items())
+
+    @classmethod
+    def from_python(cls, value):
+        # Finding entries needs Roc's hashing, so dictionaries can only be built in Roc.
+        raise TypeError(
+            f"{cls.__name__} can't be built in Python. Pass a list of pairs to Roc and use "
+            "Dict.from_list there instead."
+        )
+
+
+class _RocSetBase(ctypes.Structure):
+    """A Roc `Set`. Python can't build one: `from_python` raises `TypeError`."""
+
+    _roc_refcounted_ = True
+
+    def elements(self):
+        """The elements, converted to Python values, in insertion order."""
+        return [key for key, _ in self.dict.items()]
+
+    def to_python(self):
+        return set(self.elements())
+
+    def __repr__(self):
+        return f"{type(self).__name__}({self.elements()!r})"
+
+    @classmethod
+    def from_python(cls, value):
+        raise TypeError(
+            f"{cls.__name__} can't be built in Python. Pass a list to Roc and use "
+            "Set.from_list there instead."
+        )
+
+    def release(self):
+        self.dict.release()
+
+
+class _Pair(ctypes.Structure):
+    """A key and value in a dictionary, which Roc stores as a tuple."""
+
+    _roc_fields_ = ("key", "value")
+
+    def to_python(self):
+        return (to_python(self.key), to_python(self.value))
+
+    def release(self):
+        release(self.key)
+        release(self.value)
+
+
+_dict_types = {}
+
+
+def RocDict(key_type, value_type):
+    """The type of a Roc `Dict` with the given key and value types."""
+    key = (key_type, value_type)
+    dict_type = _dict_types.get(key)
+
+    if dict_type is None:
+        pair_fields, pair_alignment = _lay_out([("key", key_type), ("value", value_type)])
+        pair_type = type(
+            f"RocDictPair({_type_name(key_type)}, {_type_name(value_type)})",
+            (_Pair,),
+            {
+                "_fields_": pair_fields,
+                "_roc_alignment_": pair_alignment,
+                "_roc_refcounted_": is_refcounted(key_type) or is_refcounted(value_type),
+            },
+        )
+        fields, dict_alignment = _lay_out(
+            [
+                ("buckets", RocList(_Bucket)),
+                ("data", RocList(pair_type)),
+                ("max_bucket_capacity", ctypes.c_uint64),
+                ("max_load_factor", ctypes.c_float),
+                ("shifts", ctypes.c_uint8),
+            ]
+        )
+        dict_type = type(
+            f"RocDict({_type_name(key_type)}, {_type_name(value_type)})",
+            (_RocDictBase,),
+            {"_fields_": fields, "_roc_alignment_": dict_alignment},
+        )
+        _dict_types[key] = dict_type
+
+    return dict_type
+
+
+_set_types = {}
+
+
+def RocSet(element_type):
+    """The type of a Roc `Set` of the given element type, which Roc stores as a `Dict k {}`."""
+    set_type = _set_types.get(element_type)
+
+    if set_type is None:
+        dict_type = RocDict(element_type, RocUnit)
+        set_type = type(
+            f"RocSet({_type_name(element_type)})",
+            (_RocSetBase,),
+            {"_fields_": [("dict", dict_type)], "_roc_alignment_": alignment(dict_type)},
+        )
+        _set_types[element_type] = set_type
+
+    return set_type
+
+
+class _RocInt128(ctypes.Structure):
+    _fields_ = [("low", ctypes.c_uint64), ("high", ctypes.c_uint64)]
+    # Roc (like LLVM) aligns 128-bit integers to 16 bytes, more than ctypes would.
+    _roc_alignment_ = 16
+    _signed_ = False
+
+    def to_int(self):
+        value = (self.high << 64) | self.low
+
+        if self._signed_ and value >= 1 << 127:
+            value -= 1 << 128
+
+        return value
+
+    def to_python(self):
+        return self.to_int()
+
+    def __repr__(self):
+        return f"{type(self).__name__}({self.to_python()!r})"
+
+    @classmethod
+    def from_python(cls, value):
+        value = int(value)
+        bits = value & ((1 << 128) - 1)
+
+        return cls(bits & ((1 << 64) - 1), bits >> 64)
+
+
+class RocI128(_RocInt128):
+    """A Roc `I128`. `to_python` gives an `int`."""
+
+    _signed_ = True
+
+
+class RocU128(_RocInt128):
+    """A Roc `U128`. `to_python` gives an `int`."""
+
+
+class RocDec(RocI128):
+    """A Roc `Dec`: a fixed-point decimal with 18 digits after the point.
+
+    `to_python` gives a `decimal.Decimal`.
+    """
+
+    _SCALE = 10**18
+
+    def to_python(self):
+        with decimal.localcontext() as context:
+            context.prec = 40
+
+            return decimal.Decimal(self.to_int()) / self._SCALE
+
+    @classmethod
+    def from_python(cls, value):
+        with decimal.localcontext() as context:
+            context.prec = 60
+            # Going through str keeps floats like 0.1 from bringing their binary rounding error.
+            exact = decimal.Decimal(str(value) if isinstance(value, float) else value)
+            scaled = exact * cls._SCALE
+
+            if scaled != scaled.to_integral_value():
+                raise ValueError(f"{value} has more than 18 digits after the decimal point")
+
+        return super().from_python(int(scaled))
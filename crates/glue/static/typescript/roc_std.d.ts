@@ -0,0 +1,102 @@
+// Types for roc_std.js, which reads and writes Roc's builtin types in the linear memory of a
+// Roc app compiled with `--target=wasm32`.
+
+/** The exports of a Roc wasm module that the glue needs, besides its entry points. */
+export interface RocExports {
+  memory: WebAssembly.Memory;
+  roc_alloc(size: number, alignment: number): number;
+  roc_dealloc(address: number, alignment: number): void;
+  [name: string]: unknown;
+}
+
+/** The memory and allocator of an instantiated Roc wasm module. */
+export declare class RocMemory {
+  constructor(exports: WebAssembly.Exports);
+
+  readonly exports: RocExports;
+
+  readonly view: DataView;
+
+  bytes(address: number, length: number): Uint8Array;
+  alloc(size: number, alignment: number): number;
+  dealloc(address: number, alignment: number): void;
+}
+
+/** How a Roc type is laid out in memory, and how to convert it to and from JavaScript. */
+export interface Codec<T> {
+  readonly size: number;
+  readonly align: number;
+  readonly refcounted: boolean;
+  read(memory: RocMemory, address: number): T;
+  write(memory: RocMemory, address: number, value: T): void;
+  release(memory: RocMemory, address: number): void;
+  /** Converts the value to a wasm argument, for types that are passed by value. */
+  toWasm?(value: T): number | bigint;
+}
+
+/** A tag of a tag union, like `Rectangle 2.0 3.5` or `Ok "done"`. */
+export type Tag<Name extends string, Payload = never> = [Payload] extends [never]
+  ? { tag: Name }
+  : { tag: Name; payload: Payload };
+
+export declare function take<T>(memory: RocMemory, codec: Codec<T>, address: number): T;
+
+export declare function call<T>(
+  memory: RocMemory,
+  name: string,
+  ret: Codec<T>,
+  argCodecs: Codec<any>[],
+  args: unknown[]
+): T;
+
+export declare const U8: Codec<number>;
+export declare const I8: Codec<number>;
+export declare const U16: Codec<number>;
+export declare const I16: Codec<number>;
+export declare const U32: Codec<number>;
+export declare const I32: Codec<number>;
+export declare const U64: Codec<bigint>;
+export declare const I64: Codec<bigint>;
+export declare const U128: Codec<bigint>;
+export declare const I128: Codec<bigint>;
+export declare const F32: Codec<number>;
+export declare const F64: Codec<number>;
+export declare const Dec: Codec<string>;
+export declare const Bool: Codec<boolean>;
+export declare const Unit: Codec<null>;
+export declare const Str: Codec<string>;
+
+export declare function List<T>(element: Codec<T>): Codec<T[]>;
+export declare function Box<T>(contents: Codec<T>): Codec<T>;
+export declare function Result<T, E>(ok: Codec<T>, err: Codec<E>): Codec<Tag<"Ok", T> | Tag<"Err", E>>;
+export declare function Dict<K, V>(key: Codec<K>, value: Codec<V>): Codec<Map<K, V>>;
+export declare function Set<T>(element: Codec<T>): Codec<globalThis.Set<T>>;
+
+export declare function record<T>(layout: {
+  size: number;
+  align: number;
+  fields: [name: string, offset: number, codec: Codec<any>][];
+}): Codec<T>;
+
+export declare function tagPayload<T extends unknown[]>(layout: {
+  size: number;
+  align: number;
+  fields: [position: number, offset: number, codec: Codec<any>][];
+}): Codec<T>;
+
+export declare function enumeration<T extends string>(layout: { size: number; tags: T[] }): Codec<T>;
+
+export declare function tagUnion<T>(layout: {
+  size: number;
+  align: number;
+  discriminantOffset: number;
+  discriminantSize: number;
+  tags: [name: string, payload: Codec<any> | null][];
+}): Codec<T>;
+
+export declare function singleTag<T>(layout: {
+  size: number;
+  align: number;
+  tag: string;
+  fields: [position: number, offset: number, codec: Codec<any>][];
+}): Codec<T>;
@@ -0,0 +1,719 @@
+// Reads and writes Roc's builtin types in the linear memory of a Roc app compiled with
+// `--target=wasm32`.
+//
+// The modules that `roc glue` generates with TypeScriptGlue.roc describe a platform's types as
+// codecs built from these. A codec knows a type's size and alignment, and how to:
+//
+// - `write` a JavaScript value into memory as that Roc type. Anything it needs to allocate is
+//   allocated with the app's `roc_alloc`, so it can be handed to Roc, which takes ownership.
+// - `read` a Roc value from memory into JavaScript values: strings, numbers, `bigint`s for
+//   64-bit and 128-bit integers, arrays, objects for records, and `{ tag, payload }` objects
+//   for tag unions.
+// - `release` a Roc value that JavaScript owns (like one returned by Roc), which decrements its
+//   reference counts and frees whatever is no longer referenced.
+//
+// The generated entry point wrappers do all of this, so most code only needs `bind`.
+
+const POINTER_SIZE = 4;
+
+// The high bit of a Str or List's length marks it as a seamless slice of another allocation.
+const SEAMLESS_SLICE_BIT = 0x80000000;
+
+// A reference count of 0 marks a constant that's never freed, like a string literal.
+const REFCOUNT_CONSTANT = 0;
+const REFCOUNT_ONE = 1;
+
+const encoder = new TextEncoder();
+const decoder = new TextDecoder();
+
+/** The memory and allocator of an instantiated Roc wasm module. */
+export class RocMemory {
+  constructor(exports) {
+    for (const name of ["memory", "roc_alloc", "roc_dealloc"]) {
+      if (!(name in exports)) {
+        throw new Error(
+          `The Roc wasm module doesn't export \`${name}\`, which the glue needs. Make sure the platform's host exports it.`
+        );
+      }
+    }
+
+    this.exports = exports;
+  }
+
+  /** A view of the whole memory. It has to be made again whenever the memory grows. */
+  get view() {
+    const buffer = this.exports.memory.buffer;
+
+    if (this._view === undefined || this._view.buffer !== buffer) {
+      this._view = new DataView(buffer);
+    }
+
+    return this._view;
+  }
+
+  bytes(address, length) {
+    return new Uint8Array(this.exports.memory.buffer, address, length);
+  }
+
+  alloc(size, alignment) {
+    const address = this.exports.roc_alloc(Math.max(size, 1), alignment);
+
+    if (address === 0) {
+      throw new Error("roc_alloc returned a null pointer");
+    }
+
+    return address;
+  }
+
+  dealloc(address, alignment) {
+    this.exports.roc_dealloc(address, alignment);
+  }
+
+  /** Allocates `dataBytes` with a reference count of 1 in front, like Roc's builtins do. */
+  allocRefcounted(dataBytes, elementAlignment, elementsRefcounted, elementCount) {
+    const extraBytes = Math.max(refcountSpace(elementsRefcounted), elementAlignment);
+    const allocation = this.alloc(
+      extraBytes + dataBytes,
+      Math.max(POINTER_SIZE, elementAlignment)
+    );
+    const data = allocation + extraBytes;
+
+    this.view.setInt32(data - POINTER_SIZE, REFCOUNT_ONE, true);
+
+    if (elementsRefcounted) {
+      // Seamless slices of the allocation read the element count from here to free it.
+      this.view.setUint32(data - 2 * POINTER_SIZE, elementCount, true);
+    }
+
+    return data;
+  }
+
+  /** Decrements a reference count, calling `free` if it was the last reference. */
+  decrement(refcountAddress, free) {
+    const refcount = this.view.getInt32(refcountAddress, true);
+
+    if (refcount === REFCOUNT_CONSTANT) {
+      return;
+    }
+
+    if (refcount === REFCOUNT_ONE) {
+      free();
+    } else {
+      this.view.setInt32(refcountAddress, refcount - 1, true);
+    }
+  }
+}
+
+function refcountSpace(elementsRefcounted) {
+  return elementsRefcounted ? 2 * POINTER_SIZE : POINTER_SIZE;
+}
+
+function alignUp(offset, alignment) {
+  return Math.ceil(offset / alignment) * alignment;
+}
+
+/** Reads a Roc value, then releases it. */
+export function take(memory, codec, address) {
+  const value = codec.read(memory, address);
+
+  codec.release(memory, address);
+
+  return value;
+}
+
+/**
+ * Calls a Roc entry point.
+ *
+ * The return value is written to memory that's passed as the first argument. Numbers, Bools
+ * and enumerations are passed as they are, and other arguments are written to memory and
+ * passed by pointer. Roc takes ownership of the arguments, and JavaScript of what Roc returns.
+ */
+export function call(memory, name, ret, argCodecs, args) {
+  const entryPoint = memory.exports[name];
+
+  if (typeof entryPoint !== "function") {
+    throw new Error(`The Roc wasm module doesn't export the entry point \`${name}\`.`);
+  }
+
+  const retAddress = memory.alloc(ret.size, ret.align);
+  const temporaries = [[retAddress, ret.align]];
+
+  try {
+    const wasmArgs = argCodecs.map((codec, index) => {
+      if (codec.toWasm !== undefined) {
+        return codec.toWasm(args[index]);
+      }
+
+      const address = memory.alloc(codec.size, codec.align);
+
+      temporaries.push([address, codec.align]);
+      codec.write(memory, address, args[index]);
+
+      return address;
+    });
+
+    entryPoint(retAddress, ...wasmArgs);
+
+    return take(memory, ret, retAddress);
+  } finally {
+    for (const [address, alignment] of temporaries) {
+      memory.dealloc(address, alignment);
+    }
+  }
+}
+
+// 64-bit integers are `bigint`s, which is also how wasm takes them as arguments.
+function number(size, get, set, fromJs = (value) => value) {
+  return {
+    size,
+    align: size,
+    refcounted: false,
+    read: (memory, address) => memory.view[get](address, true),
+    write: (memory, address, value) => memory.view[set](address, fromJs(value), true),
+    release() {},
+    toWasm: fromJs,
+  };
+}
+
+export const U8 = number(1, "getUint8", "setUint8");
+export const I8 = number(1, "getInt8", "setInt8");
+export const U16 = number(2, "getUint16", "setUint16");
+export const I16 = number(2, "getInt16", "setInt16");
+export const U32 = number(4, "getUint32", "setUint32");
+export const I32 = number(4, "getInt32", "setInt32");
+export const U64 = number(8, "getBigUint64", "setBigUint64", BigInt);
+export const I64 = number(8, "getBigInt64", "setBigInt64", BigInt);
+export const F32 = number(4, "getFloat32", "setFloat32");
+export const F64 = number(8, "getFloat64", "setFloat64");
+
+export const Bool = {
+  size: 1,
+  align: 1,
+  refcounted: false,
+  read: (memory, address) => memory.view.getUint8(address) !== 0,
+  write: (memory, address, value) => memory.view.setUint8(address, value ? 1 : 0),
+  release() {},
+  toWasm: (value) => (value ? 1 : 0),
+};
+
+/** `{}`, and other types that don't take up any memory. */
+export const Unit = {
+  size: 0,
+  align: 1,
+  refcounted: false,
+  read: () => null,
+  write() {},
+  release() {},
+};
+
+// 128-bit integers are stored as two little-endian 64-bit halves, and aligned to 16 bytes.
+function int128(signed) {
+  return {
+    size: 16,
+    align: 16,
+    refcounted: false,
+    read(memory, address) {
+      const low = memory.view.getBigUint64(address, true);
+      const high = memory.view.getBigUint64(address + 8, true);
+      const value = (high << 64n) | low;
+
+      return signed ? BigInt.asIntN(128, value) : value;
+    },
+    write(memory, address, value) {
+      const bits = BigInt.asUintN(128, BigInt(value));
+
+      memory.view.setBigUint64(address, BigInt.asUintN(64, bits), true);
+      memory.view.setBigUint64(address + 8, bits >> 64n, true);
+    },
+    release() {},
+  };
+}
+
+export const U128 = int128(false);
+export const I128 = int128(true);
+
+// A Dec is an I128 of the number times 10^18.
+const DEC_SCALE = 10n ** 18n;
+const DEC_DECIMALS = 18;
+
+/** A Roc `Dec`, as a decimal string like `"-1.5"`, so that it doesn't lose any precision. */
+export const Dec = {
+  ...I128,
+  read(memory, address) {
+    const scaled = I128.read(memory, address);
+    const sign = scaled < 0n ? "-" : "";
+    const magnitude = scaled < 0n ? -scaled : scaled;
+    const whole = magnitude / DEC_SCALE;
+    const fraction = (magnitude % DEC_SCALE)
+      .toString()
+      .padStart(DEC_DECIMALS, "0")
+      .replace(/0+$/, "");
+
+    return fraction === "" ? `${sign}${whole}` : `${sign}${whole}.${fraction}`;
+  },
+  write(memory, address, value) {
+    const match = /^(-?)(\d+)(?:\.(\d{0,18}))?$/.exec(String(value).trim());
+
+    if (match === null) {
+      throw new RangeError(`${value} is not a Dec: it needs to be a decimal number with at most 18 decimal places`);
+    }
+
+    const [, sign, whole, fraction = ""] = match;
+    const magnitude = BigInt(whole) * DEC_SCALE + BigInt(fraction.padEnd(DEC_DECIMALS, "0"));
+
+    I128.write(memory, address, sign === "-" ? -magnitude : magnitude);
+  },
+};
+
+/** A Roc `Str`, as a JavaScript string. */
+export const Str = {
+  size: 3 * POINTER_SIZE,
+  align: POINTER_SIZE,
+  refcounted: true,
+  read(memory, address) {
+    return decoder.decode(strBytes(memory, address));
+  },
+  write(memory, address, value) {
+    const utf8 = encoder.encode(value);
+
+    if (utf8.length < Str.size) {
+      // Small strings are stored inline, with their length in the last byte and its high
+      // bit set.
+      const inline = memory.bytes(address, Str.size);
+
+      inline.fill(0);
+      inline.set(utf8);
+      inline[Str.size - 1] = utf8.length | 0x80;
+
+      return;
+    }
+
+    const data = memory.allocRefcounted(utf8.length, 1, false, 0);
+
+    memory.bytes(data, utf8.length).set(utf8);
+    writeTriple(memory, address, data, utf8.length, utf8.length);
+  },
+  release(memory, address) {
+    if (isSmallStr(memory, address)) {
+      return;
+    }
+
+    const [data, length, capacityOrAllocPtr] = readTriple(memory, address);
+
+    if (data === 0) {
+      return;
+    }
+
+    const allocationData = length & SEAMLESS_SLICE_BIT ? (capacityOrAllocPtr << 1) >>> 0 : data;
+    const refcountAddress = allocationData - POINTER_SIZE;
+
+    memory.decrement(refcountAddress, () => memory.dealloc(refcountAddress, 1));
+  },
+};
+
+function isSmallStr(memory, address) {
+  return (memory.view.getUint8(address + Str.size - 1) & 0x80) !== 0;
+}
+
+function strBytes(memory, address) {
+  if (isSmallStr(memory, address)) {
+    const length = memory.view.getUint8(address + Str.size - 1) ^ 0x80;
+
+    return memory.bytes(address, length);
+  }
+
+  const [data, length] = readTriple(memory, address);
+
+  return data === 0 ? new Uint8Array(0) : memory.bytes(data, length & ~SEAMLESS_SLICE_BIT);
+}
+
+function readTriple(memory, address) {
+  return [0, 1, 2].map((index) => memory.view.getUint32(address + index * POINTER_SIZE, true));
+}
+
+function writeTriple(memory, address, first, second, third) {
+  [first, second, third].forEach((value, index) =>
+    memory.view.setUint32(address + index * POINTER_SIZE, value, true)
+  );
+}
+
+const listCodecs = new Map();
+
+/** A Roc `List`, as an array. */
+export function List(element) {
+  let codec = listCodecs.get(element);
+
+  if (codec === undefined) {
+    const stride = alignUp(element.size, element.align);
+
+    codec = {
+      size: 3 * POINTER_SIZE,
+      align: POINTER_SIZE,
+      refcounted: true,
+      read(memory, address) {
+        const [elements, length] = readTriple(memory, address);
+        const values = [];
+
+        for (let index = 0; index < (length & ~SEAMLESS_SLICE_BIT); index++) {
+          values.push(element.read(memory, elements + index * stride));
+        }
+
+        return values;
+      },
+      write(memory, address, values) {
+        if (values.length === 0) {
+          writeTriple(memory, address, 0, 0, 0);
+
+          return;
+        }
+
+        const data = memory.allocRefcounted(
+          values.length * stride,
+          element.align,
+          element.refcounted,
+          values.length
+        );
+
+        values.forEach((value, index) => element.write(memory, data + index * stride, value));
+        writeTriple(memory, address, data, values.length, values.length);
+      },
+      release(memory, address) {
+        const [elements, length, capacityOrAllocPtr] = readTriple(memory, address);
+
+        if (elements === 0) {
+          return;
+        }
+
+        let allocationData = elements;
+        let elementCount = length;
+
+        if (length & SEAMLESS_SLICE_BIT) {
+          allocationData = (capacityOrAllocPtr << 1) >>> 0;
+          elementCount = element.refcounted
+            ? memory.view.getUint32(allocationData - 2 * POINTER_SIZE, true)
+            : 0;
+        }
+
+        const allocation =
+          allocationData - Math.max(refcountSpace(element.refcounted), element.align);
+
+        memory.decrement(allocationData - POINTER_SIZE, () => {
+          if (element.refcounted) {
+            for (let index = 0; index < elementCount; index++) {
+              element.release(memory, allocationData + index * stride);
+            }
+          }
+
+          memory.dealloc(allocation, Math.max(POINTER_SIZE, element.align));
+        });
+      },
+    };
+
+    listCodecs.set(element, codec);
+  }
+
+  return codec;
+}
+
+const boxCodecs = new Map();
+
+/** A Roc `Box`, as the value inside it. */
+export function Box(contents) {
+  let codec = boxCodecs.get(contents);
+
+  if (codec === undefined) {
+    codec = {
+      size: POINTER_SIZE,
+      align: POINTER_SIZE,
+      refcounted: true,
+      read: (memory, address) => contents.read(memory, memory.view.getUint32(address, true)),
+      write(memory, address, value) {
+        const data = memory.allocRefcounted(contents.size, contents.align, false, 0);
+
+        contents.write(memory, data, value);
+        memory.view.setUint32(address, data, true);
+      },
+      release(memory, address) {
+        const data = memory.view.getUint32(address, true);
+        const extraBytes = Math.max(POINTER_SIZE, contents.align);
+
+        memory.decrement(data - POINTER_SIZE, () => {
+          contents.release(memory, data);
+          memory.dealloc(data - extraBytes, extraBytes);
+        });
+      },
+    };
+
+    boxCodecs.set(contents, codec);
+  }
+
+  return codec;
+}
+
+/**
+ * A Roc record, as an object.
+ *
+ * `fields` has the name, offset and codec of each field.
+ */
+export function record({ size, align, fields }) {
+  return {
+    size,
+    align,
+    refcounted: fields.some(([, , codec]) => codec.refcounted),
+    read(memory, address) {
+      const value = {};
+
+      for (const [name, offset, codec] of fields) {
+        value[name] = codec.read(memory, address + offset);
+      }
+
+      return value;
+    },
+    write(memory, address, value) {
+      const missing = fields.filter(([name]) => !(name in value)).map(([name]) => name);
+
+      if (missing.length > 0) {
+        throw new TypeError(`The record is missing the fields ${missing.join(", ")}`);
+      }
+
+      for (const [name, offset, codec] of fields) {
+        codec.write(memory, address + offset, value[name]);
+      }
+    },
+    release(memory, address) {
+      for (const [, offset, codec] of fields) {
+        codec.release(memory, address + offset);
+      }
+    },
+  };
+}
+
+/**
+ * The payload of a tag with more than one value, as an array of them.
+ *
+ * `fields` has the position, offset and codec of each value.
+ */
+export function tagPayload({ size, align, fields }) {
+  const codec = record({ size, align, fields });
+
+  return {
+    ...codec,
+    read(memory, address) {
+      const values = [];
+
+      for (const [position, offset, field] of fields) {
+        values[position] = field.read(memory, address + offset);
+      }
+
+      return values;
+    },
+    write(memory, address, values) {
+      if (values.length !== fields.length) {
+        throw new TypeError(`The payload has ${fields.length} values, but got ${values.length}`);
+      }
+
+      codec.write(memory, address, values);
+    },
+  };
+}
+
+/** A tag union whose tags have no payloads, like `[Red, Green, Blue]`, as the tag's name. */
+export function enumeration({ size, tags }) {
+  const int = { 1: U8, 2: U16, 4: U32, 8: U64 }[size];
+  const discriminant = (name) => {
+    const index = tags.indexOf(name);
+
+    if (index === -1) {
+      throw new TypeError(`${name} is not one of the tags ${tags.join(", ")}`);
+    }
+
+    return index;
+  };
+
+  return {
+    size,
+    align: size,
+    refcounted: false,
+    read: (memory, address) => tags[Number(int.read(memory, address))],
+    write: (memory, address, name) =>
+      int.write(memory, address, discriminant(name)),
+    release() {},
+    toWasm: (name) => int.toWasm(discriminant(name)),
+  };
+}
+
+/**
+ * A tag union with payloads, as `{ tag, payload }`. Tags without a payload don't have one.
+ *
+ * `tags` has the name and payload codec (or `null`) of each tag, in the order of their
+ * discriminants, which go after the payload.
+ */
+export function tagUnion({ size, align, discriminantOffset, discriminantSize, tags }) {
+  const int = { 1: U8, 2: U16, 4: U32, 8: U64 }[discriminantSize];
+  const readDiscriminant = (memory, address) =>
+    Number(int.read(memory, address + discriminantOffset));
+
+  return {
+    size,
+    align,
+    refcounted: tags.some(([, payload]) => payload !== null && payload.refcounted),
+    read(memory, address) {
+      const [tag, payload] = tags[readDiscriminant(memory, address)];
+
+      return payload === null ? { tag } : { tag, payload: payload.read(memory, address) };
+    },
+    write(memory, address, value) {
+      const discriminant = tags.findIndex(([tag]) => tag === value.tag);
+
+      if (discriminant === -1) {
+        throw new TypeError(`${value.tag} is not one of the tags ${tags.map(([tag]) => tag).join(", ")}`);
+      }
+
+      const [, payload] = tags[discriminant];
+
+      memory.bytes(address, size).fill(0);
+
+      if (payload !== null) {
+        payload.write(memory, address, value.payload);
+      }
+
+      int.write(memory, address + discriminantOffset, discriminant);
+    },
+    release(memory, address) {
+      const [, payload] = tags[readDiscriminant(memory, address)];
+
+      if (payload !== null) {
+        payload.release(memory, address);
+      }
+    },
+  };
+}
+
+/** A tag union with one tag, like `[Wrapper Str U64]`, as `{ tag, payload }`. */
+export function singleTag({ size, align, tag, fields }) {
+  const payload = tagPayload({ size, align, fields });
+
+  return {
+    ...payload,
+    read(memory, address) {
+      const values = payload.read(memory, address);
+
+      if (values.length === 0) {
+        return { tag };
+      }
+
+      return { tag, payload: values.length === 1 ? values[0] : values };
+    },
+    write(memory, address, value) {
+      if (value.tag !== tag) {
+        throw new TypeError(`${value.tag} is not the tag ${tag}`);
+      }
+
+      payload.write(memory, address, fields.length === 1 ? [value.payload] : value.payload ?? []);
+    },
+  };
+}
+
+const resultCodecs = new Map();
+
+/** A Roc `Result`, as `{ tag: "Ok", payload }` or `{ tag: "Err", payload }`. */
+export function Result(ok, err) {
+  const codecs = resultCodecs.get(ok) ?? new Map();
+  let codec = codecs.get(err);
+
+  if (codec === undefined) {
+    const align = Math.max(ok.align, err.align);
+    const discriminantOffset = alignUp(Math.max(ok.size, err.size), align);
+
+    codec = tagUnion({
+      size: alignUp(discriminantOffset + 1, align),
+      align,
+      discriminantOffset,
+      discriminantSize: 1,
+      tags: [
+        ["Err", err],
+        ["Ok", ok],
+      ],
+    });
+    codecs.set(err, codec);
+    resultCodecs.set(ok, codecs);
+  }
+
+  return codec;
+}
+
+// Dict k v := { buckets : List Bucket, data : List (k, v), max_bucket_capacity : U64,
+// max_load_factor : F32, shifts : U8 }, laid out like any other record.
+const BUCKET = record({
+  size: 8,
+  align: 4,
+  fields: [
+    ["data_index", 0, U32],
+    ["dist_and_fingerprint", 4, U32],
+  ],
+});
+
+function dictLayout(key, value) {
+  const [first, second] = key.align >= value.align ? [key, value] : [value, key];
+  const secondOffset = alignUp(first.size, second.align);
+  const align = Math.max(key.align, value.align);
+  const pair = tagPayload({
+    size: alignUp(secondOffset + second.size, align),
+    align,
+    fields:
+      first === key
+        ? [
+            [0, 0, key],
+            [1, secondOffset, value],
+          ]
+        : [
+            [1, 0, value],
+            [0, secondOffset, key],
+          ],
+  });
+
+  return record({
+    size: 40,
+    align: 8,
+    fields: [
+      ["max_bucket_capacity", 0, U64],
+      ["buckets", 8, List(BUCKET)],
+      ["data", 20, List(pair)],
+      ["max_load_factor", 32, F32],
+      ["shifts", 36, U8],
+    ],
+  });
+}
+
+function readOnly(codec, name, read) {
+  return {
+    ...codec,
+    read,
+    write() {
+      throw new TypeError(
+        `The glue can't build a Roc ${name} yet. Pass a List of entries, and use ${name}.from_list in Roc.`
+      );
+    },
+  };
+}
+
+/**
+ * A Roc `Dict`, as a `Map`. Only Roc can build one, because building the buckets means
+ * hashing the keys the way Roc does.
+ */
+export function Dict(key, value) {
+  const layout = dictLayout(key, value);
+
+  return readOnly(layout, "Dict", (memory, address) => new Map(layout.read(memory, address).data));
+}
+
+/** A Roc `Set`, as a `Set`. Only Roc can build one, like a `Dict`. */
+export function Set(element) {
+  const layout = dictLayout(element, Unit);
+
+  return readOnly(
+    layout,
+    "Set",
+    (memory, address) => new globalThis.Set(layout.read(memory, address).data.map(([key]) => key))
+  );
+}
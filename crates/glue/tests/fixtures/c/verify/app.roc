@@ -0,0 +1,3 @@
+app [main] { pf: platform "platform.roc" }
+
+main = { a: 1, b: 2, c: 3 }
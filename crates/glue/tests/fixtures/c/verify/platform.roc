@@ -0,0 +1,12 @@
+platform "test-platform"
+    requires {} { main : _ }
+    exposes []
+    packages {}
+    imports []
+    provides [main_for_host]
+
+# Roc sorts these fields by alignment, so they aren't laid out in the order they're written.
+Mixed : { a : U8, b : U64, c : U16 }
+
+main_for_host : Mixed
+main_for_host = main
@@ -0,0 +1,23 @@
+platform "test-platform"
+    requires {} { main : _ }
+    exposes []
+    packages {}
+    imports []
+    provides [main_for_host]
+
+Shape : [Circle F64, Rectangle F64 F64, Point]
+
+Input : { name : Str, scores : List I64, shape : Shape }
+
+Output : {
+    greeting : Str,
+    total : I64,
+    grade : [Pass, Fail, Incomplete],
+    area : F64,
+    words : List Str,
+    counts : Dict Str U64,
+    first_score : Result I64 Str,
+}
+
+main_for_host : Input -> Output
+main_for_host = \input -> main(input)
@@ -0,0 +1,33 @@
+# Calls the Roc app through the generated glue. Takes the paths of the Roc library and the host.
+import os
+import sys
+
+sys.path.insert(0, os.path.join(os.path.dirname(__file__), "test_glue"))
+
+import roc_app  # noqa: E402
+
+roc_app.load(sys.argv[1], sys.argv[2])
+
+output = roc_app.main_for_host(
+    {
+        "name": "Ada Lovelace, the first programmer",
+        "scores": [40, 70],
+        "shape": ("Rectangle", (2.0, 3.5)),
+    }
+)
+
+print(f"greeting: {output['greeting']}")
+print(f"total: {output['total']}, grade: {output['grade']}, area: {output['area']}")
+print(f"words: {output['words']}")
+print(f"counts: {sorted(output['counts'].items())}")
+print(f"first_score: {output['first_score']}")
+
+output = roc_app.main_for_host({"name": "Grace", "scores": [], "shape": roc_app.Tag("Circle", 1.0)})
+
+print(f"grade: {output['grade']}, area: {output['area']}, first_score: {output['first_score']}")
+
+# Long enough not to be a small string, so it's allocated, and has to be released.
+with roc_app.owned(roc_app.roc_std.RocStr.from_python("a string that owned releases")) as string:
+    print(f"owned: {string.to_python()}")
+
+print(f"live allocations: {roc_app.live_allocations()}")
@@ -0,0 +1,11 @@
+app [main] { pf: platform "platform.roc" }
+
+main = \{ counts, tags, by_kind } ->
+    apples = Dict.get(counts, "apples") |> Result.with_default(0)
+    new_counts =
+        counts
+        |> Dict.insert("apples", apples + 1)
+        |> Dict.insert("pears", 3)
+        |> Dict.remove("bananas")
+
+    { counts: new_counts, tags: Set.insert(tags, "fresh"), by_kind: Dict.insert(by_kind, Veg, 1) }
@@ -0,0 +1,13 @@
+platform "test-platform"
+    requires {} { main : Inventory -> Inventory }
+    exposes []
+    packages {}
+    imports []
+    provides [main_for_host]
+
+Kind : [Fruit, Veg]
+
+Inventory : { counts : Dict Str U64, tags : Set Str, by_kind : Dict Kind U64 }
+
+main_for_host : Inventory -> Inventory
+main_for_host = \inventory -> main(inventory)
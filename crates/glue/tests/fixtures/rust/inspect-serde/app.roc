@@ -0,0 +1,9 @@
+app [main] { pf: platform "platform.roc" }
+
+main = {
+    name: "Sketch",
+    color: Green,
+    origin: Point(1, -2),
+    shapes: [Circle(1.5), Rectangle(2, 3.25), Empty],
+    visible: Bool.true,
+}
@@ -0,0 +1,23 @@
+platform "test-platform"
+    requires {} { main : _ }
+    exposes []
+    packages {}
+    imports []
+    provides [main_for_host]
+
+Color : [Red, Green, Blue]
+
+Point : [Point I64 I64]
+
+Shape : [Circle F64, Rectangle F64 F64, Empty]
+
+Drawing : {
+    name : Str,
+    color : Color,
+    origin : Point,
+    shapes : List Shape,
+    visible : Bool,
+}
+
+main_for_host : Drawing
+main_for_host = main
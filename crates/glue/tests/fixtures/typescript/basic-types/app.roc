@@ -0,0 +1,18 @@
+app [main] { pf: platform "platform.roc" }
+
+main = \{ name, scores, shape } ->
+    total = List.sum(scores)
+    words = Str.split_on(name, " ")
+
+    {
+        greeting: "Hello, ${name}!",
+        total,
+        grade: if List.is_empty(scores) then Incomplete else if total >= 100 then Pass else Fail,
+        area: when shape is
+            Circle(radius) -> 3.0 * radius * radius
+            Rectangle(width, height) -> width * height
+            Point -> 0.0,
+        words,
+        counts: Dict.from_list(List.map(words, \word -> (word, Str.count_utf8_bytes(word)))),
+        first_score: List.first(scores) |> Result.map_err(\_ -> "no scores"),
+    }
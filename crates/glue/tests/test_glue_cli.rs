@@ -69,13 +69,21 @@ mod glue_cli_tests {
                     // TODO after #5924 is fixed; remove this
                     let skip_on_linux_surgical_linker = ["rust_closures", "rust_option", "rust_nullable_wrapped", "rust_nullable_unwrapped", "rust_nonnullable_unwrapped", "rust_enumeration", "rust_nested_record", "rust_advanced_recursive_union"];
 
+                    // These hosts use roc_std's RocDict, which needs the app's Dict seed.
+                    let needs_dict_seed = ["rust_dict_and_set"];
+                    let dict_seed_args: &[&str] = if needs_dict_seed.contains(&test_name_str) {
+                        &["--export-dict-seed"]
+                    } else {
+                        &[]
+                    };
+
                     // Validate linux with the default linker.
                     if !(cfg!(target_os = "linux") && (skip_on_linux_surgical_linker.contains(&test_name_str))) {
-                        validate(dir.clone(), ["--build-host", "--suppress-build-host-warning"]);
+                        validate(dir.clone(), ["--build-host", "--suppress-build-host-warning"].iter().chain(dict_seed_args).copied());
                     }
 
                     if TEST_LEGACY_LINKER {
-                        validate(dir, ["--build-host", "--suppress-build-host-warning", "--linker=legacy"]);
+                        validate(dir, ["--build-host", "--suppress-build-host-warning", "--linker=legacy"].iter().chain(dict_seed_args).copied());
                     }
                 }
             )*
@@ -148,6 +156,16 @@ mod glue_cli_tests {
         rust_multiple_modules:"rust/multiple-modules" => indoc!(r#"
             combined was: Combined { s1: DepStr1::S("hello"), s2: DepStr2::R("world") }
         "#),
+        rust_inspect_serde:"rust/inspect-serde" => indoc!(r#"
+            Drawing was: {color: Green, name: "Sketch", origin: (Point 1 -2), shapes: [(Circle 1.5), (Rectangle 2 3.25), Empty], visible: Bool.true}
+            As JSON: {"color":"Green","name":"Sketch","origin":{"Point":[1,-2]},"shapes":[{"Circle":1.5},{"Rectangle":[2.0,3.25]},"Empty"],"visible":true}
+        "#),
+        rust_dict_and_set:"rust/dict-and-set" => indoc!(r#"
+            Inventory was: {by_kind: {Fruit: 2, Veg: 1}, counts: {"apples": 3, "pears": 3}, tags: {"ripe", "fresh"}}
+            Apples: Some(3), bananas: None
+            Fresh: true
+            Fruit: Some(2), veg: Some(1)
+        "#),
         // issue https://github.com/roc-lang/roc/issues/6121
         // TODO: re-enable this test. Currently it is flaking on macos x86-64 with a bad exit code.
         // nested_record:"nested-record" => "Record was: Outer { y: \"foo\", z: [1, 2], x: Inner { b: 24.0, a: 5 } }\n",
@@ -171,6 +189,207 @@ mod glue_cli_tests {
         "#),
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn c_verify() {
+        let dir = fixtures_dir("c/verify");
+        let out = generate_glue_for(&dir, ["--verify"]);
+        let header = std::fs::read_to_string(dir.join("test_glue").join("roc_app.h")).unwrap();
+
+        // Roc orders the fields by alignment, not the order they're written in.
+        assert!(header.contains(indoc!(
+            r#"
+            typedef struct Mixed {
+                uint64_t b;
+                uint16_t c;
+                uint8_t a;
+            } Mixed;
+            "#
+        )));
+        assert!(header.contains(indoc!(
+            r#"
+            #if defined(__x86_64__) || defined(_M_X64)
+            _Static_assert(sizeof(Mixed) == 16, "Mixed is 16 bytes on x86_64");
+            _Static_assert(_Alignof(Mixed) == 8, "Mixed is aligned to 8 bytes on x86_64");
+            _Static_assert(offsetof(Mixed, b) == 0, "Mixed.b is at offset 0 on x86_64");
+            _Static_assert(offsetof(Mixed, c) == 8, "Mixed.c is at offset 8 on x86_64");
+            _Static_assert(offsetof(Mixed, a) == 10, "Mixed.a is at offset 10 on x86_64");
+            "#
+        )));
+        assert!(
+            out.stdout
+                .contains("✅ The host's compiler lays out the glue's types the way Roc does."),
+            "Unexpected stdout:\n\n{}",
+            out.stdout
+        );
+
+        // A host that declared the fields in the order they're written would disagree with Roc.
+        let mismatched_dir = tempfile::tempdir().unwrap();
+        let mismatched = header.replace(
+            "    uint64_t b;\n    uint16_t c;\n    uint8_t a;\n",
+            "    uint8_t a;\n    uint64_t b;\n    uint16_t c;\n",
+        );
+
+        assert_ne!(mismatched, header);
+        std::fs::write(mismatched_dir.path().join("roc_app.h"), mismatched).unwrap();
+        assert!(!roc_glue::verify::verify(mismatched_dir.path()).unwrap());
+    }
+
+    // Python can't go through `fixtures!`, because there's no Roc host to build: the app is built
+    // as a shared library, and Python loads it along with roc_host.c from the generated glue.
+    #[test]
+    #[cfg(unix)]
+    #[ignore = "needs python3 and cc, run with --ignored"]
+    fn python_basic_types() {
+        let dir = fixtures_dir("python/basic-types");
+
+        generate_glue_for(&dir, std::iter::empty());
+
+        ExecCli::new("build", dir.join("app.roc"))
+            .arg("--lib")
+            .run()
+            .assert_clean_success();
+
+        let glue_dir = dir.join("test_glue");
+        let host_lib = glue_dir.join(format!("libroc_host.{}", std::env::consts::DLL_EXTENSION));
+        let cc_status = std::process::Command::new("cc")
+            .args(["-shared", "-fPIC", "-o"])
+            .arg(&host_lib)
+            .arg(glue_dir.join("roc_host.c"))
+            .status()
+            .expect("Unable to run cc to build roc_host.c");
+
+        assert!(cc_status.success());
+
+        let out = std::process::Command::new("python3")
+            .arg(dir.join("test.py"))
+            .arg(dir.join(format!("app.{}", std::env::consts::DLL_EXTENSION)))
+            .arg(&host_lib)
+            .output()
+            .expect("Unable to run python3");
+        let stdout = String::from_utf8_lossy(&out.stdout);
+        let stderr = String::from_utf8_lossy(&out.stderr);
+
+        assert!(out.status.success(), "test.py failed:\n\n{stderr}");
+        assert_eq!(
+            stdout,
+            indoc!(
+                r#"
+                greeting: Hello, Ada Lovelace, the first programmer!
+                total: 110, grade: Pass, area: 7.0
+                words: ['Ada', 'Lovelace,', 'the', 'first', 'programmer']
+                counts: [('Ada', 3), ('Lovelace,', 9), ('first', 5), ('programmer', 10), ('the', 3)]
+                first_score: Tag(name='Ok', payload=40)
+                grade: Incomplete, area: 3.0, first_score: Tag(name='Err', payload='no scores')
+                owned: a string that owned releases
+                live allocations: 0
+                "#
+            )
+        );
+    }
+
+    // Running the TypeScript glue needs a wasm host that exports `roc_alloc` and `roc_dealloc`,
+    // so this checks the generated codecs, whose layouts are the part that's easy to get wrong.
+    #[test]
+    fn typescript_basic_types() {
+        let dir = fixtures_dir("typescript/basic-types");
+
+        generate_glue_for(&dir, std::iter::empty());
+
+        let glue_dir = dir.join("test_glue");
+        let roc_app_js = std::fs::read_to_string(glue_dir.join("roc_app.js")).unwrap();
+        let roc_app_d_ts = std::fs::read_to_string(glue_dir.join("roc_app.d.ts")).unwrap();
+
+        for expected in [
+            indoc!(
+                r#"
+                export const Input = roc.record({
+                  size: 48,
+                  align: 8,
+                  fields: [
+                    ["shape", 0, Shape],
+                    ["name", 24, roc.Str],
+                    ["scores", 36, roc.List(roc.I64)],
+                  ],
+                });
+                "#
+            ),
+            indoc!(
+                r#"
+                export const Output = roc.record({
+                  size: 112,
+                  align: 8,
+                  fields: [
+                    ["area", 0, roc.F64],
+                    ["counts", 8, roc.Dict(roc.Str, roc.U64)],
+                    ["first_score", 48, roc.Result(roc.I64, roc.Str)],
+                    ["total", 72, roc.I64],
+                    ["greeting", 80, roc.Str],
+                    ["words", 92, roc.List(roc.Str)],
+                    ["grade", 104, Grade],
+                  ],
+                });
+                "#
+            ),
+            indoc!(
+                r#"
+                export const Shape = roc.tagUnion({
+                  size: 24,
+                  align: 8,
+                  discriminantOffset: 16,
+                  discriminantSize: 1,
+                  tags: [
+                    ["Circle", roc.F64],
+                    ["Point", null],
+                    ["Rectangle", Shape_Rectangle],
+                  ],
+                });
+                "#
+            ),
+            r#"return roc.call(memory, "roc__main_for_host_1_exposed_generic", Output, [Input], [arg0]);"#,
+        ] {
+            assert!(
+                roc_app_js.contains(expected),
+                "roc_app.js doesn't contain\n\n{expected}\n\nIt was:\n\n{roc_app_js}"
+            );
+        }
+
+        for expected in [
+            r#"export type Shape = Tag<"Circle", number> | Tag<"Point"> | Tag<"Rectangle", Shape_Rectangle>;"#,
+            r#"export type Grade = "Fail" | "Incomplete" | "Pass";"#,
+            "  main_for_host(arg0: Input): Output;",
+        ] {
+            assert!(
+                roc_app_d_ts.contains(expected),
+                "roc_app.d.ts doesn't contain\n\n{expected}\n\nIt was:\n\n{roc_app_d_ts}"
+            );
+        }
+    }
+
+    // The codecs in roc_std.js, against a WebAssembly.Memory that the test builds by hand.
+    #[test]
+    #[ignore = "needs node, run with --ignored"]
+    fn typescript_roc_std_codecs() {
+        let test_file = fixtures_dir("")
+            .parent()
+            .unwrap()
+            .join("typescript")
+            .join("roc_std.test.mjs");
+
+        let out = std::process::Command::new("node")
+            .arg("--test")
+            .arg(test_file)
+            .output()
+            .expect("Unable to run node");
+        let stdout = String::from_utf8_lossy(&out.stdout);
+        let stderr = String::from_utf8_lossy(&out.stderr);
+
+        assert!(
+            out.status.success(),
+            "roc_std.test.mjs failed:\n\n{stdout}{stderr}"
+        );
+    }
+
     fn check_for_tests(all_fixtures: &mut roc_collections::VecSet<String>) {
         use roc_collections::VecSet;
 
@@ -240,6 +459,8 @@ mod glue_cli_tests {
             "rust" => "RustGlue.roc",
             "zig" => "ZigGlue.roc",
             "c" => "CGlue.roc",
+            "python" => "PythonGlue.roc",
+            "typescript" => "TypeScriptGlue.roc",
             unknown_subfolder => panic!("I don't know which glue file to use for tests in the `{}` subfolder! Please add one here!", unknown_subfolder),
         };
 
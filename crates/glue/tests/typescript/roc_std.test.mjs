@@ -0,0 +1,300 @@
+// Tests the codecs of static/typescript/roc_std.js against a hand-built WebAssembly.Memory, with
+// an allocator that keeps track of what's allocated, so leaks and double frees show up.
+//
+// Run with `node --test crates/glue/tests/typescript/roc_std.test.mjs`.
+
+import assert from "node:assert/strict";
+import { readFile } from "node:fs/promises";
+import test from "node:test";
+
+// roc_std.js is an ES module, but there's no package.json next to it saying so.
+const rocStdSource = await readFile(
+  new URL("../../static/typescript/roc_std.js", import.meta.url),
+  "utf8"
+);
+const roc = await import(`data:text/javascript,${encodeURIComponent(rocStdSource)}`);
+
+const POINTER_SIZE = 4;
+const SEAMLESS_SLICE_BIT = 0x80000000;
+
+/** A memory with a bump allocator. `live` has the address of each allocation not freed yet. */
+function testMemory(entryPoints = {}) {
+  const live = new globalThis.Set();
+  let next = 16;
+
+  const memory = new roc.RocMemory({
+    memory: new WebAssembly.Memory({ initial: 1 }),
+    roc_alloc(size, alignment) {
+      const address = Math.ceil(next / alignment) * alignment;
+
+      next = address + size;
+      live.add(address);
+
+      return address;
+    },
+    roc_dealloc(address) {
+      assert.ok(live.delete(address), `${address} was freed, but it wasn't allocated`);
+    },
+    ...entryPoints,
+  });
+
+  return { memory, live };
+}
+
+/** Writes `value` to newly allocated memory, and returns its address. */
+function write(memory, codec, value) {
+  const address = memory.alloc(codec.size, codec.align);
+
+  codec.write(memory, address, value);
+
+  return address;
+}
+
+test("numbers round-trip", () => {
+  const { memory } = testMemory();
+
+  for (const [codec, value] of [
+    [roc.U8, 255],
+    [roc.I16, -2],
+    [roc.U32, 4000000000],
+    [roc.I64, -(2n ** 63n)],
+    [roc.U64, 2n ** 64n - 1n],
+    [roc.F32, 1.5],
+    [roc.F64, -0.1],
+    [roc.Bool, true],
+    [roc.I128, -(2n ** 127n)],
+    [roc.U128, 2n ** 128n - 1n],
+  ]) {
+    assert.equal(codec.read(memory, write(memory, codec, value)), value);
+  }
+
+  // The bytes of a negative I128 are its two's complement, with the low half first.
+  const address = write(memory, roc.I128, -2n);
+
+  assert.equal(memory.view.getBigUint64(address, true), 2n ** 64n - 2n);
+  assert.equal(memory.view.getBigUint64(address + 8, true), 2n ** 64n - 1n);
+});
+
+test("Dec is an I128 of the number times 10^18", () => {
+  const { memory } = testMemory();
+  const address = write(memory, roc.Dec, "-1.5");
+
+  assert.equal(roc.I128.read(memory, address), -1500000000000000000n);
+  assert.equal(roc.Dec.read(memory, address), "-1.5");
+  assert.equal(roc.Dec.read(memory, write(memory, roc.Dec, "42")), "42");
+  assert.equal(roc.Dec.read(memory, write(memory, roc.Dec, "0.000000000000000001")), "0.000000000000000001");
+  assert.throws(() => write(memory, roc.Dec, "1e3"), RangeError);
+});
+
+test("small strings are stored inline", () => {
+  const { memory, live } = testMemory();
+  const address = write(memory, roc.Str, "hello");
+  const allocations = live.size;
+
+  assert.equal(memory.view.getUint8(address + roc.Str.size - 1), 5 | 0x80);
+  assert.equal(roc.Str.read(memory, address), "hello");
+
+  roc.Str.release(memory, address);
+  assert.equal(live.size, allocations);
+});
+
+test("big strings are refcounted, and freed on their last release", () => {
+  const { memory, live } = testMemory();
+  const text = "a string too long to be stored inline: ✓";
+  const address = write(memory, roc.Str, text);
+  const [data, length, capacity] = [0, 1, 2].map((index) =>
+    memory.view.getUint32(address + index * POINTER_SIZE, true)
+  );
+
+  assert.equal(length, new TextEncoder().encode(text).length);
+  assert.equal(capacity, length);
+  assert.equal(memory.view.getInt32(data - POINTER_SIZE, true), 1);
+  assert.equal(roc.Str.read(memory, address), text);
+
+  memory.view.setInt32(data - POINTER_SIZE, 2, true);
+  roc.Str.release(memory, address);
+  assert.equal(memory.view.getInt32(data - POINTER_SIZE, true), 1);
+  assert.ok(live.has(data - POINTER_SIZE));
+
+  roc.Str.release(memory, address);
+  assert.ok(!live.has(data - POINTER_SIZE));
+});
+
+test("seamless slices read their part of the string, and free the whole allocation", () => {
+  const { memory, live } = testMemory();
+  const address = write(memory, roc.Str, "the whole string, and a slice of it");
+  const data = memory.view.getUint32(address, true);
+
+  // A slice of "whole": its start, its length with the seamless slice bit, and the original
+  // allocation shifted right by one.
+  const slice = memory.alloc(roc.Str.size, roc.Str.align);
+
+  memory.view.setUint32(slice, data + 4, true);
+  memory.view.setUint32(slice + 4, 5 | SEAMLESS_SLICE_BIT, true);
+  memory.view.setUint32(slice + 8, data >>> 1, true);
+
+  assert.equal(roc.Str.read(memory, slice), "whole");
+
+  roc.Str.release(memory, slice);
+  assert.ok(!live.has(data - POINTER_SIZE));
+});
+
+test("lists release their elements along with themselves", () => {
+  const { memory, live } = testMemory();
+  const codec = roc.List(roc.Str);
+  const words = ["short", "a string that has to be allocated", "another allocated string"];
+  const allocations = live.size;
+  const address = write(memory, codec, words);
+
+  // The list's own allocation, then one for each big string, and the list value itself.
+  assert.equal(live.size, allocations + 4);
+  assert.deepEqual(codec.read(memory, address), words);
+
+  // Elements that are refcounted have the element count in front of the refcount.
+  const data = memory.view.getUint32(address, true);
+
+  assert.equal(memory.view.getUint32(data - 2 * POINTER_SIZE, true), words.length);
+
+  codec.release(memory, address);
+  assert.equal(live.size, allocations + 1);
+
+  const empty = write(memory, codec, []);
+
+  assert.deepEqual(codec.read(memory, empty), []);
+  codec.release(memory, empty);
+});
+
+test("records and tag unions read and write their fields at their offsets", () => {
+  const { memory, live } = testMemory();
+  const shape = roc.tagUnion({
+    size: 24,
+    align: 8,
+    discriminantOffset: 16,
+    discriminantSize: 1,
+    tags: [
+      ["Circle", roc.F64],
+      ["Point", null],
+      [
+        "Rectangle",
+        roc.tagPayload({
+          size: 16,
+          align: 8,
+          fields: [
+            [0, 0, roc.F64],
+            [1, 8, roc.F64],
+          ],
+        }),
+      ],
+    ],
+  });
+  const input = roc.record({
+    size: 48,
+    align: 8,
+    fields: [
+      ["shape", 0, shape],
+      ["name", 24, roc.Str],
+      ["scores", 36, roc.List(roc.I64)],
+    ],
+  });
+  const value = {
+    shape: { tag: "Rectangle", payload: [2, 3.5] },
+    name: "Ada Lovelace, the first programmer",
+    scores: [40n, 70n],
+  };
+  const allocations = live.size;
+  const address = write(memory, input, value);
+
+  assert.deepEqual(input.read(memory, address), value);
+  assert.equal(memory.view.getUint8(address + 16), 2);
+  assert.equal(memory.view.getFloat64(address + 8, true), 3.5);
+  assert.equal(roc.Str.read(memory, address + 24), value.name);
+
+  input.release(memory, address);
+  assert.equal(live.size, allocations + 1);
+
+  assert.deepEqual(shape.read(memory, write(memory, shape, { tag: "Point" })), { tag: "Point" });
+  assert.throws(() => write(memory, shape, { tag: "Triangle" }), TypeError);
+  assert.throws(() => write(memory, input, { name: "Ada" }), TypeError);
+});
+
+test("results are laid out like other tag unions, with Err first", () => {
+  const { memory } = testMemory();
+  const codec = roc.Result(roc.I64, roc.Str);
+
+  assert.equal(codec.size, 24);
+  assert.equal(codec.align, 8);
+
+  const ok = write(memory, codec, { tag: "Ok", payload: 40n });
+  const err = write(memory, codec, { tag: "Err", payload: "no scores" });
+
+  assert.equal(memory.view.getUint8(ok + 16), 1);
+  assert.equal(memory.view.getUint8(err + 16), 0);
+  assert.deepEqual(codec.read(memory, ok), { tag: "Ok", payload: 40n });
+  assert.deepEqual(codec.read(memory, err), { tag: "Err", payload: "no scores" });
+});
+
+test("enumerations are passed to wasm as their discriminant", () => {
+  const { memory } = testMemory();
+  const grade = roc.enumeration({ size: 1, tags: ["Fail", "Incomplete", "Pass"] });
+
+  assert.equal(grade.toWasm("Pass"), 2);
+  assert.equal(grade.read(memory, write(memory, grade, "Incomplete")), "Incomplete");
+  assert.throws(() => grade.toWasm("Excellent"), TypeError);
+});
+
+test("dicts read their entries from their data list", () => {
+  const { memory, live } = testMemory();
+  const codec = roc.Dict(roc.Str, roc.U64);
+  const address = memory.alloc(codec.size, codec.align);
+
+  memory.bytes(address, codec.size).fill(0);
+
+  // The (Str, U64) pairs put the U64 first, because it has the bigger alignment.
+  const entries = [
+    ["Ada", 3n],
+    ["a key that has to be allocated", 30n],
+  ];
+  const pairSize = 24;
+  const data = memory.allocRefcounted(entries.length * pairSize, 8, true, entries.length);
+
+  entries.forEach(([key, value], index) => {
+    roc.U64.write(memory, data + index * pairSize, value);
+    roc.Str.write(memory, data + index * pairSize + 8, key);
+  });
+
+  memory.view.setUint32(address + 20, data, true);
+  memory.view.setUint32(address + 24, entries.length, true);
+  memory.view.setUint32(address + 28, entries.length, true);
+
+  assert.deepEqual(codec.read(memory, address), new Map(entries));
+
+  const allocations = live.size;
+
+  codec.release(memory, address);
+  assert.equal(live.size, allocations - 2);
+
+  assert.throws(() => codec.write(memory, address, new Map()), TypeError);
+  assert.throws(() => roc.Set(roc.Str).write(memory, address, new globalThis.Set()), TypeError);
+});
+
+test("calls free the arguments' memory and release what the entry point returns", () => {
+  const { memory, live } = testMemory({
+    // Like a Roc function `\name -> "Hello, ${name}!"`, which owns its argument.
+    roc__greet_1_exposed_generic(retAddress, nameAddress) {
+      const name = roc.Str.read(memory, nameAddress);
+
+      roc.Str.release(memory, nameAddress);
+      roc.Str.write(memory, retAddress, `Hello, ${name}!`);
+    },
+  });
+  const allocations = live.size;
+
+  const greeting = roc.call(memory, "roc__greet_1_exposed_generic", roc.Str, [roc.Str], [
+    "Ada Lovelace, the first programmer",
+  ]);
+
+  assert.equal(greeting, "Hello, Ada Lovelace, the first programmer!");
+  assert.equal(live.size, allocations);
+
+  assert.throws(() => roc.call(memory, "roc__missing_1_exposed_generic", roc.Str, [], []));
+});
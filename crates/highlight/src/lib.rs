@@ -12,8 +12,45 @@ pub fn highlight_roc_code_inline(code: &str) -> String {
     format!("<code>{}</code>", buf.join(""))
 }
 
+/// The HTML of each token in `code`. Text after the last token that the highlighter couldn't
+/// make sense of, like a trailing newline or what follows a syntax error, comes last, unhighlighted.
 pub fn highlight(code: &str) -> Vec<String> {
-    let mut buf: Vec<String> = Vec::new();
+    highlight_spans(code)
+        .into_iter()
+        .map(|(class, text)| span_html(class, text))
+        .collect()
+}
+
+/// Like [highlight], but gives the HTML of each line of `code` separately, for example to
+/// number them. Spans that cover several lines are split up at the line breaks.
+pub fn highlight_lines(code: &str) -> Vec<String> {
+    let mut lines = vec![String::new()];
+
+    for (class, text) in highlight_spans(code) {
+        for (index, segment) in text.split('\n').enumerate() {
+            if index > 0 {
+                lines.push(String::new());
+            }
+
+            if !segment.is_empty() {
+                lines
+                    .last_mut()
+                    .unwrap()
+                    .push_str(&span_html(class, segment));
+            }
+        }
+    }
+
+    if code.ends_with('\n') {
+        lines.pop();
+    }
+
+    lines
+}
+
+/// The text of each token in `code`, with the CSS class to highlight it with.
+fn highlight_spans(code: &str) -> Vec<(Option<&'static str>, &str)> {
+    let mut buf = Vec::new();
     let mut offset = 0;
 
     // Sometimes code snippets start with "»" in order to show that they're in the repl.
@@ -21,7 +58,7 @@ pub fn highlight(code: &str) -> Vec<String> {
     const REPL_PROMPT: &str = "»";
 
     let code = if let Some(stripped) = code.strip_prefix(REPL_PROMPT) {
-        buf = push_html_span(buf, REPL_PROMPT, "kw");
+        buf.push((Some("kw"), REPL_PROMPT));
 
         stripped
     } else {
@@ -34,7 +71,7 @@ pub fn highlight(code: &str) -> Vec<String> {
         match location.value {
             // Comments `#` and Documentation comments `##`
             Token::LineComment | Token::DocComment => {
-                buf = push_html_span(buf, current_text, "comment");
+                buf.push((Some("comment"), current_text));
             }
             // Number, String, Tag, Type literals
             Token::SingleQuote
@@ -43,7 +80,7 @@ pub fn highlight(code: &str) -> Vec<String> {
             | Token::EscapedChar
             | Token::Interpolated
             | Token::Number => {
-                buf = push_html_span(buf, current_text, "literal");
+                buf.push((Some("literal"), current_text));
             }
             // Keywords and punctuation
             Token::Keyword
@@ -56,7 +93,7 @@ pub fn highlight(code: &str) -> Vec<String> {
             | Token::Colon
             | Token::And
             | Token::QuestionMark => {
-                buf = push_html_span(buf, current_text, "kw");
+                buf.push((Some("kw"), current_text));
             }
             // Operators
             Token::Percent
@@ -76,7 +113,7 @@ pub fn highlight(code: &str) -> Vec<String> {
             | Token::Multiply
             | Token::Plus
             | Token::DoubleAnd => {
-                buf = push_html_span(buf, current_text, "op");
+                buf.push((Some("op"), current_text));
             }
             // Delimieters
             Token::Paren
@@ -85,42 +122,78 @@ pub fn highlight(code: &str) -> Vec<String> {
             | Token::Comma
             | Token::Bar
             | Token::Decimal => {
-                buf = push_html_span(buf, current_text, "delimiter");
+                buf.push((Some("delimiter"), current_text));
             }
             // Types, Tags, and Modules
             Token::UpperIdent | Token::AtSign => {
-                buf = push_html_span(buf, current_text, "upperident");
+                buf.push((Some("upperident"), current_text));
             }
             // Variables modules and field names
             Token::LowerIdent | Token::Underscore => {
-                buf = push_html_span(buf, current_text, "lowerident");
+                buf.push((Some("lowerident"), current_text));
             }
             // Anyting else that wasn't tokenised
             Token::Error | Token::Other => {
-                buf = push_html(buf, current_text);
+                buf.push((None, current_text));
             }
         }
 
         offset = location.byte_range().end;
     }
 
+    // Whatever the highlighter couldn't make sense of, e.g. after a syntax error
+    if offset < code.len() {
+        buf.push((None, &code[offset..]));
+    }
+
     buf
 }
 
-fn push_html_span(mut buf: Vec<String>, curr: &str, class: &str) -> Vec<String> {
+fn span_html(class: Option<&str>, text: &str) -> String {
     // html escape strings from source code
-    let escaped = html_escape::encode_text(curr);
+    let escaped = html_escape::encode_text(text);
 
-    buf.push(format!("<span class=\"{class}\">{escaped}</span>"));
-
-    buf
+    match class {
+        Some(class) => format!("<span class=\"{class}\">{escaped}</span>"),
+        None => escaped.into_owned(),
+    }
 }
 
-fn push_html(mut buf: Vec<String>, curr: &str) -> Vec<String> {
-    // html escape strings from source code
-    let escaped = html_escape::encode_text(curr);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlight_keeps_text_after_the_last_token() {
+        assert_eq!(
+            highlight("x = 1\n"),
+            [
+                "<span class=\"lowerident\">x</span>",
+                "<span class=\"kw\"> =</span>",
+                "<span class=\"literal\"> 1</span>",
+                "\n",
+            ]
+        );
+    }
+
+    #[test]
+    fn highlight_lines_splits_spans_at_line_breaks() {
+        assert_eq!(
+            highlight_lines("x = \"\"\"\n    a\n    \"\"\""),
+            [
+                "<span class=\"lowerident\">x</span><span class=\"kw\"> =</span><span class=\"literal\"> \"\"\"</span>",
+                "<span class=\"literal\">    a</span>",
+                "<span class=\"literal\">    \"\"\"</span>",
+            ]
+        );
+    }
 
-    buf.push(format!("{escaped}"));
+    #[test]
+    fn highlight_lines_has_no_empty_line_after_a_trailing_newline() {
+        let line = "<span class=\"lowerident\">x</span><span class=\"kw\"> =</span><span class=\"literal\"> 1</span>";
 
-    buf
+        assert_eq!(highlight_lines("x = 1\n"), [line]);
+        assert_eq!(highlight_lines("x = 1"), [line]);
+        assert_eq!(highlight_lines("x = 1\n\n"), [line, ""]);
+    }
 }
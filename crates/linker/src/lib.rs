@@ -78,16 +78,27 @@ pub fn generate_stub_lib_from_loaded(
     stub_lib
 }
 
+/// Exported by apps built with `--export-dict-seed`, so that hosts can hash Dict keys the way
+/// the app does.
+const ROC_DICT_PSEUDO_SEED: &str = "roc_dict_pseudo_seed";
+
 pub struct ExposedSymbols {
     // usually just `mainForhost`
     pub top_level_values: Vec<String>,
 
     // old type exposing mechanism
     pub exported_closure_types: Vec<String>,
+
+    // whether the app exports `roc_dict_pseudo_seed`
+    pub export_dict_seed: bool,
 }
 
 impl ExposedSymbols {
-    pub fn from_exposed_to_host(interns: &Interns, exposed_to_host: &ExposedToHost) -> Vec<String> {
+    pub fn from_exposed_to_host(
+        interns: &Interns,
+        exposed_to_host: &ExposedToHost,
+        export_dict_seed: bool,
+    ) -> Vec<String> {
         let mut custom_names = Vec::new();
 
         for x in exposed_to_host.top_level_values.keys() {
@@ -131,11 +142,7 @@ impl ExposedSymbols {
             custom_names.extend([format!("roc__{sym}_{id}_caller")]);
         }
 
-        // on windows (PE) binary search is used on the symbols,
-        // so they must be in alphabetical order
-        custom_names.sort_unstable();
-
-        custom_names
+        finish_custom_names(custom_names, export_dict_seed)
     }
 
     pub fn stub_dll_symbols(&self) -> Vec<String> {
@@ -157,12 +164,21 @@ impl ExposedSymbols {
             }
         }
 
-        // on windows (PE) binary search is used on the symbols,
-        // so they must be in alphabetical order
-        custom_names.sort_unstable();
+        finish_custom_names(custom_names, self.export_dict_seed)
+    }
+}
 
-        custom_names
+fn finish_custom_names(mut custom_names: Vec<String>, export_dict_seed: bool) -> Vec<String> {
+    if export_dict_seed {
+        // roc_std's RocDict hashes keys with the app's Dict seed.
+        custom_names.push(ROC_DICT_PSEUDO_SEED.to_string());
     }
+
+    // on windows (PE) binary search is used on the symbols,
+    // so they must be in alphabetical order
+    custom_names.sort_unstable();
+
+    custom_names
 }
 
 fn generate_dynamic_lib(target: Target, stub_dll_symbols: &[String], stub_lib_path: &Path) {
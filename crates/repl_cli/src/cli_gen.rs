@@ -13,81 +13,188 @@ use roc_mono::layout::STLayoutInterner;
 use roc_parse::ast::Expr;
 use roc_repl_eval::eval::jit_to_ast;
 use roc_repl_eval::gen::{format_answer, ReplOutput};
+use roc_repl_eval::pretty::PrintOptions;
 use roc_repl_eval::{ReplApp, ReplAppMemory};
 use roc_std::RocStr;
 use roc_target::Target;
 use roc_types::pretty_print::{name_and_print_var, DebugPrint};
 use roc_types::subs::Subs;
 
-pub fn eval_llvm(
-    mut loaded: MonomorphizedModule<'_>,
-    target: Target,
-    opt_level: OptLevel,
-) -> Option<ReplOutput> {
-    let arena = Bump::new();
-
-    debug_assert_eq!(loaded.exposed_to_host.top_level_values.len(), 1);
-    let (main_fn_symbol, main_fn_var) = loaded
-        .exposed_to_host
-        .top_level_values
-        .iter()
-        .next()
-        .unwrap();
-    let main_fn_symbol = *main_fn_symbol;
-    let main_fn_var = *main_fn_var;
-
-    // pretty-print the expr type string for later.
-    let expr_type_str = name_and_print_var(
-        main_fn_var,
-        &mut loaded.subs,
-        loaded.module_id,
-        &loaded.interns,
-        DebugPrint::NOTHING,
-    );
+use crate::Backend;
+use tempfile::NamedTempFile;
+
+/// The dev backend can build dylibs for the host on these platforms.
+const DEV_BACKEND_SUPPORTED: bool = cfg!(all(
+    any(target_os = "linux", target_os = "macos"),
+    any(target_arch = "x86_64", target_arch = "aarch64")
+));
+
+/// Generates code for REPL inputs with the given [Backend].
+///
+/// This lives as long as the REPL does, so the builtins object the dev backend's output
+/// gets linked against is only written once. Nothing else carries over between inputs: the
+/// loader has no way to reuse its state, so each input is loaded and type checked from scratch.
+#[derive(Default)]
+pub struct CliGen {
+    backend: Backend,
+    /// The builtins that the dev backend's output gets linked against. Writing this object
+    /// out is a big part of what evaluating a line costs, so it only happens once per session.
+    builtins_host: Option<NamedTempFile>,
+}
 
-    let (_, main_fn_layout) = *loaded
-        .procedures
-        .keys()
-        .find(|(s, _)| *s == main_fn_symbol)?;
-
-    let interns = loaded.interns.clone();
-
-    #[cfg(not(all(
-        any(target_os = "linux", target_os = "macos"),
-        any(target_arch = "x86_64", target_arch = "aarch64")
-    )))]
-    let (lib, main_fn_name, subs, layout_interner) =
-        mono_module_to_dylib_llvm(&arena, target, loaded, opt_level)
-            .expect("we produce a valid Dylib");
-
-    #[cfg(all(
-        any(target_os = "linux", target_os = "macos"),
-        any(target_arch = "x86_64", target_arch = "aarch64")
-    ))]
-    let (lib, main_fn_name, subs, layout_interner) =
-        mono_module_to_dylib_asm(&arena, target, loaded, opt_level)
-            .expect("We failed to produce a valid Dylib.\nTIP: if you're on macos, try this:\n\t<https://github.com/roc-lang/roc/issues/5797#issuecomment-1786105269>");
-
-    let mut app = CliApp { lib };
-
-    let expr = jit_to_ast(
-        &arena,
-        &mut app,
-        main_fn_name,
-        main_fn_layout,
-        main_fn_var,
-        &subs,
-        &interns,
-        layout_interner.into_global().fork(),
-        target,
-    );
+impl CliGen {
+    pub fn new(backend: Backend) -> Self {
+        Self {
+            backend,
+            builtins_host: None,
+        }
+    }
+
+    pub fn eval(
+        &mut self,
+        mut loaded: MonomorphizedModule<'_>,
+        target: Target,
+        opt_level: OptLevel,
+        print_options: PrintOptions,
+    ) -> Option<ReplOutput> {
+        let arena = Bump::new();
+
+        debug_assert_eq!(loaded.exposed_to_host.top_level_values.len(), 1);
+        let (main_fn_symbol, main_fn_var) = loaded
+            .exposed_to_host
+            .top_level_values
+            .iter()
+            .next()
+            .unwrap();
+        let main_fn_symbol = *main_fn_symbol;
+        let main_fn_var = *main_fn_var;
+
+        // pretty-print the expr type string for later.
+        let expr_type_str = name_and_print_var(
+            main_fn_var,
+            &mut loaded.subs,
+            loaded.module_id,
+            &loaded.interns,
+            DebugPrint::NOTHING,
+        );
+
+        let (_, main_fn_layout) = *loaded
+            .procedures
+            .keys()
+            .find(|(s, _)| *s == main_fn_symbol)?;
+
+        let interns = loaded.interns.clone();
+
+        let (lib, main_fn_name, subs, layout_interner) =
+            self.mono_module_to_dylib(&arena, target, loaded, opt_level);
+
+        let mut app = CliApp { lib };
+
+        let expr = jit_to_ast(
+            &arena,
+            &mut app,
+            main_fn_name,
+            main_fn_layout,
+            main_fn_var,
+            &subs,
+            &interns,
+            layout_interner.into_global().fork(),
+            target,
+            print_options.max_length,
+        );
+
+        let expr_str = format_answer(&arena, expr, print_options);
+
+        Some(ReplOutput {
+            expr: expr_str,
+            expr_type: expr_type_str,
+        })
+    }
 
-    let expr_str = format_answer(&arena, expr).to_string();
+    fn mono_module_to_dylib<'a>(
+        &mut self,
+        arena: &'a Bump,
+        target: Target,
+        loaded: MonomorphizedModule<'a>,
+        opt_level: OptLevel,
+    ) -> (Library, &'a str, Subs, STLayoutInterner<'a>) {
+        // The dev backend panics on anything it doesn't implement yet, so check for that
+        // before building anything with it.
+        let use_dev_backend = self.backend == Backend::Dev
+            && DEV_BACKEND_SUPPORTED
+            && roc_gen_dev::supports_procedures(&loaded.layout_interner, &loaded.procedures);
+
+        if use_dev_backend {
+            self.mono_module_to_dylib_asm(arena, target, loaded)
+        } else {
+            mono_module_to_dylib_llvm(arena, target, loaded, opt_level)
+                .expect("we produce a valid Dylib")
+        }
+    }
 
-    Some(ReplOutput {
-        expr: expr_str,
-        expr_type: expr_type_str,
-    })
+    fn mono_module_to_dylib_asm<'a>(
+        &mut self,
+        arena: &'a Bump,
+        target: Target,
+        mut loaded: MonomorphizedModule<'a>,
+    ) -> (Library, &'a str, Subs, STLayoutInterner<'a>) {
+        let env = roc_gen_dev::Env {
+            arena,
+            module_id: loaded.module_id,
+            exposed_to_host: loaded
+                .exposed_to_host
+                .top_level_values
+                .keys()
+                .copied()
+                .collect(),
+            lazy_literals: true,
+            mode: roc_gen_dev::AssemblyBackendMode::Repl,
+        };
+
+        let module_object = roc_gen_dev::build_module(
+            &env,
+            &mut loaded.interns,
+            &mut loaded.layout_interner,
+            target,
+            loaded.procedures,
+        );
+
+        // Every evaluation gets its own directory, because loading a dylib from a path that
+        // was loaded before can give back the old library.
+        let dir = tempfile::tempdir().unwrap();
+        let app_o_file = dir.path().join("app.o");
+
+        let module_out = module_object
+            .write()
+            .expect("failed to build output object");
+        std::fs::write(&app_o_file, module_out).expect("failed to write object to file");
+
+        let builtins_host = self.builtins_host.get_or_insert_with(|| {
+            roc_bitcode::host_tempfile().expect("failed to write host builtins object to tempfile")
+        });
+
+        let (mut child, dylib_path) = roc_build::link::link(
+            target,
+            app_o_file.clone(),
+            // Long term we probably want a smarter way to link in zig builtins.
+            // With the current method all methods are kept and it adds about 100k to all outputs.
+            &[
+                app_o_file.to_str().unwrap(),
+                builtins_host.path().to_str().unwrap(),
+            ],
+            roc_build::link::LinkType::Dylib,
+        )
+        .expect("failed to link dynamic library");
+
+        child.wait().unwrap();
+
+        // Load the dylib
+        let path = dylib_path.as_path().to_str().unwrap();
+
+        let lib = unsafe { Library::new(path) }.expect("We failed to produce a valid Dylib.\nTIP: if you're on macos, try this:\n\t<https://github.com/roc-lang/roc/issues/5797#issuecomment-1786105269>");
+
+        (lib, "test_main", loaded.subs, loaded.layout_interner)
+    }
 }
 
 struct CliApp {
@@ -169,13 +276,6 @@ impl ReplAppMemory for CliMemory {
     }
 }
 
-#[cfg_attr(
-    all(
-        any(target_os = "linux", target_os = "macos"),
-        any(target_arch = "x86_64", target_arch = "aarch64")
-    ),
-    allow(unused)
-)]
 fn mono_module_to_dylib_llvm<'a>(
     arena: &'a Bump,
     target: Target,
@@ -263,82 +363,3 @@ fn mono_module_to_dylib_llvm<'a>(
     llvm_module_to_dylib(env.module, target, opt_level)
         .map(|lib| (lib, main_fn_name, subs, layout_interner))
 }
-
-#[cfg_attr(
-    not(all(
-        any(target_os = "linux", target_os = "macos"),
-        any(target_arch = "x86_64", target_arch = "aarch64")
-    )),
-    allow(unused)
-)]
-fn mono_module_to_dylib_asm<'a>(
-    arena: &'a Bump,
-    target: Target,
-    loaded: MonomorphizedModule<'a>,
-    _opt_level: OptLevel,
-) -> Result<(libloading::Library, &'a str, Subs, STLayoutInterner<'a>), libloading::Error> {
-    // let dir = std::env::temp_dir().join("roc_repl");
-    let dir = tempfile::tempdir().unwrap();
-
-    let app_o_file = dir.path().join("app.o");
-
-    let MonomorphizedModule {
-        module_id,
-        procedures,
-        host_exposed_lambda_sets: _,
-        exposed_to_host,
-        mut interns,
-        subs,
-        mut layout_interner,
-        ..
-    } = loaded;
-
-    let lazy_literals = true;
-    let env = roc_gen_dev::Env {
-        arena,
-        module_id,
-        exposed_to_host: exposed_to_host.top_level_values.keys().copied().collect(),
-        lazy_literals,
-        mode: roc_gen_dev::AssemblyBackendMode::Repl,
-    };
-
-    let module_object =
-        roc_gen_dev::build_module(&env, &mut interns, &mut layout_interner, target, procedures);
-
-    let module_out = module_object
-        .write()
-        .expect("failed to build output object");
-    std::fs::write(&app_o_file, module_out).expect("failed to write object to file");
-
-    // TODO make this an environment variable
-    if false {
-        let file_path = std::env::temp_dir().join("app.o");
-        println!("gen-test object file written to {}", file_path.display());
-        std::fs::copy(&app_o_file, file_path).unwrap();
-    }
-
-    let builtins_host_tempfile =
-        roc_bitcode::host_tempfile().expect("failed to write host builtins object to tempfile");
-
-    let (mut child, dylib_path) = roc_build::link::link(
-        target,
-        app_o_file.clone(),
-        // Long term we probably want a smarter way to link in zig builtins.
-        // With the current method all methods are kept and it adds about 100k to all outputs.
-        &[
-            app_o_file.to_str().unwrap(),
-            builtins_host_tempfile.path().to_str().unwrap(),
-        ],
-        roc_build::link::LinkType::Dylib,
-    )
-    .expect("failed to link dynamic library");
-
-    child.wait().unwrap();
-
-    // Load the dylib
-    let path = dylib_path.as_path().to_str().unwrap();
-
-    let lib = unsafe { Library::new(path) }?;
-
-    Ok((lib, "test_main", subs, layout_interner))
-}
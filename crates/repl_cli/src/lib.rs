@@ -6,25 +6,31 @@ use const_format::concatcp;
 use roc_load::MonomorphizedModule;
 use roc_mono::ir::OptLevel;
 use roc_repl_eval::gen::Problems;
+use roc_repl_eval::pretty::PrintOptions;
 use roc_repl_ui::colors::{CYAN, END_COL};
+use roc_repl_ui::completion::complete;
 use roc_repl_ui::repl_state::{ReplAction, ReplState};
-use roc_repl_ui::{format_output, is_incomplete, CONT_PROMPT, PROMPT, SHORT_INSTRUCTIONS, TIPS};
+use roc_repl_ui::{
+    format_defs, format_output, is_incomplete, CONT_PROMPT, PROMPT, SHORT_INSTRUCTIONS, TIPS,
+};
 use roc_reporting::report::{
     strip_colors, to_file_problem_report_string, ANSI_STYLE_CODES, DEFAULT_PALETTE,
 };
 use roc_target::Target;
+use rustyline::completion::Completer;
 use rustyline::highlight::{Highlighter, PromptInfo};
 use rustyline::validate::{self, ValidationContext, ValidationResult, Validator};
-use rustyline::Config;
-use rustyline_derive::{Completer, Helper, Hinter};
+use rustyline::{Config, Context};
+use rustyline_derive::{Helper, Hinter};
 use std::any::Any;
 use std::backtrace::Backtrace;
 use std::borrow::Cow;
 use std::panic::{AssertUnwindSafe, PanicInfo};
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex, OnceLock};
 use target_lexicon::Triple;
 
-use crate::cli_gen::eval_llvm;
+use crate::cli_gen::CliGen;
 
 pub const WELCOME_MESSAGE: &str = concatcp!(
     "\n  The rockin' ",
@@ -35,7 +41,19 @@ pub const WELCOME_MESSAGE: &str = concatcp!(
     "\n\n"
 );
 
-#[derive(Completer, Helper, Hinter, Default)]
+/// Which backend generates code for what the REPL evaluates.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// The dev backend, which starts up much faster than LLVM. Programs that use anything
+    /// it doesn't support yet, according to [roc_gen_dev::supports_procedures], get built with
+    /// LLVM instead.
+    #[default]
+    Dev,
+    /// Always LLVM.
+    Llvm,
+}
+
+#[derive(Helper, Hinter, Default)]
 pub struct ReplHelper {
     validator: InputValidator,
     state: ReplState,
@@ -92,13 +110,25 @@ pub fn main(has_color: bool, has_header: bool) -> i32 {
     editor.set_helper(Some(repl_helper));
     let target = Triple::host().into();
     let mut arena = Bump::new();
+    let history_path = history_path();
+    let mut cli_gen = CliGen::default();
+
+    // Neither the cache dir nor the history exist the first time the REPL runs,
+    // so ignore errors here.
+    if let Some(dir) = history_path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    let _ = editor.load_history(&history_path);
 
     loop {
         match editor.readline(&strip_colors_if_necessary(PROMPT)) {
             Ok(line) => {
                 let line = line.trim();
 
-                editor.add_history_entry(line);
+                if editor.add_history_entry(line) {
+                    // Save after every entry, so history survives the REPL crashing.
+                    let _ = editor.save_history(&history_path);
+                }
 
                 let repl_state = &mut editor
                     .helper_mut()
@@ -113,8 +143,13 @@ pub fn main(has_color: bool, has_header: bool) -> i32 {
                 .unwrap_or_else(|e| notify_repl_panic(target, e));
 
                 match action {
-                    ReplAction::Eval { opt_mono, problems } => {
-                        let output = evaluate(opt_mono, problems, target);
+                    ReplAction::Eval {
+                        opt_mono,
+                        problems,
+                        print_options,
+                    } => {
+                        let output =
+                            evaluate_with(&mut cli_gen, opt_mono, problems, print_options, target);
                         // If there was no output, don't print a blank line!
                         // (This happens for something like a type annotation.)
                         if !output.is_empty() {
@@ -133,6 +168,20 @@ pub fn main(has_color: bool, has_header: bool) -> i32 {
                     ReplAction::Help => {
                         println!("{TIPS}");
                     }
+                    ReplAction::Type {
+                        opt_output,
+                        problems,
+                    } => {
+                        let output = format_output(ANSI_STYLE_CODES, opt_output, problems);
+                        println!("{}", strip_colors_if_necessary(&output));
+                    }
+                    ReplAction::Defs { defs, problems } => {
+                        let output = format_defs(ANSI_STYLE_CODES, defs, problems);
+                        println!("{}", strip_colors_if_necessary(&output));
+                    }
+                    ReplAction::Message(message) => {
+                        println!("{}", strip_colors_if_necessary(&message));
+                    }
                     ReplAction::Nothing => {}
                 }
             }
@@ -190,12 +239,36 @@ Machine Target: {:#?}
 pub fn evaluate(
     opt_mono: Option<MonomorphizedModule<'_>>,
     problems: Problems,
+    print_options: PrintOptions,
     target: Target,
+    backend: Backend,
 ) -> String {
-    let opt_output = opt_mono.and_then(|mono| eval_llvm(mono, target, OptLevel::Normal));
+    evaluate_with(
+        &mut CliGen::new(backend),
+        opt_mono,
+        problems,
+        print_options,
+        target,
+    )
+}
+
+fn evaluate_with(
+    cli_gen: &mut CliGen,
+    opt_mono: Option<MonomorphizedModule<'_>>,
+    problems: Problems,
+    print_options: PrintOptions,
+    target: Target,
+) -> String {
+    let opt_output =
+        opt_mono.and_then(|mono| cli_gen.eval(mono, target, OptLevel::Normal, print_options));
     format_output(ANSI_STYLE_CODES, opt_output, problems)
 }
 
+/// Where REPL history is kept between sessions, e.g. ~/.cache/roc/repl_history.txt
+fn history_path() -> PathBuf {
+    roc_packaging::cache::roc_cache_dir().join("repl_history.txt")
+}
+
 #[derive(Default)]
 struct InputValidator {}
 
@@ -209,6 +282,21 @@ impl Validator for InputValidator {
     }
 }
 
+impl Completer for ReplHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let target = Triple::host().into();
+
+        Ok(complete(&self.state, line, pos, target))
+    }
+}
+
 impl Highlighter for ReplHelper {
     fn has_continuation_prompt(&self) -> bool {
         true
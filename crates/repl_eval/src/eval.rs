@@ -21,6 +21,7 @@ use roc_types::subs::{
     Content, FlatType, GetSubsSlice, RecordFields, Subs, TagExt, TupleElems, UnionTags, Variable,
 };
 
+use crate::pretty::more_items;
 use crate::{ReplApp, ReplAppMemory};
 
 struct Env<'a, 'env> {
@@ -29,6 +30,9 @@ struct Env<'a, 'env> {
     target: Target,
     interns: &'a Interns,
     layout_cache: LayoutCache<'a>,
+    /// How many elements of each list get read. The rest are left out, which keeps printing
+    /// a huge list from converting all of it first.
+    max_list_length: usize,
 }
 
 /// JIT execute the given main function, and then wrap its results in an Expr
@@ -39,6 +43,9 @@ struct Env<'a, 'env> {
 /// By traversing the type signature while we're traversing the layout, once
 /// we get to a struct or tag, we know what the labels are and can turn them
 /// back into the appropriate user-facing literals.
+///
+/// Only the first `max_list_length` elements of each list are read, followed by a
+/// [more_items] placeholder for the rest.
 #[allow(clippy::too_many_arguments)]
 pub fn jit_to_ast<'a, A: ReplApp<'a>>(
     arena: &'a Bump,
@@ -50,6 +57,7 @@ pub fn jit_to_ast<'a, A: ReplApp<'a>>(
     interns: &'a Interns,
     layout_interner: TLLayoutInterner<'a>,
     target: Target,
+    max_list_length: usize,
 ) -> Expr<'a> {
     let mut env = Env {
         arena,
@@ -57,6 +65,7 @@ pub fn jit_to_ast<'a, A: ReplApp<'a>>(
         target,
         interns,
         layout_cache: LayoutCache::new(layout_interner, target),
+        max_list_length,
     };
 
     match layout {
@@ -958,10 +967,11 @@ fn list_to_ast<'a, M: ReplAppMemory>(
     };
 
     let arena = env.arena;
-    let mut output = Vec::with_capacity_in(len, arena);
+    let shown = len.min(env.max_list_length);
+    let mut output = Vec::with_capacity_in(shown + 1, arena);
     let elem_size = env.layout_cache.interner.stack_size(elem_layout) as usize;
 
-    for index in 0..len {
+    for index in 0..shown {
         let offset_bytes = index * elem_size;
         let elem_addr = addr + offset_bytes;
         let (newtype_containers, _alias_content, elem_content) =
@@ -983,6 +993,12 @@ fn list_to_ast<'a, M: ReplAppMemory>(
         output.push(&*arena.alloc(expr));
     }
 
+    if shown < len {
+        let more = Loc::at_zero(more_items(arena, len - shown));
+
+        output.push(&*arena.alloc(more));
+    }
+
     let output = output.into_bump_slice();
 
     Expr::List(Collection::with_items(output))
@@ -5,40 +5,38 @@ use roc_problem::Severity;
 use roc_reporting::report::Palette;
 use std::path::PathBuf;
 
-use roc_fmt::annotation::Formattable;
-use roc_fmt::annotation::{Newlines, Parens};
-use roc_load::{LoadingProblem, MonomorphizedModule};
+use roc_collections::MutMap;
+use roc_load::{LoadedModule, LoadingProblem, MonomorphizedModule};
+use roc_module::symbol::{Interns, ModuleId};
 use roc_parse::ast::Expr;
 use roc_region::all::LineInfo;
 use roc_reporting::report::{can_problem, type_problem, RocDocAllocator};
 use roc_solve::FunctionKind;
+use roc_solve_problem::TypeError;
 use roc_target::Target;
 
+use crate::pretty::{pretty_print, PrintOptions};
+
 #[derive(Debug)]
 pub struct ReplOutput {
     pub expr: String,
     pub expr_type: String,
 }
 
-pub fn format_answer<'a>(arena: &'a Bump, answer: Expr<'_>) -> &'a str {
+pub fn format_answer(arena: &Bump, answer: Expr<'_>, options: PrintOptions) -> String {
     match answer {
-        Expr::Closure(_, _) => "<function>",
-        _ => {
-            let mut expr = roc_fmt::Buf::new_in(
-                arena,
-                roc_fmt::MigrationFlags {
-                    snakify: false,
-                    parens_and_commas: false,
-                },
-            );
-
-            answer.format_with_options(&mut expr, Parens::NotNeeded, Newlines::Yes, 0);
-
-            expr.into_bump_str()
-        }
+        Expr::Closure(_, _) => "<function>".to_string(),
+        _ => pretty_print(arena, &answer, options),
     }
 }
 
+/// A package the REPL's module depends on, like `json` after `import "https://…" as json`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplPackage {
+    pub shorthand: String,
+    pub url: String,
+}
+
 #[derive(Default, Debug)]
 pub struct Problems {
     pub errors: Vec<String>,
@@ -53,6 +51,7 @@ impl Problems {
 
 pub fn compile_to_mono<'a, 'i, I: Iterator<Item = &'i str>>(
     arena: &'a Bump,
+    packages: &[ReplPackage],
     defs: I,
     expr: &str,
     target: Target,
@@ -60,7 +59,7 @@ pub fn compile_to_mono<'a, 'i, I: Iterator<Item = &'i str>>(
 ) -> (Option<MonomorphizedModule<'a>>, Problems) {
     let filename = PathBuf::from("replfile.roc");
     let src_dir = PathBuf::from(".");
-    let (bytes_before_expr, module_src) = promote_expr_to_module(arena, defs, expr);
+    let (bytes_before_expr, module_src) = promote_expr_to_module(arena, packages, defs, expr);
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
         filename,
@@ -108,6 +107,97 @@ pub fn compile_to_mono<'a, 'i, I: Iterator<Item = &'i str>>(
         ..
     } = &mut loaded;
 
+    let problems = report_problems(
+        sources,
+        can_problems,
+        type_problems,
+        interns,
+        module_src,
+        bytes_before_expr,
+        palette,
+    );
+
+    (Some(loaded), problems)
+}
+
+/// Like [compile_to_mono], but stops after type checking. This is enough for commands
+/// like `:type`, which only need to know types, and is faster because it skips mono.
+pub fn typecheck<'a, 'i, I: Iterator<Item = &'i str>>(
+    arena: &'a Bump,
+    packages: &[ReplPackage],
+    defs: I,
+    expr: &str,
+    target: Target,
+    palette: Palette,
+) -> (Option<LoadedModule>, Problems) {
+    let filename = PathBuf::from("replfile.roc");
+    let src_dir = PathBuf::from(".");
+    let (bytes_before_expr, module_src) = promote_expr_to_module(arena, packages, defs, expr);
+    let loaded = roc_load::load_and_typecheck_str(
+        arena,
+        filename,
+        module_src,
+        src_dir,
+        None,
+        target,
+        FunctionKind::LambdaSet,
+        roc_reporting::report::RenderTarget::ColorTerminal,
+        RocCacheDir::Persistent(cache::roc_cache_packages_dir().as_path()),
+        palette,
+    );
+
+    let mut loaded = match loaded {
+        Ok(v) => v,
+        Err(LoadingProblem::FormattedReport(report, _)) => {
+            return (
+                None,
+                Problems {
+                    errors: vec![report],
+                    warnings: Vec::new(),
+                },
+            );
+        }
+        Err(e) => {
+            return (
+                None,
+                Problems {
+                    errors: vec![format!("Error while loading module: {e:?}")],
+                    warnings: Vec::new(),
+                },
+            );
+        }
+    };
+
+    let LoadedModule {
+        interns,
+        sources,
+        can_problems,
+        type_problems,
+        ..
+    } = &mut loaded;
+
+    let problems = report_problems(
+        sources,
+        can_problems,
+        type_problems,
+        interns,
+        module_src,
+        bytes_before_expr,
+        palette,
+    );
+
+    (Some(loaded), problems)
+}
+
+fn report_problems(
+    sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+    can_problems: &mut MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
+    type_problems: &mut MutMap<ModuleId, Vec<TypeError>>,
+    interns: &Interns,
+    module_src: &str,
+    bytes_before_expr: usize,
+    palette: Palette,
+) -> Problems {
     let mut problems = Problems::default();
 
     let errors = &mut problems.errors;
@@ -171,19 +261,42 @@ pub fn compile_to_mono<'a, 'i, I: Iterator<Item = &'i str>>(
         }
     }
 
-    (Some(loaded), problems)
+    problems
 }
 
-fn promote_expr_to_module<'a, 'i, I: Iterator<Item = &'i str>>(
+/// Wraps the defs and the expr in the app module the REPL compiles, which depends on `packages`.
+/// Also returns where the expr starts in that module, so problems in past defs can be skipped.
+pub fn promote_expr_to_module<'a, 'i, I: Iterator<Item = &'i str>>(
     arena: &'a Bump,
+    packages: &[ReplPackage],
     defs: I,
     expr: &str,
 ) -> (usize, &'a str) {
-    const REPL_MODULE_HEADER: &str = "app \"app\" provides [repl_output] to \"./platform\"\n\n";
+    const REPL_MODULE_PROVIDES: &str = " provides [repl_output] to \"./platform\"\n\n";
     const REPL_MODULE_MAIN_DEF: &str = "repl_output =\n";
     const INDENT: &str = "    ";
 
-    let mut buffer = bumpalo::collections::string::String::from_str_in(REPL_MODULE_HEADER, arena);
+    let mut buffer = bumpalo::collections::string::String::from_str_in("app \"app\"", arena);
+
+    if !packages.is_empty() {
+        buffer.push_str(" packages {");
+
+        for (index, ReplPackage { shorthand, url }) in packages.iter().enumerate() {
+            if index > 0 {
+                buffer.push(',');
+            }
+
+            buffer.push(' ');
+            buffer.push_str(shorthand);
+            buffer.push_str(": \"");
+            buffer.push_str(url);
+            buffer.push('"');
+        }
+
+        buffer.push_str(" }");
+    }
+
+    buffer.push_str(REPL_MODULE_PROVIDES);
 
     for line in defs {
         // don't indent the defs
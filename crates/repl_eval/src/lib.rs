@@ -5,6 +5,7 @@ use roc_target::Target;
 
 pub mod eval;
 pub mod gen;
+pub mod pretty;
 
 pub trait ReplApp<'a> {
     type Memory: 'a + ReplAppMemory;
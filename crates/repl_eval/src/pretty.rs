@@ -0,0 +1,330 @@
+//! Lays out the values the REPL prints. Values that fit in the width stay on one line, and
+//! records, lists, tuples, and tags that don't are broken over several lines, the way
+//! `roc format` would. Long strings are cut short, and so is anything nested too deep. Long lists
+//! were already cut short when they were read, and end in a [more_items] placeholder.
+use bumpalo::Bump;
+use roc_fmt::annotation::Formattable;
+use roc_fmt::annotation::{Newlines, Parens};
+use roc_parse::ast::{AssignedField, Collection, Expr, StrLiteral};
+use roc_region::all::Loc;
+
+const INDENT: usize = 4;
+
+/// How much of a value the REPL prints, set with `:set print-depth` and `:set print-length`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrintOptions {
+    /// How deeply records, lists, tuples, and tag payloads get printed before they're shown as `…`
+    pub max_depth: usize,
+    /// How many elements of a list, or characters of a string, get printed
+    pub max_length: usize,
+    /// The column that output should try to stay within
+    pub width: usize,
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 16,
+            max_length: 100,
+            width: 80,
+        }
+    }
+}
+
+/// Stands in for the elements at the end of a list that weren't read, because there were more
+/// than [PrintOptions::max_length] of them. It prints as `… 3 more`.
+pub fn more_items(arena: &Bump, hidden: usize) -> Expr<'_> {
+    Expr::Var {
+        module_name: "",
+        ident: arena.alloc_str(&format!("… {hidden} more")),
+    }
+}
+
+pub fn pretty_print(arena: &Bump, expr: &Expr<'_>, options: PrintOptions) -> String {
+    let printer = Printer { arena, options };
+
+    printer.print(expr, Parens::NotNeeded, 0, 0, 0)
+}
+
+struct Printer<'b> {
+    arena: &'b Bump,
+    options: PrintOptions,
+}
+
+impl Printer<'_> {
+    /// Prints `expr` starting at `column`, where continuation lines are indented by `indent`.
+    fn print(
+        &self,
+        expr: &Expr<'_>,
+        parens: Parens,
+        depth: usize,
+        indent: usize,
+        column: usize,
+    ) -> String {
+        let flat = self.flat(expr, parens, depth);
+
+        if column + flat.chars().count() <= self.options.width || !self.can_break(expr, depth) {
+            return flat;
+        }
+
+        match expr {
+            Expr::List(items) => {
+                let items = items
+                    .iter()
+                    .map(|item| self.print_item(&item.value, depth, indent))
+                    .collect();
+
+                self.multiline("[", items, "]", indent)
+            }
+            Expr::Tuple(items) => {
+                let items = items
+                    .iter()
+                    .map(|item| self.print_item(&item.value, depth, indent))
+                    .collect();
+
+                self.multiline("(", items, ")", indent)
+            }
+            Expr::Record(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|field| self.print_field(&field.value, depth, indent))
+                    .collect();
+
+                self.multiline("{", fields, "}", indent)
+            }
+            Expr::Apply(tag, args, _) => {
+                if parens == Parens::InApply {
+                    let inner = self.print(expr, Parens::NotNeeded, depth, indent, column + 1);
+
+                    return format!("({inner})");
+                }
+
+                self.print_apply(&tag.value, args, depth, indent, column)
+            }
+            _ => flat,
+        }
+    }
+
+    /// Prints `expr` all on one line.
+    fn flat(&self, expr: &Expr<'_>, parens: Parens, depth: usize) -> String {
+        if depth >= self.options.max_depth && self.is_nested(expr) {
+            return "…".to_string();
+        }
+
+        match expr {
+            Expr::List(items) => {
+                let items: Vec<_> = items
+                    .iter()
+                    .map(|item| self.flat(&item.value, Parens::NotNeeded, depth + 1))
+                    .collect();
+
+                format!("[{}]", items.join(", "))
+            }
+            Expr::Tuple(items) => {
+                let items: Vec<_> = items
+                    .iter()
+                    .map(|item| self.flat(&item.value, Parens::NotNeeded, depth + 1))
+                    .collect();
+
+                format!("({})", items.join(", "))
+            }
+            Expr::Record(fields) if !fields.is_empty() && self.has_plain_fields(fields) => {
+                let fields: Vec<_> = fields
+                    .iter()
+                    .map(|field| match &field.value {
+                        AssignedField::RequiredValue(name, _, value) => format!(
+                            "{}: {}",
+                            name.value,
+                            self.flat(&value.value, Parens::NotNeeded, depth + 1)
+                        ),
+                        _ => unreachable!("checked by has_plain_fields"),
+                    })
+                    .collect();
+
+                format!("{{ {} }}", fields.join(", "))
+            }
+            Expr::Apply(tag, args, _) => {
+                let mut applied = self.leaf(&tag.value, Parens::NotNeeded);
+
+                for arg in args.iter() {
+                    applied.push(' ');
+                    applied.push_str(&self.flat(&arg.value, Parens::InApply, depth + 1));
+                }
+
+                if parens == Parens::InApply {
+                    format!("({applied})")
+                } else {
+                    applied
+                }
+            }
+            Expr::Str(StrLiteral::PlainLine(string)) => self.string(string),
+            _ => self.leaf(expr, parens),
+        }
+    }
+
+    /// Prints one element of a multiline list or tuple, which starts on its own line.
+    fn print_item(&self, item: &Expr<'_>, depth: usize, indent: usize) -> String {
+        let indent = indent + INDENT;
+
+        self.print(item, Parens::NotNeeded, depth + 1, indent, indent)
+    }
+
+    fn print_field(
+        &self,
+        field: &AssignedField<'_, Expr<'_>>,
+        depth: usize,
+        indent: usize,
+    ) -> String {
+        match field {
+            AssignedField::RequiredValue(name, _, value) => {
+                let prefix = format!("{}: ", name.value);
+                let column = indent + INDENT + prefix.chars().count();
+                let value = self.print(
+                    &value.value,
+                    Parens::NotNeeded,
+                    depth + 1,
+                    indent + INDENT,
+                    column,
+                );
+
+                prefix + &value
+            }
+            _ => unreachable!("only records with plain fields get broken over lines"),
+        }
+    }
+
+    /// Prints a tag or opaque type applied to its payload. If the last argument is a record,
+    /// list, or tuple and everything before it fits on the line, only the last one gets broken
+    /// up, like `Ok {` followed by its fields. Otherwise every argument goes on its own line.
+    fn print_apply(
+        &self,
+        tag: &Expr<'_>,
+        args: &[&Loc<Expr<'_>>],
+        depth: usize,
+        indent: usize,
+        column: usize,
+    ) -> String {
+        let mut applied = self.leaf(tag, Parens::NotNeeded);
+
+        if let Some((last, init)) = args.split_last() {
+            let init: Vec<_> = init
+                .iter()
+                .map(|arg| self.flat(&arg.value, Parens::InApply, depth + 1))
+                .collect();
+            let init_width: usize = init.iter().map(|arg| arg.chars().count() + 1).sum();
+
+            let last_is_bracketed =
+                matches!(last.value, Expr::Record(_) | Expr::List(_) | Expr::Tuple(_));
+
+            if last_is_bracketed
+                && column + applied.chars().count() + init_width < self.options.width
+            {
+                for arg in init {
+                    applied.push(' ');
+                    applied.push_str(&arg);
+                }
+
+                let column = column + applied.chars().count() + 1;
+                let last = self.print(&last.value, Parens::InApply, depth + 1, indent, column);
+
+                applied.push(' ');
+                applied.push_str(&last);
+            } else {
+                let indent = indent + INDENT;
+
+                for arg in args {
+                    let arg = self.print(&arg.value, Parens::InApply, depth + 1, indent, indent);
+
+                    applied.push('\n');
+                    applied.push_str(&" ".repeat(indent));
+                    applied.push_str(&arg);
+                }
+            }
+        }
+
+        applied
+    }
+
+    fn multiline(&self, open: &str, items: Vec<String>, close: &str, indent: usize) -> String {
+        let mut answer = String::from(open);
+
+        for item in items {
+            answer.push('\n');
+            answer.push_str(&" ".repeat(indent + INDENT));
+            answer.push_str(&item);
+
+            // The `… 3 more` line that ends a list that was cut short isn't an element
+            if !item.starts_with('…') {
+                answer.push(',');
+            }
+        }
+
+        answer.push('\n');
+        answer.push_str(&" ".repeat(indent));
+        answer.push_str(close);
+
+        answer
+    }
+
+    fn string(&self, string: &str) -> String {
+        match string.char_indices().nth(self.options.max_length) {
+            Some((end, _)) => {
+                let shown = Expr::Str(StrLiteral::PlainLine(&string[..end]));
+                let hidden = string[end..].chars().count();
+                let quoted = self.leaf(&shown, Parens::NotNeeded);
+
+                // Put the ellipsis inside the closing quotes, which are `"""` if the string has
+                // more than one line.
+                let closing = if quoted.ends_with("\"\"\"") {
+                    "\"\"\""
+                } else {
+                    "\""
+                };
+                let unclosed = &quoted[..quoted.len() - closing.len()];
+
+                format!("{unclosed}…{closing} ({hidden} more chars)")
+            }
+            None => self.leaf(&Expr::Str(StrLiteral::PlainLine(string)), Parens::NotNeeded),
+        }
+    }
+
+    fn leaf(&self, expr: &Expr<'_>, parens: Parens) -> String {
+        let mut buf = roc_fmt::Buf::new_in(
+            self.arena,
+            roc_fmt::MigrationFlags {
+                snakify: false,
+                parens_and_commas: false,
+            },
+        );
+
+        expr.format_with_options(&mut buf, parens, Newlines::Yes, 0);
+
+        buf.into_bump_str().to_string()
+    }
+
+    fn is_nested(&self, expr: &Expr<'_>) -> bool {
+        match expr {
+            Expr::List(items) | Expr::Tuple(items) => !items.is_empty(),
+            Expr::Record(fields) => !fields.is_empty(),
+            Expr::Apply(_, args, _) => !args.is_empty(),
+            _ => false,
+        }
+    }
+
+    fn can_break(&self, expr: &Expr<'_>, depth: usize) -> bool {
+        if depth >= self.options.max_depth {
+            return false;
+        }
+
+        match expr {
+            Expr::Record(fields) => !fields.is_empty() && self.has_plain_fields(fields),
+            _ => self.is_nested(expr),
+        }
+    }
+
+    fn has_plain_fields(&self, fields: &Collection<'_, Loc<AssignedField<'_, Expr<'_>>>>) -> bool {
+        fields
+            .iter()
+            .all(|field| matches!(field.value, AssignedField::RequiredValue(..)))
+    }
+}
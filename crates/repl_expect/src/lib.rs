@@ -77,6 +77,7 @@ pub fn get_values<'a>(
             interns,
             layout_interner.fork(),
             target,
+            usize::MAX,
         );
 
         app.offset += layout_cache.interner.stack_size_and_alignment(layout).0 as usize;
@@ -95,7 +96,9 @@ mod test {
     use pretty_assertions::assert_eq;
     use roc_error_macros::internal_error;
     use roc_gen_llvm::{llvm::build::LlvmBackendMode, run_roc::RocCallResult, run_roc_dylib};
-    use roc_load::{ExecutionMode, FunctionKind, LoadConfig, LoadMonomorphizedError, Threading};
+    use roc_load::{
+        ExecutionMode, FunctionKind, LoadConfig, LoadMonomorphizedError, TestOptions, Threading,
+    };
     use roc_packaging::cache::RocCacheDir;
     use roc_reporting::report::{RenderTarget, DEFAULT_PALETTE};
     use target_lexicon::Triple;
@@ -123,7 +126,7 @@ mod test {
             render: RenderTarget::ColorTerminal,
             palette: DEFAULT_PALETTE,
             threading: Threading::Single,
-            exec_mode: ExecutionMode::Test,
+            exec_mode: ExecutionMode::Test(TestOptions::default()),
         };
         let loaded = match roc_load::load_and_monomorphize_from_str(
             arena,
@@ -18,6 +18,7 @@ use roc_gen_llvm::{
 use roc_load::{Expectations, MonomorphizedModule};
 use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_mono::{
+    coverage::CoveragePoint,
     ir::OptLevel,
     layout::{GlobalLayoutInterner, STLayoutInterner},
 };
@@ -140,6 +141,57 @@ pub fn run_toplevel_expects<'a, W: std::io::Write>(
     )
 }
 
+/// How often the code at `point` ran, for `roc test --coverage`.
+///
+/// Code that was never specialized has no counter in the dylib, and so ran 0 times.
+pub fn coverage_hits(lib: &libloading::Library, point: &CoveragePoint) -> u64 {
+    let name = point.counter_name();
+
+    match unsafe { lib.get::<*const u64>(name.as_bytes()) } {
+        Ok(counter) => unsafe { **counter },
+        Err(_) => 0,
+    }
+}
+
+/// Set up the memory that inline expects report to, for running benchmarks outside of
+/// [run_toplevel_expects]. It must outlive the calls to the benchmarks.
+pub fn bench_memory(lib: &libloading::Library) -> ExpectMemory<'static> {
+    let shm_name = format!("/roc_expect_buffer_{}", std::process::id());
+    let mut memory = ExpectMemory::create_or_reuse_mmap(&shm_name);
+
+    memory.set_shared_buffer(lib);
+
+    memory
+}
+
+/// A benchmark in the dylib, which `roc bench` calls many times in a row.
+pub struct BenchFunction<'lib> {
+    main: libloading::Symbol<'lib, unsafe extern "C" fn(*mut RocCallResult<()>)>,
+}
+
+impl<'lib> BenchFunction<'lib> {
+    pub fn new(lib: &'lib libloading::Library, bench: &ToplevelBench<'_>) -> Self {
+        let main = unsafe { lib.get(bench.name.as_bytes()) }.unwrap_or_else(|err| {
+            internal_error!("benchmark {} is missing from the dylib: {err}", bench.name)
+        });
+
+        Self { main }
+    }
+
+    /// Run the benchmark once. If it crashes, returns the crash message.
+    pub fn call(&self) -> Result<(), String> {
+        let mut result = std::mem::MaybeUninit::uninit();
+
+        let result: Result<(), (String, _)> = unsafe {
+            (self.main)(result.as_mut_ptr());
+            result.assume_init()
+        }
+        .into();
+
+        result.map_err(|(message, _crash_tag)| message)
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn run_expects_with_memory<'a, W: std::io::Write>(
     writer: &mut W,
@@ -446,9 +498,17 @@ pub struct ToplevelExpect<'a> {
     pub region: Region,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct ToplevelBench<'a> {
+    pub name: &'a str,
+    /// The `bench_` function that is being timed
+    pub symbol: Symbol,
+}
+
 #[derive(Debug)]
 pub struct ExpectFunctions<'a> {
     pub pure: BumpVec<'a, ToplevelExpect<'a>>,
+    pub benches: BumpVec<'a, ToplevelBench<'a>>,
 }
 
 pub fn expect_mono_module_to_dylib<'a>(
@@ -505,9 +565,12 @@ pub fn expect_mono_module_to_dylib<'a>(
     let expects_symbols = toplevel_expects
         .iter()
         .map(|(module_id, expects)| {
+            // benches are thunks of type `{}`, so they are exposed the same way as expects
+            let symbols = expects.pure.keys().chain(expects.benches.keys()).copied();
+
             (
                 *module_id,
-                bumpalo::collections::Vec::from_iter_in(expects.pure.keys().copied(), env.arena),
+                bumpalo::collections::Vec::from_iter_in(symbols, env.arena),
             )
         })
         .collect();
@@ -523,7 +586,22 @@ pub fn expect_mono_module_to_dylib<'a>(
     let mut modules_expects: MutMap<ModuleId, ExpectFunctions> = MutMap::default();
 
     for (module_id, expects) in toplevel_expects.into_iter() {
-        let expect_names = expect_names.get(&module_id).unwrap();
+        let (expect_names, bench_names) = expect_names
+            .get(&module_id)
+            .unwrap()
+            .split_at(expects.pure.len());
+
+        let benches = bumpalo::collections::Vec::from_iter_in(
+            expects
+                .benches
+                .values()
+                .zip(bench_names.iter())
+                .map(|(symbol, name)| ToplevelBench {
+                    symbol: *symbol,
+                    name,
+                }),
+            env.arena,
+        );
 
         let expects_pure =
             bumpalo::collections::Vec::from_iter_in(
@@ -537,7 +615,10 @@ pub fn expect_mono_module_to_dylib<'a>(
                 env.arena,
             );
 
-        let expect_funs = ExpectFunctions { pure: expects_pure };
+        let expect_funs = ExpectFunctions {
+            pure: expects_pure,
+            benches,
+        };
 
         modules_expects.insert(module_id, expect_funs);
     }
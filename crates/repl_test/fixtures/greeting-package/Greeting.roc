@@ -0,0 +1,4 @@
+module [hello]
+
+hello : Str -> Str
+hello = |name| "Hello, ${name}!"
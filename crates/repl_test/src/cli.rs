@@ -15,7 +15,7 @@ pub struct Out {
     pub status: ExitStatus,
 }
 
-fn path_to_roc_binary() -> PathBuf {
+pub(crate) fn path_to_roc_binary() -> PathBuf {
     // Adapted from https://github.com/volta-cli/volta/blob/cefdf7436a15af3ce3a38b8fe53bb0cfdb37d3dd/tests/acceptance/support/sandbox.rs#L680
     // by the Volta Contributors - license information can be found in
     // the legal_details file in the root directory of this distribution.
@@ -126,14 +126,11 @@ pub fn expect_success(input: &str, expected: &str) {
 
     assert_multiline_str_eq!("", out.stderr.as_str());
 
-    let mut iter = out.stdout.lines().rev();
-    let line = iter.next().unwrap();
+    // Values too wide for one line get broken over several, so compare as many lines as expected.
+    let lines: Vec<&str> = out.stdout.trim_end().lines().collect();
+    let answer = lines[lines.len().saturating_sub(expected.lines().count())..].join("\n");
 
-    if line.is_empty() {
-        assert_multiline_str_eq!(expected, iter.next().unwrap().trim_end());
-    } else {
-        assert_multiline_str_eq!(expected, line);
-    }
+    assert_multiline_str_eq!(expected, answer.as_str());
 
     assert!(out.status.success());
 }
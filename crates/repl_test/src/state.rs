@@ -1,10 +1,12 @@
 use bumpalo::Bump;
 use indoc::indoc;
-use roc_repl_cli::{evaluate, ReplHelper};
-use roc_repl_ui::is_incomplete;
+use roc_repl_cli::{evaluate, Backend, ReplHelper};
+use roc_repl_eval::gen::{promote_expr_to_module, ReplPackage};
 use roc_repl_ui::repl_state::{ReplAction, ReplState};
-use roc_reporting::report::DEFAULT_PALETTE;
+use roc_repl_ui::{format_defs, format_output, is_incomplete};
+use roc_reporting::report::{ANSI_STYLE_CODES, DEFAULT_PALETTE};
 use rustyline::Editor;
+use std::path::Path;
 use target_lexicon::Triple;
 
 // These are tests of the REPL state machine. They work without actually
@@ -123,6 +125,103 @@ fn partial_record_definition() {
     }
 }
 
+#[test]
+fn blank_line_inside_brackets() {
+    let mut input = "x = [".to_string();
+    incomplete(&mut input);
+
+    input.push_str("1,");
+    incomplete(&mut input);
+
+    // Inside the brackets, blank lines don't end the input.
+    incomplete(&mut input);
+    incomplete(&mut input);
+
+    input.push_str("2]");
+    complete(&input, &mut ReplState::new(), "[1, 2] : List (Num *)");
+}
+
+#[test]
+fn completion() {
+    let mut state = ReplState::new();
+    let target = Triple::host().into();
+    let arena = Bump::new();
+
+    // Completion only needs the def to be in scope, not evaluated.
+    state.step(
+        &arena,
+        "record = { name: \"Roc\", nested: { year: 2025 } }",
+        target,
+        DEFAULT_PALETTE,
+    );
+
+    assert_eq!(
+        roc_repl_ui::completion::complete(&state, "rec", 3, target),
+        (0, vec!["record".to_string()])
+    );
+    assert_eq!(
+        roc_repl_ui::completion::complete(&state, "List.map_", 9, target),
+        (
+            5,
+            vec![
+                "map_try".to_string(),
+                "map_try!".to_string(),
+                "map_with_index".to_string()
+            ]
+        )
+    );
+    assert_eq!(
+        roc_repl_ui::completion::complete(&state, "record.nested.y", 15, target),
+        (14, vec!["year".to_string()])
+    );
+
+    // Redefining the record changes its fields, even though the same thing gets completed.
+    state.step(
+        &arena,
+        "record = { name: \"Roc\", nested: { yell: Bool.true } }",
+        target,
+        DEFAULT_PALETTE,
+    );
+
+    assert_eq!(
+        roc_repl_ui::completion::complete(&state, "record.nested.y", 15, target),
+        (14, vec!["yell".to_string()])
+    );
+    assert_eq!(
+        roc_repl_ui::completion::complete(&state, ":re", 3, target),
+        (0, vec![":reload".to_string()])
+    );
+}
+
+#[test]
+fn backends_agree() {
+    let target = Triple::host().into();
+
+    for input in [
+        "1 + 2",
+        "\"hello\"",
+        "[1, 2, 3]",
+        "{ a: 1.5, b: Bool.true }",
+        // The dev backend doesn't support this yet, so it gets built with LLVM either way.
+        "Str.release_excess_capacity(\"hello\")",
+    ] {
+        let outputs = [Backend::Dev, Backend::Llvm].map(|backend| {
+            let arena = Bump::new();
+
+            match ReplState::new().step(&arena, input, target, DEFAULT_PALETTE) {
+                ReplAction::Eval {
+                    opt_mono,
+                    problems,
+                    print_options,
+                } => evaluate(opt_mono, problems, print_options, target, backend),
+                action => panic!("Unexpected action: {action:?}"),
+            }
+        });
+
+        assert_eq!(outputs[0], outputs[1], "{input}");
+    }
+}
+
 #[test]
 fn tips() {
     assert!(!is_incomplete(""));
@@ -145,6 +244,360 @@ fn standalone_annotation() {
     assert!(matches!(action, ReplAction::Nothing));
 }
 
+#[test]
+fn type_command() {
+    let mut state = ReplState::new();
+
+    complete("x = 5", &mut state, "5 : Num *");
+    command(":type x + 1", &mut state, "x + 1 : Num *");
+    command(":type |a| a", &mut state, "|a| a : a -> a");
+    command(":type", &mut state, "Usage: :type <expr>");
+}
+
+#[test]
+fn defs_command() {
+    let mut state = ReplState::new();
+
+    command(":defs", &mut state, "Nothing has been defined yet.");
+    complete("x = 5", &mut state, "5 : Num *");
+    command("Color : [Red, Green]", &mut state, "");
+    complete("y = \"hi\"", &mut state, "\"hi\" : Str");
+    command(
+        ":defs",
+        &mut state,
+        indoc!(
+            r#"
+            x : Num *
+            Color : [Red, Green]
+            y : Str"#
+        ),
+    );
+}
+
+#[test]
+fn load_and_reload() {
+    let dir = std::env::temp_dir().join("roc_repl_test_load_and_reload");
+    let path = dir.join("Lib.roc");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        &path,
+        indoc!(
+            r#"
+            module [double]
+
+            double : I64 -> I64
+            double = |n| n * 2
+
+            expect double(2) == 4
+            "#
+        ),
+    )
+    .unwrap();
+
+    let mut state = ReplState::new();
+
+    command(
+        &format!(":load {}", path.display()),
+        &mut state,
+        &format!("Loaded 1 def from {}", path.display()),
+    );
+    complete("double(21)", &mut state, "42 : I64");
+
+    std::fs::write(&path, "triple = |n| n * 3\n").unwrap();
+
+    command(
+        ":reload",
+        &mut state,
+        &format!("Reloaded {} (1 def)", path.display()),
+    );
+    command(":type triple", &mut state, "triple : Num a -> Num a");
+}
+
+#[test]
+fn save_command() {
+    let dir = std::env::temp_dir().join("roc_repl_test_save_command");
+    let path = dir.join("Saved.roc");
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let mut state = ReplState::new();
+
+    command(
+        &format!(":save {}", path.display()),
+        &mut state,
+        "There are no defs to save yet.",
+    );
+    complete("x = 5", &mut state, "5 : Num *");
+    complete("y = x + 1", &mut state, "6 : Num *");
+    command(
+        &format!(":save {}", path.display()),
+        &mut state,
+        &format!("Saved 2 defs to {}", path.display()),
+    );
+
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        indoc!(
+            r#"
+            module [x, y]
+
+            x = 5
+
+            y = x + 1
+            "#
+        )
+    );
+}
+
+#[test]
+fn save_keeps_latest_defs_and_loads_again() {
+    let dir = std::env::temp_dir().join("roc_repl_test_save_keeps_latest_defs");
+    let path = dir.join("Saved.roc");
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let mut state = ReplState::new();
+    let arena = Bump::new();
+    let target = Triple::host().into();
+
+    complete("x = 5", &mut state, "5 : Num *");
+    command("Color : [Red]", &mut state, "");
+    command("Color : [Red, Green]", &mut state, "");
+    // A standalone annotation is submitted by pressing Enter again.
+    command("y : Str\n", &mut state, "");
+    complete("y = \"hi\"", &mut state, "\"hi\" : Str");
+    // Redefining x shadows the first def. Whatever this reports, the REPL remembers the new def.
+    state.step(&arena, "x = 6", target, DEFAULT_PALETTE);
+
+    command(
+        &format!(":save {}", path.display()),
+        &mut state,
+        &format!("Saved 3 defs to {}", path.display()),
+    );
+
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        indoc!(
+            r#"
+            module [Color, y, x]
+
+            Color : [Red, Green]
+
+            y : Str
+            y = "hi"
+
+            x = 6
+            "#
+        )
+    );
+
+    let mut reloaded = ReplState::new();
+
+    command(
+        &format!(":load {}", path.display()),
+        &mut reloaded,
+        &format!("Loaded 3 defs from {}", path.display()),
+    );
+    complete("x", &mut reloaded, "6 : Num *");
+    command(
+        ":defs",
+        &mut reloaded,
+        indoc!(
+            r#"
+            Color : [Red, Green]
+            y : Str
+            x : Num *"#
+        ),
+    );
+}
+
+#[test]
+fn import_from_unknown_package() {
+    let mut state = ReplState::new();
+
+    command(
+        "import json.Decode",
+        &mut state,
+        indoc!(
+            r#"
+            There's no package called `json` in this session. Import one first, like this:
+
+                import "https://…" as json"#
+        ),
+    );
+}
+
+/// Puts the fixture package where it would be downloaded to, so the REPL (and `roc`) find it
+/// in the cache instead of downloading it. Returns its URL.
+fn cache_greeting_package() -> &'static str {
+    let url = "https://example.com/roc-repl-test/greeting-package.tar.br";
+    let fixture_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/greeting-package");
+    let package_dir = roc_packaging::cache::roc_cache_packages_dir()
+        .join("example.com/roc-repl-test/greeting-package");
+    std::fs::create_dir_all(&package_dir).unwrap();
+
+    for file in ["main.roc", "Greeting.roc"] {
+        std::fs::copy(fixture_dir.join(file), package_dir.join(file)).unwrap();
+    }
+
+    url
+}
+
+#[test]
+fn import_package_by_url() {
+    let url = cache_greeting_package();
+    let mut state = ReplState::new();
+
+    command(&format!("import \"{url}\" as greet"), &mut state, "");
+    command("import greet.Greeting", &mut state, "");
+    complete(
+        "Greeting.hello(\"Roc\")",
+        &mut state,
+        "\"Hello, Roc!\" : Str",
+    );
+}
+
+#[test]
+#[cfg(not(feature = "wasm"))]
+fn save_declares_packages_in_main_roc() {
+    let url = cache_greeting_package();
+    let dir = std::env::temp_dir().join("roc_repl_test_save_declares_packages");
+    let path = dir.join("Saved.roc");
+    let main_path = dir.join("main.roc");
+    // A main.roc from an earlier run would be kept as it is.
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let mut state = ReplState::new();
+
+    command(&format!("import \"{url}\" as greet"), &mut state, "");
+    command("import greet.Greeting", &mut state, "");
+    complete(
+        "greeting = Greeting.hello(\"Roc\")",
+        &mut state,
+        "\"Hello, Roc!\" : Str",
+    );
+    command(
+        &format!(":save {}", path.display()),
+        &mut state,
+        &format!(
+            "Saved 1 def to {}, and its packages to {}",
+            path.display(),
+            main_path.display()
+        ),
+    );
+
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        indoc!(
+            r#"
+            module [greeting]
+
+            import greet.Greeting
+
+            greeting = Greeting.hello("Roc")
+            "#
+        )
+    );
+    assert_eq!(
+        std::fs::read_to_string(&main_path).unwrap(),
+        format!("package [Saved] {{ greet: \"{url}\" }}\n")
+    );
+
+    // The compiler finds the package the same way.
+    let check = std::process::Command::new(crate::cli::path_to_roc_binary())
+        .args(["check", path.to_str().unwrap()])
+        .output()
+        .unwrap();
+
+    assert!(
+        check.status.success(),
+        "roc check failed on the saved module:\n{}{}",
+        String::from_utf8_lossy(&check.stdout),
+        String::from_utf8_lossy(&check.stderr)
+    );
+
+    let mut reloaded = ReplState::new();
+
+    command(
+        &format!(":load {}", path.display()),
+        &mut reloaded,
+        &format!("Loaded 1 def from {}", path.display()),
+    );
+    complete("greeting", &mut reloaded, "\"Hello, Roc!\" : Str");
+}
+
+#[test]
+fn repl_module_depends_on_packages() {
+    let arena = Bump::new();
+    let packages = [
+        ReplPackage {
+            shorthand: "json".to_string(),
+            url: "https://example.com/json/abc.tar.br".to_string(),
+        },
+        ReplPackage {
+            shorthand: "cli".to_string(),
+            url: "https://example.com/cli/def.tar.br".to_string(),
+        },
+    ];
+    let (_, module_src) =
+        promote_expr_to_module(&arena, &packages, ["import json.Decode"].into_iter(), "1");
+
+    assert_eq!(
+        module_src.lines().next(),
+        Some(
+            r#"app "app" packages { json: "https://example.com/json/abc.tar.br", cli: "https://example.com/cli/def.tar.br" } provides [repl_output] to "./platform""#
+        )
+    );
+}
+
+#[test]
+fn wide_values_are_broken_over_lines() {
+    complete(
+        r#"{ first_name: "Ada", last_name: "Lovelace", born: 1815, known_for: "the first program" }"#,
+        &mut ReplState::new(),
+        indoc!(
+            r#"
+            {
+                born: 1815,
+                first_name: "Ada",
+                known_for: "the first program",
+                last_name: "Lovelace",
+            } : { born : Num *, first_name : Str, known_for : Str, last_name : Str }"#
+        ),
+    );
+}
+
+#[test]
+fn print_settings() {
+    let mut state = ReplState::new();
+
+    command(":set", &mut state, "print-depth 16\nprint-length 100");
+    command(
+        ":set print-length many",
+        &mut state,
+        "Usage: :set print-length <number>",
+    );
+    command(
+        ":set colors on",
+        &mut state,
+        "There's no setting called colors. The settings are print-depth and print-length.",
+    );
+
+    command(":set print-length 3", &mut state, "");
+    complete(
+        "[1, 2, 3, 4, 5]",
+        &mut state,
+        "[1, 2, 3, … 2 more] : List (Num *)",
+    );
+    complete(r#""abcdef""#, &mut state, r#""abc…" (3 more chars) : Str"#);
+
+    command(":set print-depth 1", &mut state, "");
+    complete(
+        "{ a: { b: 1 }, c: 2 }",
+        &mut state,
+        "{ a: …, c: 2 } : { a : { b : Num * }, c : Num * }",
+    );
+}
+
 /// validate and step the given input, then check the Result vs the output
 /// with ANSI escape codes stripped.
 fn complete(input: &str, state: &mut ReplState, expected_start: &str) {
@@ -157,8 +610,12 @@ fn complete(input: &str, state: &mut ReplState, expected_start: &str) {
     editor.set_helper(Some(repl_helper));
 
     match action {
-        ReplAction::Eval { opt_mono, problems } => {
-            let string = evaluate(opt_mono, problems, target);
+        ReplAction::Eval {
+            opt_mono,
+            problems,
+            print_options,
+        } => {
+            let string = evaluate(opt_mono, problems, print_options, target, Backend::Llvm);
             let escaped =
                 std::string::String::from_utf8(strip_ansi_escapes::strip(string.trim()).unwrap())
                     .unwrap();
@@ -193,8 +650,12 @@ fn error(input: &str, state: &mut ReplState, expected_step_result: String) {
     editor.set_helper(Some(repl_helper));
 
     match action {
-        ReplAction::Eval { opt_mono, problems } => {
-            let string = evaluate(opt_mono, problems, target);
+        ReplAction::Eval {
+            opt_mono,
+            problems,
+            print_options,
+        } => {
+            let string = evaluate(opt_mono, problems, print_options, target, Backend::Llvm);
             let escaped =
                 std::string::String::from_utf8(strip_ansi_escapes::strip(string.trim()).unwrap())
                     .unwrap();
@@ -205,3 +666,25 @@ fn error(input: &str, state: &mut ReplState, expected_step_result: String) {
         }
     }
 }
+
+/// step a REPL command (like `:type`), then check its output with ANSI escape codes stripped.
+fn command(input: &str, state: &mut ReplState, expected: &str) {
+    assert!(!is_incomplete(input));
+    let arena = Bump::new();
+    let target = Triple::host().into();
+
+    let output = match state.step(&arena, input, target, DEFAULT_PALETTE) {
+        ReplAction::Type {
+            opt_output,
+            problems,
+        } => format_output(ANSI_STYLE_CODES, opt_output, problems),
+        ReplAction::Defs { defs, problems } => format_defs(ANSI_STYLE_CODES, defs, problems),
+        ReplAction::Message(message) => message,
+        ReplAction::Nothing => String::new(),
+        action => panic!("Unexpected action: {:?}", action),
+    };
+    let escaped =
+        std::string::String::from_utf8(strip_ansi_escapes::strip(output.trim()).unwrap()).unwrap();
+
+    assert_eq!(expected, escaped);
+}
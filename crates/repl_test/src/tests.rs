@@ -1032,7 +1032,12 @@ fn nullable_wrapped_tag_union() {
                 bbqPlate
                 "#
         ),
-        r#"Halved (Halved (Whole "Brisket") (Whole "Ribs")) (Halved (Whole "Coleslaw") Empty) : Container Str"#,
+        indoc!(
+            r#"
+                Halved
+                    (Halved (Whole "Brisket") (Whole "Ribs"))
+                    (Halved (Whole "Coleslaw") Empty) : Container Str"#
+        ),
     )
 }
 
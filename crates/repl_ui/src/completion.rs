@@ -0,0 +1,129 @@
+//! Tab completion for the REPL.
+//!
+//! Completes REPL commands, names in scope (defs, imports, builtin modules, and keywords),
+//! members of builtin modules after `List.` and the like, and record fields after `rec.`,
+//! which takes type checking the part before the dot.
+
+use std::sync::OnceLock;
+
+use bumpalo::Bump;
+use roc_module::symbol::ModuleId;
+use roc_parse::ast::Header;
+use roc_parse::header::parse_header;
+use roc_parse::state::State;
+use roc_target::Target;
+
+use crate::repl_state::ReplState;
+
+const COMMANDS: &[&str] = &[
+    ":defs", ":exit", ":help", ":load", ":q", ":quit", ":reload", ":save", ":set", ":type",
+];
+
+const KEYWORDS: &[&str] = &[
+    "as", "crash", "dbg", "else", "expect", "if", "import", "is", "return", "then", "when",
+];
+
+const BUILTIN_MODULES: &[(&str, ModuleId)] = &[
+    ("Bool", ModuleId::BOOL),
+    ("Box", ModuleId::BOX),
+    ("Decode", ModuleId::DECODE),
+    ("Dict", ModuleId::DICT),
+    ("Encode", ModuleId::ENCODE),
+    ("Hash", ModuleId::HASH),
+    ("Inspect", ModuleId::INSPECT),
+    ("List", ModuleId::LIST),
+    ("Num", ModuleId::NUM),
+    ("Result", ModuleId::RESULT),
+    ("Set", ModuleId::SET),
+    ("Str", ModuleId::STR),
+];
+
+/// Completes the word that ends at `pos` in `line`. Returns where that word (or the part of it
+/// after the last `.`) starts, and what could replace it, sorted.
+pub fn complete(state: &ReplState, line: &str, pos: usize, target: Target) -> (usize, Vec<String>) {
+    let before = &line[..pos];
+
+    if before.trim_start().starts_with(':') && !before.trim_start().contains(char::is_whitespace) {
+        let start = before.len() - before.trim_start().len();
+
+        return (start, matching(COMMANDS.iter().copied(), &before[start..]));
+    }
+
+    let start = before
+        .rfind(|ch: char| !(ch.is_alphanumeric() || matches!(ch, '_' | '.' | '!')))
+        .map(|index| index + 1)
+        .unwrap_or(0);
+    let word = &before[start..];
+
+    if word.is_empty() || word.starts_with(|ch: char| ch.is_ascii_digit() || ch == '.') {
+        return (pos, Vec::new());
+    }
+
+    match word.rsplit_once('.') {
+        Some((qualifier, partial)) => {
+            let start = pos - partial.len();
+
+            if qualifier.starts_with(char::is_uppercase) {
+                let members = builtin_exposes()
+                    .iter()
+                    .filter(|(module, _)| *module == qualifier)
+                    .flat_map(|(_, exposes)| exposes.iter().map(String::as_str));
+
+                (start, matching(members, partial))
+            } else {
+                let arena = Bump::new();
+                let fields = state.record_fields(&arena, qualifier, target);
+
+                (start, matching(fields.iter().map(String::as_str), partial))
+            }
+        }
+        None => {
+            let names = state
+                .names_in_scope()
+                .chain(BUILTIN_MODULES.iter().map(|(module, _)| *module))
+                .chain(KEYWORDS.iter().copied());
+
+            (start, matching(names, word))
+        }
+    }
+}
+
+fn matching<'a>(candidates: impl Iterator<Item = &'a str>, prefix: &str) -> Vec<String> {
+    let mut matches: Vec<String> = candidates
+        .filter(|candidate| candidate.starts_with(prefix))
+        .map(str::to_string)
+        .collect();
+
+    matches.sort();
+    matches.dedup();
+
+    matches
+}
+
+/// What each builtin module exposes, read from the `module [...]` header of its source.
+fn builtin_exposes() -> &'static [(&'static str, Vec<String>)] {
+    static EXPOSES: OnceLock<Vec<(&'static str, Vec<String>)>> = OnceLock::new();
+
+    EXPOSES.get_or_init(|| {
+        BUILTIN_MODULES
+            .iter()
+            .map(|&(name, module_id)| {
+                let arena = Bump::new();
+                let src = roc_builtins::roc::module_source(module_id);
+                let exposes = match parse_header(&arena, State::new(src.as_bytes())) {
+                    Ok((header, _)) => match header.item {
+                        Header::Module(module) => module
+                            .exposes
+                            .iter()
+                            .map(|exposed| exposed.value.item().as_str().to_string())
+                            .collect(),
+                        _ => Vec::new(),
+                    },
+                    Err(_) => Vec::new(),
+                };
+
+                (name, exposes)
+            })
+            .collect()
+    })
+}
@@ -1,6 +1,7 @@
 //! UI functionality, shared between CLI and web, for the Read-Evaluate-Print-Loop (REPL).
 // We don't do anything here related to the terminal (doesn't exist on the web) or LLVM (too big for the web).
 pub mod colors;
+pub mod completion;
 pub mod repl_state;
 
 use bumpalo::Bump;
@@ -38,6 +39,13 @@ pub const TIPS: &str = concatcp!(
             "  - ",
             END_COL,
             GREEN,
+            "tab",
+            END_COL,
+            " completes names, module members, and record fields\n",
+            CYAN,
+            "  - ",
+            END_COL,
+            GREEN,
             ":q",
             END_COL,
             " quits\n",
@@ -45,6 +53,60 @@ pub const TIPS: &str = concatcp!(
             "  - ",
             END_COL,
             GREEN,
+            ":type <expr>",
+            END_COL,
+            " shows an expression's type without evaluating it\n",
+            CYAN,
+            "  - ",
+            END_COL,
+            GREEN,
+            ":defs",
+            END_COL,
+            " lists everything defined so far, with types\n",
+            CYAN,
+            "  - ",
+            END_COL,
+            GREEN,
+            ":load <file>",
+            END_COL,
+            " brings a file's defs into scope, and ",
+            GREEN,
+            ":reload",
+            END_COL,
+            " reads them again\n",
+            CYAN,
+            "  - ",
+            END_COL,
+            GREEN,
+            ":save <file>",
+            END_COL,
+            " writes this session's defs to a module\n",
+            CYAN,
+            "  - ",
+            END_COL,
+            GREEN,
+            ":set print-depth <n>",
+            END_COL,
+            " and ",
+            GREEN,
+            ":set print-length <n>",
+            END_COL,
+            " limit how much of a value gets printed\n",
+            CYAN,
+            "  - ",
+            END_COL,
+            GREEN,
+            "import \"https://…\" as pkg",
+            END_COL,
+            " adds a package, whose modules can then be imported like ",
+            GREEN,
+            "import pkg.Module",
+            END_COL,
+            "\n",
+            CYAN,
+            "  - ",
+            END_COL,
+            GREEN,
             ":help",
             END_COL,
             " shows this text again\n",
@@ -59,6 +121,11 @@ pub const PROMPT: &str = concatcp!(CYAN, "»", END_COL, " ");
 pub const CONT_PROMPT: &str = concatcp!(CYAN, "…", END_COL, " ");
 
 pub fn is_incomplete(input: &str) -> bool {
+    // Inside brackets or a string, the input can't be done yet, even after a blank line.
+    if has_unclosed_delimiters(input) {
+        return true;
+    }
+
     let arena = Bump::new();
 
     match parse_src(&arena, input) {
@@ -84,10 +151,95 @@ pub fn is_incomplete(input: &str) -> bool {
                 false
             }
         }
-        ParseOutcome::Empty | ParseOutcome::Help | ParseOutcome::Exit | ParseOutcome::SyntaxErr => {
-            false
+        ParseOutcome::Empty
+        | ParseOutcome::Help
+        | ParseOutcome::Exit
+        | ParseOutcome::Command(_)
+        | ParseOutcome::SyntaxErr => false,
+    }
+}
+
+/// Whether `input` has an open bracket, brace, paren, or string that hasn't been closed yet.
+/// Mismatched closers count as closed, so the parser can report them.
+fn has_unclosed_delimiters(input: &str) -> bool {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Open {
+        Delimiter(char),
+        Interpolation,
+        Str,
+        MultilineStr,
+    }
+
+    let mut stack = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match stack.last().copied() {
+            Some(string @ (Open::Str | Open::MultilineStr)) => match ch {
+                '\\' => {
+                    chars.next();
+                }
+                '$' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    stack.push(Open::Interpolation);
+                }
+                '"' => {
+                    let closed = string == Open::Str
+                        || (chars.next_if_eq(&'"').is_some() && chars.next_if_eq(&'"').is_some());
+
+                    if closed {
+                        stack.pop();
+                    }
+                }
+                // A single-line string can't continue on the next line; let the parser say so.
+                '\n' if string == Open::Str => {
+                    stack.pop();
+                }
+                _ => {}
+            },
+            _ => match ch {
+                '#' => while chars.next_if(|&next| next != '\n').is_some() {},
+                '"' => {
+                    if chars.next_if_eq(&'"').is_some() {
+                        if chars.next_if_eq(&'"').is_some() {
+                            stack.push(Open::MultilineStr);
+                        }
+                        // Otherwise, that was an empty string.
+                    } else {
+                        stack.push(Open::Str);
+                    }
+                }
+                '\'' => {
+                    while let Some(next) = chars.next_if(|&next| next != '\n') {
+                        match next {
+                            '\\' => {
+                                chars.next();
+                            }
+                            '\'' => break,
+                            _ => {}
+                        }
+                    }
+                }
+                '(' | '[' | '{' => stack.push(Open::Delimiter(ch)),
+                ')' | ']' | '}' => {
+                    let matches = match stack.pop() {
+                        Some(Open::Delimiter(open)) => {
+                            matches!((open, ch), ('(', ')') | ('[', ']') | ('{', '}'))
+                        }
+                        Some(Open::Interpolation) => ch == '}',
+                        _ => false,
+                    };
+
+                    if !matches {
+                        return false;
+                    }
+                }
+                _ => {}
+            },
         }
     }
+
+    !stack.is_empty()
 }
 
 pub fn format_output(
@@ -95,17 +247,7 @@ pub fn format_output(
     opt_output: Option<ReplOutput>,
     problems: Problems,
 ) -> String {
-    let mut buf = String::new();
-
-    for message in problems.errors.iter().chain(problems.warnings.iter()) {
-        if !buf.is_empty() {
-            buf.push_str("\n\n");
-        }
-
-        buf.push('\n');
-        buf.push_str(message);
-        buf.push('\n');
-    }
+    let mut buf = format_problems(&problems);
 
     if let Some(ReplOutput { expr, expr_type }) = opt_output {
         // If expr was empty, it was a type annotation or ability declaration;
@@ -130,3 +272,43 @@ pub fn format_output(
 
     buf
 }
+
+/// Formats the output of `:defs`, one def per line.
+pub fn format_defs(style_codes: StyleCodes, defs: Vec<ReplOutput>, problems: Problems) -> String {
+    let mut buf = format_problems(&problems);
+
+    if defs.is_empty() {
+        buf.push_str("\nNothing has been defined yet.");
+    }
+
+    for ReplOutput { expr, expr_type } in defs {
+        buf.push('\n');
+        buf.push_str(&expr);
+
+        // Type definitions are printed as they were written.
+        if !expr_type.is_empty() {
+            buf.push_str(style_codes.green);
+            buf.push_str(" : ");
+            buf.push_str(style_codes.reset);
+            buf.push_str(&expr_type);
+        }
+    }
+
+    buf
+}
+
+fn format_problems(problems: &Problems) -> String {
+    let mut buf = String::new();
+
+    for message in problems.errors.iter().chain(problems.warnings.iter()) {
+        if !buf.is_empty() {
+            buf.push_str("\n\n");
+        }
+
+        buf.push('\n');
+        buf.push_str(message);
+        buf.push('\n');
+    }
+
+    buf
+}
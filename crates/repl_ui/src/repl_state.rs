@@ -1,28 +1,62 @@
-use std::path::PathBuf;
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
 use std::{fs, io};
 
 use bumpalo::Bump;
-use roc_collections::MutSet;
+use roc_collections::{MutMap, MutSet};
 use roc_load::MonomorphizedModule;
-use roc_parse::ast::{Defs, Expr, Pattern, StrLiteral, TypeDef, TypeHeader, ValueDef};
+use roc_parse::ast::{
+    Defs, Expr, ExtractSpaces, Header, ModuleImport, Pattern, StrLiteral, TypeDef, TypeHeader,
+    ValueDef,
+};
 use roc_parse::expr::parse_repl_defs_and_optional_expr;
+use roc_parse::header::{parse_header, parse_module_defs, AppHeader, PackageHeader};
 use roc_parse::parser::EWhen;
 use roc_parse::parser::{EClosure, EExpr, EPattern};
 use roc_parse::state::State;
 use roc_region::all::Loc;
-use roc_repl_eval::gen::{compile_to_mono, Problems};
-use roc_reporting::report::Palette;
+use roc_repl_eval::gen::{
+    compile_to_mono, promote_expr_to_module, typecheck, Problems, ReplOutput, ReplPackage,
+};
+use roc_repl_eval::pretty::PrintOptions;
+use roc_reporting::report::{Palette, DEFAULT_PALETTE};
 use roc_target::Target;
+use roc_types::pretty_print::{name_and_print_var, DebugPrint};
+use roc_types::subs::{Content, FlatType};
+use roc_types::types::AliasKind;
 
 #[derive(Debug, Clone, PartialEq)]
 enum PastDef {
-    Def { ident: String, src: String },
+    Def {
+        ident: String,
+        src: String,
+    },
+    /// A type annotation entered on its own, like `x : Str`, before the def it annotates.
+    Annotation {
+        ident: String,
+        src: String,
+    },
     Import(String),
 }
 
+/// A file brought into scope with `:load`. Its defs are kept apart from the ones
+/// entered in the REPL, so that `:reload` can replace them.
+struct LoadedFile {
+    path: PathBuf,
+    defs: Vec<PastDef>,
+}
+
 pub struct ReplState {
     past_defs: Vec<PastDef>,
     past_def_idents: MutSet<String>,
+    loaded_files: Vec<LoadedFile>,
+    packages: Vec<ReplPackage>,
+    /// Where each package in `packages` was installed, by shorthand.
+    package_dirs: MutMap<String, PathBuf>,
+    print_options: PrintOptions,
+    /// The record fields found for the last module that completion type checked, keyed by
+    /// that module's source, so pressing Tab again doesn't type check the same thing twice.
+    last_record_fields: RefCell<Option<(String, Vec<String>)>>,
 }
 
 impl Default for ReplState {
@@ -37,6 +71,7 @@ pub enum ReplAction<'a> {
     Eval {
         opt_mono: Option<MonomorphizedModule<'a>>,
         problems: Problems,
+        print_options: PrintOptions,
     },
     Exit,
     Help,
@@ -44,6 +79,18 @@ pub enum ReplAction<'a> {
         filename: PathBuf,
         error: io::ErrorKind,
     },
+    /// The result of `:type`, which type checks the expression without evaluating it.
+    Type {
+        opt_output: Option<ReplOutput>,
+        problems: Problems,
+    },
+    /// The result of `:defs`. Type definitions have an empty `expr_type`.
+    Defs {
+        defs: Vec<ReplOutput>,
+        problems: Problems,
+    },
+    /// Something to tell the user, e.g. that a file was loaded.
+    Message(String),
     Nothing,
 }
 
@@ -52,6 +99,11 @@ impl ReplState {
         Self {
             past_defs: Default::default(),
             past_def_idents: Default::default(),
+            loaded_files: Default::default(),
+            packages: Default::default(),
+            package_dirs: Default::default(),
+            print_options: Default::default(),
+            last_record_fields: Default::default(),
         }
     }
 
@@ -66,6 +118,9 @@ impl ReplState {
         let src: &str = match parse_src(arena, line) {
             ParseOutcome::Empty | ParseOutcome::Help => return ReplAction::Help,
             ParseOutcome::Exit => return ReplAction::Exit,
+            ParseOutcome::Command(command) => {
+                return self.run_command(arena, command, target, palette)
+            }
             ParseOutcome::Incomplete | ParseOutcome::SyntaxErr => {
                 pending_past_def = None;
 
@@ -136,10 +191,11 @@ impl ReplState {
                                     _,
                                 ) => {
                                     // Record the standalone type annotation for future use.
-                                    self.add_past_def(
-                                        ident.trim_end().to_string(),
-                                        line[vd.byte_range()].to_string(),
-                                    );
+                                    self.past_def_idents.insert(ident.trim_end().to_string());
+                                    self.past_defs.push(PastDef::Annotation {
+                                        ident: ident.trim_end().to_string(),
+                                        src: line[vd.byte_range()].to_string(),
+                                    });
 
                                     // Return early without running eval, since standalone annotations
                                     // cannot be evaluated as expressions.
@@ -196,37 +252,46 @@ impl ReplState {
                                 ValueDef::Expect { .. } => {
                                     todo!("handle receiving an `expect` - what should the repl do for that?")
                                 }
-                                ValueDef::ModuleImport(import) => match import.name.value.package {
-                                    Some(_) => {
-                                        todo!("handle importing a module from a package")
-                                    }
-                                    None => {
-                                        let mut filename = PathBuf::new();
+                                ValueDef::ModuleImport(import) => {
+                                    let dir = match import.name.value.package {
+                                        Some(shorthand) => match self.package_dirs.get(shorthand) {
+                                            Some(dir) => dir.as_path(),
+                                            None => {
+                                                return ReplAction::Message(format!(
+                                                    "There's no package called `{shorthand}` in this session. Import one first, like this:\n\n    import \"https://…\" as {shorthand}"
+                                                ));
+                                            }
+                                        },
+                                        None => Path::new(""),
+                                    };
+                                    let filename = module_path(dir, &import);
 
-                                        for part in import.name.value.name.parts() {
-                                            filename.push(part);
-                                        }
+                                    // Check we can read the file before we add it to past defs.
+                                    // If we didn't do this, the bad import would remain in past_defs
+                                    // and we'd report it on every subsequent evaluation.
+                                    if let Err(err) = fs::metadata(&filename) {
+                                        return ReplAction::FileProblem {
+                                            filename,
+                                            error: err.kind(),
+                                        };
+                                    }
 
-                                        filename.set_extension("roc");
+                                    self.past_defs
+                                        .push(PastDef::Import(line[vd.byte_range()].to_string()));
 
-                                        // Check we can read the file before we add it to past defs.
-                                        // If we didn't do this, the bad import would remain in past_defs
-                                        // and we'd report it on every subsequent evaluation.
-                                        if let Err(err) = fs::metadata(&filename) {
-                                            return ReplAction::FileProblem {
-                                                filename,
-                                                error: err.kind(),
-                                            };
+                                    return ReplAction::Nothing;
+                                }
+                                ValueDef::IngestedFileImport(file) => {
+                                    // A URL can't be ingested, so `import "https://…" as json`
+                                    // imports the package at that URL instead.
+                                    if let (StrLiteral::PlainLine(url), None) =
+                                        (file.path.value, file.annotation)
+                                    {
+                                        if url.contains("://") {
+                                            return self.add_package(file.name.item.value, url);
                                         }
-
-                                        self.past_defs.push(PastDef::Import(
-                                            line[vd.byte_range()].to_string(),
-                                        ));
-
-                                        return ReplAction::Nothing;
                                     }
-                                },
-                                ValueDef::IngestedFileImport(file) => {
+
                                     if let StrLiteral::PlainLine(path) = file.path.value {
                                         let filename = PathBuf::from(path);
                                         if let Err(err) = fs::metadata(&filename) {
@@ -257,22 +322,18 @@ impl ReplState {
             }
         };
 
-        let (opt_mono, problems) = compile_to_mono(
-            arena,
-            self.past_defs.iter().map(|past_def| match past_def {
-                PastDef::Def { ident: _, src } => src.as_str(),
-                PastDef::Import(src) => src.as_str(),
-            }),
-            src,
-            target,
-            palette,
-        );
+        let (opt_mono, problems) =
+            compile_to_mono(arena, &self.packages, self.all_defs(), src, target, palette);
 
         if let Some((ident, src)) = pending_past_def {
             self.add_past_def(ident, src);
         }
 
-        ReplAction::Eval { opt_mono, problems }
+        ReplAction::Eval {
+            opt_mono,
+            problems,
+            print_options: self.print_options,
+        }
     }
 
     fn add_past_def(&mut self, ident: String, src: String) {
@@ -282,6 +343,701 @@ impl ReplState {
 
         self.past_defs.push(PastDef::Def { ident, src });
     }
+
+    /// Installs the package at `url` (unless it's already in the cache), so the REPL's module
+    /// can depend on it, and its modules can be imported with `import shorthand.Module`.
+    #[cfg(not(target_family = "wasm"))]
+    fn add_package<'a>(&mut self, shorthand: &str, url: &str) -> ReplAction<'a> {
+        use roc_packaging::cache::{self, RocCacheDir};
+        use roc_reporting::report::to_https_problem_report_string;
+
+        let cache_dir = cache::roc_cache_packages_dir();
+
+        match cache::install_package(RocCacheDir::Persistent(&cache_dir), url) {
+            Ok((package_dir, opt_root_module)) => {
+                // A package's modules are next to its root module, which the URL can put
+                // in a subdirectory, e.g. with #src/main.roc
+                let modules_dir = match opt_root_module.and_then(|root| Path::new(root).parent()) {
+                    Some(subdir) => package_dir.join(subdir),
+                    None => package_dir,
+                };
+                let package = ReplPackage {
+                    shorthand: shorthand.to_string(),
+                    url: url.to_string(),
+                };
+
+                // Importing a shorthand again replaces the package it referred to.
+                self.packages
+                    .retain(|existing| existing.shorthand != shorthand);
+                self.packages.push(package);
+                self.package_dirs.insert(shorthand.to_string(), modules_dir);
+
+                ReplAction::Nothing
+            }
+            Err(problem) => ReplAction::Message(to_https_problem_report_string(
+                url,
+                problem,
+                PathBuf::from("replfile.roc"),
+            )),
+        }
+    }
+
+    #[cfg(target_family = "wasm")]
+    fn add_package<'a>(&mut self, _shorthand: &str, _url: &str) -> ReplAction<'a> {
+        ReplAction::Message(
+            "The web version of the REPL cannot import packages... for now!".to_string(),
+        )
+    }
+
+    /// Every name that's in scope: defs, plus what imports bring in.
+    pub(crate) fn names_in_scope(&self) -> impl Iterator<Item = &str> {
+        self.loaded_files
+            .iter()
+            .flat_map(|file| file.defs.iter())
+            .chain(self.past_defs.iter())
+            .flat_map(|past_def| match past_def {
+                PastDef::Def { ident, src: _ } | PastDef::Annotation { ident, src: _ } => {
+                    vec![ident.as_str()]
+                }
+                PastDef::Import(src) => imported_names(src),
+            })
+    }
+
+    /// The fields of the record that `expr` evaluates to, or nothing if it isn't a record.
+    pub(crate) fn record_fields(&self, arena: &Bump, expr: &str, target: Target) -> Vec<String> {
+        let (_, module_src) = promote_expr_to_module(arena, &self.packages, self.all_defs(), expr);
+
+        if let Some((last_src, fields)) = self.last_record_fields.borrow().as_ref() {
+            if last_src == module_src {
+                return fields.clone();
+            }
+        }
+
+        let fields = self.typecheck_record_fields(arena, expr, target);
+
+        *self.last_record_fields.borrow_mut() = Some((module_src.to_string(), fields.clone()));
+
+        fields
+    }
+
+    fn typecheck_record_fields(&self, arena: &Bump, expr: &str, target: Target) -> Vec<String> {
+        let (opt_loaded, _) = typecheck(
+            arena,
+            &self.packages,
+            self.all_defs(),
+            expr,
+            target,
+            DEFAULT_PALETTE,
+        );
+        let Some(loaded) = opt_loaded else {
+            return Vec::new();
+        };
+        let Some(&(mut var)) = loaded.exposed_to_host.values().next() else {
+            return Vec::new();
+        };
+        let subs = loaded.solved.inner();
+
+        loop {
+            match subs.get_content_without_compacting(var) {
+                Content::Alias(_, _, real_var, AliasKind::Structural) => var = *real_var,
+                Content::Structure(FlatType::Record(fields, ext)) => {
+                    return match fields.unsorted_iterator(subs, *ext) {
+                        Ok(fields) => fields.map(|(name, _)| name.as_str().to_string()).collect(),
+                        Err(_) => Vec::new(),
+                    };
+                }
+                _ => return Vec::new(),
+            }
+        }
+    }
+
+    /// The source of every def in scope: first the ones from loaded files, then the ones entered here.
+    fn all_defs(&self) -> impl Iterator<Item = &str> {
+        self.loaded_files
+            .iter()
+            .flat_map(|file| file.defs.iter())
+            .chain(self.past_defs.iter())
+            .map(|past_def| match past_def {
+                PastDef::Def { ident: _, src } | PastDef::Annotation { ident: _, src } => {
+                    src.as_str()
+                }
+                PastDef::Import(src) => src.as_str(),
+            })
+    }
+
+    fn run_command<'a>(
+        &mut self,
+        arena: &'a Bump,
+        command: ReplCommand<'_>,
+        target: Target,
+        palette: Palette,
+    ) -> ReplAction<'a> {
+        match command {
+            ReplCommand::Type("") => ReplAction::Message("Usage: :type <expr>".to_string()),
+            ReplCommand::Load("") => ReplAction::Message("Usage: :load <file>".to_string()),
+            ReplCommand::Save("") => ReplAction::Message("Usage: :save <file>".to_string()),
+            ReplCommand::Type(expr) => self.type_of(arena, expr, target, palette),
+            ReplCommand::Load(path) => self.load(arena, PathBuf::from(path)),
+            ReplCommand::Reload => self.reload(arena),
+            ReplCommand::Save(path) => self.save(arena, PathBuf::from(path)),
+            ReplCommand::Defs => self.defs(arena, target, palette),
+            ReplCommand::Set(setting) => self.set(setting),
+        }
+    }
+
+    /// `:set print-depth 4` or `:set print-length 20`. With no setting, shows the current ones.
+    fn set(&mut self, setting: &str) -> ReplAction<'static> {
+        let options = &mut self.print_options;
+        let (name, value) = setting
+            .split_once(char::is_whitespace)
+            .unwrap_or((setting, ""));
+
+        let field = match name {
+            "" => {
+                return ReplAction::Message(format!(
+                    "print-depth {}\nprint-length {}",
+                    options.max_depth, options.max_length
+                ))
+            }
+            "print-depth" => &mut options.max_depth,
+            "print-length" => &mut options.max_length,
+            _ => {
+                return ReplAction::Message(format!(
+                "There's no setting called {name}. The settings are print-depth and print-length."
+            ))
+            }
+        };
+
+        match value.trim().parse() {
+            Ok(value) => {
+                *field = value;
+
+                ReplAction::Nothing
+            }
+            Err(_) => ReplAction::Message(format!("Usage: :set {name} <number>")),
+        }
+    }
+
+    fn type_of<'a>(
+        &self,
+        arena: &'a Bump,
+        expr: &str,
+        target: Target,
+        palette: Palette,
+    ) -> ReplAction<'a> {
+        let (opt_loaded, problems) = typecheck(
+            arena,
+            &self.packages,
+            self.all_defs(),
+            expr,
+            target,
+            palette,
+        );
+
+        let opt_output = opt_loaded.and_then(|mut loaded| {
+            // The expr is the only thing the REPL's module provides.
+            let var = *loaded.exposed_to_host.values().next()?;
+            let expr_type = name_and_print_var(
+                var,
+                loaded.solved.inner_mut(),
+                loaded.module_id,
+                &loaded.interns,
+                DebugPrint::NOTHING,
+            );
+
+            Some(ReplOutput {
+                expr: expr.to_string(),
+                expr_type,
+            })
+        });
+
+        ReplAction::Type {
+            opt_output,
+            problems,
+        }
+    }
+
+    fn defs<'a>(&self, arena: &'a Bump, target: Target, palette: Palette) -> ReplAction<'a> {
+        let (opt_loaded, problems) = typecheck(
+            arena,
+            &self.packages,
+            self.all_defs(),
+            "{}",
+            target,
+            palette,
+        );
+        let mut defs = Vec::new();
+
+        if let Some(mut loaded) = opt_loaded {
+            let home = loaded.module_id;
+            let declarations = loaded.declarations_by_id.remove(&home).unwrap_or_default();
+            let mut seen = MutSet::default();
+
+            let past_defs = self
+                .loaded_files
+                .iter()
+                .flat_map(|file| file.defs.iter())
+                .chain(self.past_defs.iter());
+
+            for past_def in past_defs {
+                let PastDef::Def { ident, src } = past_def else {
+                    continue;
+                };
+
+                if !seen.insert(ident.as_str()) {
+                    continue;
+                }
+
+                if ident.starts_with(char::is_uppercase) {
+                    // Type definitions already say everything there is to know about them.
+                    defs.push(ReplOutput {
+                        expr: src.trim().to_string(),
+                        expr_type: String::new(),
+                    });
+
+                    continue;
+                }
+
+                let opt_var = declarations
+                    .symbols
+                    .iter()
+                    .position(|symbol| symbol.value.as_str(&loaded.interns) == ident)
+                    .map(|index| declarations.variables[index]);
+
+                if let Some(var) = opt_var {
+                    defs.push(ReplOutput {
+                        expr: ident.clone(),
+                        expr_type: name_and_print_var(
+                            var,
+                            loaded.solved.inner_mut(),
+                            home,
+                            &loaded.interns,
+                            DebugPrint::NOTHING,
+                        ),
+                    });
+                }
+            }
+        }
+
+        ReplAction::Defs { defs, problems }
+    }
+
+    fn load<'a>(&mut self, arena: &Bump, path: PathBuf) -> ReplAction<'a> {
+        let FileDefs { defs, packages } = match read_file_defs(arena, &path) {
+            Ok(file_defs) => file_defs,
+            Err(problem) => return problem.into_action(),
+        };
+
+        for ReplPackage { shorthand, url } in packages {
+            if let problem @ ReplAction::Message(_) = self.add_package(&shorthand, &url) {
+                return problem;
+            }
+        }
+
+        let message = format!("Loaded {} from {}", count_defs(defs.len()), path.display());
+
+        match self.loaded_files.iter_mut().find(|file| file.path == path) {
+            Some(file) => file.defs = defs,
+            None => self.loaded_files.push(LoadedFile { path, defs }),
+        }
+
+        ReplAction::Message(message)
+    }
+
+    fn reload<'a>(&mut self, arena: &Bump) -> ReplAction<'a> {
+        if self.loaded_files.is_empty() {
+            return ReplAction::Message(
+                "There are no loaded files to reload. Use :load <file> to load one.".to_string(),
+            );
+        }
+
+        let mut reloaded = Vec::with_capacity(self.loaded_files.len());
+        let mut packages = Vec::new();
+
+        for file in self.loaded_files.iter_mut() {
+            match read_file_defs(arena, &file.path) {
+                Ok(file_defs) => {
+                    file.defs = file_defs.defs;
+                    packages.extend(file_defs.packages);
+                }
+                Err(problem) => return problem.into_action(),
+            }
+
+            reloaded.push(format!(
+                "{} ({})",
+                file.path.display(),
+                count_defs(file.defs.len())
+            ));
+        }
+
+        for ReplPackage { shorthand, url } in packages {
+            if let problem @ ReplAction::Message(_) = self.add_package(&shorthand, &url) {
+                return problem;
+            }
+        }
+
+        ReplAction::Message(format!("Reloaded {}", reloaded.join(", ")))
+    }
+
+    /// Writes the defs entered in this session (but not the ones from loaded files) to a module
+    /// that exposes all of them, so it can be loaded later.
+    ///
+    /// A module can only define each name once, so only the latest def of each name is saved.
+    /// A module gets its packages from the `main.roc` next to it, so the packages imported by
+    /// URL go in a `main.roc` package written next to the saved module, unless there's one there
+    /// already. That's where both `roc check` and `:load` find them.
+    fn save<'a>(&self, arena: &Bump, path: PathBuf) -> ReplAction<'a> {
+        // The latest def of each name, along with the standalone annotation entered before it.
+        let mut latest: Vec<(&str, Option<&str>, &str)> = Vec::new();
+        let mut pending_annotations: MutMap<&str, &str> = MutMap::default();
+        let mut imports = String::new();
+
+        for past_def in self.past_defs.iter() {
+            match past_def {
+                PastDef::Def { ident, src } => {
+                    let annotation = pending_annotations.remove(ident.as_str());
+
+                    latest.retain(|(existing, _, _)| *existing != ident.as_str());
+                    latest.push((ident.as_str(), annotation, src.as_str()));
+                }
+                PastDef::Annotation { ident, src } => {
+                    pending_annotations.insert(ident.as_str(), src.as_str());
+                }
+                PastDef::Import(src) => {
+                    imports.push_str(src.trim_end());
+                    imports.push('\n');
+                }
+            }
+        }
+
+        if latest.is_empty() {
+            return ReplAction::Message("There are no defs to save yet.".to_string());
+        }
+
+        let exposed: Vec<&str> = latest.iter().map(|(ident, _, _)| *ident).collect();
+        let mut module = format!("module [{}]\n\n", exposed.join(", "));
+
+        if !imports.is_empty() {
+            module.push_str(&imports);
+            module.push('\n');
+        }
+
+        for (index, (_, annotation, src)) in latest.iter().enumerate() {
+            if index > 0 {
+                module.push_str("\n\n");
+            }
+
+            if let Some(annotation) = annotation {
+                module.push_str(annotation.trim_end());
+                module.push('\n');
+            }
+
+            module.push_str(src.trim_end());
+        }
+
+        module.push('\n');
+
+        if let Err(err) = fs::write(&path, module) {
+            return ReplAction::FileProblem {
+                filename: path,
+                error: err.kind(),
+            };
+        }
+
+        let mut message = format!("Saved {} to {}", count_defs(latest.len()), path.display());
+
+        if self.packages.is_empty() {
+            return ReplAction::Message(message);
+        }
+
+        let main_path = path.with_file_name(MAIN_FILENAME);
+
+        if main_path.exists() {
+            let declared = read_main_packages(arena, &main_path).unwrap_or_default();
+            let missing: Vec<String> = self
+                .packages
+                .iter()
+                .filter(|package| !declared.contains(package))
+                .map(|ReplPackage { shorthand, url }| format!("{shorthand}: \"{url}\""))
+                .collect();
+
+            if !missing.is_empty() {
+                message.push_str(&format!(
+                    "\n\nIt imports packages that {} doesn't declare. Add them to its packages:\n\n    {}",
+                    main_path.display(),
+                    missing.join(",\n    ")
+                ));
+            }
+        } else {
+            let module_name = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .filter(|stem| stem.starts_with(char::is_uppercase));
+            let packages: Vec<String> = self
+                .packages
+                .iter()
+                .map(|ReplPackage { shorthand, url }| format!("{shorthand}: \"{url}\""))
+                .collect();
+            let package = format!(
+                "package [{}] {{ {} }}\n",
+                module_name.unwrap_or_default(),
+                packages.join(", ")
+            );
+
+            if let Err(err) = fs::write(&main_path, package) {
+                return ReplAction::FileProblem {
+                    filename: main_path,
+                    error: err.kind(),
+                };
+            }
+
+            message.push_str(&format!(", and its packages to {}", main_path.display()));
+        }
+
+        ReplAction::Message(message)
+    }
+}
+
+/// The file a module's packages are declared in, like the compiler's default app or package.
+const MAIN_FILENAME: &str = "main.roc";
+
+/// Where the module an import refers to lives, e.g. `dir/Foo/Bar.roc` for `import Foo.Bar`.
+fn module_path(dir: &Path, import: &ModuleImport<'_>) -> PathBuf {
+    let mut filename = dir.to_path_buf();
+
+    for part in import.name.value.name.parts() {
+        filename.push(part);
+    }
+
+    filename.set_extension("roc");
+
+    filename
+}
+
+/// The names an import brings into scope, e.g. `Bar` and `baz` for
+/// `import pkg.Bar exposing [baz]`, or `data` for `import "data.json" as data : Str`.
+fn imported_names(src: &str) -> Vec<&str> {
+    let mut words = src.split_whitespace().skip(1);
+    let mut names = Vec::new();
+
+    if let Some(module) = words.next() {
+        match (words.next(), words.next()) {
+            (Some("as"), Some(alias)) => names.push(alias.trim_end_matches(':')),
+            _ if module.starts_with('"') => {}
+            _ => match module.split_once('.') {
+                Some((package, name)) if package.starts_with(char::is_lowercase) => {
+                    names.push(name)
+                }
+                _ => names.push(module),
+            },
+        }
+    }
+
+    if let Some((_, exposing)) = src.split_once("exposing") {
+        let exposed = exposing
+            .trim_start()
+            .trim_start_matches('[')
+            .split(']')
+            .next()
+            .unwrap_or_default();
+
+        names.extend(
+            exposed
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty()),
+        );
+    }
+
+    names
+}
+
+fn count_defs(count: usize) -> String {
+    match count {
+        1 => "1 def".to_string(),
+        count => format!("{count} defs"),
+    }
+}
+
+enum LoadProblem {
+    File(PathBuf, io::ErrorKind),
+    Syntax(PathBuf),
+}
+
+impl LoadProblem {
+    fn into_action<'a>(self) -> ReplAction<'a> {
+        match self {
+            LoadProblem::File(filename, error) => ReplAction::FileProblem { filename, error },
+            LoadProblem::Syntax(path) => ReplAction::Message(format!(
+                "I couldn't parse {}. Run `roc check {}` to see what's wrong with it.",
+                path.display(),
+                path.display()
+            )),
+        }
+    }
+}
+
+/// The defs and imports `:load` reads from a file, and the packages its imports are from.
+struct FileDefs {
+    defs: Vec<PastDef>,
+    packages: Vec<ReplPackage>,
+}
+
+/// Reads the top-level defs and imports of a module, or of a file that's just defs.
+///
+/// Other top-level statements, like `expect`s, are skipped. Imports are resolved relative
+/// to the directory the REPL is running in, like imports entered in the REPL. The packages
+/// they import from come from the closest `main.roc`, like they do for the compiler.
+fn read_file_defs(arena: &Bump, path: &Path) -> Result<FileDefs, LoadProblem> {
+    let src = read_to_arena(arena, path)?;
+
+    let state = match parse_header(arena, State::new(src.as_bytes())) {
+        Ok((_, state)) => state,
+        Err(_) => State::new(src.as_bytes()),
+    };
+
+    let defs = parse_module_defs(arena, state, Defs::default())
+        .map_err(|_| LoadProblem::Syntax(path.to_path_buf()))?;
+
+    let mut past_defs = Vec::with_capacity(defs.len());
+    let mut imported_shorthands = MutSet::default();
+
+    for def in defs.loc_defs() {
+        match def {
+            Ok(td) => {
+                let (TypeDef::Alias { header, .. }
+                | TypeDef::Opaque { header, .. }
+                | TypeDef::Ability { header, .. }) = td.value;
+
+                past_defs.push(PastDef::Def {
+                    ident: header.name.value.trim_end().to_string(),
+                    src: src[td.byte_range()].to_string(),
+                });
+            }
+            Err(vd) => match &vd.value {
+                ValueDef::Annotation(
+                    Loc {
+                        value: Pattern::Identifier { ident },
+                        ..
+                    },
+                    _,
+                ) => past_defs.push(PastDef::Annotation {
+                    ident: ident.to_string(),
+                    src: src[vd.byte_range()].to_string(),
+                }),
+                ValueDef::Body(
+                    Loc {
+                        value: Pattern::Identifier { ident },
+                        ..
+                    },
+                    _,
+                )
+                | ValueDef::AnnotatedBody {
+                    body_pattern:
+                        Loc {
+                            value: Pattern::Identifier { ident },
+                            ..
+                        },
+                    ..
+                } => past_defs.push(PastDef::Def {
+                    ident: ident.to_string(),
+                    src: src[vd.byte_range()].to_string(),
+                }),
+                ValueDef::ModuleImport(import) => {
+                    imported_shorthands.extend(import.name.value.package);
+                    past_defs.push(PastDef::Import(src[vd.byte_range()].to_string()))
+                }
+                ValueDef::IngestedFileImport(_) => {
+                    past_defs.push(PastDef::Import(src[vd.byte_range()].to_string()))
+                }
+                _ => {}
+            },
+        }
+    }
+
+    let mut packages = match find_main(path) {
+        Some(main_path) if !imported_shorthands.is_empty() => {
+            read_main_packages(arena, &main_path)?
+        }
+        _ => Vec::new(),
+    };
+
+    packages.retain(|package| imported_shorthands.contains(package.shorthand.as_str()));
+
+    Ok(FileDefs {
+        defs: past_defs,
+        packages,
+    })
+}
+
+fn read_to_arena<'a>(arena: &'a Bump, path: &Path) -> Result<&'a str, LoadProblem> {
+    match fs::read_to_string(path) {
+        Ok(src) => Ok(arena.alloc_str(&src)),
+        Err(err) => Err(LoadProblem::File(path.to_path_buf(), err.kind())),
+    }
+}
+
+/// The `main.roc` the compiler takes a module's packages from: the closest one in the
+/// module's directory or a directory above it.
+fn find_main(path: &Path) -> Option<PathBuf> {
+    let path = path.canonicalize().ok()?;
+
+    path.ancestors()
+        .skip(1)
+        .map(|dir| dir.join(MAIN_FILENAME))
+        .find(|main_path| main_path.is_file())
+}
+
+/// The packages an app or package header declares by URL. The REPL can't install a platform,
+/// or a package that's a path on disk.
+fn read_main_packages(arena: &Bump, main_path: &Path) -> Result<Vec<ReplPackage>, LoadProblem> {
+    let src = read_to_arena(arena, main_path)?;
+    let (header, _) = parse_header(arena, State::new(src.as_bytes()))
+        .map_err(|_| LoadProblem::Syntax(main_path.to_path_buf()))?;
+
+    let entries = match header.item {
+        Header::App(AppHeader { packages, .. })
+        | Header::Package(PackageHeader { packages, .. }) => packages.value.items,
+        _ => &[],
+    };
+
+    Ok(entries
+        .iter()
+        .map(|entry| entry.value.extract_spaces().item)
+        .filter(|entry| entry.platform_marker.is_none())
+        .map(|entry| ReplPackage {
+            shorthand: entry.shorthand.to_string(),
+            url: entry.package_name.value.as_str().to_string(),
+        })
+        .filter(|package| package.url.contains("://"))
+        .collect())
+}
+
+/// A REPL command, like `:type` or `:load`. Everything after the command's name is its argument.
+#[derive(Debug, PartialEq)]
+pub enum ReplCommand<'a> {
+    Type(&'a str),
+    Load(&'a str),
+    Reload,
+    Save(&'a str),
+    Defs,
+    Set(&'a str),
+}
+
+fn parse_command(line: &str) -> Option<ReplCommand<'_>> {
+    let line = line.trim();
+    let (name, arg) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let arg = arg.trim();
+
+    match name.to_lowercase().as_str() {
+        ":type" => Some(ReplCommand::Type(arg)),
+        ":load" => Some(ReplCommand::Load(arg)),
+        ":reload" => Some(ReplCommand::Reload),
+        ":save" => Some(ReplCommand::Save(arg)),
+        ":defs" => Some(ReplCommand::Defs),
+        ":set" => Some(ReplCommand::Set(arg)),
+        _ => None,
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -292,6 +1048,7 @@ pub enum ParseOutcome<'a> {
     Empty,
     Help,
     Exit,
+    Command(ReplCommand<'a>),
 }
 
 /// Special case some syntax errors to allow for multi-line inputs
@@ -307,6 +1064,10 @@ fn parse_outcome_for_error(e: EExpr<'_>) -> ParseOutcome<'_> {
 }
 
 pub fn parse_src<'a>(arena: &'a Bump, line: &'a str) -> ParseOutcome<'a> {
+    if let Some(command) = parse_command(line) {
+        return ParseOutcome::Command(command);
+    }
+
     match line.trim().to_lowercase().as_str() {
         "" => ParseOutcome::Empty,
         ":help" => ParseOutcome::Help,
@@ -9,10 +9,11 @@ use roc_parse::ast::Expr;
 use roc_repl_eval::{
     eval::jit_to_ast,
     gen::{format_answer, ReplOutput},
+    pretty::PrintOptions,
     ReplApp, ReplAppMemory,
 };
 use roc_repl_ui::{
-    format_output,
+    format_defs, format_output,
     repl_state::{ReplAction, ReplState},
     TIPS,
 };
@@ -207,9 +208,19 @@ pub async fn entrypoint_from_js(src: String) -> String {
             "The web version of the REPL cannot import files... for now!".to_string()
         }
         ReplAction::Nothing => String::new(),
-        ReplAction::Eval { opt_mono, problems } => {
+        ReplAction::Message(message) => message,
+        ReplAction::Type {
+            opt_output,
+            problems,
+        } => format_output(HTML_STYLE_CODES, opt_output, problems),
+        ReplAction::Defs { defs, problems } => format_defs(HTML_STYLE_CODES, defs, problems),
+        ReplAction::Eval {
+            opt_mono,
+            problems,
+            print_options,
+        } => {
             let opt_output = match opt_mono {
-                Some(mono) => eval_wasm(arena, target, mono).await,
+                Some(mono) => eval_wasm(arena, target, mono, print_options).await,
                 None => None,
             };
 
@@ -222,6 +233,7 @@ async fn eval_wasm<'a>(
     arena: &'a Bump,
     target: Target,
     mono: MonomorphizedModule<'a>,
+    print_options: PrintOptions,
 ) -> Option<ReplOutput> {
     let MonomorphizedModule {
         module_id,
@@ -315,10 +327,11 @@ async fn eval_wasm<'a>(
         &interns,
         layout_interner.into_global().fork(),
         target,
+        print_options.max_length,
     );
 
     // Transform the Expr to a string
-    let expr = format_answer(arena, res_answer).to_string();
+    let expr = format_answer(arena, res_answer, print_options);
 
     Some(ReplOutput { expr, expr_type })
 }
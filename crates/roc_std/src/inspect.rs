@@ -0,0 +1,134 @@
+use core::fmt;
+use std::convert::Infallible;
+
+use crate::{RocBox, RocDec, RocList, RocRefcounted, RocResult, RocStr, I128, U128};
+
+/// Formats a value the way Roc's `Inspect.to_str` (and so `dbg`) does, e.g. `{name: "Sam"}`
+/// or `(Ok [1, 2])`, so a host can log values exactly as the Roc code that made them would.
+///
+/// `roc glue` implements this, along with `Display`, for the types it generates.
+pub trait RocInspect {
+    fn inspect(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+macro_rules! roc_inspect_display_impl {
+    ( $( $T:ty ),+ ) => {
+        $(
+            impl RocInspect for $T {
+                fn inspect(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::Display::fmt(self, f)
+                }
+            }
+        )+
+    };
+}
+
+roc_inspect_display_impl!(u8, u16, u32, u64, u128, U128);
+roc_inspect_display_impl!(i8, i16, i32, i64, i128, I128);
+roc_inspect_display_impl!(RocDec);
+
+macro_rules! roc_inspect_float_impl {
+    ( $( $T:ty ),+ ) => {
+        $(
+            impl RocInspect for $T {
+                fn inspect(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    // Roc spells NaN the way Zig does; everything else matches Rust's `Display`.
+                    if self.is_nan() {
+                        f.write_str("nan")
+                    } else {
+                        fmt::Display::fmt(self, f)
+                    }
+                }
+            }
+        )+
+    };
+}
+
+roc_inspect_float_impl!(f32, f64);
+
+impl RocInspect for bool {
+    fn inspect(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if *self { "Bool.true" } else { "Bool.false" })
+    }
+}
+
+/// The empty record.
+impl RocInspect for () {
+    fn inspect(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("{}")
+    }
+}
+
+impl RocInspect for Infallible {
+    fn inspect(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {}
+    }
+}
+
+impl RocInspect for RocStr {
+    fn inspect(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("\"")?;
+
+        for c in self.as_str().chars() {
+            // Roc escapes these invisible characters, and nothing else.
+            match c {
+                '\u{feff}' | '\u{200b}' | '\u{200c}' | '\u{200d}' => {
+                    write!(f, "\\u({:x})", c as u32)?
+                }
+                _ => fmt::Write::write_char(f, c)?,
+            }
+        }
+
+        f.write_str("\"")
+    }
+}
+
+impl<T> RocInspect for RocList<T>
+where
+    T: RocInspect + RocRefcounted,
+{
+    fn inspect(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+
+        for (index, elem) in self.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+
+            elem.inspect(f)?;
+        }
+
+        f.write_str("]")
+    }
+}
+
+/// `Box` is opaque, so Roc doesn't show what's inside it.
+impl<T> RocInspect for RocBox<T>
+where
+    T: RocRefcounted,
+{
+    fn inspect(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<opaque>")
+    }
+}
+
+impl<T, E> RocInspect for RocResult<T, E>
+where
+    T: RocInspect,
+    E: RocInspect,
+{
+    fn inspect(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.as_result_of_refs() {
+            Ok(payload) => {
+                f.write_str("(Ok ")?;
+                (**payload).inspect(f)?;
+            }
+            Err(payload) => {
+                f.write_str("(Err ")?;
+                (**payload).inspect(f)?;
+            }
+        }
+
+        f.write_str(")")
+    }
+}
@@ -12,12 +12,21 @@ use core::ops::Drop;
 use core::str;
 use std::convert::Infallible;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+mod inspect;
 mod roc_box;
+mod roc_dict;
+mod roc_hash;
 mod roc_list;
 mod roc_str;
 mod storage;
 
+pub use inspect::RocInspect;
 pub use roc_box::RocBox;
+pub use roc_dict::{RocDict, RocDictIter, RocSet};
+pub use roc_hash::{RocHash, RocHasher};
 pub use roc_list::{ReadOnlyRocList, RocList, SendSafeRocList};
 pub use roc_str::{InteriorNulError, ReadOnlyRocStr, RocStr, SendSafeRocStr};
 pub use storage::Storage;
@@ -35,6 +44,8 @@ extern "C" {
     pub fn roc_panic(c_ptr: *mut c_void, tag_id: u32);
     pub fn roc_dbg(loc: *mut c_void, msg: *mut c_void, src: *mut c_void);
     pub fn roc_memset(dst: *mut c_void, c: i32, n: usize) -> *mut c_void;
+    /// The seed the Roc app's `Dict` hashes keys with. Only `RocDict` and `RocSet` use it.
+    pub fn roc_dict_pseudo_seed() -> u64;
 }
 
 pub fn roc_alloc_refcounted<T>() -> *mut T {
@@ -265,6 +276,40 @@ impl<T, E> From<Result<T, E>> for RocResult<T, E> {
     }
 }
 
+/// Serializes the way a Rust `Result` does, e.g. `{"Ok": 42}` in JSON.
+#[cfg(feature = "serde")]
+impl<T, E> Serialize for RocResult<T, E>
+where
+    T: Serialize,
+    E: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let result: Result<&T, &E> = match self.as_result_of_refs() {
+            Ok(payload) => Ok(&**payload),
+            Err(payload) => Err(&**payload),
+        };
+
+        result.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T, E> Deserialize<'de> for RocResult<T, E>
+where
+    T: Deserialize<'de>,
+    E: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Result::<T, E>::deserialize(deserializer).map(RocResult::from)
+    }
+}
+
 #[repr(u8)]
 #[derive(Clone, Copy)]
 enum RocResultTag {
@@ -478,6 +523,31 @@ impl fmt::Display for RocDec {
     }
 }
 
+/// Serializes as a string like `"1.25"`, because most formats' numbers can't hold every `Dec`.
+#[cfg(feature = "serde")]
+impl Serialize for RocDec {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.to_str_helper(&mut ArrayString::new()))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for RocDec {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let string = String::deserialize(deserializer)?;
+
+        RocDec::from_str(&string).ok_or_else(|| {
+            serde::de::Error::invalid_value(serde::de::Unexpected::Str(&string), &"a Dec")
+        })
+    }
+}
+
 impl PartialOrd for RocDec {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -518,6 +588,26 @@ impl fmt::Display for I128 {
     }
 }
 
+#[cfg(feature = "serde")]
+impl Serialize for I128 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        i128::from(*self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for I128 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        i128::deserialize(deserializer).map(I128::from)
+    }
+}
+
 impl PartialOrd for I128 {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -564,6 +654,26 @@ impl fmt::Display for U128 {
     }
 }
 
+#[cfg(feature = "serde")]
+impl Serialize for U128 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        u128::from(*self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for U128 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        u128::deserialize(deserializer).map(U128::from)
+    }
+}
+
 impl PartialOrd for U128 {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -12,6 +12,9 @@ use core::{
 
 use std::os::raw::c_void;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
 #[repr(C)]
 pub struct RocBox<T>
 where
@@ -153,6 +156,32 @@ where
     }
 }
 
+#[cfg(feature = "serde")]
+impl<T> Serialize for RocBox<T>
+where
+    T: Serialize + RocRefcounted,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.deref().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> Deserialize<'de> for RocBox<T>
+where
+    T: Deserialize<'de> + RocRefcounted,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(RocBox::new)
+    }
+}
+
 impl<T> Clone for RocBox<T>
 where
     T: RocRefcounted,
@@ -206,12 +235,9 @@ where
 
         if needs_dealloc {
             unsafe {
-                // Drop the stored contents.
-                let contents_ptr = contents.as_ptr();
-
-                if T::is_refcounted() {
-                    ptr::read(contents_ptr).dec();
-                }
+                // Drop the stored contents. Their destructors decrement any refcounts inside them,
+                // so calling `dec` on them as well would free those allocations twice.
+                ptr::drop_in_place(contents.as_ptr());
 
                 let alignment = Self::alloc_alignment();
 
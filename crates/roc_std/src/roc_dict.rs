@@ -0,0 +1,1012 @@
+use core::{
+    cmp::Ordering,
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+    mem::{self, ManuallyDrop},
+};
+use std::collections::hash_map::DefaultHasher;
+
+use crate::roc_hash::hash_unordered;
+use crate::{
+    roc_dict_pseudo_seed, roc_refcounted_noop_impl, RocHash, RocHasher, RocInspect, RocList,
+    RocRefcounted,
+};
+
+#[cfg(feature = "serde")]
+use core::marker::PhantomData;
+#[cfg(feature = "serde")]
+use serde::{
+    de::{Deserializer, MapAccess, SeqAccess, Visitor},
+    ser::{SerializeMap, SerializeSeq, Serializer},
+    Deserialize, Serialize,
+};
+
+/// These match the constants of the same names in Dict.roc.
+const DEFAULT_MAX_LOAD_FACTOR: f32 = 0.8;
+const INITIAL_SHIFTS: u8 = 64 - 3;
+const DIST_INC: u32 = 1 << 8;
+const FINGERPRINT_MASK: u32 = DIST_INC - 1;
+const MAX_SIZE: u64 = 1 << 32;
+const MAX_BUCKET_COUNT: u64 = MAX_SIZE;
+
+/// An entry in a Dict's hash index, which maps hashes to entries in its `data`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C)]
+struct Bucket {
+    /// The upper 3 bytes are the distance to the bucket the hash points to,
+    /// and the lowest byte is a fingerprint of the hash.
+    dist_and_fingerprint: u32,
+    data_index: u32,
+}
+
+roc_refcounted_noop_impl!(Bucket);
+
+const EMPTY_BUCKET: Bucket = Bucket {
+    dist_and_fingerprint: 0,
+    data_index: 0,
+};
+
+/// Roc's `Dict`, laid out the way Dict.roc lays it out.
+///
+/// Its hash index works the way Dict.roc's does, and hashes keys with [`RocHash`] and the seed
+/// the Roc app's `Dict` uses, so Roc and the host can each look up and insert keys in dicts
+/// the other built. An app built with `roc build --export-dict-seed` exports that seed as
+/// `roc_dict_pseudo_seed`; without the flag, or in a host that isn't linked with a Roc app
+/// (like a test), the host has to provide that function itself.
+#[cfg(target_pointer_width = "64")]
+#[repr(C)]
+pub struct RocDict<K, V>
+where
+    K: RocRefcounted,
+    V: RocRefcounted,
+{
+    buckets: RocList<Bucket>,
+    data: RocList<RocDictEntry<K, V>>,
+    max_bucket_capacity: u64,
+    max_load_factor: f32,
+    shifts: u8,
+}
+
+/// Roc's `Dict`, laid out the way Dict.roc lays it out.
+///
+/// On 32-bit targets, Roc puts `max_bucket_capacity` first, since a U64 is more
+/// aligned than a List there. See the 64-bit version for more.
+#[cfg(target_pointer_width = "32")]
+#[repr(C)]
+pub struct RocDict<K, V>
+where
+    K: RocRefcounted,
+    V: RocRefcounted,
+{
+    max_bucket_capacity: u64,
+    buckets: RocList<Bucket>,
+    data: RocList<RocDictEntry<K, V>>,
+    max_load_factor: f32,
+    shifts: u8,
+}
+
+impl<K, V> RocDict<K, V>
+where
+    K: RocRefcounted,
+    V: RocRefcounted,
+{
+    pub fn empty() -> Self {
+        Self {
+            buckets: RocList::empty(),
+            data: RocList::empty(),
+            max_bucket_capacity: 0,
+            max_load_factor: DEFAULT_MAX_LOAD_FACTOR,
+            shifts: INITIAL_SHIFTS,
+        }
+    }
+
+    /// Create an empty RocDict with enough space preallocated to store
+    /// the requested number of entries.
+    pub fn with_capacity(num_entries: usize) -> Self {
+        let size = (num_entries as u64).min(MAX_SIZE);
+        let shifts = calc_shifts_for_size(size, DEFAULT_MAX_LOAD_FACTOR);
+        let bucket_count = calc_num_buckets(shifts);
+
+        Self {
+            buckets: empty_buckets(bucket_count),
+            data: RocList::with_capacity(size as usize),
+            max_bucket_capacity: max_bucket_capacity(bucket_count, DEFAULT_MAX_LOAD_FACTOR),
+            max_load_factor: DEFAULT_MAX_LOAD_FACTOR,
+            shifts,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Iterates over the entries in the order they were inserted,
+    /// like `Dict.walk` does in Roc.
+    pub fn iter(&self) -> RocDictIter<'_, K, V> {
+        RocDictIter(self.data.as_slice().iter())
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V>
+    where
+        K: RocHash + PartialEq,
+    {
+        self.find(key)
+            .map(|data_index| self.data[data_index].value())
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool
+    where
+        K: RocHash + PartialEq,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Inserts the entry, returning the value it replaced if the key was already present.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V>
+    where
+        K: RocHash + Clone + PartialEq,
+        V: Clone,
+    {
+        if !self.data.is_unique() {
+            self.data = self.data.iter().cloned().collect();
+        }
+
+        if !self.buckets.is_unique() {
+            self.buckets = self.buckets.iter().copied().collect();
+        }
+
+        self.insert_into_unique(key, value)
+    }
+
+    fn sorted_by_key(&self) -> Vec<(&K, &V)>
+    where
+        K: Ord,
+    {
+        let mut entries: Vec<_> = self.iter().collect();
+        entries.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+        entries
+    }
+
+    /// Returns the index in `data` of the entry with this key, like `find` in Dict.roc.
+    fn find(&self, key: &K) -> Option<usize>
+    where
+        K: RocHash + PartialEq,
+    {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let hash = hash_key(key);
+        let buckets = self.buckets.as_slice();
+        let mut dist_and_fingerprint = dist_and_fingerprint_from_hash(hash);
+        let mut bucket_index = bucket_index_from_hash(hash, self.shifts);
+
+        loop {
+            let bucket = buckets[bucket_index];
+
+            if dist_and_fingerprint == bucket.dist_and_fingerprint {
+                let data_index = bucket.data_index as usize;
+
+                if self.data[data_index].key() == key {
+                    return Some(data_index);
+                }
+            } else if dist_and_fingerprint > bucket.dist_and_fingerprint {
+                return None;
+            }
+
+            bucket_index = next_bucket_index(bucket_index, buckets.len());
+            dist_and_fingerprint += DIST_INC;
+        }
+    }
+
+    /// Like `insert` in Dict.roc. The caller makes sure `data` and `buckets` are unique.
+    fn insert_into_unique(&mut self, key: K, value: V) -> Option<V>
+    where
+        K: RocHash + PartialEq,
+    {
+        if self.len() as u64 >= self.max_bucket_capacity {
+            self.increase_size();
+        }
+
+        let hash = hash_key(&key);
+        let mut dist_and_fingerprint = dist_and_fingerprint_from_hash(hash);
+        let mut bucket_index = bucket_index_from_hash(hash, self.shifts);
+
+        loop {
+            let loaded = self.buckets[bucket_index];
+
+            if dist_and_fingerprint == loaded.dist_and_fingerprint {
+                let entry = &mut self.data.as_mut_slice()[loaded.data_index as usize];
+
+                if *entry.key() == key {
+                    return Some(mem::replace(entry.value_mut(), value));
+                }
+            } else if dist_and_fingerprint > loaded.dist_and_fingerprint {
+                let data_index = self.data.len() as u32;
+
+                self.data.push(RocDictEntry::new(key, value));
+                place_and_shift_up(
+                    self.buckets.as_mut_slice(),
+                    Bucket {
+                        dist_and_fingerprint,
+                        data_index,
+                    },
+                    bucket_index,
+                );
+
+                return None;
+            }
+
+            bucket_index = next_bucket_index(bucket_index, self.buckets.len());
+            dist_and_fingerprint += DIST_INC;
+        }
+    }
+
+    fn increase_size(&mut self)
+    where
+        K: RocHash,
+    {
+        if self.max_bucket_capacity == MAX_BUCKET_COUNT {
+            panic!("Dict hit limit of {MAX_BUCKET_COUNT} elements. Unable to grow more.");
+        }
+
+        self.rebuild_buckets(self.shifts - 1);
+    }
+
+    /// Replaces the buckets with `2^(64 - shifts)` new ones, which index the entries in `data`.
+    fn rebuild_buckets(&mut self, shifts: u8)
+    where
+        K: RocHash,
+    {
+        let bucket_count = calc_num_buckets(shifts);
+        let mut buckets = empty_buckets(bucket_count);
+
+        for (data_index, entry) in self.data.iter().enumerate() {
+            let hash = hash_key(entry.key());
+            let mut dist_and_fingerprint = dist_and_fingerprint_from_hash(hash);
+            let mut bucket_index = bucket_index_from_hash(hash, shifts);
+
+            while dist_and_fingerprint < buckets[bucket_index].dist_and_fingerprint {
+                bucket_index = next_bucket_index(bucket_index, buckets.len());
+                dist_and_fingerprint += DIST_INC;
+            }
+
+            place_and_shift_up(
+                buckets.as_mut_slice(),
+                Bucket {
+                    dist_and_fingerprint,
+                    data_index: data_index as u32,
+                },
+                bucket_index,
+            );
+        }
+
+        self.buckets = buckets;
+        self.max_bucket_capacity = max_bucket_capacity(bucket_count, self.max_load_factor);
+        self.shifts = shifts;
+    }
+}
+
+fn hash_key<K: RocHash>(key: &K) -> u64 {
+    let mut hasher = RocHasher::with_seed(unsafe { roc_dict_pseudo_seed() });
+    key.roc_hash(&mut hasher);
+
+    hasher.complete()
+}
+
+fn dist_and_fingerprint_from_hash(hash: u64) -> u32 {
+    (hash as u32 & FINGERPRINT_MASK) | DIST_INC
+}
+
+fn bucket_index_from_hash(hash: u64, shifts: u8) -> usize {
+    (hash >> shifts) as usize
+}
+
+fn next_bucket_index(bucket_index: usize, bucket_count: usize) -> usize {
+    if bucket_index + 1 != bucket_count {
+        bucket_index + 1
+    } else {
+        0
+    }
+}
+
+/// Puts the bucket at `bucket_index`, moving the buckets after it along until one lands in an
+/// empty bucket, like `place_and_shift_up` in Dict.roc.
+fn place_and_shift_up(buckets: &mut [Bucket], mut bucket: Bucket, mut bucket_index: usize) {
+    loop {
+        let loaded = mem::replace(&mut buckets[bucket_index], bucket);
+
+        if loaded.dist_and_fingerprint == 0 {
+            return;
+        }
+
+        bucket = Bucket {
+            dist_and_fingerprint: loaded.dist_and_fingerprint + DIST_INC,
+            ..loaded
+        };
+        bucket_index = next_bucket_index(bucket_index, buckets.len());
+    }
+}
+
+fn empty_buckets(bucket_count: u64) -> RocList<Bucket> {
+    core::iter::repeat(EMPTY_BUCKET)
+        .take(bucket_count as usize)
+        .collect()
+}
+
+fn calc_num_buckets(shifts: u8) -> u64 {
+    1u64.checked_shl(64 - shifts as u32)
+        .unwrap_or(u64::MAX)
+        .min(MAX_BUCKET_COUNT)
+}
+
+fn max_bucket_capacity(bucket_count: u64, max_load_factor: f32) -> u64 {
+    if bucket_count == MAX_BUCKET_COUNT {
+        // Once there are as many buckets as there can be, every one of them can be used.
+        MAX_BUCKET_COUNT
+    } else {
+        (bucket_count as f32 * max_load_factor).floor() as u64
+    }
+}
+
+fn calc_shifts_for_size(size: u64, max_load_factor: f32) -> u8 {
+    let mut shifts = INITIAL_SHIFTS;
+
+    while shifts > 0 && ((calc_num_buckets(shifts) as f32 * max_load_factor).floor() as u64) < size
+    {
+        shifts -= 1;
+    }
+
+    shifts
+}
+
+impl<K, V> Default for RocDict<K, V>
+where
+    K: RocRefcounted,
+    V: RocRefcounted,
+{
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<K, V> Clone for RocDict<K, V>
+where
+    K: RocRefcounted,
+    V: RocRefcounted,
+{
+    fn clone(&self) -> Self {
+        Self {
+            buckets: self.buckets.clone(),
+            data: self.data.clone(),
+            max_bucket_capacity: self.max_bucket_capacity,
+            max_load_factor: self.max_load_factor,
+            shifts: self.shifts,
+        }
+    }
+}
+
+/// Like Roc's `==` on dicts, this ignores the order the entries were inserted in.
+impl<K, V> PartialEq for RocDict<K, V>
+where
+    K: RocHash + PartialEq + RocRefcounted,
+    V: PartialEq + RocRefcounted,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl<K, V> Eq for RocDict<K, V>
+where
+    K: RocHash + Eq + RocRefcounted,
+    V: Eq + RocRefcounted,
+{
+}
+
+/// Dicts have no order in Roc, so this compares their entries sorted by key,
+/// which keeps it consistent with `==`.
+impl<K, V> PartialOrd for RocDict<K, V>
+where
+    K: RocHash + Ord + RocRefcounted,
+    V: PartialOrd + RocRefcounted,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.sorted_by_key().partial_cmp(&other.sorted_by_key())
+    }
+}
+
+impl<K, V> Ord for RocDict<K, V>
+where
+    K: RocHash + Ord + RocRefcounted,
+    V: Ord + RocRefcounted,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sorted_by_key().cmp(&other.sorted_by_key())
+    }
+}
+
+/// Like Roc's `Hash` for dicts, this combines the entries' hashes in a way
+/// that doesn't depend on their order.
+impl<K, V> Hash for RocDict<K, V>
+where
+    K: Hash + RocRefcounted,
+    V: Hash + RocRefcounted,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let combined = self.iter().fold(0u64, |sum, entry| {
+            let mut hasher = DefaultHasher::new();
+            entry.hash(&mut hasher);
+
+            sum.wrapping_add(hasher.finish())
+        });
+
+        self.len().hash(state);
+        combined.hash(state);
+    }
+}
+
+/// Like `Hash` for `Dict` in Roc, which hashes the entries with `Hash.hash_unordered`.
+impl<K, V> RocHash for RocDict<K, V>
+where
+    K: RocHash + RocRefcounted,
+    V: RocHash + RocRefcounted,
+{
+    fn roc_hash(&self, hasher: &mut RocHasher) {
+        hash_unordered(hasher, self.iter(), |hasher, (key, value)| {
+            key.roc_hash(hasher);
+            value.roc_hash(hasher);
+        });
+    }
+}
+
+impl<K, V> Debug for RocDict<K, V>
+where
+    K: Debug + RocRefcounted,
+    V: Debug + RocRefcounted,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V> RocRefcounted for RocDict<K, V>
+where
+    K: RocRefcounted,
+    V: RocRefcounted,
+{
+    fn inc(&mut self) {
+        self.buckets.inc();
+        self.data.inc();
+    }
+
+    fn dec(&mut self) {
+        self.buckets.dec();
+        self.data.dec();
+    }
+
+    fn is_refcounted() -> bool {
+        true
+    }
+}
+
+/// Like Roc's `Dict.from_list`, later entries replace earlier ones with the same key.
+impl<K, V> FromIterator<(K, V)> for RocDict<K, V>
+where
+    K: RocHash + PartialEq + RocRefcounted,
+    V: RocRefcounted,
+{
+    fn from_iter<I>(into: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let iter = into.into_iter();
+        let mut dict = Self::with_capacity(iter.size_hint().0);
+
+        for (key, value) in iter {
+            dict.insert_into_unique(key, value);
+        }
+
+        dict
+    }
+}
+
+impl<K, V, const SIZE: usize> From<[(K, V); SIZE]> for RocDict<K, V>
+where
+    K: RocHash + PartialEq + RocRefcounted,
+    V: RocRefcounted,
+{
+    fn from(array: [(K, V); SIZE]) -> Self {
+        Self::from_iter(array)
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a RocDict<K, V>
+where
+    K: RocRefcounted,
+    V: RocRefcounted,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = RocDictIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V> RocInspect for RocDict<K, V>
+where
+    K: RocInspect + RocRefcounted,
+    V: RocInspect + RocRefcounted,
+{
+    fn inspect(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("{")?;
+
+        for (index, (key, value)) in self.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+
+            key.inspect(f)?;
+            f.write_str(": ")?;
+            value.inspect(f)?;
+        }
+
+        f.write_str("}")
+    }
+}
+
+pub struct RocDictIter<'a, K, V>(core::slice::Iter<'a, RocDictEntry<K, V>>)
+where
+    K: RocRefcounted,
+    V: RocRefcounted;
+
+impl<'a, K, V> Iterator for RocDictIter<'a, K, V>
+where
+    K: RocRefcounted,
+    V: RocRefcounted,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|entry| (entry.key(), entry.value()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+#[repr(C)]
+struct KeyFirst<K, V> {
+    key: K,
+    value: V,
+}
+
+#[repr(C)]
+struct ValueFirst<K, V> {
+    value: V,
+    key: K,
+}
+
+/// A `(k, v)` tuple in a Dict's `data`. Roc sorts tuple elements by decreasing alignment
+/// (keeping their order when it's a tie), so which one comes first depends on `K` and `V`.
+///
+/// Both orders pad out to the same size, so the union is exactly as big as Roc's tuple.
+#[repr(C)]
+union RocDictEntry<K, V> {
+    key_first: ManuallyDrop<KeyFirst<K, V>>,
+    value_first: ManuallyDrop<ValueFirst<K, V>>,
+}
+
+impl<K, V> RocDictEntry<K, V> {
+    fn is_key_first() -> bool {
+        mem::align_of::<K>() >= mem::align_of::<V>()
+    }
+
+    fn new(key: K, value: V) -> Self {
+        if Self::is_key_first() {
+            Self {
+                key_first: ManuallyDrop::new(KeyFirst { key, value }),
+            }
+        } else {
+            Self {
+                value_first: ManuallyDrop::new(ValueFirst { value, key }),
+            }
+        }
+    }
+
+    fn key(&self) -> &K {
+        unsafe {
+            if Self::is_key_first() {
+                &self.key_first.key
+            } else {
+                &self.value_first.key
+            }
+        }
+    }
+
+    fn value(&self) -> &V {
+        unsafe {
+            if Self::is_key_first() {
+                &self.key_first.value
+            } else {
+                &self.value_first.value
+            }
+        }
+    }
+
+    fn key_mut(&mut self) -> &mut K {
+        unsafe {
+            if Self::is_key_first() {
+                &mut self.key_first.key
+            } else {
+                &mut self.value_first.key
+            }
+        }
+    }
+
+    fn value_mut(&mut self) -> &mut V {
+        unsafe {
+            if Self::is_key_first() {
+                &mut self.key_first.value
+            } else {
+                &mut self.value_first.value
+            }
+        }
+    }
+}
+
+impl<K, V> Clone for RocDictEntry<K, V>
+where
+    K: Clone,
+    V: Clone,
+{
+    fn clone(&self) -> Self {
+        Self::new(self.key().clone(), self.value().clone())
+    }
+}
+
+/// Like the elements of a RocList, entries are only released through `dec`.
+impl<K, V> RocRefcounted for RocDictEntry<K, V>
+where
+    K: RocRefcounted,
+    V: RocRefcounted,
+{
+    fn inc(&mut self) {
+        self.key_mut().inc();
+        self.value_mut().inc();
+    }
+
+    fn dec(&mut self) {
+        self.key_mut().dec();
+        self.value_mut().dec();
+    }
+
+    fn is_refcounted() -> bool {
+        K::is_refcounted() || V::is_refcounted()
+    }
+}
+
+/// Roc's `Set`, which is a `Dict` whose values are all `{}`.
+#[repr(transparent)]
+pub struct RocSet<T>(RocDict<T, ()>)
+where
+    T: RocRefcounted;
+
+impl<T> RocSet<T>
+where
+    T: RocRefcounted,
+{
+    pub fn empty() -> Self {
+        Self(RocDict::empty())
+    }
+
+    /// Create an empty RocSet with enough space preallocated to store
+    /// the requested number of elements.
+    pub fn with_capacity(num_elems: usize) -> Self {
+        Self(RocDict::with_capacity(num_elems))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterates over the elements in the order they were inserted.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.0.keys()
+    }
+
+    pub fn contains(&self, elem: &T) -> bool
+    where
+        T: RocHash + PartialEq,
+    {
+        self.0.contains_key(elem)
+    }
+
+    /// Returns whether the element was newly inserted.
+    pub fn insert(&mut self, elem: T) -> bool
+    where
+        T: RocHash + Clone + PartialEq,
+    {
+        self.0.insert(elem, ()).is_none()
+    }
+}
+
+impl<T> Default for RocSet<T>
+where
+    T: RocRefcounted,
+{
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<T> Clone for RocSet<T>
+where
+    T: RocRefcounted,
+{
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> PartialEq for RocSet<T>
+where
+    T: RocHash + PartialEq + RocRefcounted,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T> Eq for RocSet<T> where T: RocHash + Eq + RocRefcounted {}
+
+impl<T> PartialOrd for RocSet<T>
+where
+    T: RocHash + Ord + RocRefcounted,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for RocSet<T>
+where
+    T: RocHash + Ord + RocRefcounted,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<T> Hash for RocSet<T>
+where
+    T: Hash + RocRefcounted,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl<T> RocHash for RocSet<T>
+where
+    T: RocHash + RocRefcounted,
+{
+    fn roc_hash(&self, hasher: &mut RocHasher) {
+        self.0.roc_hash(hasher);
+    }
+}
+
+impl<T> Debug for RocSet<T>
+where
+    T: Debug + RocRefcounted,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T> RocRefcounted for RocSet<T>
+where
+    T: RocRefcounted,
+{
+    fn inc(&mut self) {
+        self.0.inc();
+    }
+
+    fn dec(&mut self) {
+        self.0.dec();
+    }
+
+    fn is_refcounted() -> bool {
+        true
+    }
+}
+
+impl<T> FromIterator<T> for RocSet<T>
+where
+    T: RocHash + PartialEq + RocRefcounted,
+{
+    fn from_iter<I>(into: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Self(into.into_iter().map(|elem| (elem, ())).collect())
+    }
+}
+
+impl<T, const SIZE: usize> From<[T; SIZE]> for RocSet<T>
+where
+    T: RocHash + PartialEq + RocRefcounted,
+{
+    fn from(array: [T; SIZE]) -> Self {
+        Self::from_iter(array)
+    }
+}
+
+impl<T> RocInspect for RocSet<T>
+where
+    T: RocInspect + RocRefcounted,
+{
+    fn inspect(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("{")?;
+
+        for (index, elem) in self.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+
+            elem.inspect(f)?;
+        }
+
+        f.write_str("}")
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<K, V> Serialize for RocDict<K, V>
+where
+    K: Serialize + RocRefcounted,
+    V: Serialize + RocRefcounted,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (key, value) in self {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, K, V> Deserialize<'de> for RocDict<K, V>
+where
+    K: Deserialize<'de> + RocHash + PartialEq + RocRefcounted,
+    V: Deserialize<'de> + RocRefcounted,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(RocDictVisitor {
+            marker: PhantomData,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+struct RocDictVisitor<K, V> {
+    marker: PhantomData<(K, V)>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, K, V> Visitor<'de> for RocDictVisitor<K, V>
+where
+    K: Deserialize<'de> + RocHash + PartialEq + RocRefcounted,
+    V: Deserialize<'de> + RocRefcounted,
+{
+    type Value = RocDict<K, V>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut out = RocDict::with_capacity(map.size_hint().unwrap_or(0));
+
+        while let Some((key, value)) = map.next_entry()? {
+            out.insert_into_unique(key, value);
+        }
+
+        Ok(out)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> Serialize for RocSet<T>
+where
+    T: Serialize + RocRefcounted,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for elem in self.iter() {
+            seq.serialize_element(elem)?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> Deserialize<'de> for RocSet<T>
+where
+    T: Deserialize<'de> + RocHash + PartialEq + RocRefcounted,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(RocSetVisitor {
+            marker: PhantomData,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+struct RocSetVisitor<T> {
+    marker: PhantomData<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> Visitor<'de> for RocSetVisitor<T>
+where
+    T: Deserialize<'de> + RocHash + PartialEq + RocRefcounted,
+{
+    type Value = RocSet<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut out = RocSet::with_capacity(seq.size_hint().unwrap_or(0));
+
+        while let Some(elem) = seq.next_element()? {
+            out.0.insert_into_unique(elem, ());
+        }
+
+        Ok(out)
+    }
+}
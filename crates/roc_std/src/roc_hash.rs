@@ -0,0 +1,323 @@
+use crate::{RocDec, RocList, RocRefcounted, RocResult, RocStr, I128, U128};
+
+/// Feeds a value to a [`RocHasher`] the way Roc's `Hash` ability does, so a host can compute
+/// the same hashes as the Roc code it runs. `RocDict` and `RocSet` need this to build the hash
+/// index Roc uses to look up their keys.
+///
+/// `roc glue` implements this for the records and non-recursive tag unions it generates.
+pub trait RocHash {
+    fn roc_hash(&self, hasher: &mut RocHasher);
+}
+
+/// Roc's `LowLevelHasher`, the hasher `Dict` uses: a 64-bit, little-endian form of
+/// [wyhash final4](https://github.com/wangyi-fudan/wyhash/blob/77e50f267fbc7b8e2d09f2d455219adb70ad4749/wyhash.h).
+///
+/// This must stay in sync with the implementation at the bottom of Dict.roc.
+#[derive(Clone, Copy, Debug)]
+pub struct RocHasher {
+    initialized_seed: u64,
+    state: u64,
+}
+
+const WYP0: u64 = 0xa0761d6478bd642f;
+const WYP1: u64 = 0xe7037ed1a0b428db;
+const WYP2: u64 = 0x8ebc6af09c88c6e3;
+const WYP3: u64 = 0x589965cc75374cc3;
+
+impl RocHasher {
+    pub fn with_seed(seed: u64) -> Self {
+        let initialized_seed = wymix(seed ^ WYP0, WYP1) ^ seed;
+
+        Self {
+            initialized_seed,
+            state: seed,
+        }
+    }
+
+    pub fn complete(&self) -> u64 {
+        self.state
+    }
+
+    fn combine(&mut self, a: u64, b: u64, seed: u64, length: u64) {
+        let (lower, upper) = wymum(a ^ WYP1, b ^ seed);
+        let hash = wymix(lower ^ WYP0 ^ length, upper ^ WYP1);
+
+        self.state = wymix(self.state, hash);
+    }
+
+    pub fn add_u8(&mut self, u8: u8) {
+        let p0 = u8 as u64;
+        let a = (p0 << 16) | (p0 << 8) | p0;
+
+        self.combine(a, 0, self.initialized_seed, 1);
+    }
+
+    pub fn add_u16(&mut self, u16: u16) {
+        let p0 = (u16 & 0xFF) as u64;
+        let p1 = (u16 >> 8) as u64;
+        let a = (p0 << 16) | (p1 << 8) | p1;
+
+        self.combine(a, 0, self.initialized_seed, 2);
+    }
+
+    pub fn add_u32(&mut self, u32: u32) {
+        let p0 = u32 as u64;
+        let a = (p0 << 32) | p0;
+
+        self.combine(a, a, self.initialized_seed, 4);
+    }
+
+    pub fn add_u64(&mut self, u64: u64) {
+        let p0 = u64 & 0xFFFF_FFFF;
+        let p1 = u64 >> 32;
+        let a = (p0 << 32) | p1;
+        let b = (p1 << 32) | p0;
+
+        self.combine(a, b, self.initialized_seed, 8);
+    }
+
+    pub fn add_u128(&mut self, u128: u128) {
+        let lower = u128 as u64;
+        let upper = (u128 >> 64) as u64;
+        let p0 = lower & 0xFFFF_FFFF;
+        let p1 = lower >> 32;
+        let p2 = upper & 0xFFFF_FFFF;
+        let p3 = upper >> 32;
+        let a = (p0 << 32) | p2;
+        let b = (p3 << 32) | p1;
+
+        self.combine(a, b, self.initialized_seed, 16);
+    }
+
+    pub fn add_bytes(&mut self, bytes: &[u8]) {
+        let length = bytes.len();
+
+        let (a, b, seed) = if length <= 16 {
+            if length >= 4 {
+                let x = (length >> 3) << 2;
+                let a = (wyr4(bytes, 0) << 32) | wyr4(bytes, x);
+                let b = (wyr4(bytes, length - 4) << 32) | wyr4(bytes, length - 4 - x);
+
+                (a, b, self.initialized_seed)
+            } else if length > 0 {
+                (wyr3(bytes, 0, length), 0, self.initialized_seed)
+            } else {
+                (0, 0, self.initialized_seed)
+            }
+        } else if length <= 48 {
+            hash_bytes_helper16(self.initialized_seed, bytes, 0, length)
+        } else {
+            hash_bytes_helper48(self.initialized_seed, bytes)
+        };
+
+        self.combine(a, b, seed, length as u64);
+    }
+}
+
+fn hash_bytes_helper48(seed: u64, bytes: &[u8]) -> (u64, u64, u64) {
+    let (mut seed, mut see1, mut see2) = (seed, seed, seed);
+    let mut index = 0;
+    let mut remaining = bytes.len();
+
+    loop {
+        seed = wymix(wyr8(bytes, index) ^ WYP1, wyr8(bytes, index + 8) ^ seed);
+        see1 = wymix(
+            wyr8(bytes, index + 16) ^ WYP2,
+            wyr8(bytes, index + 24) ^ see1,
+        );
+        see2 = wymix(
+            wyr8(bytes, index + 32) ^ WYP3,
+            wyr8(bytes, index + 40) ^ see2,
+        );
+        remaining -= 48;
+        index += 48;
+
+        if remaining <= 48 {
+            break;
+        }
+    }
+
+    let final_seed = see2 ^ see1 ^ seed;
+
+    if remaining > 16 {
+        hash_bytes_helper16(final_seed, bytes, index, remaining)
+    } else {
+        (
+            wyr8(bytes, index + remaining - 16),
+            wyr8(bytes, index + remaining - 8),
+            final_seed,
+        )
+    }
+}
+
+fn hash_bytes_helper16(
+    mut seed: u64,
+    bytes: &[u8],
+    mut index: usize,
+    mut remaining: usize,
+) -> (u64, u64, u64) {
+    loop {
+        seed = wymix(wyr8(bytes, index) ^ WYP1, wyr8(bytes, index + 8) ^ seed);
+        remaining -= 16;
+        index += 16;
+
+        if remaining <= 16 {
+            break;
+        }
+    }
+
+    (
+        wyr8(bytes, index + remaining - 16),
+        wyr8(bytes, index + remaining - 8),
+        seed,
+    )
+}
+
+fn wymix(a: u64, b: u64) -> u64 {
+    let (lower, upper) = wymum(a, b);
+
+    lower ^ upper
+}
+
+fn wymum(a: u64, b: u64) -> (u64, u64) {
+    let r = (a as u128).wrapping_mul(b as u128);
+
+    (r as u64, (r >> 64) as u64)
+}
+
+fn wyr8(bytes: &[u8], index: usize) -> u64 {
+    u64::from_le_bytes(bytes[index..index + 8].try_into().unwrap())
+}
+
+fn wyr4(bytes: &[u8], index: usize) -> u64 {
+    u32::from_le_bytes(bytes[index..index + 4].try_into().unwrap()) as u64
+}
+
+fn wyr3(bytes: &[u8], index: usize, k: usize) -> u64 {
+    let p1 = bytes[index] as u64;
+    let p2 = bytes[index + (k >> 1)] as u64;
+    let p3 = bytes[index + k - 1] as u64;
+
+    (p1 << 16) | (p2 << 8) | p3
+}
+
+macro_rules! roc_hash_unsigned_impl {
+    ( $( $T:ty => $add:ident ),+ ) => {
+        $(
+            impl RocHash for $T {
+                fn roc_hash(&self, hasher: &mut RocHasher) {
+                    hasher.$add(*self);
+                }
+            }
+        )+
+    };
+}
+
+roc_hash_unsigned_impl!(u8 => add_u8, u16 => add_u16, u32 => add_u32, u64 => add_u64, u128 => add_u128);
+
+// Roc hashes signed integers as the unsigned integers with the same bits.
+macro_rules! roc_hash_signed_impl {
+    ( $( $T:ty => $U:ty ),+ ) => {
+        $(
+            impl RocHash for $T {
+                fn roc_hash(&self, hasher: &mut RocHasher) {
+                    (*self as $U).roc_hash(hasher);
+                }
+            }
+        )+
+    };
+}
+
+roc_hash_signed_impl!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128);
+
+impl RocHash for U128 {
+    fn roc_hash(&self, hasher: &mut RocHasher) {
+        u128::from(*self).roc_hash(hasher);
+    }
+}
+
+impl RocHash for I128 {
+    fn roc_hash(&self, hasher: &mut RocHasher) {
+        i128::from(*self).roc_hash(hasher);
+    }
+}
+
+impl RocHash for RocDec {
+    fn roc_hash(&self, hasher: &mut RocHasher) {
+        // Like Num.without_decimal_point
+        i128::from_ne_bytes(self.to_ne_bytes()).roc_hash(hasher);
+    }
+}
+
+impl RocHash for bool {
+    fn roc_hash(&self, hasher: &mut RocHasher) {
+        hasher.add_u8(*self as u8);
+    }
+}
+
+/// `{}` adds nothing to the hash.
+impl RocHash for () {
+    fn roc_hash(&self, _hasher: &mut RocHasher) {}
+}
+
+impl RocHash for RocStr {
+    fn roc_hash(&self, hasher: &mut RocHasher) {
+        hasher.add_bytes(self.as_bytes());
+    }
+}
+
+impl<T> RocHash for RocList<T>
+where
+    T: RocHash + RocRefcounted,
+{
+    fn roc_hash(&self, hasher: &mut RocHasher) {
+        for elem in self.iter() {
+            elem.roc_hash(hasher);
+        }
+    }
+}
+
+/// `Result` is the tag union `[Err e, Ok a]`, so `Err` has discriminant 0 and `Ok` has 1.
+impl<T, E> RocHash for RocResult<T, E>
+where
+    T: RocHash,
+    E: RocHash,
+{
+    fn roc_hash(&self, hasher: &mut RocHasher) {
+        match self.as_result_of_refs() {
+            Ok(payload) => {
+                hasher.add_u8(1);
+                payload.roc_hash(hasher);
+            }
+            Err(payload) => {
+                hasher.add_u8(0);
+                payload.roc_hash(hasher);
+            }
+        }
+    }
+}
+
+/// Like Roc's `Hash.hash_unordered`, which `Dict` and `Set` use: the elements' hashes are
+/// combined in a way that doesn't depend on their order.
+pub(crate) fn hash_unordered<I, F>(hasher: &mut RocHasher, elems: I, mut hash_elem: F)
+where
+    I: IntoIterator,
+    F: FnMut(&mut RocHasher, I::Item),
+{
+    let combined = elems.into_iter().fold(0u64, |accum, elem| {
+        // Each element starts from the same state, so the order doesn't matter.
+        let mut elem_hasher = *hasher;
+        hash_elem(&mut elem_hasher, elem);
+
+        let (next_accum, overflowed) = accum.overflowing_add(elem_hasher.complete());
+
+        // Add the lost bit back in, so overflowing doesn't lose entropy.
+        if overflowed {
+            next_accum.wrapping_add(1)
+        } else {
+            next_accum
+        }
+    });
+
+    hasher.add_u64(combined);
+}
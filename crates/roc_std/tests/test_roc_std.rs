@@ -52,9 +52,18 @@ pub unsafe extern "C" fn roc_memset(dst: *mut c_void, c: i32, n: usize) -> *mut
     libc::memset(dst, c, n)
 }
 
+#[cfg(test)]
+#[no_mangle]
+pub extern "C" fn roc_dict_pseudo_seed() -> u64 {
+    0x526F_6352_616E_643F
+}
+
 #[cfg(test)]
 mod test_roc_std {
-    use roc_std::{RocBox, RocDec, RocList, RocResult, RocStr, SendSafeRocStr};
+    use roc_std::{
+        RocBox, RocDec, RocDict, RocHash, RocHasher, RocInspect, RocList, RocResult, RocSet,
+        RocStr, SendSafeRocStr,
+    };
 
     fn roc_str_byte_representation(string: &RocStr) -> [u8; RocStr::SIZE] {
         unsafe { core::mem::transmute_copy(string) }
@@ -199,6 +208,73 @@ mod test_roc_std {
         assert_eq!(orig, deserialized);
     }
 
+    #[test]
+    #[cfg(feature = "serde")]
+    fn roc_result_serde_roundtrip() {
+        let orig: RocResult<RocStr, u8> = RocResult::ok(RocStr::from("done"));
+
+        let serialized = serde_json::to_string(&orig).expect("failed to serialize result");
+        assert_eq!(serialized, r#"{"Ok":"done"}"#);
+
+        let deserialized = serde_json::from_str::<RocResult<RocStr, u8>>(&serialized)
+            .expect("failed to deserialize result");
+        assert_eq!(orig, deserialized);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn roc_dec_serde_roundtrip() {
+        let orig = RocDec::from_str("-1234.5678").unwrap();
+
+        let serialized = serde_json::to_string(&orig).expect("failed to serialize dec");
+        assert_eq!(serialized, r#""-1234.5678""#);
+
+        let deserialized =
+            serde_json::from_str::<RocDec>(&serialized).expect("failed to deserialize dec");
+        assert_eq!(orig, deserialized);
+
+        assert!(serde_json::from_str::<RocDec>(r#""not a dec""#).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn roc_box_serde_roundtrip() {
+        let orig = RocBox::new(RocList::from_slice(&[1u8, 2, 3]));
+
+        let serialized = serde_json::to_string(&orig).expect("failed to serialize box");
+        assert_eq!(serialized, "[1,2,3]");
+
+        let deserialized = serde_json::from_str::<RocBox<RocList<u8>>>(&serialized)
+            .expect("failed to deserialize box");
+        assert_eq!(orig, deserialized);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn roc_dict_serde_roundtrip() {
+        let orig = RocDict::from([(RocStr::from("one"), 1u8), (RocStr::from("two"), 2)]);
+
+        let serialized = serde_json::to_string(&orig).expect("failed to serialize dict");
+        assert_eq!(serialized, r#"{"one":1,"two":2}"#);
+
+        let deserialized = serde_json::from_str::<RocDict<RocStr, u8>>(&serialized)
+            .expect("failed to deserialize dict");
+        assert_eq!(orig, deserialized);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn roc_set_serde_roundtrip() {
+        let orig = RocSet::from([3u16, 1, 2]);
+
+        let serialized = serde_json::to_string(&orig).expect("failed to serialize set");
+        assert_eq!(serialized, "[3,1,2]");
+
+        let deserialized =
+            serde_json::from_str::<RocSet<u16>>(&serialized).expect("failed to deserialize set");
+        assert_eq!(orig, deserialized);
+    }
+
     #[test]
     fn list_from_iter() {
         let elems: [i64; 5] = [1, 2, 3, 4, 5];
@@ -275,6 +351,20 @@ mod test_roc_std {
         assert_eq!(roc_box.into_inner(), contents)
     }
 
+    #[test]
+    fn dropping_roc_box_decrements_contents_once() {
+        let list = RocList::from_slice(&[1u8, 2, 3]);
+        let roc_box = RocBox::new(list.clone());
+
+        assert!(!list.is_unique());
+
+        drop(roc_box);
+
+        // The box held the only other reference, so `list` must still be alive and now unique.
+        assert!(list.is_unique());
+        assert_eq!(list.as_slice(), &[1, 2, 3]);
+    }
+
     #[test]
     fn roc_dec_fmt() {
         assert_eq!(
@@ -336,6 +426,47 @@ mod test_roc_std {
         assert_eq!(RocDec::from(0), RocDec::default());
     }
 
+    struct Inspected<'a, T>(&'a T);
+
+    impl<T: RocInspect> core::fmt::Display for Inspected<'_, T> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            self.0.inspect(f)
+        }
+    }
+
+    fn inspect<T: RocInspect>(value: &T) -> String {
+        Inspected(value).to_string()
+    }
+
+    #[test]
+    fn roc_inspect_matches_roc() {
+        assert_eq!(inspect(&true), "Bool.true");
+        assert_eq!(inspect(&-42i64), "-42");
+        assert_eq!(inspect(&24.0f64), "24");
+        assert_eq!(inspect(&f32::NAN), "nan");
+        assert_eq!(inspect(&RocDec::from_str("0.5").unwrap()), "0.5");
+        assert_eq!(inspect(&()), "{}");
+        assert_eq!(
+            inspect(&RocStr::from("zero\u{200b}width")),
+            "\"zero\\u(200b)width\""
+        );
+        assert_eq!(
+            inspect(&RocList::from_slice(&[
+                RocStr::from("a"),
+                RocStr::from("b")
+            ])),
+            r#"["a", "b"]"#
+        );
+        assert_eq!(inspect(&RocList::<u8>::empty()), "[]");
+        assert_eq!(inspect(&RocBox::new(1u8)), "<opaque>");
+
+        let ok: RocResult<RocList<u8>, RocStr> = RocResult::ok(RocList::from_slice(&[1, 2]));
+        assert_eq!(inspect(&ok), "(Ok [1, 2])");
+
+        let err: RocResult<RocList<u8>, RocStr> = RocResult::err(RocStr::from("oops"));
+        assert_eq!(inspect(&err), r#"(Err "oops")"#);
+    }
+
     #[test]
     fn safe_send_no_copy() {
         let x = RocStr::from("This is a long string but still unique. Yay!!!");
@@ -449,6 +580,170 @@ mod test_roc_std {
         assert_eq!(sliced.as_slice(), &[1, 2, 3, 5]);
     }
 
+    #[test]
+    fn roc_dict_size() {
+        // { buckets : List Bucket, data : List (k, v), max_bucket_capacity : U64, max_load_factor : F32, shifts : U8 }
+        assert_eq!(core::mem::size_of::<RocDict<RocStr, u8>>(), 64);
+        assert_eq!(core::mem::size_of::<RocSet<RocStr>>(), 64);
+    }
+
+    #[test]
+    fn roc_dict_from_iter() {
+        let dict: RocDict<u8, RocStr> = [(1, "one"), (2, "two"), (1, "uno")]
+            .into_iter()
+            .map(|(key, value)| (key, RocStr::from(value)))
+            .collect();
+
+        // Like Dict.from_list, later entries win, but keep the key's original place.
+        assert_eq!(dict.len(), 2);
+        assert_eq!(dict.get(&1), Some(&RocStr::from("uno")));
+        assert_eq!(dict.get(&3), None);
+        assert_eq!(dict.keys().copied().collect::<Vec<_>>(), [1, 2]);
+    }
+
+    #[test]
+    fn roc_dict_value_before_key() {
+        // Roc puts the more-aligned value before the key in each (k, v) entry.
+        let dict = RocDict::from([(1u8, 10u64), (2, 20), (3, 30)]);
+
+        assert_eq!(
+            dict.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(),
+            [(1, 10), (2, 20), (3, 30)]
+        );
+        assert!(dict.contains_key(&3));
+    }
+
+    #[test]
+    fn roc_dict_insert_into_shared() {
+        let long = RocStr::from("a string long enough to live on the heap");
+        let mut dict = RocDict::from([(RocStr::from("key"), long.clone())]);
+        let shared = dict.clone();
+
+        assert_eq!(
+            dict.insert(RocStr::from("key"), RocStr::from("short")),
+            Some(long.clone())
+        );
+        assert_eq!(dict.insert(RocStr::from("other"), long.clone()), None);
+
+        // The clone still has the original entries.
+        assert_eq!(shared.len(), 1);
+        assert_eq!(shared.get(&RocStr::from("key")), Some(&long));
+        assert_eq!(dict.len(), 2);
+        assert_eq!(dict.get(&RocStr::from("key")), Some(&RocStr::from("short")));
+    }
+
+    #[test]
+    fn roc_dict_eq_ignores_order() {
+        let a = RocDict::from([(1u8, 1u8), (2, 2)]);
+        let b = RocDict::from([(2u8, 2u8), (1, 1)]);
+        let c = RocDict::from([(2u8, 2u8), (1, 3)]);
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(a.cmp(&b), core::cmp::Ordering::Equal);
+        assert_eq!(a.cmp(&c), core::cmp::Ordering::Less);
+        assert_eq!(hash(&a), hash(&b));
+    }
+
+    fn hash<T: core::hash::Hash>(value: &T) -> u64 {
+        use core::hash::Hasher;
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn roc_set_insert() {
+        let mut set = RocSet::from([RocStr::from("a"), RocStr::from("b")]);
+
+        assert!(!set.insert(RocStr::from("a")));
+        assert!(set.insert(RocStr::from("c")));
+        assert!(set.contains(&RocStr::from("c")));
+        assert_eq!(set.len(), 3);
+    }
+
+    // These match the expects at the bottom of Dict.roc, which use the same seed.
+    #[test]
+    fn roc_hasher_matches_roc() {
+        fn hash(add: impl FnOnce(&mut RocHasher)) -> u64 {
+            let mut hasher = RocHasher::with_seed(0x526F_6352_616E_643F);
+            add(&mut hasher);
+            hasher.complete()
+        }
+
+        assert_eq!(hash(|h| h.add_bytes(&[])), 0xD59C59757DBBE6B3);
+        assert_eq!(hash(|h| h.add_bytes(&[0x42])), 0x38CE03D0E61AF963);
+        assert_eq!(hash(|h| h.add_u8(0x42)), 0x38CE03D0E61AF963);
+        assert_eq!(hash(|h| h.add_u16(0xFFFF)), 0xE1CB2FA0D6A64113);
+        assert_eq!(hash(|h| h.add_bytes(&[0x36, 0xA7])), 0x26B8319EDAF81B15);
+        assert_eq!(hash(|h| h.add_u32(0x21EE_2FA9)), 0xA499EFE4C1454D09);
+        assert_eq!(
+            hash(|h| h.add_u128(0xA742_9ABB_3423_DDE1_03C7_4468_8FB1_665D)),
+            0xDD39A206AED64C73
+        );
+        assert_eq!(
+            hash(|h| RocStr::from("abcdefghijklmnopqrstuvwxyz").roc_hash(h)),
+            0x51C59DF5B1D15F40
+        );
+        assert_eq!(
+            hash(|h| h.add_bytes(b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789")),
+            0xD8D0A129D97A4E95
+        );
+        assert_eq!(
+            hash(|h| h.add_bytes(
+                b"1234567890123456789012345678901234567890123456789012345678901234567890"
+            )),
+            0x8188065B44FB4AAA
+        );
+        assert_eq!(hash(|h| h.add_bytes(&[0x77; 100])), 0x47A2A606EADF3378);
+    }
+
+    #[test]
+    fn roc_dict_finds_keys_after_growing() {
+        let mut dict = RocDict::empty();
+
+        for key in 0..1000u64 {
+            assert_eq!(dict.insert(key, key * 2), None);
+        }
+
+        let preallocated: RocDict<u64, u64> = (0..1000u64).map(|key| (key, key * 2)).collect();
+
+        for key in 0..1000u64 {
+            assert_eq!(dict.get(&key), Some(&(key * 2)));
+            assert_eq!(preallocated.get(&key), Some(&(key * 2)));
+        }
+
+        assert_eq!(dict.get(&1000), None);
+        assert_eq!(dict, preallocated);
+    }
+
+    #[test]
+    fn roc_set_roc_hash_ignores_order() {
+        fn hash(set: &RocSet<u8>) -> u64 {
+            let mut hasher = RocHasher::with_seed(0x526F_6352_616E_643F);
+            set.roc_hash(&mut hasher);
+            hasher.complete()
+        }
+
+        let a = RocSet::from([0u8, 1, 2, 3, 4]);
+        let b = RocSet::from([4u8, 3, 2, 1, 0]);
+        let c = RocSet::from([4u8, 3, 2, 1]);
+
+        assert_eq!(hash(&a), hash(&b));
+        assert_ne!(hash(&a), hash(&c));
+    }
+
+    #[test]
+    fn roc_dict_and_set_inspect() {
+        let dict = RocDict::from([(RocStr::from("a"), 1u8), (RocStr::from("b"), 2)]);
+        let set = RocSet::from([1i64, 2]);
+
+        assert_eq!(inspect(&dict), r#"{"a": 1, "b": 2}"#);
+        assert_eq!(inspect(&RocDict::<u8, u8>::empty()), "{}");
+        assert_eq!(inspect(&set), "{1, 2}");
+    }
+
     #[test]
     fn split_whitespace() {
         let example = RocStr::from("chaos is a ladder");
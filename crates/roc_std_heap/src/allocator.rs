@@ -0,0 +1,65 @@
+use std::ffi::c_void;
+
+/// What a host's `roc_alloc`, `roc_realloc` and `roc_dealloc` externs do, so allocators
+/// can wrap one another. A host picks one and forwards its externs to it:
+///
+/// ```ignore
+/// static ALLOCATOR: ArenaAllocator<Malloc> = ArenaAllocator::new(Malloc);
+///
+/// #[no_mangle]
+/// pub unsafe extern "C" fn roc_alloc(size: usize, alignment: u32) -> *mut c_void {
+///     ALLOCATOR.alloc(size, alignment)
+/// }
+/// ```
+///
+/// # Safety
+///
+/// Implementations must return memory that is valid for `size` bytes and aligned to
+/// `alignment`, the way Roc expects from `roc_alloc`.
+pub unsafe trait RocAllocator {
+    /// # Safety
+    ///
+    /// Same as `roc_alloc`.
+    unsafe fn alloc(&self, size: usize, alignment: u32) -> *mut c_void;
+
+    /// # Safety
+    ///
+    /// Same as `roc_realloc`: `ptr` must have come from this allocator.
+    unsafe fn realloc(
+        &self,
+        ptr: *mut c_void,
+        new_size: usize,
+        old_size: usize,
+        alignment: u32,
+    ) -> *mut c_void;
+
+    /// # Safety
+    ///
+    /// Same as `roc_dealloc`: `ptr` must have come from this allocator, and not be used again.
+    unsafe fn dealloc(&self, ptr: *mut c_void, alignment: u32);
+}
+
+/// The C allocator, which is what most hosts use for Roc's allocations.
+///
+/// `malloc` aligns to 16 bytes on the targets Roc supports, which covers every Roc type.
+pub struct Malloc;
+
+unsafe impl RocAllocator for Malloc {
+    unsafe fn alloc(&self, size: usize, _alignment: u32) -> *mut c_void {
+        libc::malloc(size)
+    }
+
+    unsafe fn realloc(
+        &self,
+        ptr: *mut c_void,
+        new_size: usize,
+        _old_size: usize,
+        _alignment: u32,
+    ) -> *mut c_void {
+        libc::realloc(ptr, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut c_void, _alignment: u32) {
+        libc::free(ptr)
+    }
+}
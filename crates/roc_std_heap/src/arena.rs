@@ -0,0 +1,482 @@
+//! A bump allocator for hosts that call into Roc many times, for short amounts of work each.
+//!
+//! An [Arena] runs one host call at a time on its thread. Every Roc allocation made during the
+//! call is bumped out of one mmap, and once the call returns, all of it is freed at once by
+//! resetting the bump pointer. This avoids a `malloc` and `free` per allocation, which adds up
+//! for something like a web server that makes a call per request.
+//!
+//! Resetting is only sound if nothing allocated during the call is still alive, so the arena
+//! counts live allocations. Roc frees everything it allocates once the last reference goes
+//! away, so an allocation that's still live at the end of a call was kept by the host (e.g. in
+//! a value the call returned) or leaked. [Arena::run] reports these as [Escaped], and keeps the
+//! arena's memory around until they've been freed too.
+//!
+//! For the arena to see Roc's allocations, the host's `roc_alloc`, `roc_realloc` and
+//! `roc_dealloc` must go through an [ArenaAllocator]. It uses a fallback allocator for
+//! allocations made outside of [Arena::run], or that don't fit in the arena.
+
+use memmap2::MmapMut;
+use std::{
+    cell::{Cell, RefCell},
+    error::Error,
+    ffi::c_void,
+    fmt, io, ptr,
+    rc::Rc,
+};
+
+use crate::RocAllocator;
+
+thread_local! {
+    /// The region of the arena that's running a call on this thread, if any.
+    static ACTIVE: RefCell<Option<Rc<Region>>> = const { RefCell::new(None) };
+
+    /// Regions that allocations escaped from, which deallocations may still point into.
+    static ESCAPED: RefCell<Vec<Rc<Region>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A per-thread bump region for the Roc allocations made during one host call.
+///
+/// Arenas can't be sent between threads, since Roc's allocations find the arena through
+/// the thread that's running [Arena::run]. Servers typically keep one per worker thread.
+pub struct Arena {
+    region: Rc<Region>,
+}
+
+impl Arena {
+    /// Reserves `max_bytes` of address space for the arena. Like the other heaps in this crate,
+    /// the OS only backs the pages that get used.
+    pub fn new(max_bytes: usize) -> io::Result<Arena> {
+        Region::new(max_bytes).map(|region| Arena {
+            region: Rc::new(region),
+        })
+    }
+
+    /// Runs `call` with this thread's Roc allocations going into the arena, then frees them all.
+    ///
+    /// If any of those allocations are still live when `call` returns, this returns [Escaped]
+    /// instead, which still has the value `call` returned. The arena keeps its memory until
+    /// they've been freed, and then it resets as usual.
+    pub fn run<R>(&self, call: impl FnOnce() -> R) -> Result<R, Escaped<R>> {
+        self.region.reset_if_unused();
+
+        let guard = ActiveGuard::enter(&self.region);
+        let value = call();
+        drop(guard);
+
+        match self.region.live.get() {
+            0 => Ok(value),
+            allocations => Err(Escaped { value, allocations }),
+        }
+    }
+
+    /// The number of bytes the allocations since the last reset have taken up.
+    pub fn bytes_used(&self) -> usize {
+        self.region.used.get()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.region.capacity
+    }
+
+    /// The number of allocations in the arena that haven't been freed yet.
+    pub fn live_allocations(&self) -> usize {
+        self.region.live.get()
+    }
+
+    pub fn in_range<U>(&self, ptr: *const U) -> bool {
+        self.region.in_range(ptr as _)
+    }
+}
+
+/// Makes a region the active one for the duration of a call, and puts things back afterwards,
+/// even if the call panics.
+struct ActiveGuard {
+    region: Rc<Region>,
+    previous: Option<Rc<Region>>,
+}
+
+impl ActiveGuard {
+    fn enter(region: &Rc<Region>) -> Self {
+        let previous = ACTIVE.with(|active| active.replace(Some(region.clone())));
+
+        ActiveGuard {
+            region: region.clone(),
+            previous,
+        }
+    }
+}
+
+impl Drop for ActiveGuard {
+    fn drop(&mut self) {
+        ACTIVE.with(|active| active.replace(self.previous.take()));
+
+        if self.region.live.get() == 0 {
+            self.region.reset();
+        } else {
+            ESCAPED.with(|escaped| {
+                let mut escaped = escaped.borrow_mut();
+
+                if !escaped.iter().any(|other| Rc::ptr_eq(other, &self.region)) {
+                    escaped.push(self.region.clone());
+                }
+            });
+        }
+    }
+}
+
+/// Allocations that were still live at the end of [Arena::run].
+pub struct Escaped<R> {
+    value: R,
+    allocations: usize,
+}
+
+impl<R> Escaped<R> {
+    /// The value the call returned, which may be what's holding on to the allocations.
+    pub fn into_inner(self) -> R {
+        self.value
+    }
+
+    pub fn allocations(&self) -> usize {
+        self.allocations
+    }
+}
+
+impl<R> fmt::Debug for Escaped<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Escaped")
+            .field("allocations", &self.allocations)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R> fmt::Display for Escaped<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocation(s) escaped the arena they were made in",
+            self.allocations
+        )
+    }
+}
+
+impl<R> Error for Escaped<R> {}
+
+/// Sends Roc's allocations to the arena running a call on the current thread,
+/// and everything else to `fallback`.
+pub struct ArenaAllocator<A> {
+    fallback: A,
+}
+
+impl<A> ArenaAllocator<A> {
+    pub const fn new(fallback: A) -> Self {
+        ArenaAllocator { fallback }
+    }
+}
+
+unsafe impl<A: RocAllocator> RocAllocator for ArenaAllocator<A> {
+    unsafe fn alloc(&self, size: usize, alignment: u32) -> *mut c_void {
+        let from_arena = ACTIVE.with(|active| {
+            active
+                .borrow()
+                .as_ref()
+                .and_then(|region| region.bump(size, alignment))
+        });
+
+        match from_arena {
+            Some(ptr) => ptr,
+            None => self.fallback.alloc(size, alignment),
+        }
+    }
+
+    unsafe fn realloc(
+        &self,
+        ptr: *mut c_void,
+        new_size: usize,
+        old_size: usize,
+        alignment: u32,
+    ) -> *mut c_void {
+        let in_active = ACTIVE.with(|active| {
+            active
+                .borrow()
+                .as_ref()
+                .filter(|region| region.in_range(ptr))
+                .map(|region| region.realloc(ptr, new_size, old_size, alignment))
+        });
+
+        match in_active {
+            Some(Some(new_ptr)) => new_ptr,
+            Some(None) => self.move_to_fallback(ptr, new_size, old_size, alignment),
+            None if in_escaped_region(ptr) => {
+                // The arena this came from isn't running a call, so don't grow into it.
+                self.move_to_fallback(ptr, new_size, old_size, alignment)
+            }
+            None => self.fallback.realloc(ptr, new_size, old_size, alignment),
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut c_void, alignment: u32) {
+        if !release(ptr) {
+            self.fallback.dealloc(ptr, alignment)
+        }
+    }
+}
+
+impl<A: RocAllocator> ArenaAllocator<A> {
+    unsafe fn move_to_fallback(
+        &self,
+        ptr: *mut c_void,
+        new_size: usize,
+        old_size: usize,
+        alignment: u32,
+    ) -> *mut c_void {
+        let new_ptr = self.fallback.alloc(new_size, alignment);
+
+        ptr::copy_nonoverlapping(ptr as *const u8, new_ptr as *mut u8, old_size.min(new_size));
+        release(ptr);
+
+        new_ptr
+    }
+}
+
+fn in_escaped_region(ptr: *mut c_void) -> bool {
+    ESCAPED.with(|escaped| escaped.borrow().iter().any(|region| region.in_range(ptr)))
+}
+
+/// Frees `ptr` if it's in one of this thread's arenas, returning whether it was.
+fn release(ptr: *mut c_void) -> bool {
+    let in_active = ACTIVE.with(|active| match active.borrow().as_ref() {
+        Some(region) if region.in_range(ptr) => {
+            region.release();
+            true
+        }
+        _ => false,
+    });
+
+    in_active
+        || ESCAPED.with(|escaped| {
+            let mut escaped = escaped.borrow_mut();
+
+            match escaped.iter().position(|region| region.in_range(ptr)) {
+                Some(index) => {
+                    let region = &escaped[index];
+                    region.release();
+
+                    // Everything that escaped is gone now, so the arena can start over.
+                    if region.live.get() == 0 {
+                        region.reset();
+                        escaped.swap_remove(index);
+                    }
+
+                    true
+                }
+                None => false,
+            }
+        })
+}
+
+struct Region {
+    // Keeps the mapping alive; `start` points into it.
+    _data: MmapMut,
+    start: *mut u8,
+    capacity: usize,
+    used: Cell<usize>,
+    /// The offset of the most recent allocation, which can grow in place.
+    last: Cell<usize>,
+    live: Cell<usize>,
+}
+
+impl Region {
+    fn new(capacity: usize) -> io::Result<Region> {
+        debug_assert!(capacity > 0);
+
+        let mut data = MmapMut::map_anon(capacity)?;
+        let start = data.as_mut_ptr();
+
+        Ok(Region {
+            _data: data,
+            start,
+            capacity,
+            used: Cell::new(0),
+            last: Cell::new(0),
+            live: Cell::new(0),
+        })
+    }
+
+    fn in_range(&self, ptr: *const c_void) -> bool {
+        let start = self.start as usize;
+        let ptr = ptr as usize;
+
+        start <= ptr && ptr < start + self.capacity
+    }
+
+    fn bump(&self, size: usize, alignment: u32) -> Option<*mut c_void> {
+        // The mmap is page-aligned, so aligning the offset aligns the pointer.
+        let alignment = (alignment as usize).max(1);
+        let offset = self.used.get().checked_next_multiple_of(alignment)?;
+        let end = offset.checked_add(size)?;
+
+        if end > self.capacity {
+            return None;
+        }
+
+        self.used.set(end);
+        self.last.set(offset);
+        self.live.set(self.live.get() + 1);
+
+        Some(unsafe { self.start.add(offset) } as *mut c_void)
+    }
+
+    /// Returns `None` if the arena doesn't have room for `new_size` bytes.
+    unsafe fn realloc(
+        &self,
+        ptr: *mut c_void,
+        new_size: usize,
+        old_size: usize,
+        alignment: u32,
+    ) -> Option<*mut c_void> {
+        let offset = ptr as usize - self.start as usize;
+
+        if offset == self.last.get() {
+            // This is the most recent allocation, so it can grow (or shrink) in place.
+            let end = offset.checked_add(new_size)?;
+
+            if end > self.capacity {
+                return None;
+            }
+
+            self.used.set(end);
+
+            return Some(ptr);
+        }
+
+        let new_ptr = self.bump(new_size, alignment)?;
+
+        ptr::copy_nonoverlapping(ptr as *const u8, new_ptr as *mut u8, old_size.min(new_size));
+        self.release();
+
+        Some(new_ptr)
+    }
+
+    fn release(&self) {
+        debug_assert!(
+            self.live.get() > 0,
+            "freed more arena allocations than were made"
+        );
+
+        self.live.set(self.live.get() - 1);
+    }
+
+    fn reset(&self) {
+        self.used.set(0);
+        self.last.set(0);
+    }
+
+    fn reset_if_unused(&self) {
+        if self.live.get() == 0 {
+            self.reset();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Malloc;
+
+    static ALLOCATOR: ArenaAllocator<Malloc> = ArenaAllocator::new(Malloc);
+
+    #[test]
+    fn frees_everything_after_a_call() {
+        let arena = Arena::new(4096).unwrap();
+
+        let answer = arena
+            .run(|| unsafe {
+                let a = ALLOCATOR.alloc(24, 8);
+                let b = ALLOCATOR.alloc(3, 1);
+                let c = ALLOCATOR.alloc(16, 16);
+
+                assert!(arena.in_range(a) && arena.in_range(b) && arena.in_range(c));
+                assert_eq!(c as usize % 16, 0);
+                assert_eq!(arena.live_allocations(), 3);
+
+                ALLOCATOR.dealloc(b, 1);
+                ALLOCATOR.dealloc(a, 8);
+                ALLOCATOR.dealloc(c, 16);
+
+                42
+            })
+            .unwrap();
+
+        assert_eq!(answer, 42);
+        assert_eq!(arena.bytes_used(), 0);
+    }
+
+    #[test]
+    fn reports_escapes_and_resets_once_they_are_freed() {
+        let arena = Arena::new(4096).unwrap();
+
+        let escaped = arena.run(|| unsafe { ALLOCATOR.alloc(8, 8) }).unwrap_err();
+
+        assert_eq!(escaped.allocations(), 1);
+
+        let ptr = escaped.into_inner();
+        assert!(arena.in_range(ptr));
+        assert!(arena.bytes_used() > 0);
+
+        // Freeing it outside of a call still goes back to the arena.
+        unsafe { ALLOCATOR.dealloc(ptr, 8) };
+        assert_eq!(arena.live_allocations(), 0);
+        assert_eq!(arena.bytes_used(), 0);
+    }
+
+    #[test]
+    fn realloc_grows_the_last_allocation_in_place() {
+        let arena = Arena::new(4096).unwrap();
+
+        arena
+            .run(|| unsafe {
+                let a = ALLOCATOR.alloc(8, 8);
+                (a as *mut u64).write(7);
+
+                let grown = ALLOCATOR.realloc(a, 64, 8, 8);
+                assert_eq!(a, grown);
+
+                let b = ALLOCATOR.alloc(8, 8);
+                let moved = ALLOCATOR.realloc(a, 128, 64, 8);
+                assert_ne!(a, moved);
+                assert_eq!((moved as *mut u64).read(), 7);
+
+                ALLOCATOR.dealloc(b, 8);
+                ALLOCATOR.dealloc(moved, 8);
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn falls_back_when_full_or_outside_a_call() {
+        let arena = Arena::new(64).unwrap();
+
+        let outside = unsafe { ALLOCATOR.alloc(8, 8) };
+        assert!(!arena.in_range(outside));
+
+        arena
+            .run(|| unsafe {
+                let big = ALLOCATOR.alloc(128, 8);
+                assert!(!arena.in_range(big));
+
+                let small = ALLOCATOR.alloc(32, 8);
+                (small as *mut u64).write(7);
+                assert!(arena.in_range(small));
+
+                // Doesn't fit in the arena anymore, so it moves out.
+                let moved = ALLOCATOR.realloc(small, 256, 32, 8);
+                assert!(!arena.in_range(moved));
+                assert_eq!((moved as *mut u64).read(), 7);
+
+                ALLOCATOR.dealloc(big, 8);
+                ALLOCATOR.dealloc(moved, 8);
+                ALLOCATOR.dealloc(outside, 8);
+            })
+            .unwrap();
+    }
+}
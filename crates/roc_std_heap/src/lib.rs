@@ -7,6 +7,17 @@
 //!
 //! To make checking resource types quick, all heaps allocate to a single mmap.
 //! Then a simple range check on a pointer can confirm is a pointer is into a specific heap.
+//!
+//! It also has allocators a host can use for Roc's own allocations, like the per-call [Arena],
+//! or the [TrackingAllocator] for finding leaks and double frees.
+
+mod allocator;
+mod arena;
+mod tracking;
+
+pub use allocator::{Malloc, RocAllocator};
+pub use arena::{Arena, ArenaAllocator, Escaped};
+pub use tracking::{Allocation, CallStats, Problem, Report, Stats, TrackingAllocator};
 
 use memmap2::MmapMut;
 use roc_std::RocBox;
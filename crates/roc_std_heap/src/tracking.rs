@@ -0,0 +1,445 @@
+//! An allocator for finding refcounting bugs in Roc programs and hosts.
+//!
+//! [TrackingAllocator] wraps another allocator and records every live allocation along with
+//! its size and alignment. A dec that's missing leaves an allocation live after the host is
+//! done with everything, and an extra one frees it twice. Both show up in the [Report], along
+//! with frees whose alignment doesn't match what the memory was allocated with.
+//!
+//! Tracking takes a lock on every allocation, so it's meant for tests and debug builds of a
+//! host rather than for serving production traffic.
+
+use std::{
+    cell::Cell,
+    collections::{BTreeMap, BTreeSet},
+    ffi::c_void,
+    fmt,
+    sync::{Mutex, MutexGuard},
+};
+
+use crate::RocAllocator;
+
+thread_local! {
+    /// What the call being measured with [TrackingAllocator::measure] on this thread has done.
+    static CALL: Cell<Option<CallStats>> = const { Cell::new(None) };
+}
+
+/// Records every allocation made through `inner`, and checks every free against those records.
+///
+/// Problems don't panic, since that would unwind through Roc. Instead, they're recorded for
+/// [TrackingAllocator::report], and frees of memory that isn't live are skipped rather than
+/// passed on to `inner`.
+pub struct TrackingAllocator<A> {
+    inner: A,
+    state: Mutex<State>,
+}
+
+struct State {
+    live: BTreeMap<usize, Allocation>,
+    /// Pointers that have been freed and not handed out again, to tell double frees
+    /// apart from pointers this allocator never saw.
+    freed: BTreeSet<usize>,
+    problems: Vec<Problem>,
+    stats: Stats,
+}
+
+impl<A> TrackingAllocator<A> {
+    pub const fn new(inner: A) -> Self {
+        TrackingAllocator {
+            inner,
+            state: Mutex::new(State {
+                live: BTreeMap::new(),
+                freed: BTreeSet::new(),
+                problems: Vec::new(),
+                stats: Stats {
+                    bytes_live: 0,
+                    peak_bytes_live: 0,
+                    live_allocations: 0,
+                    allocations: 0,
+                    reallocations: 0,
+                    deallocations: 0,
+                },
+            }),
+        }
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.lock().stats
+    }
+
+    /// Runs `call`, and counts what this thread allocated during it.
+    pub fn measure<R>(&self, call: impl FnOnce() -> R) -> (R, CallStats) {
+        let outer = CALL.with(|stats| stats.replace(Some(CallStats::default())));
+        let value = call();
+        let stats = CALL.with(|stats| stats.replace(outer)).unwrap_or_default();
+
+        (value, stats)
+    }
+
+    /// Everything that's still live, plus every problem seen so far. Call this once the host
+    /// has dropped all of its Roc values, or those will show up as leaks too.
+    pub fn report(&self) -> Report {
+        let state = self.lock();
+
+        Report {
+            leaks: state.live.values().copied().collect(),
+            problems: state.problems.clone(),
+            stats: state.stats,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        // A panic while tracking leaves the records as they were, so they're still usable.
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+unsafe impl<A: RocAllocator> RocAllocator for TrackingAllocator<A> {
+    unsafe fn alloc(&self, size: usize, alignment: u32) -> *mut c_void {
+        // Keep the lock while calling `inner`, so another thread can't be handed this
+        // address before it's recorded.
+        let mut state = self.lock();
+        let ptr = self.inner.alloc(size, alignment);
+
+        if !ptr.is_null() {
+            state.record(ptr, size, alignment);
+            state.stats.allocations += 1;
+            count_call(|call| call.allocations += 1);
+        }
+
+        ptr
+    }
+
+    unsafe fn realloc(
+        &self,
+        ptr: *mut c_void,
+        new_size: usize,
+        old_size: usize,
+        alignment: u32,
+    ) -> *mut c_void {
+        let mut state = self.lock();
+
+        if !state.check_live(ptr, alignment) {
+            return std::ptr::null_mut();
+        }
+
+        let allocated = state.forget(ptr);
+
+        if allocated.size != old_size {
+            state.problems.push(Problem::SizeMismatch {
+                ptr: ptr as usize,
+                allocated: allocated.size,
+                given: old_size,
+            });
+        }
+
+        let new_ptr = self.inner.realloc(ptr, new_size, old_size, alignment);
+
+        if new_ptr.is_null() {
+            // The old allocation is still there.
+            state.record(ptr, allocated.size, allocated.alignment);
+        } else {
+            state.freed.insert(ptr as usize);
+            state.record(new_ptr, new_size, alignment);
+            state.stats.reallocations += 1;
+            count_call(|call| call.reallocations += 1);
+        }
+
+        new_ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut c_void, alignment: u32) {
+        let mut state = self.lock();
+
+        if !state.check_live(ptr, alignment) {
+            return;
+        }
+
+        state.forget(ptr);
+        state.freed.insert(ptr as usize);
+        state.stats.deallocations += 1;
+        count_call(|call| call.deallocations += 1);
+
+        self.inner.dealloc(ptr, alignment);
+    }
+}
+
+impl State {
+    fn record(&mut self, ptr: *mut c_void, size: usize, alignment: u32) {
+        let ptr = ptr as usize;
+
+        self.freed.remove(&ptr);
+        self.live.insert(
+            ptr,
+            Allocation {
+                ptr,
+                size,
+                alignment,
+            },
+        );
+
+        self.stats.live_allocations += 1;
+        self.stats.bytes_live += size;
+        self.stats.peak_bytes_live = self.stats.peak_bytes_live.max(self.stats.bytes_live);
+        count_call(|call| call.bytes_allocated += size);
+    }
+
+    fn forget(&mut self, ptr: *mut c_void) -> Allocation {
+        let allocation = self
+            .live
+            .remove(&(ptr as usize))
+            .expect("checked that the allocation is live");
+
+        self.stats.live_allocations -= 1;
+        self.stats.bytes_live -= allocation.size;
+
+        allocation
+    }
+
+    /// Records a problem unless `ptr` is live. A mismatched alignment is recorded,
+    /// but doesn't stop the memory from being freed.
+    fn check_live(&mut self, ptr: *mut c_void, alignment: u32) -> bool {
+        let key = ptr as usize;
+
+        match self.live.get(&key) {
+            Some(allocation) => {
+                if allocation.alignment != alignment {
+                    self.problems.push(Problem::AlignmentMismatch {
+                        ptr: key,
+                        allocated: allocation.alignment,
+                        given: alignment,
+                    });
+                }
+
+                true
+            }
+            None if self.freed.contains(&key) => {
+                self.problems.push(Problem::DoubleFree { ptr: key });
+
+                false
+            }
+            None => {
+                self.problems.push(Problem::UnknownPointer { ptr: key });
+
+                false
+            }
+        }
+    }
+}
+
+fn count_call(update: impl FnOnce(&mut CallStats)) {
+    CALL.with(|call| {
+        if let Some(mut stats) = call.get() {
+            update(&mut stats);
+            call.set(Some(stats));
+        }
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Allocation {
+    pub ptr: usize,
+    pub size: usize,
+    pub alignment: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Problem {
+    /// Freed (or reallocated) memory that had already been freed.
+    DoubleFree { ptr: usize },
+    /// Freed (or reallocated) a pointer that never came from this allocator.
+    UnknownPointer { ptr: usize },
+    /// Freed with a different alignment than the memory was allocated with.
+    AlignmentMismatch {
+        ptr: usize,
+        allocated: u32,
+        given: u32,
+    },
+    /// Reallocated with an `old_size` that isn't what was allocated.
+    SizeMismatch {
+        ptr: usize,
+        allocated: usize,
+        given: usize,
+    },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::DoubleFree { ptr } => write!(f, "double free of {ptr:#x}"),
+            Problem::UnknownPointer { ptr } => {
+                write!(f, "free of {ptr:#x}, which was never allocated")
+            }
+            Problem::AlignmentMismatch {
+                ptr,
+                allocated,
+                given,
+            } => write!(
+                f,
+                "{ptr:#x} was allocated with alignment {allocated}, but freed with alignment {given}"
+            ),
+            Problem::SizeMismatch {
+                ptr,
+                allocated,
+                given,
+            } => write!(
+                f,
+                "{ptr:#x} was allocated with {allocated} bytes, but reallocated as if it had {given}"
+            ),
+        }
+    }
+}
+
+/// Totals for everything that has gone through a [TrackingAllocator].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub bytes_live: usize,
+    pub peak_bytes_live: usize,
+    pub live_allocations: usize,
+    pub allocations: usize,
+    pub reallocations: usize,
+    pub deallocations: usize,
+}
+
+/// What one thread did during [TrackingAllocator::measure].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CallStats {
+    pub allocations: usize,
+    pub reallocations: usize,
+    pub deallocations: usize,
+    /// Includes the new sizes of reallocations.
+    pub bytes_allocated: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+    pub leaks: Vec<Allocation>,
+    pub problems: Vec<Problem>,
+    pub stats: Stats,
+}
+
+impl Report {
+    pub fn is_clean(&self) -> bool {
+        self.leaks.is_empty() && self.problems.is_empty()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stats = &self.stats;
+
+        writeln!(
+            f,
+            "{} allocations, {} reallocations, {} deallocations; peak of {} bytes live",
+            stats.allocations, stats.reallocations, stats.deallocations, stats.peak_bytes_live
+        )?;
+
+        if !self.leaks.is_empty() {
+            writeln!(
+                f,
+                "{} allocation(s) leaked, {} bytes in total:",
+                self.leaks.len(),
+                stats.bytes_live
+            )?;
+
+            for leak in &self.leaks {
+                writeln!(
+                    f,
+                    "    {:#x}: {} bytes, alignment {}",
+                    leak.ptr, leak.size, leak.alignment
+                )?;
+            }
+        }
+
+        for problem in &self.problems {
+            writeln!(f, "{problem}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Malloc;
+
+    #[test]
+    fn reports_leaks() {
+        let allocator = TrackingAllocator::new(Malloc);
+
+        unsafe {
+            let kept = allocator.alloc(24, 8);
+            let freed = allocator.alloc(16, 8);
+            allocator.dealloc(freed, 8);
+
+            let report = allocator.report();
+            assert!(!report.is_clean());
+            assert_eq!(
+                report.leaks,
+                vec![Allocation {
+                    ptr: kept as usize,
+                    size: 24,
+                    alignment: 8
+                }]
+            );
+            assert_eq!(report.stats.bytes_live, 24);
+            assert_eq!(report.stats.peak_bytes_live, 40);
+
+            allocator.dealloc(kept, 8);
+        }
+
+        assert!(allocator.report().is_clean());
+    }
+
+    #[test]
+    fn reports_double_frees_and_mismatched_alignments() {
+        let allocator = TrackingAllocator::new(Malloc);
+
+        unsafe {
+            let ptr = allocator.alloc(8, 8);
+            allocator.dealloc(ptr, 4);
+
+            // Not passed on to malloc, so this is safe.
+            allocator.dealloc(ptr, 8);
+        }
+
+        let problems = allocator.report().problems;
+        assert!(matches!(
+            problems.as_slice(),
+            [
+                Problem::AlignmentMismatch {
+                    allocated: 8,
+                    given: 4,
+                    ..
+                },
+                Problem::DoubleFree { .. }
+            ]
+        ));
+    }
+
+    #[test]
+    fn tracks_reallocations() {
+        let allocator = TrackingAllocator::new(Malloc);
+
+        let (_, call) = allocator.measure(|| unsafe {
+            let ptr = allocator.alloc(8, 8);
+            let ptr = allocator.realloc(ptr, 64, 8, 8);
+            assert_eq!(allocator.stats().bytes_live, 64);
+
+            allocator.dealloc(ptr, 8);
+        });
+
+        assert_eq!(
+            call,
+            CallStats {
+                allocations: 1,
+                reallocations: 1,
+                deallocations: 1,
+                bytes_allocated: 72,
+            }
+        );
+        assert!(allocator.report().is_clean());
+    }
+}
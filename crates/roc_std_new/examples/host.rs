@@ -0,0 +1,68 @@
+//! A host for a platform with two hosted functions, `Stdout.line!` and `Stdin.line!`.
+//!
+//! A real host would link against a compiled Roc app and call its entrypoint. Here,
+//! `roc_main` stands in for that app, calling the hosted functions the way Roc code does.
+
+use core::ffi::c_void;
+use core::ptr;
+use std::io::BufRead;
+use std::process::ExitCode;
+
+use roc_std_new::{RocEvent, RocHost, RocOps, RocStr};
+
+fn main() -> ExitCode {
+    let host = RocHost::builder()
+        .hosted("Stdout.line!", |_, line: &RocStr| {
+            println!("{}", line.as_str());
+        })
+        .hosted("Stdin.line!", |roc_ops, _: &()| {
+            let mut line = String::new();
+            let _ = std::io::stdin().lock().read_line(&mut line);
+
+            RocStr::from_str(line.trim_end_matches(['\r', '\n']), roc_ops)
+        })
+        .build();
+
+    let result = host.call(|roc_ops| unsafe { roc_main(roc_ops) });
+
+    for event in host.take_events() {
+        match event {
+            RocEvent::Dbg(message) => eprintln!("[dbg] {message}"),
+            RocEvent::ExpectFailed(message) => eprintln!("[expect failed] {message}"),
+        }
+    }
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(crash) => {
+            eprintln!("{crash}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// What `main! = |_| { name = Stdin.line!(); Stdout.line!("Hello, ${name}!") }` compiles to, roughly.
+unsafe fn roc_main(roc_ops: &RocOps) {
+    // Hosted functions are sorted by name, so Stdin.line! comes first.
+    let mut name = RocStr::empty();
+    roc_ops.call_hosted(0, (&mut name as *mut RocStr).cast(), ptr::null_mut());
+
+    roc_ops.dbg(name.as_str());
+
+    if name.is_empty() {
+        roc_ops.crash("Expected a name on stdin");
+        name.decref(roc_ops);
+        return;
+    }
+
+    let mut greeting = RocStr::from_str(&format!("Hello, {}!", name.as_str()), roc_ops);
+    let mut unit = ();
+    roc_ops.call_hosted(
+        1,
+        (&mut unit as *mut ()).cast::<c_void>(),
+        (&mut greeting as *mut RocStr).cast(),
+    );
+
+    greeting.decref(roc_ops);
+    name.decref(roc_ops);
+}
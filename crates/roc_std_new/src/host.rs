@@ -0,0 +1,502 @@
+//! A safe way to embed Roc in a Rust host.
+//!
+//! [RocHost] owns a [RocOps] and fills it in with Rust implementations:
+//! allocation goes through `std::alloc`, `dbg` and failed `expect`s are collected
+//! as [RocEvent]s, and hosted functions are ordinary Rust closures.
+//!
+//! A crash gets recorded, and [RocHost::call] returns it as an `Err`. `roc_crashed` returns to
+//! the Roc code that called it, like it does in the interpreter's hosts, so this only works for
+//! Roc code that gets back to the host after a crash. Code compiled by the dev backend traps
+//! instead, so hosts for compiled apps need to `longjmp` out of `roc_crashed` themselves.
+
+use core::ffi::c_void;
+use core::mem;
+use core::ptr::{self, NonNull};
+use std::alloc::{self, Layout};
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt;
+
+use crate::{
+    HostedFn, HostedFunctions, RocAlloc, RocCrashed, RocDbg, RocDealloc, RocExpectFailed, RocOps,
+    RocRealloc, RocRefcounted,
+};
+
+type HostedClosure = Box<dyn Fn(&RocOps, *mut c_void, *mut c_void)>;
+
+/// Something the Roc program reported while it ran.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RocEvent {
+    Dbg(String),
+    ExpectFailed(String),
+}
+
+/// The Roc program crashed, e.g. by calling `crash` or overflowing an integer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RocCrash {
+    pub message: String,
+}
+
+impl fmt::Display for RocCrash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Roc crashed: {}", self.message)
+    }
+}
+
+impl Error for RocCrash {}
+
+/// Collects the hosted functions for a [RocHost].
+#[derive(Default)]
+pub struct RocHostBuilder {
+    hosted: Vec<(String, HostedClosure)>,
+}
+
+impl RocHostBuilder {
+    /// Implement the hosted function `name` (e.g. `"Stdout.line!"`) with `f`.
+    ///
+    /// `f` gets the hosted function's arguments by reference, since Roc keeps ownership of them.
+    /// `A` and `R` must match the Roc types of the arguments and the return value; glue-generated
+    /// types do. For a function with several arguments, `A` is a `#[repr(C)]` struct of them.
+    ///
+    /// `f` must not panic: the panic can't unwind through Roc, so it aborts the process. To
+    /// give up on the Roc program, call [RocOps::crash] and return.
+    ///
+    /// Functions can be added in any order. Roc refers to them by their index after sorting by
+    /// name, and [RocHostBuilder::build] takes care of that.
+    pub fn hosted<A, R, F>(mut self, name: impl Into<String>, f: F) -> Self
+    where
+        A: RocRefcounted,
+        R: RocRefcounted,
+        F: Fn(&RocOps, &A) -> R + 'static,
+    {
+        let closure = move |roc_ops: &RocOps, ret: *mut c_void, args: *mut c_void| {
+            // Zero-sized arguments and return values may come with dangling or null pointers.
+            let args = if mem::size_of::<A>() == 0 {
+                NonNull::<A>::dangling().as_ptr()
+            } else {
+                args.cast::<A>()
+            };
+            let answer = f(roc_ops, unsafe { &*args });
+
+            if mem::size_of::<R>() == 0 {
+                mem::forget(answer);
+            } else {
+                unsafe { ptr::write(ret.cast::<R>(), answer) };
+            }
+        };
+
+        self.hosted.push((name.into(), Box::new(closure)));
+        self
+    }
+
+    pub fn build(mut self) -> RocHost {
+        assert!(
+            self.hosted.len() <= MAX_HOSTED_FNS,
+            "a RocHost supports at most {MAX_HOSTED_FNS} hosted functions, but {} were given",
+            self.hosted.len()
+        );
+
+        self.hosted.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        if let Some(pair) = self.hosted.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+            panic!("the hosted function {} was given twice", pair[0].0);
+        }
+
+        let (names, closures): (Vec<_>, Vec<_>) = self.hosted.into_iter().unzip();
+        let fns: Vec<HostedFn> = (0..closures.len())
+            .map(|index| TRAMPOLINES[index / 16][index % 16])
+            .collect();
+
+        let mut inner = Box::new(HostInner {
+            ops: RocOps {
+                env: ptr::null_mut(),
+                roc_alloc: host_alloc,
+                roc_dealloc: host_dealloc,
+                roc_realloc: host_realloc,
+                roc_dbg: host_dbg,
+                roc_expect_failed: host_expect_failed,
+                roc_crashed: host_crashed,
+                hosted_fns: HostedFunctions {
+                    count: fns.len() as u32,
+                    fns: fns.as_ptr(),
+                },
+            },
+            names,
+            closures,
+            fns,
+            events: RefCell::new(Vec::new()),
+            crash: RefCell::new(None),
+        });
+
+        // The box never moves, so this stays valid for as long as the host is alive.
+        inner.ops.env = &*inner as *const HostInner as *mut c_void;
+
+        RocHost { inner }
+    }
+}
+
+/// A [RocOps] backed by safe Rust, for calling into a Roc program.
+///
+/// The host must outlive every Roc value created with its [RocOps], like any other `RocOps`.
+pub struct RocHost {
+    inner: Box<HostInner>,
+}
+
+struct HostInner {
+    ops: RocOps,
+    names: Vec<String>,
+    closures: Vec<HostedClosure>,
+    // Keeps `ops.hosted_fns` alive.
+    #[allow(dead_code)]
+    fns: Vec<HostedFn>,
+    events: RefCell<Vec<RocEvent>>,
+    crash: RefCell<Option<RocCrash>>,
+}
+
+impl RocHost {
+    /// A host without any hosted functions.
+    pub fn new() -> Self {
+        Self::builder().build()
+    }
+
+    pub fn builder() -> RocHostBuilder {
+        RocHostBuilder::default()
+    }
+
+    pub fn ops(&self) -> &RocOps {
+        &self.inner.ops
+    }
+
+    /// Calls into Roc, turning a crash into an `Err`.
+    ///
+    /// What `f` returns after a crash may not be a valid value, so it gets leaked instead of
+    /// dropped.
+    pub fn call<R>(&self, f: impl FnOnce(&RocOps) -> R) -> Result<R, RocCrash> {
+        let answer = f(&self.inner.ops);
+
+        match self.inner.crash.take() {
+            None => Ok(answer),
+            Some(crash) => {
+                mem::forget(answer);
+                Err(crash)
+            }
+        }
+    }
+
+    /// Removes and returns everything reported since the last time this was called.
+    pub fn take_events(&self) -> Vec<RocEvent> {
+        self.inner.events.take()
+    }
+
+    /// The index Roc uses for the hosted function `name`.
+    pub fn hosted_index(&self, name: &str) -> Option<usize> {
+        self.inner
+            .names
+            .binary_search_by(|other| other.as_str().cmp(name))
+            .ok()
+    }
+}
+
+impl Default for RocHost {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for RocHost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RocHost")
+            .field("hosted", &self.inner.names)
+            .finish_non_exhaustive()
+    }
+}
+
+unsafe fn host_inner<'a>(env: *mut c_void) -> &'a HostInner {
+    &*(env as *const HostInner)
+}
+
+unsafe fn message(utf8_bytes: *const u8, len: usize) -> String {
+    String::from_utf8_lossy(core::slice::from_raw_parts(utf8_bytes, len)).into_owned()
+}
+
+// Allocations are preceded by their size, since roc_dealloc doesn't pass it in.
+fn header_size(alignment: usize) -> usize {
+    alignment.max(mem::size_of::<usize>())
+}
+
+fn layout(alignment: usize, length: usize) -> Layout {
+    let alignment = header_size(alignment);
+
+    Layout::from_size_align(length + alignment, alignment).expect("Roc asked for an invalid layout")
+}
+
+unsafe fn finish_alloc(base: *mut u8, layout: Layout, alignment: usize) -> *mut c_void {
+    if base.is_null() {
+        alloc::handle_alloc_error(layout);
+    }
+
+    let data = base.add(header_size(alignment));
+    data.cast::<usize>().sub(1).write(layout.size());
+
+    data.cast()
+}
+
+unsafe fn allocation(ptr: *mut c_void, alignment: usize) -> (*mut u8, Layout) {
+    let size = ptr.cast::<usize>().sub(1).read();
+    let alignment = header_size(alignment);
+
+    (
+        ptr.cast::<u8>().sub(alignment),
+        Layout::from_size_align_unchecked(size, alignment),
+    )
+}
+
+extern "C" fn host_alloc(args: *mut RocAlloc, _env: *mut c_void) {
+    unsafe {
+        let args = &mut *args;
+        let layout = layout(args.alignment, args.length);
+
+        args.answer = finish_alloc(alloc::alloc(layout), layout, args.alignment);
+    }
+}
+
+extern "C" fn host_dealloc(args: *mut RocDealloc, _env: *mut c_void) {
+    unsafe {
+        let args = &*args;
+        let (base, layout) = allocation(args.ptr, args.alignment);
+
+        alloc::dealloc(base, layout);
+    }
+}
+
+extern "C" fn host_realloc(args: *mut RocRealloc, _env: *mut c_void) {
+    unsafe {
+        let args = &mut *args;
+        let (base, old_layout) = allocation(args.answer, args.alignment);
+        let new_layout = layout(args.alignment, args.new_length);
+
+        args.answer = finish_alloc(
+            alloc::realloc(base, old_layout, new_layout.size()),
+            new_layout,
+            args.alignment,
+        );
+    }
+}
+
+extern "C" fn host_dbg(args: *const RocDbg, env: *mut c_void) {
+    unsafe {
+        let args = &*args;
+        let message = message(args.utf8_bytes, args.len);
+
+        host_inner(env)
+            .events
+            .borrow_mut()
+            .push(RocEvent::Dbg(message));
+    }
+}
+
+extern "C" fn host_expect_failed(args: *const RocExpectFailed, env: *mut c_void) {
+    unsafe {
+        let args = &*args;
+        let message = message(args.utf8_bytes, args.len);
+
+        host_inner(env)
+            .events
+            .borrow_mut()
+            .push(RocEvent::ExpectFailed(message));
+    }
+}
+
+extern "C" fn host_crashed(args: *const RocCrashed, env: *mut c_void) {
+    unsafe {
+        let args = &*args;
+        let mut crash = host_inner(env).crash.borrow_mut();
+
+        // The Roc code keeps running until it gets back to the host, so it can crash again.
+        // The first crash is the one that explains what went wrong.
+        if crash.is_none() {
+            *crash = Some(RocCrash {
+                message: message(args.utf8_bytes, args.len),
+            });
+        }
+    }
+}
+
+/// `HostedFn`s are plain function pointers, so each one needs to know which closure it's for.
+extern "C" fn trampoline<const INDEX: usize>(
+    roc_ops: *const RocOps,
+    ret: *mut c_void,
+    args: *mut c_void,
+) {
+    unsafe {
+        let roc_ops = &*roc_ops;
+
+        (host_inner(roc_ops.env).closures[INDEX])(roc_ops, ret, args)
+    }
+}
+
+const MAX_HOSTED_FNS: usize = 8 * 16;
+
+macro_rules! trampoline_row {
+    ($row:literal) => {
+        [
+            trampoline::<{ $row * 16 }>,
+            trampoline::<{ $row * 16 + 1 }>,
+            trampoline::<{ $row * 16 + 2 }>,
+            trampoline::<{ $row * 16 + 3 }>,
+            trampoline::<{ $row * 16 + 4 }>,
+            trampoline::<{ $row * 16 + 5 }>,
+            trampoline::<{ $row * 16 + 6 }>,
+            trampoline::<{ $row * 16 + 7 }>,
+            trampoline::<{ $row * 16 + 8 }>,
+            trampoline::<{ $row * 16 + 9 }>,
+            trampoline::<{ $row * 16 + 10 }>,
+            trampoline::<{ $row * 16 + 11 }>,
+            trampoline::<{ $row * 16 + 12 }>,
+            trampoline::<{ $row * 16 + 13 }>,
+            trampoline::<{ $row * 16 + 14 }>,
+            trampoline::<{ $row * 16 + 15 }>,
+        ]
+    };
+}
+
+static TRAMPOLINES: [[HostedFn; 16]; MAX_HOSTED_FNS / 16] = [
+    trampoline_row!(0),
+    trampoline_row!(1),
+    trampoline_row!(2),
+    trampoline_row!(3),
+    trampoline_row!(4),
+    trampoline_row!(5),
+    trampoline_row!(6),
+    trampoline_row!(7),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_roc_ops, RocList, RocStr};
+
+    #[repr(C)]
+    struct AddArgs {
+        a: i64,
+        b: i64,
+    }
+
+    crate::roc_refcounted_noop_impl!(AddArgs);
+
+    #[test]
+    fn calls_hosted_functions_by_sorted_name() {
+        let host = RocHost::builder()
+            .hosted("Stdout.line!", |roc_ops, line: &RocStr| {
+                RocStr::from_str(&format!("> {}", line.as_str()), roc_ops)
+            })
+            .hosted("Math.add", |_, args: &AddArgs| args.a + args.b)
+            .build();
+
+        assert_eq!(host.hosted_index("Math.add"), Some(0));
+        assert_eq!(host.hosted_index("Stdout.line!"), Some(1));
+        assert_eq!(host.hosted_index("Stdin.line!"), None);
+
+        let sum = host
+            .call(|roc_ops| unsafe {
+                let mut args = AddArgs { a: 2, b: 40 };
+                let mut sum = 0i64;
+                roc_ops.call_hosted(
+                    0,
+                    (&mut sum as *mut i64).cast(),
+                    (&mut args as *mut AddArgs).cast(),
+                );
+                sum
+            })
+            .unwrap();
+
+        assert_eq!(sum, 42);
+
+        let mut line = host
+            .call(|roc_ops| unsafe {
+                let mut arg = RocStr::from_str("hello", roc_ops);
+                let mut line = RocStr::empty();
+                roc_ops.call_hosted(
+                    1,
+                    (&mut line as *mut RocStr).cast(),
+                    (&mut arg as *mut RocStr).cast(),
+                );
+                arg.decref(roc_ops);
+                line
+            })
+            .unwrap();
+
+        assert_eq!(line.as_str(), "> hello");
+        line.decref(host.ops());
+    }
+
+    #[test]
+    fn crashes_become_errors() {
+        let host = RocHost::new();
+
+        let crash = host
+            .call(|roc_ops| {
+                roc_ops.crash("integer overflow");
+                roc_ops.crash("unreachable code was reached");
+            })
+            .unwrap_err();
+
+        assert_eq!(crash.message, "integer overflow");
+
+        // The host can keep calling into Roc after a crash.
+        assert_eq!(host.call(|_| 1), Ok(1));
+    }
+
+    #[test]
+    fn hosted_functions_can_crash() {
+        let host = RocHost::builder()
+            .hosted("File.read!", |roc_ops, _: &()| {
+                roc_ops.crash("file not found");
+            })
+            .build();
+
+        let crash = host
+            .call(|roc_ops| unsafe {
+                roc_ops.call_hosted(0, ptr::null_mut(), ptr::null_mut());
+            })
+            .unwrap_err();
+
+        assert_eq!(crash.to_string(), "Roc crashed: file not found");
+    }
+
+    #[test]
+    fn captures_dbg_and_expect_failed() {
+        let host = RocHost::new();
+
+        host.call(|roc_ops| {
+            roc_ops.dbg("x = 1");
+            roc_ops.expect_failed("expect x == 2");
+        })
+        .unwrap();
+
+        assert_eq!(
+            host.take_events(),
+            vec![
+                RocEvent::Dbg("x = 1".into()),
+                RocEvent::ExpectFailed("expect x == 2".into()),
+            ]
+        );
+        assert!(host.take_events().is_empty());
+    }
+
+    #[test]
+    fn allocates_like_test_roc_ops() {
+        let host = RocHost::new();
+        let test_ops = test_roc_ops();
+        let elements: Vec<u64> = (0..100).collect();
+
+        for roc_ops in [host.ops(), &test_ops] {
+            let mut list = RocList::from_slice(&elements, roc_ops);
+            list.extend_from_slice(&elements, roc_ops);
+
+            assert_eq!(list.len(), 200);
+            assert_eq!(list.as_slice()[150], 50);
+            list.decref(roc_ops);
+        }
+    }
+}
@@ -10,6 +10,8 @@
 //! - Constructors require a `&RocOps` parameter
 //! - Drop works automatically (no need for manual cleanup)
 //! - The `RocOps` must outlive all Roc values created with it
+//!
+//! To embed Roc in a Rust host without writing the `RocOps` functions by hand, see [RocHost].
 
 #![crate_type = "lib"]
 
@@ -22,12 +24,14 @@ use core::ops::Drop;
 use core::str;
 use std::convert::Infallible;
 
+mod host;
 mod roc_box;
 mod roc_list;
 mod roc_ops;
 mod roc_str;
 mod storage;
 
+pub use host::{RocCrash, RocEvent, RocHost, RocHostBuilder};
 pub use roc_box::RocBox;
 pub use roc_list::{ReadOnlyRocList, RocList, SendSafeRocList};
 pub use roc_ops::{
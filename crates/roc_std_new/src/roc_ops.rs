@@ -186,6 +186,16 @@ impl RocOps {
         (self.roc_dbg)(&args, self.env);
     }
 
+    /// Report a failed `expect` to the host.
+    #[inline]
+    pub fn expect_failed(&self, message: &str) {
+        let args = RocExpectFailed {
+            utf8_bytes: message.as_ptr() as *mut u8,
+            len: message.len(),
+        };
+        (self.roc_expect_failed)(&args, self.env);
+    }
+
     /// Report a crash to the host.
     #[inline]
     pub fn crash(&self, message: &str) {
@@ -195,6 +205,19 @@ impl RocOps {
         };
         (self.roc_crashed)(&args, self.env);
     }
+
+    /// Call the hosted function at `index`, the way compiled Roc code does.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be less than `hosted_fns.count`, and `ret` and `args` must
+    /// point to the return type and arguments that hosted function expects.
+    #[inline]
+    pub unsafe fn call_hosted(&self, index: usize, ret: *mut c_void, args: *mut c_void) {
+        debug_assert!(index < self.hosted_fns.count as usize);
+
+        (*self.hosted_fns.fns.add(index))(self, ret, args)
+    }
 }
 
 // RocOps contains raw pointers, so it's not automatically Send/Sync.
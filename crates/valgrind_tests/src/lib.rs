@@ -26,6 +26,7 @@ fn build_host() {
     let stub_dll_symbols = roc_linker::ExposedSymbols {
         top_level_values: vec![String::from("main_for_host")],
         exported_closure_types: vec![],
+        export_dict_seed: false,
     }
     .stub_dll_symbols();
 